@@ -0,0 +1,31 @@
+//! Time of a sweep of the angle and the speed, run with and without the feature "parallel":
+//!
+//!     cargo bench --bench batch
+//!     cargo bench --bench batch --features parallel
+
+use std::time::Instant;
+
+use basketball_trajectory::batch::simulate_batch;
+use basketball_trajectory::scenario::ScenarioFile;
+
+const NUM_ANGLES: usize = 1_000;
+const NUM_SPEEDS: usize = 1_000;
+
+fn main() {
+    let mut scenarios = Vec::with_capacity(NUM_ANGLES * NUM_SPEEDS);
+    for i in 0..NUM_ANGLES {
+        for j in 0..NUM_SPEEDS {
+            let mut scenario = ScenarioFile::default();
+            scenario.throw.teta_0_deg = 30.0 + 40.0 * i as f64 / NUM_ANGLES as f64;
+            scenario.throw.v_0 = 7.0 + 6.0 * j as f64 / NUM_SPEEDS as f64;
+            scenarios.push(scenario);
+        }
+    }
+
+    let start = Instant::now();
+    let results = simulate_batch(&scenarios);
+    let elapsed = start.elapsed();
+    let num_scored = results.iter().filter(|result| matches!(result, Ok(summary) if summary.flag_scored)).count();
+    println!("{} scenarios: {:0.2} s, {:0.0} scenarios/s, {} scored",
+             scenarios.len(), elapsed.as_secs_f64(), scenarios.len() as f64 / elapsed.as_secs_f64(), num_scored);
+}
@@ -0,0 +1,58 @@
+//! Time of the SVG figure of a long trajectory, with all the markers and the trail.
+//!
+//!     cargo bench --bench svg_plot
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use basketball_trajectory::plot_svg::{plot_trajectory_svg, SvgPlotOptions};
+use basketball_trajectory::trajectory::basketball_2d;
+
+const NUM_STEPS: u32 = 100_000;
+const NUM_RUNS: u32 = 10;
+
+// Counts the heap allocations, to see the ones of the figure.
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(& self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(& self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(& self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let trajectory_2d = basketball_2d(0.0, 1.5, 10.0, 45_f64.to_radians(), 8.0, 3.05, 3.0, NUM_STEPS);
+    let options = SvgPlotOptions {
+        max_markers: usize::MAX,
+        trail_sec: Some(0.5),
+        ..SvgPlotOptions::default()
+    };
+
+    let mut svg_len = 0;
+    let num_allocations_start = NUM_ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..NUM_RUNS {
+        let svg = plot_trajectory_svg(& trajectory_2d, Some((8.0, 3.05)), & options, "Benchmark");
+        svg_len = svg.to_file_string().len();
+    }
+    let elapsed = start.elapsed() / NUM_RUNS;
+    let num_allocations = (NUM_ALLOCATIONS.load(Ordering::Relaxed) - num_allocations_start) / NUM_RUNS as usize;
+    println!("{} steps: {:0.2} ms per figure, {} allocations, {} bytes",
+             NUM_STEPS, elapsed.as_secs_f64() * 1_000.0, num_allocations, svg_len);
+}
@@ -0,0 +1,47 @@
+//! Time of the sweep kernel, and the check that it classifies each candidate the same as the
+//! full simulation:
+//!
+//!     cargo bench --bench sweep
+//!     cargo bench --bench sweep --features simd
+
+use std::time::Instant;
+
+use basketball_trajectory::scenario::ScenarioFile;
+use basketball_trajectory::sweep::{classify_sweep, classify_sweep_scalar};
+
+const NUM_ANGLES: usize = 500;
+const NUM_SPEEDS: usize = 500;
+
+fn main() {
+    let scenario = ScenarioFile::default();
+    let mut candidates = Vec::with_capacity(NUM_ANGLES * NUM_SPEEDS);
+    for i in 0..NUM_ANGLES {
+        for j in 0..NUM_SPEEDS {
+            candidates.push((30.0 + 40.0 * i as f64 / NUM_ANGLES as f64, 7.0 + 6.0 * j as f64 / NUM_SPEEDS as f64));
+        }
+    }
+
+    let start = Instant::now();
+    let scored_scalar = classify_sweep_scalar(& scenario, & candidates);
+    let elapsed_scalar = start.elapsed();
+    let start = Instant::now();
+    let scored = classify_sweep(& scenario, & candidates);
+    let elapsed = start.elapsed();
+
+    let start = Instant::now();
+    let scored_full: Vec<bool> = candidates.iter().map(|(teta_0_deg, v_0)| {
+        let mut candidate = scenario.clone();
+        candidate.throw.teta_0_deg = *teta_0_deg;
+        candidate.throw.v_0 = *v_0;
+        candidate.simulate_2d().0
+    }).collect();
+    let elapsed_full = start.elapsed();
+
+    assert_eq!(scored_scalar, scored_full, "The scalar kernel differs from the simulation.");
+    assert_eq!(scored, scored_full, "The kernel differs from the simulation.");
+    println!("{} candidates, {} scored, the same as the simulation.", candidates.len(),
+             scored.iter().filter(|flag_scored| **flag_scored).count());
+    println!("  simulation:    {:0.1} ms", elapsed_full.as_secs_f64() * 1_000.0);
+    println!("  scalar kernel: {:0.1} ms", elapsed_scalar.as_secs_f64() * 1_000.0);
+    println!("  kernel{}: {:0.1} ms", if cfg!(feature = "simd") {" (simd)"} else {"       "}, elapsed.as_secs_f64() * 1_000.0);
+}
@@ -0,0 +1,232 @@
+//! The arc of the shot in the categories of the coaches, flat, medium or high, from the angle
+//! that the ball comes down through the height of the rim, and a short advice for the release.
+//!
+//! Below about 32° the ball, 24 cm wide, doesn't fit through the 45.7 cm rim seen from its
+//! path, so the flat shots only score with a perfect aim.
+//!
+//! A shot that misses is short, long or too low to reach the rim, and the correction is the
+//! speed that scores with the same angle, and the angle that scores with the same speed. Of the
+//! two angles that score, the one with a medium arc, so the correction agrees with the advice.
+
+use crate::i18n::{fill, Messages, Num};
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{required_speed, sample_velocity, Trajectory, GRAVITY};
+use crate::units::{degrees_to_radians, radians_to_degrees};
+
+// The limits of the medium arc, in degrees below the horizontal at the height of the rim.
+const FLAT_ENTRY_MAX_DEG: f64 = 35.0;
+const HIGH_ENTRY_MIN_DEG: f64 = 55.0;
+// The release angles that give a medium arc from the usual distances.
+const ADVISED_RELEASE_MIN_DEG: f64 = 45.0;
+const ADVISED_RELEASE_MAX_DEG: f64 = 55.0;
+const RELEASE_ADVICE_STEP_DEG: f64 = 5.0;
+// Closer than this to the center of the basket the miss isn't short or long, it's the aim.
+const MISS_MIN_DISTANCE: f64 = 0.01; // m
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ArcKind {
+    Flat,
+    Medium,
+    High,
+}
+
+impl ArcKind {
+    pub fn from_entry_angle(entry_angle_deg: f64) -> ArcKind {
+        if entry_angle_deg < FLAT_ENTRY_MAX_DEG {
+            ArcKind::Flat
+        } else if entry_angle_deg > HIGH_ENTRY_MIN_DEG {
+            ArcKind::High
+        } else {
+            ArcKind::Medium
+        }
+    }
+
+    pub fn name(self, messages: & Messages) -> &'static str {
+        match self {
+            ArcKind::Flat => messages.arc_flat,
+            ArcKind::Medium => messages.arc_medium,
+            ArcKind::High => messages.arc_high,
+        }
+    }
+}
+
+/// The arc of a shot, with the release angle and the entry angle in degrees.
+#[derive(Clone, Copy, Debug)]
+pub struct ArcClass {
+    pub kind: ArcKind,
+    pub release_angle_deg: f64,
+    pub entry_angle_deg: f64,
+}
+
+impl ArcClass {
+    /// None when the ball doesn't come down through the height of the rim.
+    pub fn new(teta_0_deg: f64, trajectory_2d: & Trajectory, basket_pos_y: f64) -> Option<ArcClass> {
+        let entry_angle_deg = descent_angle(trajectory_2d, basket_pos_y)?;
+        Some(ArcClass { kind: ArcKind::from_entry_angle(entry_angle_deg), release_angle_deg: teta_0_deg, entry_angle_deg })
+    }
+
+    /// The advice for the release, like "entry angle 28° is too flat for reliable makes; try ≥ 45° release".
+    pub fn advice(& self, messages: & Messages) -> String {
+        // At least RELEASE_ADVICE_STEP_DEG away from the release, when it's already in the advised range.
+        let entry_angle = format!("{:0.0}", Num(self.entry_angle_deg));
+        let release_angle = f64::round(self.release_angle_deg);
+        match self.kind {
+            ArcKind::Flat => fill(messages.advice_flat,
+                                  &[& entry_angle, & f64::max(ADVISED_RELEASE_MIN_DEG, release_angle + RELEASE_ADVICE_STEP_DEG)]),
+            ArcKind::Medium => fill(messages.advice_medium, &[& entry_angle]),
+            ArcKind::High => fill(messages.advice_high,
+                                  &[& entry_angle, & f64::min(ADVISED_RELEASE_MAX_DEG, release_angle - RELEASE_ADVICE_STEP_DEG)]),
+        }
+    }
+}
+
+/// The angle below the horizontal, in degrees, of the ball when it comes down through the
+/// height of the rim, interpolated between the samples around it.
+pub fn descent_angle(trajectory_2d: & Trajectory, basket_pos_y: f64) -> Option<f64> {
+    let samples = & trajectory_2d.1;
+    let i = (1..samples.len()).find(|&i| samples[i - 1].1.1 >= basket_pos_y && samples[i].1.1 < basket_pos_y)?;
+    let (y_0, y_1) = (samples[i - 1].1.1, samples[i].1.1);
+    let frac = (y_0 - basket_pos_y) / (y_0 - y_1);
+    let (v_x_0, v_y_0) = sample_velocity(samples, i - 1);
+    let (v_x_1, v_y_1) = sample_velocity(samples, i);
+    let v_x = v_x_0 + (v_x_1 - v_x_0) * frac;
+    let v_y = v_y_0 + (v_y_1 - v_y_0) * frac;
+    Some(radians_to_degrees(f64::atan2(-v_y, f64::abs(v_x))))
+}
+
+/// The angle below the horizontal, in degrees, of the ball without the air at delta_x meters
+/// along the court from the release, with v_0 in m/s and teta_0 in degrees.
+fn entry_angle(v_0: f64, teta_0_deg: f64, delta_x: f64) -> f64 {
+    let teta_0 = degrees_to_radians(teta_0_deg);
+    let (v_0_x, v_0_y) = (v_0 * f64::cos(teta_0), v_0 * f64::sin(teta_0));
+    radians_to_degrees(f64::atan2(GRAVITY * delta_x / v_0_x - v_0_y, v_0_x))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MissKind {
+    /// The ball comes down through the height of the rim before the basket.
+    Short,
+    /// The ball comes down through the height of the rim after the basket.
+    Long,
+    /// The top of the arc is below the rim.
+    Low,
+}
+
+/// How a shot missed, and the release that scores with the same angle or with the same speed.
+#[derive(Clone, Copy, Debug)]
+pub struct MissCorrection {
+    pub kind: MissKind,
+    /// From the center of the basket, along the court, where the ball comes down through the
+    /// height of the rim, or from the rim down to the top of the arc, in m.
+    pub miss_distance: f64,
+    /// The change of v_0 with the same angle, None when no speed scores.
+    pub v_0_change: Option<f64>,
+    /// The angle in degrees that scores with the same speed, the one with a medium arc, else the
+    /// closest one of the two.
+    pub teta_0_deg: Option<f64>,
+}
+
+impl MissCorrection {
+    /// The miss of the shot of the scenario without the air, None when it's not a miss, or the
+    /// ball goes away from the basket.
+    pub fn new(scenario: & ScenarioFile) -> Option<MissCorrection> {
+        let throw = & scenario.throw;
+        let (delta_x, delta_y) = (scenario.scene.basket_pos_x - throw.pos_0_x, scenario.scene.basket_pos_y - throw.pos_0_y);
+        let teta_0 = degrees_to_radians(throw.teta_0_deg);
+        let (v_0_x, v_0_y) = (throw.v_0 * f64::cos(teta_0), throw.v_0 * f64::sin(teta_0));
+        if delta_x <= 0.0 || v_0_x <= 0.0 {
+            return None;
+        }
+        // The descending instant at the height of the rim, from y_0 + v_0_y * t - g * t^2 / 2 = y.
+        let discriminant = v_0_y * v_0_y - 2.0 * GRAVITY * delta_y;
+        let (kind, miss_distance) = if discriminant < 0.0 {
+            (MissKind::Low, delta_y - v_0_y * v_0_y / (2.0 * GRAVITY))
+        } else {
+            let t = (v_0_y + f64::sqrt(discriminant)) / GRAVITY;
+            let miss_x = v_0_x * t - delta_x;
+            if f64::abs(miss_x) < MISS_MIN_DISTANCE {
+                return None;
+            }
+            (if miss_x < 0.0 {MissKind::Short} else {MissKind::Long}, f64::abs(miss_x))
+        };
+        let v_0_change = required_speed(throw.pos_0_x, throw.pos_0_y, teta_0, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y)
+            .map(|v_0| v_0 - throw.v_0);
+        // From y = tan(teta) * dx - a * (1 + tan(teta)^2), with a = g * dx^2 / (2 * v_0^2).
+        let a = GRAVITY * delta_x * delta_x / (2.0 * throw.v_0 * throw.v_0);
+        let discriminant = delta_x * delta_x - 4.0 * a * (a + delta_y);
+        let flag_not_medium = |teta_deg: f64| ArcKind::from_entry_angle(entry_angle(throw.v_0, teta_deg, delta_x)) != ArcKind::Medium;
+        let teta_0_deg = (discriminant >= 0.0).then(|| {
+            [-1.0, 1.0].map(|sign| radians_to_degrees(f64::atan((delta_x + sign * f64::sqrt(discriminant)) / (2.0 * a))))
+                .into_iter()
+                .min_by(|teta_1, teta_2| flag_not_medium(*teta_1).cmp(& flag_not_medium(*teta_2))
+                    .then(f64::abs(teta_1 - throw.teta_0_deg).total_cmp(& f64::abs(teta_2 - throw.teta_0_deg))))
+        }).flatten();
+        Some(MissCorrection { kind, miss_distance, v_0_change, teta_0_deg })
+    }
+
+    /// The suggestion, like "the ball passed 0.42 m short of the basket at the height of the rim,
+    /// increase v_0 by 0.30 m/s or raise the angle to 48.0°".
+    pub fn suggestion(& self, messages: & Messages, teta_0_deg: f64) -> String {
+        let miss_distance = format!("{:0.2}", Num(self.miss_distance));
+        let mut text = match self.kind {
+            MissKind::Short => fill(messages.correction_short, &[& miss_distance]),
+            MissKind::Long => fill(messages.correction_long, &[& miss_distance]),
+            MissKind::Low => fill(messages.correction_low, &[& miss_distance]),
+        };
+        let mut changes = Vec::new();
+        if let Some(v_0_change) = self.v_0_change {
+            let speed = format!("{:0.2}", Num(f64::abs(v_0_change)));
+            changes.push(fill(if v_0_change > 0.0 {messages.correction_faster} else {messages.correction_slower}, &[& speed]));
+        }
+        if let Some(corrected_deg) = self.teta_0_deg {
+            let angle = format!("{:0.1}", Num(corrected_deg));
+            changes.push(fill(if corrected_deg > teta_0_deg {messages.correction_raise} else {messages.correction_lower}, &[& angle]));
+        }
+        if !changes.is_empty() {
+            text.push_str(" — ");
+            text.push_str(&changes.join(messages.correction_or));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::i18n::EN;
+
+    #[test]
+    fn correction_of_the_default_shot_agrees_with_the_advice() {
+        // 45° and 10 m/s from 8 m comes down flat and long, the advice is a higher release.
+        let scenario = ScenarioFile::default();
+        let correction = MissCorrection::new(& scenario).unwrap();
+        assert_eq!(correction.kind, MissKind::Long);
+        assert!(correction.v_0_change.unwrap() < 0.0);
+        let teta_0_deg = correction.teta_0_deg.unwrap();
+        assert!(teta_0_deg > scenario.throw.teta_0_deg + RELEASE_ADVICE_STEP_DEG, "{}", teta_0_deg);
+        let delta_x = scenario.scene.basket_pos_x - scenario.throw.pos_0_x;
+        assert_eq!(ArcKind::from_entry_angle(entry_angle(scenario.throw.v_0, teta_0_deg, delta_x)), ArcKind::Medium);
+        assert!(correction.suggestion(& EN, scenario.throw.teta_0_deg).contains("raise the angle"));
+    }
+
+    #[test]
+    fn correction_keeps_the_closest_angle_without_a_medium_arc() {
+        // 1 m from the basket one angle that scores comes down steep and the other one goes up
+        // through the height of the rim.
+        let mut scenario = ScenarioFile::default();
+        scenario.throw.pos_0_x = 7.0;
+        scenario.throw.v_0 = 6.0;
+        scenario.throw.teta_0_deg = 85.0;
+        let correction = MissCorrection::new(& scenario).unwrap();
+        assert_eq!(correction.kind, MissKind::Short);
+        let teta_0_deg = correction.teta_0_deg.unwrap();
+        assert!(f64::abs(teta_0_deg - 78.0) < 0.1, "{}", teta_0_deg);
+    }
+
+    #[test]
+    fn arc_kinds() {
+        assert_eq!(ArcKind::from_entry_angle(30.0), ArcKind::Flat);
+        assert_eq!(ArcKind::from_entry_angle(45.0), ArcKind::Medium);
+        assert_eq!(ArcKind::from_entry_angle(60.0), ArcKind::High);
+    }
+}
@@ -0,0 +1,248 @@
+//! Many scenarios at once, for the sweeps of the inputs. With the feature "parallel" the
+//! scenarios are simulated in all the cores with rayon, the results are in the same order.
+//! The sweeps of the angle and the speed of a scenario, of the Monte Carlo shots, the make rate
+//! chart and the free throws, go through classify_batch, in chunks of the sweep kernel.
+//!
+//! The long batches write a checkpoint file, a line of JSON with the number of scenarios and a
+//! hash of them and then a line for each scenario done, and a run resumed from it skips them, so
+//! a batch stopped with Ctrl-C doesn't start again from zero. A checkpoint of other scenarios is
+//! rejected:
+//!
+//! ```text
+//! {"num_scenarios":1000,"scenarios_hash":"5f1d0c6e2a9b8e47"}
+//! {"index":0,"result":{"Ok":{"flag_scored":true,"entry":{...},"apex_y":3.4,...}}}
+//! ```
+//!
+//! The summary of a batch is a line for each scenario, its name, the angle and the speed of the
+//! release, the result, the closest distance to the center of the basket and the flight time.
+
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::scenario::ScenarioFile;
+use crate::sweep::classify_sweep;
+use crate::trajectory::ShotSummary;
+
+/// The scenarios simulated between two writes of the checkpoint file.
+pub const CHECKPOINT_NUM_SCENARIOS: usize = 256;
+/// The candidates of a chunk of classify_batch, a multiple of the 4 lanes of the SIMD kernel.
+pub const SWEEP_CHUNK_LEN: usize = 4096;
+
+/// The summary of the shot, or why it couldn't be simulated.
+pub type SimResult = Result<ShotSummary, String>;
+
+#[derive(Serialize, Deserialize)]
+struct CheckpointHeader {
+    num_scenarios: usize,
+    /// FNV-1a of the scenarios in JSON, in hexadecimal.
+    scenarios_hash: String,
+}
+
+// FNV-1a, 64 bits, the same in every run and every version of Rust.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+// The hash of the scenarios of a checkpoint, from their serialization.
+fn scenarios_hash(scenarios: & [ScenarioFile]) -> String {
+    let json = serde_json::to_string(scenarios).unwrap_or_default();
+    let hash = json.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME));
+    format!("{:016x}", hash)
+}
+
+#[derive(Serialize, Deserialize)]
+struct CheckpointRecord {
+    index: usize,
+    result: SimResult,
+}
+
+pub fn simulate_one(scenario: & ScenarioFile) -> SimResult {
+    scenario.validate()?;
+    let trajectory_2d = scenario.simulate_2d();
+    ShotSummary::new(& trajectory_2d, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y)
+        .ok_or("No samples above the floor.".to_string())
+}
+
+/// The result of each scenario, in the order of the scenarios.
+pub fn simulate_batch(scenarios: & [ScenarioFile]) -> Vec<SimResult> {
+    #[cfg(feature = "parallel")]
+    let results = scenarios.par_iter().map(simulate_one).collect();
+    #[cfg(not(feature = "parallel"))]
+    let results = scenarios.iter().map(simulate_one).collect();
+    results
+}
+
+/// If each candidate (teta_0 in degrees, v_0 in m/s) scored, with the other inputs of the
+/// scenario, the same as classify_sweep(). With the feature "parallel" the chunks of the
+/// candidates are classified in all the cores.
+pub fn classify_batch(scenario: & ScenarioFile, candidates: & [(f64, f64)]) -> Vec<bool> {
+    #[cfg(feature = "parallel")]
+    let scored = candidates.par_chunks(SWEEP_CHUNK_LEN).flat_map_iter(|chunk| classify_sweep(scenario, chunk)).collect();
+    #[cfg(not(feature = "parallel"))]
+    let scored = candidates.chunks(SWEEP_CHUNK_LEN).flat_map(|chunk| classify_sweep(scenario, chunk)).collect();
+    scored
+}
+
+/// The batch with a checkpoint file, the results in the order of the scenarios and the number
+/// of them read from the checkpoint. With flag_resume the scenarios of the file are skipped,
+/// otherwise the file starts again.
+pub fn simulate_batch_checkpoint(scenarios: & [ScenarioFile], checkpoint_filename: & str,
+                                 flag_resume: bool) -> Result<(Vec<SimResult>, usize), String> {
+    let write_error = |error: std::io::Error| format!("Can't write the checkpoint file {}: {}", checkpoint_filename, error);
+    let mut results: Vec<Option<SimResult>> = vec![None; scenarios.len()];
+    let hash = scenarios_hash(scenarios);
+    let mut file = if flag_resume {
+        let flag_full_lines = read_checkpoint(checkpoint_filename, & hash, & mut results)?;
+        let mut file = OpenOptions::new().append(true).open(checkpoint_filename).map_err(write_error)?;
+        // The records start in a new line after the cut one.
+        if !flag_full_lines {
+            writeln!(file).map_err(write_error)?;
+        }
+        file
+    } else {
+        let mut file = File::create(checkpoint_filename).map_err(write_error)?;
+        let header = CheckpointHeader { num_scenarios: scenarios.len(), scenarios_hash: hash };
+        writeln!(file, "{}", serde_json::to_string(& header).unwrap_or_default()).map_err(write_error)?;
+        file
+    };
+    let num_resumed = results.iter().filter(|result| result.is_some()).count();
+
+    let pending: Vec<usize> = (0..scenarios.len()).filter(|&i| results[i].is_none()).collect();
+    for indexes in pending.chunks(CHECKPOINT_NUM_SCENARIOS) {
+        #[cfg(feature = "parallel")]
+        let chunk_results: Vec<SimResult> = indexes.par_iter().map(|&i| simulate_one(& scenarios[i])).collect();
+        #[cfg(not(feature = "parallel"))]
+        let chunk_results: Vec<SimResult> = indexes.iter().map(|&i| simulate_one(& scenarios[i])).collect();
+        // The whole chunk in one write, a stop leaves at most a partial last line.
+        let mut lines = String::new();
+        for (&index, result) in indexes.iter().zip(chunk_results) {
+            let record = CheckpointRecord { index, result };
+            lines.push_str(& serde_json::to_string(& record).unwrap_or_default());
+            lines.push('\n');
+            results[index] = Some(record.result);
+        }
+        file.write_all(lines.as_bytes()).and_then(|_| file.flush()).map_err(write_error)?;
+    }
+    Ok((results.into_iter().flatten().collect(), num_resumed))
+}
+
+// Fills the results of the scenarios done in the checkpoint file, of a batch with the same
+// scenarios, the ones of the hash. The last line can be cut by the stop, it's ignored, and false
+// when the file doesn't end with a full line.
+fn read_checkpoint(checkpoint_filename: & str, hash: & str, results: & mut [Option<SimResult>]) -> Result<bool, String> {
+    let text = std::fs::read_to_string(checkpoint_filename)
+        .map_err(|error| format!("Can't read the checkpoint file {}: {}", checkpoint_filename, error))?;
+    let mut lines = text.lines();
+    let header: CheckpointHeader = lines.next()
+        .and_then(|line| serde_json::from_str(line).ok())
+        .ok_or(format!("{}: Invalid checkpoint, expected the number and the hash of the scenarios in the first line.",
+                       checkpoint_filename))?;
+    if header.num_scenarios != results.len() {
+        return Err(format!("{}: The checkpoint is of a batch of {} scenarios, not of {}.",
+                           checkpoint_filename, header.num_scenarios, results.len()));
+    }
+    if header.scenarios_hash != hash {
+        return Err(format!("{}: The checkpoint is of a batch of other scenarios, the batch file changed.", checkpoint_filename));
+    }
+    for line in lines {
+        if let Ok(record) = serde_json::from_str::<CheckpointRecord>(line) {
+            if let Some(result) = results.get_mut(record.index) {
+                *result = Some(record.result);
+            }
+        }
+    }
+    Ok(text.ends_with('\n'))
+}
+
+/// The name of the i-th scenario, from 0, the one in the file or its number.
+pub fn scenario_name(i: usize, scenario: & ScenarioFile) -> String {
+    if scenario.name.is_empty() { format!("scenario {}", i + 1) } else { scenario.name.clone() }
+}
+
+/// The summary of the batch as CSV, a line for each scenario with the numbers at precision
+/// decimal places. The result is scored, missed or error, and the error is in the last column.
+pub fn summary_csv(scenarios: & [ScenarioFile], results: & [SimResult], precision: usize) -> String {
+    let mut csv = String::from("name,teta_0_deg,v_0,result,closest_dist,flight_sec,error\n");
+    for (i, (scenario, result)) in scenarios.iter().zip(results).enumerate() {
+        let (teta_0_deg, v_0) = (scenario.throw.teta_0_deg, scenario.throw.v_0);
+        let line = match result {
+            Ok(summary) => format!("{},{:.*},{:.*},{},{:.*},{:.*},", csv_field(&scenario_name(i, scenario)),
+                                   precision, teta_0_deg, precision, v_0, if summary.flag_scored {"scored"} else {"missed"},
+                                   precision, summary.closest_dist, precision, summary.flight_sec),
+            Err(error) => format!("{},{:.*},{:.*},error,,,{}", csv_field(&scenario_name(i, scenario)),
+                                  precision, teta_0_deg, precision, v_0, csv_field(error)),
+        };
+        csv.push_str(&line);
+        csv.push('\n');
+    }
+    csv
+}
+
+// The field between quotes when it has a comma, a quote or a line break, the quotes doubled.
+fn csv_field(text: & str) -> String {
+    if text.contains([',', '"', '\n']) { format!("\"{}\"", text.replace('"', "\"\"")) } else { text.to_string() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scenarios() -> Vec<ScenarioFile> {
+        [40.0, 45.0, 50.0].map(|teta_0_deg| {
+            let mut scenario = ScenarioFile::default();
+            scenario.throw.teta_0_deg = teta_0_deg;
+            scenario
+        }).to_vec()
+    }
+
+    fn checkpoint_filename(name: & str) -> String {
+        std::env::temp_dir().join(format!("basketball_trajectory_{}_{}.ndjson", name, std::process::id()))
+            .to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn resume_of_the_same_scenarios() {
+        let filename = checkpoint_filename("same");
+        let scenarios = scenarios();
+        let (results, num_resumed) = simulate_batch_checkpoint(& scenarios, & filename, false).unwrap();
+        assert_eq!((results.len(), num_resumed), (3, 0));
+        let (resumed, num_resumed) = simulate_batch_checkpoint(& scenarios, & filename, true).unwrap();
+        assert_eq!(num_resumed, 3);
+        assert_eq!(resumed.len(), 3);
+        let _ = std::fs::remove_file(& filename);
+    }
+
+    #[test]
+    fn resume_of_other_scenarios_is_rejected() {
+        let filename = checkpoint_filename("other");
+        let mut scenarios = scenarios();
+        simulate_batch_checkpoint(& scenarios, & filename, false).unwrap();
+        scenarios[1].throw.v_0 += 0.5;
+        let error = simulate_batch_checkpoint(& scenarios, & filename, true).err().unwrap();
+        assert!(error.contains("other scenarios"), "{}", error);
+        let _ = std::fs::remove_file(& filename);
+    }
+
+    #[test]
+    fn classify_batch_in_chunks() {
+        let scenario = ScenarioFile::default();
+        // More than two chunks, the last one with a remainder lane.
+        let candidates: Vec<(f64, f64)> = (0..2 * SWEEP_CHUNK_LEN + 5)
+            .map(|i| (40.0 + 20.0 * i as f64 / SWEEP_CHUNK_LEN as f64, 9.0 + (i % 97) as f64 * 0.02))
+            .collect();
+        let scored = classify_batch(& scenario, & candidates);
+        assert!(scored.iter().any(|flag_scored| *flag_scored));
+        assert_eq!(scored, classify_sweep(& scenario, & candidates));
+    }
+
+    #[test]
+    fn hash_of_the_scenarios() {
+        let scenarios = scenarios();
+        assert_eq!(scenarios_hash(& scenarios), scenarios_hash(& scenarios.clone()));
+        assert_ne!(scenarios_hash(& scenarios), scenarios_hash(& scenarios[..2]));
+        assert_eq!(scenarios_hash(& scenarios).len(), 16);
+    }
+}
@@ -0,0 +1,108 @@
+//! The benchmarks of the bench subcommand, the throughput of the simulations and of the SVG
+//! figure in samples per second, and their heap allocations, to compare the versions.
+//!
+//! The allocations are counted by CountingAllocator, the binary installs it as the global
+//! allocator with the feature "bench", without it they are 0.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::plot_svg::{plot_trajectory_svg, SvgPlotOptions};
+use crate::rim::basketball_3d_rim;
+use crate::scenario::ScenarioFile;
+
+/// The simulations with a step of integration, the drag and the rim, have this fraction of the
+/// samples, each one is many times slower.
+const INTEGRATED_FRACTION: u64 = 10;
+
+static NUM_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static NUM_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// The system allocator that counts the allocations and their bytes.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(& self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        NUM_BYTES.fetch_add(layout.size() as u64, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(& self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(& self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        NUM_BYTES.fetch_add(new_size as u64, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// The result of a benchmark.
+#[derive(Clone, Debug)]
+pub struct BenchResult {
+    pub name: &'static str,
+    pub num_samples: u64,
+    pub seconds: f64,
+    pub num_allocations: u64,
+    pub num_bytes: u64,
+}
+
+impl BenchResult {
+    pub fn samples_per_sec(& self) -> f64 {
+        self.num_samples as f64 / self.seconds
+    }
+}
+
+// Times the run and counts its allocations.
+fn measure(name: &'static str, num_samples: u64, run: impl FnOnce()) -> BenchResult {
+    let (allocations_start, bytes_start) = (NUM_ALLOCATIONS.load(Ordering::Relaxed), NUM_BYTES.load(Ordering::Relaxed));
+    let start = Instant::now();
+    run();
+    let seconds = start.elapsed().as_secs_f64();
+    BenchResult {
+        name,
+        num_samples,
+        seconds,
+        num_allocations: NUM_ALLOCATIONS.load(Ordering::Relaxed) - allocations_start,
+        num_bytes: NUM_BYTES.load(Ordering::Relaxed) - bytes_start,
+    }
+}
+
+/// Runs the benchmarks of the default shot, num_samples of the ideal flight and a tenth of them
+/// of the simulations with the drag and with the rim, and the SVG figure of a tenth of them.
+pub fn run_benches(num_samples: u32) -> Vec<BenchResult> {
+    let mut scenario = ScenarioFile::default();
+    let num_integrated = u32::max(num_samples / INTEGRATED_FRACTION as u32, 3);
+    let mut results = Vec::new();
+
+    scenario.simulation.num_steps = num_samples;
+    results.push(measure("ideal 2D", num_samples as u64, || { black_box(scenario.simulate_2d()); }));
+    results.push(measure("ideal 2D streamed", num_samples as u64 + 1, || {
+        for sample in scenario.flight_2d() {
+            black_box(sample);
+        }
+    }));
+
+    scenario.simulation.num_steps = num_integrated;
+    results.push(measure("drag RK4 2D", num_integrated as u64, || { black_box(scenario.simulate_2d_drag()); }));
+    let (throw, scene) = (& scenario.throw, & scenario.scene);
+    results.push(measure("rim 3D", num_integrated as u64, || {
+        black_box(basketball_3d_rim(throw.pos_0_x, throw.pos_0_y, throw.pos_0_z,
+                                    throw.v_0, throw.teta_0_deg.to_radians(), throw.phi_0_deg.to_radians(),
+                                    scene.basket_pos_x, scene.basket_pos_y, scene.basket_pos_z,
+                                    scenario.simulation.simulation_sec, num_integrated, scenario.ball.radius,
+                                    scenario.ball.pressure_bar));
+    }));
+
+    let trajectory_2d = scenario.simulate_2d();
+    let basket_pos = Some((scene.basket_pos_x, scene.basket_pos_y));
+    results.push(measure("SVG figure", trajectory_2d.1.len() as u64, || {
+        let svg = plot_trajectory_svg(& trajectory_2d, basket_pos, & SvgPlotOptions::default(), "Benchmark");
+        black_box(svg.to_file_string());
+    }));
+    results
+}
@@ -0,0 +1,146 @@
+//! Desktop GUI, sliders for the shot and the trajectory redrawn while they move.
+//!
+//! Run with:
+//!
+//!     cargo run --release --features gui --bin basketball_gui
+
+use eframe::egui;
+
+use basketball_trajectory::trajectory::{basket_backboard_x, basketball_2d, ShotSummary, Trajectory,
+                                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, RIM_DIAMETER};
+
+const SIMULATION_SEC: f64 = 3.0;
+const NUM_STEPS: u32 = 120;
+
+fn main() -> eframe::Result<()> {
+    let options = eframe::NativeOptions {
+        viewport: egui::ViewportBuilder::default().with_inner_size([960.0, 540.0]),
+        ..Default::default()
+    };
+    eframe::run_native("Basketball trajectory", options,
+                       Box::new(|_creation_context| Ok(Box::new(ShotApp::default()))))
+}
+
+struct ShotApp {
+    v_0: f64,
+    teta_0_deg: f64,
+    pos_0_y: f64,
+    basket_pos_x: f64,
+    basket_pos_y: f64,
+    flag_animate: bool,
+}
+
+impl Default for ShotApp {
+    fn default() -> Self {
+        ShotApp {
+            v_0: 10.0,
+            teta_0_deg: 58.0,
+            pos_0_y: 1.5,
+            basket_pos_x: 8.0,
+            basket_pos_y: 3.05,
+            flag_animate: true,
+        }
+    }
+}
+
+impl ShotApp {
+    fn trajectory(& self) -> Trajectory {
+        basketball_2d(0.0, self.pos_0_y,
+                      self.v_0, self.teta_0_deg.to_radians(),
+                      self.basket_pos_x, self.basket_pos_y,
+                      SIMULATION_SEC, NUM_STEPS)
+    }
+}
+
+impl eframe::App for ShotApp {
+    fn ui(& mut self, ui: & mut egui::Ui, _frame: & mut eframe::Frame) {
+        egui::Panel::left("inputs").show(ui, |ui| {
+            ui.heading("Shot");
+            ui.add(egui::Slider::new(& mut self.teta_0_deg, 0.0..=89.0).text("angle (°)"));
+            ui.add(egui::Slider::new(& mut self.v_0, 1.0..=20.0).text("speed (m/s)"));
+            ui.add(egui::Slider::new(& mut self.pos_0_y, 0.5..=3.0).text("throw height (m)"));
+            ui.heading("Basket");
+            ui.add(egui::Slider::new(& mut self.basket_pos_x, 1.0..=15.0).text("distance (m)"));
+            ui.add(egui::Slider::new(& mut self.basket_pos_y, 1.0..=4.0).text("height (m)"));
+            ui.checkbox(& mut self.flag_animate, "Animate the ball");
+            if ui.button("Reset").clicked() {
+                *self = ShotApp::default();
+            }
+        });
+
+        let trajectory_2d = self.trajectory();
+        egui::Panel::bottom("summary").show(ui, |ui| {
+            match ShotSummary::new(& trajectory_2d, self.basket_pos_x, self.basket_pos_y) {
+                Some(summary) => ui.label(format!("{} | apex: {:0.2} m | range: {:0.2} m | flight: {:0.2} s | closest to the basket: {:0.2} m",
+                                                  if summary.flag_scored {"SCORED"} else {"MISSED"}, summary.apex_y,
+                                                  summary.range_x, summary.flight_sec, summary.closest_dist)),
+                None => ui.label("No samples above the floor."),
+            };
+        });
+
+        egui::CentralPanel::default_margins().show(ui, |ui| {
+            let time = ui.input(|input| input.time);
+            draw_scene(ui, & trajectory_2d, self.basket_pos_x, self.basket_pos_y,
+                       if self.flag_animate { Some(time) } else { None });
+        });
+        if self.flag_animate {
+            ui.ctx().request_repaint();
+        }
+    }
+}
+
+// Draws the floor, the basket and the trajectory in the available space, with the same scale in
+// both axis. With the time, the ball is at the instant of the simulation, in a loop.
+fn draw_scene(ui: & mut egui::Ui, trajectory_2d: & Trajectory, basket_pos_x: f64, basket_pos_y: f64, time: Option<f64>) {
+    let (response, painter) = ui.allocate_painter(ui.available_size(), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_filled(rect, 0.0, egui::Color32::from_gray(20));
+
+    let samples = & trajectory_2d.1;
+    let backboard_x = basket_backboard_x(basket_pos_x);
+    let backboard_top = basket_pos_y - BACKBOARD_BELOW_RIM + BACKBOARD_HEIGHT;
+    let (x_max, y_max) = samples.iter()
+        .fold((backboard_x, backboard_top), |(x_max, y_max), (_t, (x, y), _)| (f64::max(x_max, *x), f64::max(y_max, *y)));
+    let margin = 20.0;
+    let scale = f32::min((rect.width() - 2.0 * margin) / (x_max * 1.05) as f32,
+                         (rect.height() - 2.0 * margin) / (y_max * 1.05) as f32);
+    let to_screen = |x: f64, y: f64| egui::pos2(rect.left() + margin + x as f32 * scale,
+                                                  rect.bottom() - margin - y as f32 * scale);
+
+    // Floor, rim and backboard.
+    painter.line_segment([to_screen(0.0, 0.0), to_screen(x_max * 1.05, 0.0)],
+                         egui::Stroke::new(2.0, egui::Color32::GRAY));
+    painter.line_segment([to_screen(basket_pos_x - RIM_DIAMETER / 2.0, basket_pos_y),
+                          to_screen(basket_pos_x + RIM_DIAMETER / 2.0, basket_pos_y)],
+                         egui::Stroke::new(3.0, egui::Color32::from_rgb(230, 80, 0)));
+    painter.line_segment([to_screen(backboard_x, backboard_top - BACKBOARD_HEIGHT), to_screen(backboard_x, backboard_top)],
+                         egui::Stroke::new(3.0, egui::Color32::WHITE));
+
+    // Trajectory, with a circle at each instant, the instant the ball entered the basket in green.
+    let points: Vec<egui::Pos2> = samples.iter().map(|(_t, (x, y), _)| to_screen(*x, *y)).collect();
+    painter.add(egui::Shape::line(points.clone(), egui::Stroke::new(1.5, egui::Color32::LIGHT_BLUE)));
+    for (point, (_t, _pos, flag_enter_instant)) in points.iter().zip(samples) {
+        if *flag_enter_instant {
+            painter.circle_filled(*point, 5.0, egui::Color32::GREEN);
+        } else {
+            painter.circle_filled(*point, 2.0, egui::Color32::LIGHT_BLUE);
+        }
+    }
+
+    // Animated ball, interpolated between the two samples around the simulated instant.
+    if let (Some(time), Some(last)) = (time, samples.last()) {
+        let t_last = last.0;
+        if t_last > 0.0 {
+            let t = time % t_last;
+            let i = samples.iter().position(|sample| sample.0 >= t).unwrap_or(samples.len() - 1);
+            let position = if i == 0 {
+                points[0]
+            } else {
+                let (t_0, t_1) = (samples[i - 1].0, samples[i].0);
+                let frac = if t_1 > t_0 { ((t - t_0) / (t_1 - t_0)) as f32 } else { 0.0 };
+                points[i - 1].lerp(points[i], frac)
+            };
+            painter.circle_filled(position, 6.0, egui::Color32::from_rgb(230, 110, 0));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Lightweight window, the shot animated at the real speed with the court drawn to scale.
+//!
+//! Keys: Up/Down change the angle, Left/Right change the speed, Space pauses and R restarts.
+//!
+//! Run with:
+//!
+//!     cargo run --release --features live_2d --bin basketball_live
+
+use macroquad::prelude::*;
+
+use basketball_trajectory::trajectory::{basket_backboard_x, basketball_2d, ShotSummary, Trajectory,
+                                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, RIM_DIAMETER};
+
+const POS_0_Y: f64 = 1.5;
+const BASKET_POS: (f64, f64) = (8.0, 3.05);
+const SIMULATION_SEC: f64 = 3.0;
+const NUM_STEPS: u32 = 120;
+const BALL_RADIUS: f64 = 0.12;     // m - A size 7 ball.
+const PAUSE_AFTER_SEC: f64 = 1.0;  // s - Time with the ball stopped before the shot repeats.
+
+fn simulate(v_0: f64, teta_0_deg: f64) -> Trajectory {
+    basketball_2d(0.0, POS_0_Y,
+                  v_0, teta_0_deg.to_radians(),
+                  BASKET_POS.0, BASKET_POS.1,
+                  SIMULATION_SEC, NUM_STEPS)
+}
+
+// Position of the ball at the instant t, interpolated between the samples.
+fn position_at(samples: & [(f64, (f64, f64), bool)], t: f64) -> (f64, f64) {
+    let i = samples.iter().position(|sample| sample.0 >= t).unwrap_or(samples.len() - 1);
+    if i == 0 {
+        return samples[0].1;
+    }
+    let (t_0, (x_0, y_0), _) = samples[i - 1];
+    let (t_1, (x_1, y_1), _) = samples[i];
+    let frac = if t_1 > t_0 { f64::clamp((t - t_0) / (t_1 - t_0), 0.0, 1.0) } else { 0.0 };
+    (x_0 + (x_1 - x_0) * frac, y_0 + (y_1 - y_0) * frac)
+}
+
+#[macroquad::main("Basketball trajectory")]
+async fn main() {
+    let mut v_0 = 10.0;
+    let mut teta_0_deg = 58.0;
+    let mut trajectory_2d = simulate(v_0, teta_0_deg);
+    // Simulated time of the animation, it only advances when not paused.
+    let mut t = 0.0;
+    let mut flag_paused = false;
+
+    loop {
+        let mut flag_changed = false;
+        if is_key_down(KeyCode::Up)    { teta_0_deg = f64::min(teta_0_deg + 0.2, 89.0); flag_changed = true; }
+        if is_key_down(KeyCode::Down)  { teta_0_deg = f64::max(teta_0_deg - 0.2, 1.0);  flag_changed = true; }
+        if is_key_down(KeyCode::Right) { v_0 = f64::min(v_0 + 0.05, 20.0); flag_changed = true; }
+        if is_key_down(KeyCode::Left)  { v_0 = f64::max(v_0 - 0.05, 1.0);  flag_changed = true; }
+        if flag_changed {
+            trajectory_2d = simulate(v_0, teta_0_deg);
+        }
+        if is_key_pressed(KeyCode::Space) {
+            flag_paused = !flag_paused;
+        }
+        if is_key_pressed(KeyCode::R) || flag_changed {
+            t = 0.0;
+        }
+
+        let samples = & trajectory_2d.1;
+        let t_last = samples.last().map_or(0.0, |sample| sample.0);
+        if !flag_paused {
+            t += get_frame_time() as f64;
+            if t > t_last + PAUSE_AFTER_SEC {
+                t = 0.0;
+            }
+        }
+
+        // The scene to scale, from the player to behind the backboard, fitted to the window.
+        let backboard_x = basket_backboard_x(BASKET_POS.0);
+        let backboard_top = BASKET_POS.1 - BACKBOARD_BELOW_RIM + BACKBOARD_HEIGHT;
+        let (x_max, y_max) = samples.iter()
+            .fold((backboard_x + 0.5, backboard_top), |(x_max, y_max), (_t, (x, y), _)| (f64::max(x_max, *x), f64::max(y_max, *y)));
+        let margin = 30.0;
+        let scale = f32::min((screen_width() - 2.0 * margin) / x_max as f32,
+                             (screen_height() - 2.0 * margin - 40.0) / y_max as f32);
+        let floor_y = screen_height() - margin;
+        let to_screen = |(x, y): (f64, f64)| (margin + x as f32 * scale, floor_y - y as f32 * scale);
+
+        clear_background(Color::from_rgba(20, 20, 30, 255));
+
+        // Court, rim and backboard.
+        draw_line(0.0, floor_y, screen_width(), floor_y, 2.0, GRAY);
+        let (rim_x_0, rim_y) = to_screen((BASKET_POS.0 - RIM_DIAMETER / 2.0, BASKET_POS.1));
+        let (rim_x_1, _) = to_screen((BASKET_POS.0 + RIM_DIAMETER / 2.0, BASKET_POS.1));
+        draw_line(rim_x_0, rim_y, rim_x_1, rim_y, 3.0, ORANGE);
+        let (board_x, board_top) = to_screen((backboard_x, backboard_top));
+        let (_, board_bottom) = to_screen((backboard_x, backboard_top - BACKBOARD_HEIGHT));
+        draw_line(board_x, board_top, board_x, board_bottom, 4.0, WHITE);
+        draw_line(board_x, board_bottom, board_x, floor_y, 2.0, DARKGRAY);
+
+        // Path of the shot, faint, and the part that the ball already flew.
+        for pair in samples.windows(2) {
+            let (x_0, y_0) = to_screen(pair[0].1);
+            let (x_1, y_1) = to_screen(pair[1].1);
+            let color = if pair[1].0 <= t { SKYBLUE } else { Color::from_rgba(80, 110, 160, 120) };
+            draw_line(x_0, y_0, x_1, y_1, 1.5, color);
+        }
+        for (t_sample, pos, flag_enter_instant) in samples {
+            if *flag_enter_instant && *t_sample <= t {
+                let (x, y) = to_screen(*pos);
+                draw_circle_lines(x, y, 8.0, 2.0, GREEN);
+            }
+        }
+
+        if !samples.is_empty() {
+            let t_ball = f64::min(t, t_last);
+            let (x_m, y_m) = position_at(samples, t_ball);
+            let (x, y) = to_screen((x_m, y_m));
+            draw_circle(x, y, f32::max(BALL_RADIUS as f32 * scale, 3.0), ORANGE);
+            draw_text(format!("t: {:0.2} s   x: {:0.2} m   y: {:0.2} m", t_ball, x_m, y_m),
+                      margin, 28.0, 24.0, WHITE);
+        }
+        let result = match ShotSummary::new(& trajectory_2d, BASKET_POS.0, BASKET_POS.1) {
+            Some(summary) => format!("{}, closest to the basket {:0.2} m", if summary.flag_scored {"SCORED"} else {"MISSED"}, summary.closest_dist),
+            None => "No samples above the floor.".to_string(),
+        };
+        draw_text(format!("v_0: {:0.2} m/s   teta_0: {:0.1}°   {}   {}", v_0, teta_0_deg, result,
+                            if flag_paused {"(paused)"} else {""}),
+                  margin, 54.0, 22.0, LIGHTGRAY);
+
+        next_frame().await
+    }
+}
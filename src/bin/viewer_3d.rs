@@ -0,0 +1,172 @@
+//! 3D viewer, the ball flies in real time over a court with the basket.
+//!
+//! Drag with the left mouse button to orbit the camera and use the mouse wheel to zoom.
+//!
+//! Run with:
+//!
+//!     cargo run --release --features viewer_3d --bin basketball_3d
+
+use bevy::input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll};
+use bevy::prelude::*;
+
+use basketball_trajectory::rebound::NOMINAL_BALL_PRESSURE;
+use basketball_trajectory::rim::basketball_3d_rim;
+use basketball_trajectory::trajectory::{basket_backboard_x, Trajectory3D,
+                                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, BACKBOARD_WIDTH, RIM_DIAMETER, RIM_TUBE_RADIUS};
+
+// The shot, the same as the command line tool, with the angles in degrees.
+const POS_0: (f64, f64, f64) = (0.0, 1.5, 0.0);
+const V_0: f64 = 10.0;
+const TETA_0_DEG: f64 = 58.0;
+const PHI_0_DEG: f64 = 0.0;
+const BASKET_POS: (f64, f64, f64) = (8.0, 3.05, 0.0);
+const SIMULATION_SEC: f64 = 3.0;
+const NUM_STEPS: u32 = 120;
+
+const BALL_RADIUS: f32 = 0.12;     // m - A size 7 ball.
+
+#[derive(Resource)]
+struct Shot {
+    trajectory_3d: Trajectory3D,
+}
+
+/// The camera looks at the target from a distance, rotated by yaw around the vertical and by pitch.
+#[derive(Resource)]
+struct OrbitCamera {
+    target: Vec3,
+    distance: f32,
+    yaw: f32,
+    pitch: f32,
+}
+
+#[derive(Component)]
+struct Ball;
+
+fn main() {
+    // The ball bounces off the rim and the backboard.
+    let trajectory_3d = basketball_3d_rim(POS_0.0, POS_0.1, POS_0.2,
+                                          V_0, TETA_0_DEG.to_radians(), PHI_0_DEG.to_radians(),
+                                          BASKET_POS.0, BASKET_POS.1, BASKET_POS.2,
+                                          SIMULATION_SEC, NUM_STEPS, BALL_RADIUS as f64, NOMINAL_BALL_PRESSURE);
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .insert_resource(Shot { trajectory_3d })
+        .insert_resource(OrbitCamera {
+            target: Vec3::new(BASKET_POS.0 as f32 / 2.0, 2.0, 0.0),
+            distance: 14.0,
+            yaw: -0.6,
+            pitch: -0.3,
+        })
+        .add_systems(Startup, setup)
+        .add_systems(Update, (orbit_camera, move_ball, draw_trajectory))
+        .run();
+}
+
+fn setup(mut commands: Commands,
+         mut meshes: ResMut<Assets<Mesh>>,
+         mut materials: ResMut<Assets<StandardMaterial>>) {
+    let (basket_x, basket_y, basket_z) = (BASKET_POS.0 as f32, BASKET_POS.1 as f32, BASKET_POS.2 as f32);
+
+    // Court, the floor from the player to behind the basket.
+    let court_length = basket_x + 4.0;
+    commands.spawn((
+        Mesh3d(meshes.add(Plane3d::default().mesh().size(court_length, 15.0))),
+        MeshMaterial3d(materials.add(Color::srgb(0.75, 0.55, 0.35))),
+        Transform::from_xyz(court_length / 2.0 - 1.0, 0.0, basket_z),
+    ));
+
+    // Rim, a torus in the horizontal plane.
+    commands.spawn((
+        Mesh3d(meshes.add(Torus::new((RIM_DIAMETER / 2.0 - RIM_TUBE_RADIUS) as f32, (RIM_DIAMETER / 2.0 + RIM_TUBE_RADIUS) as f32))),
+        MeshMaterial3d(materials.add(Color::srgb(0.9, 0.3, 0.0))),
+        Transform::from_xyz(basket_x, basket_y, basket_z),
+    ));
+
+    // Backboard behind the rim, and its pole.
+    let backboard_x = basket_backboard_x(BASKET_POS.0) as f32;
+    let backboard_bottom = basket_y - BACKBOARD_BELOW_RIM as f32;
+    commands.spawn((
+        Mesh3d(meshes.add(Cuboid::new(0.05, BACKBOARD_HEIGHT as f32, BACKBOARD_WIDTH as f32))),
+        MeshMaterial3d(materials.add(Color::srgba(0.9, 0.9, 1.0, 0.8))),
+        Transform::from_xyz(backboard_x, backboard_bottom + BACKBOARD_HEIGHT as f32 / 2.0, basket_z),
+    ));
+    commands.spawn((
+        Mesh3d(meshes.add(Cylinder::new(0.08, backboard_bottom))),
+        MeshMaterial3d(materials.add(Color::srgb(0.3, 0.3, 0.3))),
+        Transform::from_xyz(backboard_x + 0.3, backboard_bottom / 2.0, basket_z),
+    ));
+
+    // Ball.
+    commands.spawn((
+        Ball,
+        Mesh3d(meshes.add(Sphere::new(BALL_RADIUS))),
+        MeshMaterial3d(materials.add(Color::srgb(0.9, 0.45, 0.1))),
+        Transform::from_xyz(POS_0.0 as f32, POS_0.1 as f32, POS_0.2 as f32),
+    ));
+
+    commands.spawn((
+        DirectionalLight {
+            shadow_maps_enabled: true,
+            ..default()
+        },
+        Transform::from_xyz(2.0, 10.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+    commands.spawn((Camera3d::default(), Transform::default()));
+
+    commands.spawn((
+        Text::new("Drag: orbit\nWheel: zoom"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: px(12),
+            left: px(12),
+            ..default()
+        },
+    ));
+}
+
+fn orbit_camera(mut camera: Single<& mut Transform, With<Camera3d>>,
+                mut orbit: ResMut<OrbitCamera>,
+                mouse_buttons: Res<ButtonInput<MouseButton>>,
+                mouse_motion: Res<AccumulatedMouseMotion>,
+                mouse_scroll: Res<AccumulatedMouseScroll>) {
+    if mouse_buttons.pressed(MouseButton::Left) {
+        orbit.yaw -= mouse_motion.delta.x * 0.005;
+        orbit.pitch = (orbit.pitch - mouse_motion.delta.y * 0.005).clamp(-1.5, 0.2);
+    }
+    orbit.distance = (orbit.distance * (1.0 - mouse_scroll.delta.y * 0.1)).clamp(2.0, 60.0);
+
+    camera.rotation = Quat::from_euler(EulerRot::YXZ, orbit.yaw, orbit.pitch, 0.0);
+    camera.translation = orbit.target - camera.forward() * orbit.distance;
+}
+
+// The ball at the instant of the simulation, in a loop, interpolated between the samples.
+fn move_ball(mut ball: Single<& mut Transform, With<Ball>>, shot: Res<Shot>, time: Res<Time>) {
+    let samples = & shot.trajectory_3d.1;
+    let t_last = match samples.last() {
+        Some(last) if last.0 > 0.0 => last.0,
+        _ => return,
+    };
+    let t = time.elapsed_secs_f64() % t_last;
+    let i = samples.iter().position(|sample| sample.0 >= t).unwrap_or(samples.len() - 1);
+    let to_vec3 = |(x, y, z): (f64, f64, f64)| Vec3::new(x as f32, y as f32, z as f32);
+    ball.translation = if i == 0 {
+        to_vec3(samples[0].1)
+    } else {
+        let (t_0, t_1) = (samples[i - 1].0, samples[i].0);
+        let frac = if t_1 > t_0 { ((t - t_0) / (t_1 - t_0)) as f32 } else { 0.0 };
+        to_vec3(samples[i - 1].1).lerp(to_vec3(samples[i].1), frac)
+    };
+}
+
+// The arc as a line, and a sphere at the instant the ball entered the basket.
+fn draw_trajectory(mut gizmos: Gizmos, shot: Res<Shot>) {
+    let samples = & shot.trajectory_3d.1;
+    let points = samples.iter().map(|(_t, (x, y, z), _)| Vec3::new(*x as f32, *y as f32, *z as f32));
+    gizmos.linestrip(points, Color::srgb(0.3, 0.6, 1.0));
+    for (_t, (x, y, z), flag_enter_instant) in samples {
+        if *flag_enter_instant {
+            gizmos.sphere(Isometry3d::from_translation(Vec3::new(*x as f32, *y as f32, *z as f32)),
+                          BALL_RADIUS, Color::srgb(0.0, 1.0, 0.3));
+        }
+    }
+}
@@ -0,0 +1,821 @@
+//! Command line arguments.
+//!
+//! A small hand made parser, the program only needs a few flags.
+
+use basketball_trajectory::court::League;
+use basketball_trajectory::output_names::OutputSuffix;
+use basketball_trajectory::wind::WindSample;
+use basketball_trajectory::display_cmd::{Charset, ColorMode, RenderMode};
+use basketball_trajectory::frames::Frame;
+use basketball_trajectory::i18n::Lang;
+use basketball_trajectory::monte_carlo::Defender;
+use basketball_trajectory::pass::CATCH_HEIGHT;
+use basketball_trajectory::plot_svg::{AnimationMode, Theme, SMALL_MULTIPLES_MAX_PANELS};
+use basketball_trajectory::scenario::{ScenarioFile, PRESET_NAMES};
+use basketball_trajectory::svg_gen::OutputMode;
+use basketball_trajectory::targets::Target;
+use basketball_trajectory::telemetry::TelemetryFormat;
+use basketball_trajectory::timing::DEFAULT_RELEASE_TIME;
+use basketball_trajectory::tracking::{Smoothing, DEFAULT_SMOOTHING_WINDOW};
+use basketball_trajectory::units::{parse_quantity, Quantity};
+
+const DEFAULT_RANDOM_SHOTS: usize = 5;
+const DEFAULT_BENCH_SAMPLES: u32 = 1_000_000;
+const MAX_PRECISION: usize = 12;     // decimal places
+
+pub struct CmdArgs {
+    pub svg_output_mode: OutputMode,
+    pub flag_cmd_axes: bool,
+    pub cmd_cols: Option<usize>,
+    pub cmd_rows: Option<usize>,
+    pub cmd_rows_meters: Option<f64>,
+    pub cmd_cols_meters: Option<f64>,
+    pub cmd_render_mode: RenderMode,
+    pub cmd_charset: Charset,
+    pub cmd_filename: Option<String>,
+    pub flag_cmd_play: bool,
+    /// Frames per second of --svg-frames and --cmd-play, None is the samples for --cmd-play.
+    pub fps: Option<f64>,
+    pub svg_frames_folder: Option<String>,
+    pub color_mode: ColorMode,
+    // None is the language of the locale.
+    pub lang: Option<Lang>,
+    pub flag_decimal_comma: bool,
+    pub cmd_ball_char: Option<char>,
+    pub cmd_background_char: Option<char>,
+    pub flag_svg_markers: bool,
+    pub flag_svg_responsive: bool,
+    pub theme: Theme,
+    pub animation_mode: AnimationMode,
+    pub svg_trail_sec: Option<f64>,
+    pub playback: Option<f64>,
+    pub svg_margin: f32,
+    pub svg_max_markers: usize,
+    pub svg_simplify_tolerance: f32,
+    pub html_filename: Option<String>,
+    pub flag_html_controls: bool,
+    pub flag_html_pan_zoom: bool,
+    pub html_sliders_filename: Option<String>,
+    pub svg_phase_filename: Option<String>,
+    pub svg_energy_filename: Option<String>,
+    pub svg_frame_filename: Option<String>,
+    /// The frame of the positions of the list of the instants and of --svg-frame.
+    pub frame: Frame,
+    pub svg_anaglyph_filename: Option<String>,
+    pub svg_speed_map_filename: Option<String>,
+    pub svg_grid_filename: Option<String>,
+    // The angles of --svg-grid, from, to and the step, in degrees.
+    pub grid_angles: Option<(f64, f64, f64)>,
+    pub latex_filename: Option<String>,
+    pub flag_explain: bool,
+    pub flag_compare_drag: bool,
+    pub wind: Vec<WindSample>,
+    pub temperature_c: Option<f64>,
+    pub humidity_percent: Option<f64>,
+    pub flag_rebound: bool,
+    pub ball_pressure: Option<f64>,
+    pub coriolis_latitude: Option<f64>,
+    pub azimuth_deg: f64,
+    pub tracking_filename: Option<String>,
+    pub tracking_smoothing: Option<Smoothing>,
+    pub tracking_window: usize,
+    pub scenario_filename: Option<String>,
+    pub preset_scenario: Option<ScenarioFile>,
+    pub league: League,
+    pub time_budget: Option<f64>,
+    pub release_time: f64,
+    pub save_scenario_filename: Option<String>,
+    pub save_run_filename: Option<String>,
+    /// Tolerance in meters of the keyframes of the run file, None saves all the samples.
+    pub run_keyframes_tolerance: Option<f64>,
+    pub event_log_filename: Option<String>,
+    /// Decimal places of the numbers of the outputs, None keeps the default of each one.
+    pub precision: Option<usize>,
+    pub output_suffix: OutputSuffix,
+    pub flag_force: bool,
+    pub flag_exit_code: bool,
+    pub flag_strict: bool,
+    // The run file of the replay subcommand.
+    pub replay_filename: Option<String>,
+    // The two run files of the compare subcommand.
+    pub compare_filenames: Option<(String, String)>,
+    pub svg_compare_filename: Option<String>,
+    pub batch_filename: Option<String>,
+    pub checkpoint_filename: Option<String>,
+    /// The folder of the figures and the index page of --batch.
+    pub gallery_folder: Option<String>,
+    pub batch_csv_filename: Option<String>,
+    pub flag_resume: bool,
+    pub quiz_rounds: Option<usize>,
+    // The shots of the random subcommand.
+    pub random_shots: Option<usize>,
+    // The samples of the bench subcommand.
+    pub bench_samples: Option<u32>,
+    pub pass_distance: Option<f64>,
+    pub catch_height: f64,
+    pub runner_speed: Option<f64>,
+    pub flag_free_throws: bool,
+    pub monte_carlo_shots: Option<usize>,
+    pub make_rate_shots: Option<usize>,
+    pub svg_make_rate_filename: Option<String>,
+    pub balls: Vec<(f64, f64)>,
+    pub v_0: Option<f64>,
+    pub teta_0_deg: Option<f64>,
+    pub basket_distance: Option<f64>,
+    // They replace the targets of the scenario.
+    pub targets: Vec<Target>,
+    pub velocity: Option<(f64, f64, f64)>,
+    pub player_height: Option<f64>,
+    pub standing_reach: Option<f64>,
+    pub jump_height: f64,
+    pub defender_distance: Option<f64>,
+    pub defender_reach: f64,
+    pub seed: Option<u64>,
+    pub num_steps: Option<u32>,
+    pub flag_auto_stop: bool,
+    pub post_score_sec: Option<f64>,
+    pub stream_csv_filename: Option<String>,
+    pub stream_svg_filename: Option<String>,
+    pub udp_addr: Option<String>,
+    pub udp_format: TelemetryFormat,
+    #[cfg(feature = "scripting")]
+    pub script_filename: Option<String>,
+    #[cfg(feature = "websocket")]
+    pub ws_addr: Option<String>,
+}
+
+impl Default for CmdArgs {
+    fn default() -> Self {
+        CmdArgs {
+            svg_output_mode: OutputMode::Pretty,
+            flag_cmd_axes: true,
+            cmd_cols: None,
+            cmd_rows: None,
+            cmd_rows_meters: None,
+            cmd_cols_meters: None,
+            cmd_render_mode: RenderMode::Chars,
+            cmd_charset: Charset::ascii(),
+            cmd_filename: None,
+            flag_cmd_play: false,
+            fps: None,
+            svg_frames_folder: None,
+            color_mode: ColorMode::Auto,
+            lang: None,
+            flag_decimal_comma: false,
+            cmd_ball_char: None,
+            cmd_background_char: None,
+            flag_svg_markers: true,
+            flag_svg_responsive: false,
+            theme: Theme::dark(),
+            animation_mode: AnimationMode::Smil,
+            svg_trail_sec: None,
+            playback: None,
+            svg_margin: 20.0,
+            svg_max_markers: 200,
+            svg_simplify_tolerance: 0.1,
+            html_filename: None,
+            flag_html_controls: false,
+            flag_html_pan_zoom: false,
+            html_sliders_filename: None,
+            svg_phase_filename: None,
+            svg_energy_filename: None,
+            svg_frame_filename: None,
+            frame: Frame::World,
+            svg_anaglyph_filename: None,
+            svg_speed_map_filename: None,
+            svg_grid_filename: None,
+            grid_angles: None,
+            latex_filename: None,
+            flag_explain: false,
+            flag_compare_drag: false,
+            wind: Vec::new(),
+            temperature_c: None,
+            humidity_percent: None,
+            flag_rebound: false,
+            ball_pressure: None,
+            coriolis_latitude: None,
+            azimuth_deg: 0.0,
+            tracking_filename: None,
+            tracking_smoothing: None,
+            tracking_window: DEFAULT_SMOOTHING_WINDOW,
+            scenario_filename: None,
+            preset_scenario: None,
+            league: League::Fiba,
+            time_budget: None,
+            release_time: DEFAULT_RELEASE_TIME,
+            save_scenario_filename: None,
+            save_run_filename: None,
+            run_keyframes_tolerance: None,
+            event_log_filename: None,
+            precision: None,
+            output_suffix: OutputSuffix::None,
+            flag_force: false,
+            flag_exit_code: false,
+            flag_strict: false,
+            replay_filename: None,
+            compare_filenames: None,
+            svg_compare_filename: None,
+            batch_filename: None,
+            checkpoint_filename: None,
+            gallery_folder: None,
+            batch_csv_filename: None,
+            flag_resume: false,
+            quiz_rounds: None,
+            random_shots: None,
+            bench_samples: None,
+            pass_distance: None,
+            catch_height: CATCH_HEIGHT,
+            runner_speed: None,
+            flag_free_throws: false,
+            monte_carlo_shots: None,
+            make_rate_shots: None,
+            svg_make_rate_filename: None,
+            balls: Vec::new(),
+            v_0: None,
+            teta_0_deg: None,
+            basket_distance: None,
+            targets: Vec::new(),
+            velocity: None,
+            player_height: None,
+            standing_reach: None,
+            jump_height: 0.0,
+            defender_distance: None,
+            defender_reach: Defender::DEFAULT_REACH,
+            seed: None,
+            num_steps: None,
+            flag_auto_stop: false,
+            post_score_sec: None,
+            stream_csv_filename: None,
+            stream_svg_filename: None,
+            udp_addr: None,
+            udp_format: TelemetryFormat::Json,
+            #[cfg(feature = "scripting")]
+            script_filename: None,
+            #[cfg(feature = "websocket")]
+            ws_addr: None,
+        }
+    }
+}
+
+pub fn usage() -> String {
+    "Usage: basketball_trajectory [OPTIONS]
+       basketball_trajectory replay <RUN_FILE> [OPTIONS]
+       basketball_trajectory random [SHOTS] [OPTIONS]
+       basketball_trajectory compare <RUN_A> <RUN_B> [OPTIONS]
+       basketball_trajectory bench [SAMPLES]
+
+Commands:
+  replay <RUN_FILE>                Make the outputs again from a run of --save-run, without simulating.
+  random [SHOTS]                   Simulate random shots, with the seed of --seed, and print them. [default: 5 shots]
+  compare <RUN_A> <RUN_B>          Print the differences of the positions and of the results of two runs of --save-run.
+  bench [SAMPLES]                  Time the simulations and the SVG figure and count their allocations. [default: 1000000 samples]
+
+Options:
+  --cmd-no-axes                    Print the text plot without the frame and the meter labels.
+  --cmd-cols <N>                   Columns of the text plot. [default: fits the terminal, or 80]
+  --cmd-rows <N>                   Maximum rows of the text plot. [default: fits the terminal, or 50]
+  --cmd-x-meters <METERS>          Width in meters of the text plot. [default: fits the trajectory]
+  --cmd-y-meters <METERS>          Height in meters of the text plot. [default: fits the trajectory]
+  --cmd-half-blocks                Double the vertical resolution of the text plot with the Unicode half blocks.
+  --cmd-charset <ascii|unicode>    Glyphs of the text plot, plain ASCII or Unicode symbols. [default: ascii]
+  --cmd-ball <CHAR>                Glyph of the ball in the text plot, a wide one like an emoji shifts its row.
+  --cmd-background <CHAR>          Glyph of the empty space in the text plot, like '.' for the screen readers.
+  --cmd-file <FILE>                Also write the text plot to a file.
+  --cmd-play                       Animate the ball in the text plot at the simulated rate, in a terminal.
+  --fps <FPS>                      Frames per second of --svg-frames and --cmd-play, the positions are interpolated. [default: 30, the samples in --cmd-play]
+  --color <auto|always|never>      Color the terminal output, auto when it's a terminal and NO_COLOR isn't set. [default: auto]
+  --lang <en|pt>                   Language of the printed text, English or Portuguese. [default: from the locale, or en]
+  --decimal-comma                  Write the numbers of the printed reports with a decimal comma, like 3,05 m, the exported files keep the point.
+  --svg-output <pretty|minified>   Layout of the SVG file, indented or without whitespace. [default: pretty]
+  --svg-no-markers                 Draw only the arc line, without a circle for each instant.
+  --svg-max-markers <N>            Maximum number of circles drawn for the instants. [default: 200]
+  --svg-simplify <PIXELS>          Tolerance of the simplification of the arc line, 0 keeps all points. [default: 0.1]
+  --svg-responsive                 Scale the SVG to the width of its container (width 100% and viewBox).
+  --svg-margin <PIXELS>            Empty space around the scene in the SVG. [default: 20]
+  --theme <dark|light>             Colors of the SVG, dark for the screen or light for paper. [default: dark]
+  --svg-animation <smil|css>       Animate the ball with SVG animateMotion or with CSS keyframes. [default: smil]
+  --svg-trail <SECONDS>            Animate a trail behind the ball, each position fades out in the simulated seconds.
+  --playback <FACTOR>              Speed of the animation against the simulated time, 0.25 is slow motion. [default: a loop of 3 s]
+  --svg-frames <DIR>               Also write a figure for each frame of the video of the shot, at --fps and --playback, to a folder.
+  --html <FILE>                    Also write an HTML page with the figure.
+  --html-controls                  Add play/pause and a slider to scrub the animation to the HTML page.
+  --html-pan-zoom                  Zoom with the mouse wheel and pan by dragging the figure in the HTML page.
+  --html-sliders <FILE>            Also write an HTML page with sliders of the angle and the speed that redraw the shot.
+  --svg-phase <FILE>               Also write the phase space plot, v_y against the height y, to an SVG file.
+  --svg-energy <FILE>              Also write the plot of the kinetic, potential and total energy over the time to an SVG file.
+  --frame <world|shooter|hoop>     Positions of the list of the instants from the court, the release or the rim. [default: world]
+  --svg-frame <FILE>               Also write the plot of the trajectory in the frame of --frame to an SVG file.
+  --svg-anaglyph <FILE>            Also write the shot in 3D as a red and cyan anaglyph, for 3D glasses, to an SVG file.
+  --svg-speed-map <FILE>           Also write the heatmap of the speed that scores from each point of the court to an SVG file.
+  --svg-grid <FILE>                Also write a grid of small figures of the shot at the angles of --grid-angles to an SVG file.
+  --grid-angles <FROM,TO,STEP>     The angles in degrees of the figures of --svg-grid. [default: 35,60,5]
+  --compare-drag                   Also simulate the shot with the air drag and print a table of the two.
+  --temperature <CELSIUS>          Temperature of the air, for the shot with drag, from -60 to 60. [default: 20]
+  --humidity <PERCENT>             Relative humidity of the air, for the shot with drag. [default: 0]
+  --wind <T,VX,VY>                 The wind at T s, VX m/s towards the basket and VY m/s up, repeat it for gusts, for the shot with drag.
+  --rebound                        When the shot misses off the rim or the backboard, simulate the carom and where it lands.
+  --ball-pressure <BAR>            Inflation of the ball above the air outside, a softer ball bounces less. [default: 0.55]
+  --coriolis <LATITUDE>            Print the deflection of the shot by the rotation of the Earth at the LATITUDE in degrees.
+  --azimuth <DEGREES>              Direction of the shot from the north, clockwise, for --coriolis. [default: 0]
+  --latex <FILE>                   Also write a LaTeX document with the worked solution of the shot and its figure.
+  --explain                        Print the equations with the numbers of the throw, the top of the arc, the closest instant and the landing.
+  --track <FILE>                   Overlay the measured t,x,y points of a CSV file on the SVG and print the RMS error.
+  --track-smooth <average|kalman>  Smooth the points of --track with a moving average or a Kalman filter of the flight.
+  --track-window <N>               Points of the moving average of --track-smooth. [default: 5]
+  --scenario <FILE>                Read the shot and the outputs from a scenario file, JSON, TOML (.toml) or YAML (.yaml).
+  --preset <NAME>                  Start from a preset scenario: default, free-throw, free-throw-underhand, soccer-penalty, volleyball-serve or darts.
+  --league <fiba|nba|wnba|ncaa>    Three-point line of the court, for the value of the shot. [default: fiba]
+  --time-budget <SECONDS>          Check that the release and the flight fit in the time left, like the shot clock.
+  --release-time <SECONDS>         Time from the catch to the release, for --time-budget. [default: 0.5]
+  --save-scenario <FILE>           Write the scenario of this run to a file, JSON, TOML (.toml) or YAML (.yaml).
+  --save-run <FILE>                Write the scenario and the full trajectory of this run to a JSON file, for replay.
+  --run-keyframes <METERS>         Save the run of --save-run as keyframes, the samples between them are rebuilt within METERS.
+  --event-log <FILE>               Write each sample and each event of the shot as a line of JSON to a file, or to stderr with -.
+  --precision <DIGITS>             Decimal places of the numbers of the report, the CSV, the JSON and the SVG coordinates, 0 to 12. [default: 2, 6 in the CSV, 4 in the JSON]
+  --output-suffix <SUFFIX>         Add timestamp, the UTC time of the run, or counter, the first free number, to the output filenames. [default: none]
+  --force                          Overwrite the output files that exist, instead of stopping.
+  --exit-code                      Exit with the result of the shot, 0 for a make, 2 rim out, 3 airball, 4 blocked, 5 short, 6 long, 7 obstacle.
+  --strict                         Stop with an error on an implausible input, like a release above 3 m, instead of a warning.
+  --svg-compare <FILE>             Write the arcs of the two runs of compare overlaid to an SVG file.
+  --batch <FILE>                   Simulate the scenarios of a batch file instead of the shot, JSON, TOML (.toml) or YAML (.yaml).
+  --checkpoint <FILE>              Write the results of --batch to a checkpoint file as they are computed.
+  --resume                         Continue the --batch of the --checkpoint file, without the scenarios done.
+  --gallery <DIR>                  Also write the figure of each scenario of --batch and an index.html page of them to a folder.
+  --batch-csv <FILE>               Also write the summary table of --batch to a CSV file.
+  --quiz <ROUNDS>                  Guess if random shots score and how high they go, then see the simulation, instead of the shot.
+  --seed <N>                       Seed of the random shots, the same seed gives the same shots. [default: from the clock]
+  --pass <METERS>                  Solve a chest pass, a lob and a bounce pass to a teammate METERS away, instead of the shot.
+  --catch-height <METERS>          Height that the teammate catches the passes. [default: 1.5]
+  --runner-speed <SPEED>           The teammate of --pass runs at SPEED m/s away from the passer, negative towards, and the passes lead them.
+  --free-throws                    Compare the overhand and the underhand free throws, instead of the shot.
+  --monte-carlo <SHOTS>            Simulate SHOTS shots with noise in the release and print the make rate, instead of the shot.
+  --defender <METERS>              A defender METERS in front of the shooter contests the shots of --monte-carlo and blocks the low shots.
+  --make-rate <SHOTS>              Simulate SHOTS shots with noise at each distance from 2 to 10 m and print the make rates, instead of the shot.
+  --svg-make-rate <FILE>           Also write the chart of the make rate by the distance of --make-rate to an SVG file.
+  --defender-reach <METERS>        Height of the hand of the defender. [default: 2.9]
+  --ball <V0,ANGLE>                Shoot a ball at V0 m/s and ANGLE degrees, repeat it to shoot several balls at once, instead of the shot.
+  --v0 <SPEED>                     Speed of the release, in m/s or with a unit like 36km/h, instead of the one of the scenario.
+  --angle <ANGLE>                  Angle of the release above the horizontal, in degrees or with a unit like 0.8rad.
+  --distance <LENGTH>              Horizontal distance from the release to the center of the basket, in m or with a unit like 15ft.
+  --target <X,Y[,TOLERANCE]>       Also check a target centered at X,Y m, hit within TOLERANCE m, repeat it for more. [default tolerance: 0.1]
+  --velocity <VX,VY,VZ>            Initial velocity in m/s along the XX, YY (up) and ZZ axes, instead of v_0 and the angles.
+  --player-height <METERS>         Release the ball from the height of a player METERS tall, instead of the one of the scenario.
+  --standing-reach <METERS>        Standing reach of the player of --player-height. [default: 1.33 times the height]
+  --jump <METERS>                  Height of the jump of the player of --player-height. [default: 0, a set shot]
+  --steps <N>                      Number of steps of the simulation, it replaces the one of the scenario. [default: 60]
+  --auto-stop                      Simulate until the ball scores, reaches the floor or leaves the court, instead of simulation_sec.
+  --post-score <SECONDS>           After a made basket, drop the ball through the net and bounce it for at most SECONDS.
+  --stream-csv <FILE>              Write the samples t,x,y,vx,vy,speed,ax,ay,entered to a CSV file as they are computed, instead of the shot.
+  --stream-svg <FILE>              Write a static figure to an SVG file as the samples are computed, instead of the shot.
+  --script <FILE>                  Run a rhai script of scenarios instead of the shot. [feature: scripting]
+  --udp <ADDR>                     Send each sample as JSON in a UDP packet to ADDR at the simulated rate, like 127.0.0.1:9000.
+  --udp-osc                        Send the UDP packets as OSC messages, /ball t x y vx vy entered.
+  --ws <ADDR>                      Stream the samples as JSON to one WebSocket client at ADDR, like 127.0.0.1:9001. [feature: websocket]
+  -h, --help                       Print this help.
+".to_string()
+}
+
+/// Parses the arguments, without the program name.
+/// Returns Ok(None) when the help was asked.
+pub fn parse_args<I: Iterator<Item = String>>(args: I) -> Result<Option<CmdArgs>, String> {
+    let mut cmd_args = CmdArgs::default();
+    let mut args = args.peekable();
+    // The subcommand is the first argument.
+    if args.next_if_eq("replay").is_some() {
+        cmd_args.replay_filename = Some(next_value(& mut args, "replay")?);
+    } else if args.next_if_eq("random").is_some() {
+        let num_shots = match args.next_if(|arg| !arg.starts_with('-')) {
+            Some(value) => parse_min_usize(&value, "random", 1)?,
+            None => DEFAULT_RANDOM_SHOTS,
+        };
+        cmd_args.random_shots = Some(num_shots);
+    } else if args.next_if_eq("compare").is_some() {
+        let run_a = next_value(& mut args, "compare")?;
+        let run_b = next_value(& mut args, "compare")?;
+        cmd_args.compare_filenames = Some((run_a, run_b));
+    } else if args.next_if_eq("bench").is_some() {
+        let num_samples = match args.next_if(|arg| !arg.starts_with('-')) {
+            Some(value) => parse_min_usize(&value, "bench", 10)?.min(u32::MAX as usize) as u32,
+            None => DEFAULT_BENCH_SAMPLES,
+        };
+        cmd_args.bench_samples = Some(num_samples);
+    }
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--cmd-no-axes" => cmd_args.flag_cmd_axes = false,
+            "--cmd-cols" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_cols = Some(parse_min_usize(&value, &arg, 2)?);
+            },
+            "--cmd-rows" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_rows = Some(parse_min_usize(&value, &arg, 2)?);
+            },
+            "--cmd-x-meters" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_cols_meters = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--cmd-y-meters" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_rows_meters = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--cmd-half-blocks" => cmd_args.cmd_render_mode = RenderMode::HalfBlocks,
+            "--cmd-charset" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_charset = Charset::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected ascii or unicode.", value, arg))?;
+            },
+            "--cmd-ball" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_ball_char = Some(parse_char(&value, &arg)?);
+            },
+            "--cmd-background" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.cmd_background_char = Some(parse_char(&value, &arg)?);
+            },
+            "--cmd-file" => cmd_args.cmd_filename = Some(next_value(& mut args, &arg)?),
+            "--cmd-play" => cmd_args.flag_cmd_play = true,
+            "--fps" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.fps = Some(parse_positive(&value, &arg)?);
+            },
+            "--color" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.color_mode = ColorMode::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected auto, always or never.", value, arg))?;
+            },
+            "--lang" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.lang = Some(Lang::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected en or pt.", value, arg))?);
+            },
+            "--decimal-comma" => cmd_args.flag_decimal_comma = true,
+            "--svg-output" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.svg_output_mode = OutputMode::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected pretty or minified.", value, arg))?;
+            },
+            "--svg-no-markers" => cmd_args.flag_svg_markers = false,
+            "--svg-max-markers" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.svg_max_markers = value.parse::<usize>()
+                    .map_err(|_| format!("Invalid value \"{}\" for {}, expected a non negative integer.", value, arg))?;
+            },
+            "--svg-simplify" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.svg_simplify_tolerance = value.parse::<f32>().ok().filter(|tolerance| *tolerance >= 0.0)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected a non negative number.", value, arg))?;
+            },
+            "--svg-responsive" => cmd_args.flag_svg_responsive = true,
+            "--svg-margin" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.svg_margin = value.parse::<f32>().ok().filter(|margin| *margin >= 0.0)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected a non negative number.", value, arg))?;
+            },
+            "--theme" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.theme = Theme::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected dark or light.", value, arg))?;
+            },
+            "--svg-animation" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.animation_mode = AnimationMode::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected smil or css.", value, arg))?;
+            },
+            "--svg-trail" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.svg_trail_sec = Some(parse_positive_quantity(&value, &arg, Quantity::Time)?);
+            },
+            "--playback" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.playback = Some(parse_positive(&value, &arg)?);
+            },
+            "--svg-frames" => cmd_args.svg_frames_folder = Some(next_value(& mut args, &arg)?),
+            "--html" => cmd_args.html_filename = Some(next_value(& mut args, &arg)?),
+            "--html-sliders" => cmd_args.html_sliders_filename = Some(next_value(& mut args, &arg)?),
+            "--html-controls" => cmd_args.flag_html_controls = true,
+            "--html-pan-zoom" => cmd_args.flag_html_pan_zoom = true,
+            "--svg-phase" => cmd_args.svg_phase_filename = Some(next_value(& mut args, &arg)?),
+            "--event-log" => cmd_args.event_log_filename = Some(next_value(& mut args, &arg)?),
+            "--precision" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.precision = match value.parse::<usize>() {
+                    Ok(digits) if digits <= MAX_PRECISION => Some(digits),
+                    _ => return Err(format!("Invalid value \"{}\" for {}, expected an integer from 0 to {}.", value, arg, MAX_PRECISION)),
+                };
+            },
+            "--output-suffix" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.output_suffix = OutputSuffix::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected none, timestamp or counter.", value, arg))?;
+            },
+            "--force" => cmd_args.flag_force = true,
+            "--exit-code" => cmd_args.flag_exit_code = true,
+            "--strict" => cmd_args.flag_strict = true,
+            "--svg-compare" => cmd_args.svg_compare_filename = Some(next_value(& mut args, &arg)?),
+            "--svg-energy" => cmd_args.svg_energy_filename = Some(next_value(& mut args, &arg)?),
+            "--frame" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.frame = Frame::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected world, shooter or hoop.", value, arg))?;
+            },
+            "--svg-frame" => cmd_args.svg_frame_filename = Some(next_value(& mut args, &arg)?),
+            "--svg-anaglyph" => cmd_args.svg_anaglyph_filename = Some(next_value(& mut args, &arg)?),
+            "--svg-speed-map" => cmd_args.svg_speed_map_filename = Some(next_value(& mut args, &arg)?),
+            "--svg-grid" => cmd_args.svg_grid_filename = Some(next_value(& mut args, &arg)?),
+            "--grid-angles" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.grid_angles = Some(parse_grid_angles(&value, &arg)?);
+            },
+            "--latex" => cmd_args.latex_filename = Some(next_value(& mut args, &arg)?),
+            "--compare-drag" => cmd_args.flag_compare_drag = true,
+            "--rebound" => cmd_args.flag_rebound = true,
+            "--ball-pressure" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.ball_pressure = Some(parse_positive(&value, &arg)?);
+            },
+            "--coriolis" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.coriolis_latitude = Some(parse_quantity_in_range(&value, &arg, Quantity::Angle, -90.0, 90.0)?);
+            },
+            "--azimuth" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.azimuth_deg = parse_quantity_in_range(&value, &arg, Quantity::Angle, 0.0, 360.0)?;
+            },
+            "--explain" => cmd_args.flag_explain = true,
+            "--track" => cmd_args.tracking_filename = Some(next_value(& mut args, &arg)?),
+            "--track-smooth" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.tracking_smoothing = Some(Smoothing::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected average or kalman.", value, arg))?);
+            },
+            "--track-window" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.tracking_window = parse_min_usize(&value, &arg, 3)?;
+            },
+            "--scenario" => cmd_args.scenario_filename = Some(next_value(& mut args, &arg)?),
+            "--preset" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.preset_scenario = Some(ScenarioFile::preset(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected one of {}.", value, arg, PRESET_NAMES.join(", ")))?);
+            },
+            "--league" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.league = League::from_name(&value)
+                    .ok_or(format!("Invalid value \"{}\" for {}, expected fiba, nba, wnba or ncaa.", value, arg))?;
+            },
+            "--time-budget" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.time_budget = Some(parse_positive_quantity(&value, &arg, Quantity::Time)?);
+            },
+            "--release-time" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.release_time = parse_positive_quantity(&value, &arg, Quantity::Time)?;
+            },
+            "--save-scenario" => cmd_args.save_scenario_filename = Some(next_value(& mut args, &arg)?),
+            "--save-run" => cmd_args.save_run_filename = Some(next_value(& mut args, &arg)?),
+            "--run-keyframes" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.run_keyframes_tolerance = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--batch" => cmd_args.batch_filename = Some(next_value(& mut args, &arg)?),
+            "--checkpoint" => cmd_args.checkpoint_filename = Some(next_value(& mut args, &arg)?),
+            "--gallery" => cmd_args.gallery_folder = Some(next_value(& mut args, &arg)?),
+            "--batch-csv" => cmd_args.batch_csv_filename = Some(next_value(& mut args, &arg)?),
+            "--resume" => cmd_args.flag_resume = true,
+            "--quiz" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.quiz_rounds = Some(parse_min_usize(&value, &arg, 1)?);
+            },
+            "--seed" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.seed = Some(value.parse::<u64>()
+                    .map_err(|_| format!("Invalid value \"{}\" for {}, expected a non negative integer.", value, arg))?);
+            },
+            "--pass" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.pass_distance = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--catch-height" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.catch_height = parse_positive_quantity(&value, &arg, Quantity::Length)?;
+            },
+            "--runner-speed" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.runner_speed = Some(parse_quantity_in_range(&value, &arg, Quantity::Speed, -12.0, 12.0)?);
+            },
+            "--free-throws" => cmd_args.flag_free_throws = true,
+            "--monte-carlo" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.monte_carlo_shots = Some(parse_min_usize(&value, &arg, 1)?);
+            },
+            "--make-rate" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.make_rate_shots = Some(parse_min_usize(&value, &arg, 1)?);
+            },
+            "--svg-make-rate" => cmd_args.svg_make_rate_filename = Some(next_value(& mut args, &arg)?),
+            "--defender" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.defender_distance = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--temperature" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.temperature_c = Some(parse_in_range(&value, &arg, -60.0, 60.0)?);
+            },
+            "--humidity" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.humidity_percent = Some(parse_in_range(&value, &arg, 0.0, 100.0)?);
+            },
+            "--wind" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.wind.push(parse_wind(&value, &arg)?);
+            },
+            "--v0" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.v_0 = Some(parse_positive_quantity(&value, &arg, Quantity::Speed)?);
+            },
+            "--angle" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.teta_0_deg = Some(parse_quantity_in_range(&value, &arg, Quantity::Angle, -90.0, 90.0)?);
+            },
+            "--distance" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.basket_distance = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--target" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.targets.push(parse_target(&value, &arg)?);
+            },
+            "--velocity" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.velocity = Some(parse_velocity(&value, &arg)?);
+            },
+            "--player-height" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.player_height = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--standing-reach" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.standing_reach = Some(parse_positive_quantity(&value, &arg, Quantity::Length)?);
+            },
+            "--jump" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.jump_height = parse_quantity_in_range(&value, &arg, Quantity::Length, 0.0, 1.5)?;
+            },
+            "--ball" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.balls.push(parse_ball(&value, &arg)?);
+            },
+            "--defender-reach" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.defender_reach = parse_positive_quantity(&value, &arg, Quantity::Length)?;
+            },
+            "--steps" => {
+                let value = next_value(& mut args, &arg)?;
+                let num_steps = parse_min_usize(&value, &arg, 3)?;
+                cmd_args.num_steps = Some(u32::try_from(num_steps)
+                    .map_err(|_| format!("Invalid value \"{}\" for {}, expected at most {}.", value, arg, u32::MAX))?);
+            },
+            "--auto-stop" => cmd_args.flag_auto_stop = true,
+            "--post-score" => {
+                let value = next_value(& mut args, &arg)?;
+                cmd_args.post_score_sec = Some(parse_positive_quantity(&value, &arg, Quantity::Time)?);
+            },
+            "--stream-csv" => cmd_args.stream_csv_filename = Some(next_value(& mut args, &arg)?),
+            "--stream-svg" => cmd_args.stream_svg_filename = Some(next_value(& mut args, &arg)?),
+            "--udp" => cmd_args.udp_addr = Some(next_value(& mut args, &arg)?),
+            "--udp-osc" => cmd_args.udp_format = TelemetryFormat::Osc,
+            #[cfg(feature = "scripting")]
+            "--script" => cmd_args.script_filename = Some(next_value(& mut args, &arg)?),
+            #[cfg(not(feature = "scripting"))]
+            "--script" => return Err(format!("{} needs the scripting feature, build with --features scripting.", arg)),
+            #[cfg(feature = "websocket")]
+            "--ws" => cmd_args.ws_addr = Some(next_value(& mut args, &arg)?),
+            #[cfg(not(feature = "websocket"))]
+            "--ws" => return Err(format!("{} needs the websocket feature, build with --features websocket.", arg)),
+            _ => return Err(format!("Unknown argument \"{}\".", arg)),
+        }
+    }
+    if cmd_args.run_keyframes_tolerance.is_some() && cmd_args.save_run_filename.is_none() {
+        return Err("--run-keyframes needs the file of --save-run <FILE>.".to_string());
+    }
+    if cmd_args.runner_speed.is_some() && cmd_args.pass_distance.is_none() {
+        return Err("--runner-speed needs the teammate of --pass <METERS>.".to_string());
+    }
+    if cmd_args.gallery_folder.is_some() && cmd_args.batch_filename.is_none() {
+        return Err("--gallery needs the scenarios of --batch <FILE>.".to_string());
+    }
+    if cmd_args.batch_csv_filename.is_some() && cmd_args.batch_filename.is_none() {
+        return Err("--batch-csv needs the scenarios of --batch <FILE>.".to_string());
+    }
+    if cmd_args.flag_resume && cmd_args.checkpoint_filename.is_none() {
+        return Err("--resume needs the file of --checkpoint <FILE>.".to_string());
+    }
+    if cmd_args.grid_angles.is_some() && cmd_args.svg_grid_filename.is_none() {
+        return Err("--grid-angles needs the figure of --svg-grid <FILE>.".to_string());
+    }
+    Ok(Some(cmd_args))
+}
+
+fn parse_positive(value: & str, flag: & str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(number) if number > 0.0 && number.is_finite() => Ok(number),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected a positive number.", value, flag)),
+    }
+}
+
+// A positive length, speed, angle or time, in the unit of the simulation or with a unit like 15ft.
+fn parse_positive_quantity(value: & str, flag: & str, quantity: Quantity) -> Result<f64, String> {
+    match parse_quantity(value, quantity) {
+        Some(number) if number > 0.0 => Ok(number),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected a positive {} in {}.", value, flag, quantity.name(), quantity.unit_names())),
+    }
+}
+
+fn parse_quantity_in_range(value: & str, flag: & str, quantity: Quantity, min: f64, max: f64) -> Result<f64, String> {
+    match parse_quantity(value, quantity) {
+        Some(number) if (min..=max).contains(&number) => Ok(number),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected a {} from {} to {} {}, or in {}.", value, flag, quantity.name(),
+                         min, max, quantity.units()[0].0, quantity.unit_names())),
+    }
+}
+
+// The numbers separated by commas, each one of its quantity, None for the ones that aren't.
+fn parse_quantities(value: & str, quantities: & [Quantity]) -> Vec<Option<f64>> {
+    value.split(',').enumerate()
+        .map(|(i, number)| quantities.get(i).and_then(|quantity| parse_quantity(number, *quantity)))
+        .collect()
+}
+
+// The speed and the angle of a ball, "V0,ANGLE".
+fn parse_ball(value: & str, flag: & str) -> Result<(f64, f64), String> {
+    let numbers = parse_quantities(value, &[Quantity::Speed, Quantity::Angle]);
+    match numbers[..] {
+        [Some(v_0), Some(teta_0_deg)] if v_0 > 0.0 => Ok((v_0, teta_0_deg)),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected the speed and the angle like 9.8,50.", value, flag)),
+    }
+}
+
+fn parse_in_range(value: & str, flag: & str, min: f64, max: f64) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(number) if (min..=max).contains(&number) => Ok(number),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected a number from {} to {}.", value, flag, min, max)),
+    }
+}
+
+fn parse_wind(value: & str, flag: & str) -> Result<WindSample, String> {
+    let numbers = parse_quantities(value, &[Quantity::Time, Quantity::Speed, Quantity::Speed]);
+    match numbers[..] {
+        [Some(t), Some(v_x), Some(v_y)] => Ok(WindSample { t, v_x, v_y }),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected the instant and the velocity like 0.5,-3,0.", value, flag)),
+    }
+}
+
+// The center of a target and its tolerance, "X,Y" or "X,Y,TOLERANCE".
+fn parse_target(value: & str, flag: & str) -> Result<Target, String> {
+    let numbers = parse_quantities(value, &[Quantity::Length, Quantity::Length, Quantity::Length]);
+    match numbers[..] {
+        [Some(x), Some(y)] => Ok(Target::new(x, y)),
+        [Some(x), Some(y), Some(tolerance)] if tolerance > 0.0 => Ok(Target { tolerance, ..Target::new(x, y) }),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected the center and the tolerance of a target like 5,2,0.15.", value, flag)),
+    }
+}
+
+// The angles of a sweep, "FROM,TO,STEP", up to SMALL_MULTIPLES_MAX_PANELS of them.
+fn parse_grid_angles(value: & str, flag: & str) -> Result<(f64, f64, f64), String> {
+    let numbers = parse_quantities(value, &[Quantity::Angle, Quantity::Angle, Quantity::Angle]);
+    match numbers[..] {
+        [Some(from), Some(to), Some(step)] if from > 0.0 && from <= to && to < 90.0 && step > 0.0
+                                              && (to - from) / step < SMALL_MULTIPLES_MAX_PANELS as f64 => Ok((from, to, step)),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected the angles from, to and the step, from 0 to 90° and up to {} of them, like 35,60,5.",
+                         value, flag, SMALL_MULTIPLES_MAX_PANELS)),
+    }
+}
+
+fn parse_velocity(value: & str, flag: & str) -> Result<(f64, f64, f64), String> {
+    let numbers = parse_quantities(value, &[Quantity::Speed, Quantity::Speed, Quantity::Speed]);
+    match numbers[..] {
+        [Some(v_x), Some(v_y), Some(v_z)] if (v_x, v_y, v_z) != (0.0, 0.0, 0.0) => Ok((v_x, v_y, v_z)),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected the 3 components of a velocity that isn't 0, like 7.07,7.07,0.", value, flag)),
+    }
+}
+
+fn parse_min_usize(value: & str, flag: & str, min: usize) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(number) if number >= min => Ok(number),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected an integer of at least {}.", value, flag, min)),
+    }
+}
+
+fn parse_char(value: & str, flag: & str) -> Result<char, String> {
+    let mut chars = value.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if !ch.is_control() => Ok(ch),
+        _ => Err(format!("Invalid value \"{}\" for {}, expected one character.", value, flag)),
+    }
+}
+
+fn next_value<I: Iterator<Item = String>>(args: & mut I, flag: & str) -> Result<String, String> {
+    args.next().ok_or(format!("Missing value for {}.", flag))
+}
@@ -0,0 +1,50 @@
+//! The comparison of two runs, like the same shot before and after a change in the integrator,
+//! or with and without the air drag. The runs can have different instants, so the position of the
+//! second run is interpolated at the instants of the first one.
+
+use crate::trajectory::Trajectory;
+
+/// Values closer than this are the same, in m or s, the rounding of the floats isn't a change.
+pub const SAME_TOLERANCE: f64 = 1e-9;
+
+/// The positions of the two runs at an instant of the first one.
+#[derive(Clone, Copy, Debug)]
+pub struct SampleDelta {
+    pub t: f64,
+    pub a: (f64, f64),
+    pub b: (f64, f64),
+}
+
+impl SampleDelta {
+    /// The position of b minus the one of a.
+    pub fn delta(& self) -> (f64, f64) {
+        (self.b.0 - self.a.0, self.b.1 - self.a.1)
+    }
+
+    pub fn distance(& self) -> f64 {
+        let (d_x, d_y) = self.delta();
+        f64::hypot(d_x, d_y)
+    }
+}
+
+/// The position of the trajectory at the instant t, linear between the samples. None outside of
+/// the samples.
+pub fn position_at(trajectory: & Trajectory, t: f64) -> Option<(f64, f64)> {
+    let samples = & trajectory.1;
+    let i = samples.partition_point(|(t_sample, _, _)| *t_sample < t);
+    let (t_1, (x_1, y_1), _) = *samples.get(i)?;
+    if t_1 == t {
+        return Some((x_1, y_1));
+    }
+    // Before the first sample.
+    let (t_0, (x_0, y_0), _) = *samples.get(i.checked_sub(1)?)?;
+    let frac = (t - t_0) / (t_1 - t_0);
+    Some((x_0 + (x_1 - x_0) * frac, y_0 + (y_1 - y_0) * frac))
+}
+
+/// The deltas at the instants of a that are in the time span of b.
+pub fn sample_deltas(a: & Trajectory, b: & Trajectory) -> Vec<SampleDelta> {
+    a.1.iter()
+        .filter_map(|(t, pos_a, _)| position_at(b, *t).map(|pos_b| SampleDelta { t: *t, a: *pos_a, b: pos_b }))
+        .collect()
+}
@@ -0,0 +1,50 @@
+//! The Coriolis effect of the rotation of the Earth on the shot, an example of a force that is
+//! too small to matter at the scale of a basketball court.
+//!
+//! In the frame of the court the ball has the acceleration -2 * Omega x v, with the angular
+//! velocity Omega of the Earth. It's so small that the velocity is the one of the shot without
+//! it, v = v_0 - g * t, and the deflection is its integral twice:
+//!
+//! ```text
+//! d(t) = -Omega x (v_0 * t^2 - g * t^3 / 3)
+//! ```
+//!
+//! The frame has the XX axis along the shot, YY up and ZZ to the right of the shooter.
+
+use crate::scenario::ScenarioFile;
+use crate::trajectory::GRAVITY;
+use crate::units::degrees_to_radians;
+
+pub const EARTH_ANGULAR_SPEED: f64 = 7.292_115e-5; // rad/s - One turn in a sidereal day.
+
+/// The displacement of the ball by the Coriolis effect, in m.
+#[derive(Clone, Copy, Debug)]
+pub struct CoriolisDeflection {
+    pub t: f64,
+    pub along: f64,
+    pub up: f64,
+    /// To the right of the shooter, negative to the left.
+    pub side: f64,
+}
+
+/// The deflection at the instant t of the shot of the scenario, at the latitude in degrees,
+/// negative to the south, and towards the azimuth in degrees from the north, clockwise.
+pub fn coriolis_deflection(scenario: & ScenarioFile, latitude_deg: f64, azimuth_deg: f64, t: f64) -> CoriolisDeflection {
+    let latitude = degrees_to_radians(latitude_deg);
+    let azimuth = degrees_to_radians(azimuth_deg);
+    // The axis of the Earth points to the north and up, at the latitude.
+    let omega_x = EARTH_ANGULAR_SPEED * f64::cos(latitude) * f64::cos(azimuth);
+    let omega_y = EARTH_ANGULAR_SPEED * f64::sin(latitude);
+    let omega_z = -EARTH_ANGULAR_SPEED * f64::cos(latitude) * f64::sin(azimuth);
+
+    let teta_0 = degrees_to_radians(scenario.throw.teta_0_deg);
+    let (v_0_x, v_0_y) = (scenario.throw.v_0 * f64::cos(teta_0), scenario.throw.v_0 * f64::sin(teta_0));
+    let (w_x, w_y) = (v_0_x * t * t, v_0_y * t * t - GRAVITY * t * t * t / 3.0);
+    // -Omega x w, with w_z = 0.
+    CoriolisDeflection {
+        t,
+        along: omega_z * w_y,
+        up: -omega_z * w_x,
+        side: omega_y * w_x - omega_x * w_y,
+    }
+}
@@ -0,0 +1,190 @@
+//! The three-point line of the court, an arc around the basket that becomes two straight lines
+//! near the sidelines, at the corners. The positions are on the floor, x towards the basket and
+//! z to the side, like the scenario.
+//!
+//! The line is part of the two-point area, a shooter with a foot on it scores only 2 points.
+
+use crate::trajectory::required_speed;
+
+// The half court of the shooter, FIBA, the three-point line is the one of the league.
+pub const HALF_COURT_LENGTH: f64 = 14.0;    // m - From the baseline to the center line.
+pub const COURT_WIDTH: f64 = 15.0;          // m
+pub const BASKET_FROM_BASELINE: f64 = 1.575; // m - The center of the basket.
+pub const KEY_WIDTH: f64 = 4.9;             // m - The restricted area, to the free throw line.
+pub const SPEED_MAP_CELL_SIZE: f64 = 0.25;  // m - The cells of the heatmap of the speed.
+// Faster than the shots of any player, like the ones that go almost straight up near the basket.
+pub const SPEED_MAP_MAX_SPEED: f64 = 15.0;  // m/s
+
+// The feet are about this far from the center of the shooter, along the floor.
+const FEET_REACH: f64 = 0.15; // m
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum League {
+    Fiba,
+    Nba,
+    Wnba,
+    Ncaa,
+}
+
+impl League {
+    pub fn from_name(name: & str) -> Option<League> {
+        match name {
+            "fiba" => Some(League::Fiba),
+            "nba"  => Some(League::Nba),
+            "wnba" => Some(League::Wnba),
+            "ncaa" => Some(League::Ncaa),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            League::Fiba => "FIBA",
+            League::Nba => "NBA",
+            League::Wnba => "WNBA",
+            League::Ncaa => "NCAA",
+        }
+    }
+
+    /// Radius of the arc from the center of the basket, in meters.
+    pub fn arc_radius(self) -> f64 {
+        match self {
+            League::Nba => 7.24,  // 23 ft 9 in
+            League::Fiba | League::Wnba | League::Ncaa => 6.75,
+        }
+    }
+
+    /// Distance of the straight lines of the corners from the center of the basket, to the side.
+    pub fn corner_distance(self) -> f64 {
+        match self {
+            League::Nba => 6.71,  // 22 ft
+            League::Fiba | League::Wnba | League::Ncaa => 6.6,
+        }
+    }
+}
+
+/// The value of a shot from the position of the shooter.
+#[derive(Clone, Copy, Debug)]
+pub struct ShotValue {
+    pub points: u32,
+    /// From the shooter to the center of the basket, on the floor.
+    pub distance: f64,
+    /// From the three-point line to the shooter, positive outside of it.
+    pub line_distance: f64,
+    /// The feet may be on the line, the shot is worth 2 points.
+    pub flag_on_line: bool,
+}
+
+/// The value of the shot from the shooter at (x, z) on the floor to the basket at (x, z).
+pub fn shot_value(league: League, shooter_x: f64, shooter_z: f64, basket_x: f64, basket_z: f64) -> ShotValue {
+    let (along, side) = (basket_x - shooter_x, shooter_z - basket_z);
+    let distance = f64::hypot(along, side);
+    let radius = league.arc_radius();
+    let corner = league.corner_distance();
+    // The arc meets the straight lines this far from the basket along the court, closer to the
+    // baseline than that the line is straight.
+    let arc_start = f64::sqrt(radius * radius - corner * corner);
+    let line_distance = if along < arc_start { f64::abs(side) - corner } else { distance - radius };
+    let flag_on_line = f64::abs(line_distance) < FEET_REACH;
+    let points = if line_distance > 0.0 && !flag_on_line { 3 } else { 2 };
+    ShotValue { points, distance, line_distance, flag_on_line }
+}
+
+/// The value of the shot of the 2D simulation, from the shooter at x to the basket at x, both in
+/// the vertical plane of the shot, the shooter straight in front of the basket.
+pub fn shot_value_in_plane(league: League, shooter_x: f64, basket_x: f64) -> ShotValue {
+    shot_value(league, shooter_x, 0.0, basket_x, 0.0)
+}
+
+/// The speed that takes the ball through the center of the basket from a cell of the grid of
+/// the half court, at the center (x, z) of the cell. None when no speed does with the angle, or
+/// only one faster than SPEED_MAP_MAX_SPEED.
+#[derive(Clone, Copy, Debug)]
+pub struct SpeedCell {
+    pub x: f64,
+    pub z: f64,
+    pub v_0: Option<f64>,
+}
+
+/// The speeds from the cells of cell_size meters of the half court of the basket at (x, y, z),
+/// with the release at the height pos_0_y and the angle teta_0 in radians.
+pub fn required_speed_grid(pos_0_y: f64, teta_0: f64, basket_pos: (f64, f64, f64), cell_size: f64) -> Vec<SpeedCell> {
+    let (basket_x, basket_y, basket_z) = basket_pos;
+    let baseline_x = basket_x + BASKET_FROM_BASELINE;
+    let num_rows = (HALF_COURT_LENGTH / cell_size).round() as usize;
+    let num_cols = (COURT_WIDTH / cell_size).round() as usize;
+    let mut cells = Vec::with_capacity(num_rows * num_cols);
+    for row in 0..num_rows {
+        for col in 0..num_cols {
+            let x = baseline_x - (row as f64 + 0.5) * cell_size;
+            let z = basket_z - COURT_WIDTH / 2.0 + (col as f64 + 0.5) * cell_size;
+            // The shot is in the vertical plane through the basket, only the distance matters.
+            let distance = f64::hypot(basket_x - x, z - basket_z);
+            let v_0 = required_speed(0.0, pos_0_y, teta_0, distance, basket_y).filter(|v_0| *v_0 <= SPEED_MAP_MAX_SPEED);
+            cells.push(SpeedCell { x, z, v_0 });
+        }
+    }
+    cells
+}
+
+/// The three-point line on the floor, points (x, z) from a corner of the baseline to the other.
+pub fn three_point_line(league: League, basket_x: f64, basket_z: f64) -> Vec<(f64, f64)> {
+    const ARC_POINTS: usize = 48;
+    let (radius, corner) = (league.arc_radius(), league.corner_distance());
+    let baseline_x = basket_x + BASKET_FROM_BASELINE;
+    let half_angle = f64::asin(corner / radius);
+    let mut points = vec![(baseline_x, basket_z - corner)];
+    for i in 0..=ARC_POINTS {
+        let angle = -half_angle + 2.0 * half_angle * i as f64 / ARC_POINTS as f64;
+        points.push((basket_x - radius * f64::cos(angle), basket_z + radius * f64::sin(angle)));
+    }
+    points.push((baseline_x, basket_z + corner));
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!(f64::abs(a - b) <= 1e-9 * f64::max(1.0, f64::abs(b)), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn two_point_shot_in_plane() {
+        let value = shot_value_in_plane(League::Fiba, 3.0, 8.0);
+        assert_close(value.distance, 5.0);
+        assert_close(value.line_distance, 5.0 - 6.75);
+        assert_eq!(value.points, 2);
+        assert!(!value.flag_on_line);
+        // 7 m is outside of the arc of FIBA, inside of the one of the NBA.
+        assert_eq!(shot_value_in_plane(League::Nba, 1.0, 8.0).points, 2);
+    }
+
+    #[test]
+    fn three_point_shot_in_plane() {
+        // The default scenario, 8 m from the basket along the court.
+        let value = shot_value_in_plane(League::Fiba, 0.0, 8.0);
+        assert_close(value.distance, 8.0);
+        assert_close(value.line_distance, 8.0 - 6.75);
+        assert_eq!(value.points, 3);
+        assert_eq!(shot_value_in_plane(League::Fiba, 1.0, 8.0).points, 3);
+    }
+
+    #[test]
+    fn shot_on_the_line() {
+        let value = shot_value_in_plane(League::Fiba, 8.0 - 6.8, 8.0);
+        assert!(value.flag_on_line);
+        assert_eq!(value.points, 2);
+    }
+
+    #[test]
+    fn corner_shot() {
+        // Along the baseline, 6.7 m to the side, behind the straight line of the corner.
+        let value = shot_value(League::Fiba, 8.0, 6.7, 8.0, 0.0);
+        assert_close(value.line_distance, 6.7 - 6.6);
+        assert_eq!(value.points, 2);
+        let value = shot_value(League::Fiba, 8.0, 7.0, 8.0, 0.0);
+        assert_eq!(value.points, 3);
+    }
+}
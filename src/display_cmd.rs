@@ -0,0 +1,538 @@
+//! Text mode display of the trajectory, a grid of characters in meters.
+
+use std::fmt::Write;
+
+// A character of the terminal is about twice as tall as wide.
+const CHAR_ASPECT: f64 = 2.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RenderMode {
+    /// One pixel for each character.
+    Chars,
+    /// Two pixels for each character, with the Unicode half blocks ▀ and ▄,
+    /// the double of the vertical resolution in the same number of rows.
+    HalfBlocks,
+}
+
+impl RenderMode {
+    fn pixels_per_row(& self) -> usize {
+        match self {
+            RenderMode::Chars => 1,
+            RenderMode::HalfBlocks => 2,
+        }
+    }
+}
+
+/// The glyphs of the text plot.
+#[derive(Clone, Copy, Debug)]
+pub struct Charset {
+    pub ball: char,
+    /// Line between the samples of the ball.
+    pub path: char,
+    /// Center of the marker of the instant the ball enters the basket.
+    pub score: char,
+    /// The two characters on each side of the score marker.
+    pub score_side: char,
+    pub rim: char,
+    pub rim_edge: char,
+    pub backboard: char,
+    /// The obstacles of the scenario.
+    pub obstacle: char,
+    pub floor: char,
+    pub background: char,
+}
+
+impl Charset {
+    /// Only ASCII characters, it works in every terminal and with the screen readers.
+    pub fn ascii() -> Self {
+        Charset {
+            ball: 'O',
+            path: '.',
+            score: '*',
+            score_side: '=',
+            rim: '-',
+            rim_edge: '|',
+            backboard: '#',
+            obstacle: '%',
+            floor: '_',
+            background: ' ',
+        }
+    }
+
+    pub fn unicode() -> Self {
+        Charset {
+            ball: '●',
+            path: '·',
+            score: '◉',
+            score_side: '═',
+            rim: '━',
+            rim_edge: '┃',
+            backboard: '█',
+            obstacle: '▒',
+            floor: '▁',
+            background: ' ',
+        }
+    }
+
+    pub fn from_name(name: & str) -> Option<Self> {
+        match name {
+            "ascii" => Some(Charset::ascii()),
+            "unicode" => Some(Charset::unicode()),
+            _ => None,
+        }
+    }
+}
+
+/// When to color the output with the ANSI escape codes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ColorMode {
+    /// Only when the stdout is a terminal and the NO_COLOR variable isn't set.
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub fn from_name(name: & str) -> Option<Self> {
+        match name {
+            "auto" => Some(ColorMode::Auto),
+            "always" => Some(ColorMode::Always),
+            "never" => Some(ColorMode::Never),
+            _ => None,
+        }
+    }
+
+    pub fn is_enabled(& self) -> bool {
+        match self {
+            ColorMode::Auto => {
+                // See https://no-color.org, a NO_COLOR variable with any value except empty.
+                let flag_no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+                !flag_no_color && std::io::IsTerminal::is_terminal(& std::io::stdout())
+            },
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+pub const ANSI_RESET: & str = "\x1b[0m";
+pub const ANSI_BALL: & str = "\x1b[33m";
+pub const ANSI_SCORE: & str = "\x1b[1;32m";
+const ANSI_RIM: & str = "\x1b[31m";
+const ANSI_BACKBOARD: & str = "\x1b[37m";
+const ANSI_FLOOR: & str = "\x1b[2m";
+
+// Value of the empty pixels in the grid, printed as the background of the charset.
+const EMPTY: char = '\0';
+
+/// The grid has num_rows rows of the terminal, each one with one or two rows of pixels,
+/// see RenderMode. The pixel rows and the meters grow from the bottom to the top.
+pub struct DisplayCMD {
+    buf: Vec<char>,
+    num_rows: usize,
+    num_cols: usize,
+    rows_meters: f64,
+    cols_meters: f64,
+    // Draw a frame with the tick labels in meters.
+    flag_axes: bool,
+    render_mode: RenderMode,
+    charset: Charset,
+    // Color the characters with the ANSI escape codes.
+    flag_color: bool,
+}
+
+impl DisplayCMD {
+    pub fn new(num_rows: usize, num_cols: usize, rows_meters: f64, cols_meters: f64) -> Self {
+        DisplayCMD { 
+            buf: vec![EMPTY; num_rows * num_cols],
+            num_rows,
+            num_cols,
+            rows_meters,
+            cols_meters, 
+            flag_axes: true,
+            render_mode: RenderMode::Chars,
+            charset: Charset::ascii(),
+            flag_color: false,
+        }
+    }
+
+    /// Grid that fits the scene from (0, 0) to (x_max, y_max) meters plus a 5% margin, with the
+    /// same scale in both axis so the arc isn't distorted. When the scene is wider than tall,
+    /// it uses less rows than max_rows.
+    pub fn fit_to_scene(max_rows: usize, num_cols: usize, x_max: f64, y_max: f64) -> Self {
+        assert!(max_rows > 1 && num_cols > 1);
+        let x_max = f64::max(x_max, 0.1) * 1.05;
+        let y_max = f64::max(y_max, 0.1) * 1.05;
+        // Meters per column, a row has CHAR_ASPECT times more meters.
+        let col_scale = f64::max(x_max / (num_cols - 1) as f64,
+                                 y_max / (CHAR_ASPECT * (max_rows - 1) as f64));
+        let row_scale = CHAR_ASPECT * col_scale;
+        let num_rows = usize::min(max_rows, f64::ceil(y_max / row_scale) as usize + 1);
+        DisplayCMD::new(num_rows, num_cols, row_scale * (num_rows - 1) as f64, col_scale * (num_cols - 1) as f64)
+    }
+
+    /// With axes the grid is printed inside a frame, with the tick labels in meters
+    /// on the left (YY axis) and at the bottom (XX axis).
+    pub fn set_axes(& mut self, flag_axes: bool) {
+        self.flag_axes = flag_axes;
+    }
+
+    /// Changes the render mode, it clears the grid.
+    pub fn set_render_mode(& mut self, render_mode: RenderMode) {
+        self.render_mode = render_mode;
+        self.buf = vec![EMPTY; self.num_pixel_rows() * self.num_cols];
+    }
+
+    pub fn set_charset(& mut self, charset: Charset) {
+        self.charset = charset;
+    }
+
+    pub fn set_color(& mut self, flag_color: bool) {
+        self.flag_color = flag_color;
+    }
+
+    pub fn charset(& self) -> & Charset {
+        & self.charset
+    }
+
+    /// Clears the grid, for the next frame of an animation.
+    pub fn clear(& mut self) {
+        self.buf.fill(EMPTY);
+    }
+
+    /// Number of rows of pixels, in half blocks mode there are two for each row of the terminal.
+    pub fn num_pixel_rows(& self) -> usize {
+        self.num_rows * self.render_mode.pixels_per_row()
+    }
+
+    /// Width of the grid in meters.
+    pub fn cols_meters(& self) -> f64 {
+        self.cols_meters
+    }
+
+    /// Sets the pixel, the pixels outside the grid are silently clipped.
+    /// Returns false if the pixel was clipped.
+    pub fn set_pixel(& mut self, ch: char, row: usize, col: usize) -> bool {
+        if row >= self.num_pixel_rows() || col >= self.num_cols {
+            return false;
+        }
+        self.buf[row*self.num_cols + col] = ch;
+        true
+    }
+
+    // The row of the terminal r is centered at r * rows_meters / (num_rows - 1) meters, in
+    // half blocks mode its bottom half is the pixel row 2 * r and the top half 2 * r + 1.
+    // The pixel row can be outside of the grid.
+    fn meters_to_pixel_row_unclipped(& self, row_meters_p: f64) -> i64 {
+        let row_f = (row_meters_p * (self.num_rows - 1) as f64) / self.rows_meters;
+        match self.render_mode {
+            RenderMode::Chars => f64::round(row_f) as i64,
+            RenderMode::HalfBlocks => f64::floor(2.0 * row_f + 1.0) as i64,
+        }
+    }
+
+    fn meters_to_col_unclipped(& self, col_meters_p: f64) -> i64 {
+        f64::round((col_meters_p * (self.num_cols - 1) as f64) / self.cols_meters) as i64
+    }
+
+    // Returns None if it's outside of the grid.
+    fn meters_to_pixel_row(& self, row_meters_p: f64) -> Option<usize> {
+        let row = self.meters_to_pixel_row_unclipped(row_meters_p);
+        if row >= 0 && row < self.num_pixel_rows() as i64 { Some(row as usize) } else { None }
+    }
+
+    // Returns None if it's outside of the grid.
+    fn meters_to_col(& self, col_meters_p: f64) -> Option<usize> {
+        let col = self.meters_to_col_unclipped(col_meters_p);
+        if col >= 0 && col < self.num_cols as i64 { Some(col as usize) } else { None }
+    }
+
+    /// Sets the pixel at the position in meters, the points outside of the grid are silently
+    /// clipped, so a long shot doesn't stop the program. Returns false if it was clipped.
+    pub fn set_pixel_meters(& mut self, ch: char, row_meters_p: f64, col_meters_p: f64, flag_enter_instant: bool) -> bool {
+        let (row, col) = match (self.meters_to_pixel_row(row_meters_p), self.meters_to_col(col_meters_p)) {
+            (Some(row), Some(col)) => (row, col),
+            _ => return false,
+        };
+        let mut ch = ch;
+        if flag_enter_instant {
+            // The marker of the basket, next to the edges of the grid only a part of it is visible.
+            ch = self.charset.score_side;
+            for col_marker in [col.checked_sub(2), col.checked_sub(1), Some(col + 1), Some(col + 2)].into_iter().flatten() {
+                self.set_pixel(ch, row, col_marker);
+            }
+            ch = self.charset.score;
+        }
+        self.set_pixel(ch, row, col)
+    }
+
+    /// Line from (row_meters_0, col_meters_0) to (row_meters_1, col_meters_1) with the Bresenham
+    /// algorithm, so there are no holes between two samples far apart. The pixels outside of the
+    /// grid are clipped.
+    pub fn draw_line_meters(& mut self, ch: char, row_meters_0: f64, col_meters_0: f64, row_meters_1: f64, col_meters_1: f64) {
+        let (mut row, mut col) = (self.meters_to_pixel_row_unclipped(row_meters_0), self.meters_to_col_unclipped(col_meters_0));
+        let (row_1, col_1) = (self.meters_to_pixel_row_unclipped(row_meters_1), self.meters_to_col_unclipped(col_meters_1));
+        let delta_col = (col_1 - col).abs();
+        let delta_row = -(row_1 - row).abs();
+        let step_col = if col < col_1 { 1 } else { -1 };
+        let step_row = if row < row_1 { 1 } else { -1 };
+        // A segment many times longer than the grid is of a shot far out of it, it isn't drawn
+        // to keep the loop short.
+        if delta_col - delta_row > 32 * (self.num_pixel_rows() + self.num_cols) as i64 {
+            return;
+        }
+        let mut error = delta_col + delta_row;
+        loop {
+            if row >= 0 && col >= 0 {
+                self.set_pixel(ch, row as usize, col as usize);
+            }
+            if row == row_1 && col == col_1 {
+                break;
+            }
+            let error_2 = 2 * error;
+            if error_2 >= delta_row {
+                error += delta_row;
+                col += step_col;
+            }
+            if error_2 <= delta_col {
+                error += delta_col;
+                row += step_row;
+            }
+        }
+    }
+
+    /// Horizontal line at the height row_meters_p, from col_meters_0 to col_meters_1, clipped to the grid.
+    pub fn draw_hline_meters(& mut self, ch: char, row_meters_p: f64, col_meters_0: f64, col_meters_1: f64) {
+        let row = match self.meters_to_pixel_row(row_meters_p) {
+            Some(row) => row,
+            None => return,
+        };
+        let (col_0, col_1) = self.meters_to_cells(col_meters_0, col_meters_1, self.num_cols, self.cols_meters);
+        for col in col_0..=col_1 {
+            self.set_pixel(ch, row, col);
+        }
+    }
+
+    /// Vertical line at col_meters_p, from the height row_meters_0 to row_meters_1, clipped to the grid.
+    pub fn draw_vline_meters(& mut self, ch: char, col_meters_p: f64, row_meters_0: f64, row_meters_1: f64) {
+        let col = match self.meters_to_col(col_meters_p) {
+            Some(col) => col,
+            None => return,
+        };
+        let (row_0, row_1) = self.meters_to_cells(row_meters_0, row_meters_1, self.num_pixel_rows(), self.rows_meters);
+        for row in row_0..=row_1 {
+            self.set_pixel(ch, row, col);
+        }
+    }
+
+    // The range of cells between two positions in meters, clamped to the grid.
+    fn meters_to_cells(& self, meters_0: f64, meters_1: f64, num_cells: usize, total_meters: f64) -> (usize, usize) {
+        let to_cell = |meters: f64| {
+            let cell = f64::round(meters * (num_cells - 1) as f64 / total_meters);
+            cell.clamp(0.0, (num_cells - 1) as f64) as usize
+        };
+        let (cell_0, cell_1) = (to_cell(meters_0), to_cell(meters_1));
+        (usize::min(cell_0, cell_1), usize::max(cell_0, cell_1))
+    }
+
+    pub fn get_pixel(& self, row: usize, col: usize) -> char {
+        assert!(row < self.num_pixel_rows());
+        assert!(col < self.num_cols);
+        self.buf[row*self.num_cols + col]
+    }
+
+    // The character printed in the row of the terminal, EMPTY for the background. In half blocks
+    // mode the pixels of the ball and of its path are joined in a block, the other characters,
+    // like the basket marker, are printed as is.
+    fn get_char(& self, row: usize, col: usize) -> char {
+        let is_ball = |ch: char| ch == self.charset.ball || ch == self.charset.path;
+        match self.render_mode {
+            RenderMode::Chars => self.get_pixel(row, col),
+            RenderMode::HalfBlocks => {
+                let top = self.get_pixel(2 * row + 1, col);
+                let bottom = self.get_pixel(2 * row, col);
+                match (top, bottom) {
+                    (EMPTY, EMPTY) => EMPTY,
+                    (top, EMPTY) if is_ball(top) => '▀',
+                    (EMPTY, bottom) if is_ball(bottom) => '▄',
+                    (top, bottom) if is_ball(top) && is_ball(bottom) => '█',
+                    (top, bottom) => if top != EMPTY && !is_ball(top) { top } else { bottom },
+                }
+            },
+        }
+    }
+
+    // ANSI color of a character of the grid, the background and the unknown characters aren't colored.
+    fn ansi_color(& self, ch: char) -> Option<& 'static str> {
+        let charset = & self.charset;
+        if ch == EMPTY {
+            None
+        } else if ch == charset.score || ch == charset.score_side {
+            Some(ANSI_SCORE)
+        } else if ch == charset.ball || ch == charset.path || ch == '▀' || ch == '▄' || ch == '█' {
+            Some(ANSI_BALL)
+        } else if ch == charset.rim || ch == charset.rim_edge {
+            Some(ANSI_RIM)
+        } else if ch == charset.backboard {
+            Some(ANSI_BACKBOARD)
+        } else if ch == charset.floor {
+            Some(ANSI_FLOOR)
+        } else {
+            None
+        }
+    }
+
+    // Appends the characters of the row of the terminal, with the colors the runs of
+    // characters of the same color share the escape codes.
+    fn write_row(& self, out: & mut String, row: usize) {
+        let mut current_color = None;
+        for col in 0..self.num_cols {
+            let ch = self.get_char(row, col);
+            if self.flag_color {
+                let color = self.ansi_color(ch);
+                if color != current_color {
+                    out.push_str(color.unwrap_or(ANSI_RESET));
+                    current_color = color;
+                }
+            }
+            out.push(if ch == EMPTY { self.charset.background } else { ch });
+        }
+        if current_color.is_some() {
+            out.push_str(ANSI_RESET);
+        }
+    }
+
+    pub fn print(& self) {
+        print!("{}", self.render());
+    }
+
+    /// The text plot as a String, the same that print() writes to the stdout.
+    pub fn render(& self) -> String {
+        let mut out = String::with_capacity((self.num_rows + 5) * (self.num_cols + 12));
+        if !self.flag_axes {
+            for row in (0..self.num_rows).rev() {
+                self.write_row(& mut out, row);
+                out.push('\n');
+            }
+            return out;
+        }
+
+        // Tick labels of the rows, at least 5 rows apart, and of the columns, at least 10 columns apart.
+        let row_ticks = ticks(self.rows_meters, self.num_rows, 5);
+        let col_ticks = ticks(self.cols_meters, self.num_cols, 10);
+        let mut row_labels: Vec<Option<& str>> = vec![None; self.num_rows];
+        for (row, label) in & row_ticks {
+            row_labels[*row] = Some(label);
+        }
+        let label_width = row_ticks.iter().map(|(_, label)| label.len()).max().unwrap_or(0);
+        let gutter = " ".repeat(label_width);
+
+        let _ = writeln!(out, "{} y (m)", gutter);
+        let _ = writeln!(out, "{} +{}+", gutter, "-".repeat(self.num_cols));
+        for row in (0..self.num_rows).rev() {
+            let _ = match row_labels[row] {
+                Some(label) => write!(out, "{:>w$} +", label, w = label_width),
+                None => write!(out, "{} |", gutter),
+            };
+            self.write_row(& mut out, row);
+            out.push_str("|\n");
+        }
+
+        // Bottom border with the ticks, and the labels centered under each tick.
+        let mut border: Vec<char> = vec!['-'; self.num_cols];
+        let mut label_line: Vec<char> = vec![' '; label_width + 2 + self.num_cols + 8];
+        let mut next_free = 0;
+        for (col, label) in & col_ticks {
+            border[*col] = '+';
+            let center = label_width + 2 + col;
+            let start = center.saturating_sub(label.len() / 2);
+            if start < next_free {
+                continue;
+            }
+            for (i, ch) in label.chars().enumerate() {
+                label_line[start + i] = ch;
+            }
+            next_free = start + label.len() + 1;
+        }
+        let _ = writeln!(out, "{} +{}+", gutter, border.iter().collect::<String>());
+        let _ = writeln!(out, "{}  x (m)", label_line.iter().collect::<String>().trim_end());
+        out
+    }
+}
+
+/// Size of the terminal in (columns, rows), from the COLUMNS and LINES variables or from
+/// "stty size". Returns None when the output isn't a terminal, like in a pipe or a file.
+pub fn terminal_size() -> Option<(usize, usize)> {
+    if !std::io::IsTerminal::is_terminal(& std::io::stdout()) {
+        return None;
+    }
+    let env_size = |name: & str| std::env::var(name).ok().and_then(|value| value.trim().parse::<usize>().ok());
+    if let (Some(cols), Some(rows)) = (env_size("COLUMNS"), env_size("LINES")) {
+        return Some((cols, rows));
+    }
+    let tty = std::fs::File::open("/dev/tty").ok()?;
+    let output = std::process::Command::new("stty")
+        .arg("size")
+        .stdin(tty)
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    let mut values = text.split_whitespace().map(|value| value.parse::<usize>().ok());
+    match (values.next().flatten(), values.next().flatten()) {
+        (Some(rows), Some(cols)) if rows > 0 && cols > 0 => Some((cols, rows)),
+        _ => None,
+    }
+}
+
+// Ticks at round values of meters, 1, 2 or 5 times a power of 10, with at least
+// min_cells cells between them. Returns the cell of each tick and its label.
+fn ticks(meters: f64, num_cells: usize, min_cells: usize) -> Vec<(usize, String)> {
+    if num_cells < 2 || meters <= 0.0 {
+        return vec![(0, "0".to_string())];
+    }
+    // The step in meters of min_cells cells.
+    let raw_step = min_cells as f64 * meters / (num_cells - 1) as f64;
+    let magnitude = 10_f64.powf(raw_step.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0].iter()
+                    .map(|mult| mult * magnitude)
+                    .find(|step| *step >= raw_step)
+                    .unwrap_or(10.0 * magnitude);
+    let decimals = if step >= 1.0 { 0 } else { (-step.log10()).ceil() as usize };
+    let mut ticks_vec = Vec::new();
+    let mut k = 0;
+    loop {
+        let value = k as f64 * step;
+        if value > meters * (1.0 + 1e-9) {
+            break;
+        }
+        let cell = f64::round(value * (num_cells - 1) as f64 / meters) as usize;
+        ticks_vec.push((usize::min(cell, num_cells - 1), format!("{:.*}", decimals, value)));
+        k += 1;
+    }
+    ticks_vec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks_of_the_default_plot() {
+        // The height of the default plot, 0/2/4 instead of only 0.
+        assert_eq!(ticks(4.4, 14, 5), vec![(0, "0".to_string()), (6, "2".to_string()), (12, "4".to_string())]);
+    }
+
+    #[test]
+    fn ticks_at_least_min_cells_apart() {
+        for (meters, num_cells, min_cells) in [(8.5, 81, 10), (0.3, 40, 5), (123.0, 30, 10), (4.4, 14, 5)] {
+            let ticks_vec = ticks(meters, num_cells, min_cells);
+            assert!(ticks_vec.len() >= 2);
+            assert!(ticks_vec.windows(2).all(|pair| pair[1].0 >= pair[0].0 + min_cells - 1));
+        }
+        assert_eq!(ticks(1.0, 1, 5), vec![(0, "0".to_string())]);
+    }
+}
@@ -0,0 +1,105 @@
+//! The event log of a run in NDJSON, one JSON record per line, for the tools that process or
+//! monitor the simulations without parsing the text output. Each sample is a record of
+//! telemetry::sample_json() and each event of the shot, like the apex, a bounce or the score, is
+//! a record of event_json() after the sample of its instant:
+//!
+//! ```text
+//! {"type":"sample","t":0.0000,"x":0.0000,"y":1.5000,"vx":7.0711,"vy":7.0711,"entered":false}
+//! {"type":"event","event":"release","t":0.0000,"x":0.0000,"y":1.5000}
+//! ```
+//!
+//! The contacts with the rim, the backboard and the floor are the ones of the rebound of a miss,
+//! the contact with an obstacle is the first one before the basket and the hit of each target
+//! is the first one.
+//! The last record is the result of the shot, ShotResult::json().
+
+use std::io::{self, Write};
+
+use crate::obstacles::first_hit;
+use crate::rebound::{self, ContactKind};
+use crate::scenario::ScenarioFile;
+use crate::shot_result::ShotResult;
+use crate::targets::target_results;
+use crate::telemetry::sample_json;
+use crate::trajectory::{ShotSummary, Trajectory};
+
+/// An event of the shot, its name, its instant and the position of the ball.
+#[derive(Clone, Copy, Debug)]
+pub struct ShotEvent {
+    /// release, apex, score, closest, landing, obstacle_contact, target_hit, rim_contact,
+    /// backboard_contact or bounce.
+    pub name: &'static str,
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// The events of the simulated shot of the scenario, in the order of the time.
+pub fn shot_events(scenario: & ScenarioFile, trajectory_2d: & Trajectory) -> Vec<ShotEvent> {
+    let samples = & trajectory_2d.1;
+    let Some(summary) = ShotSummary::new(trajectory_2d, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y) else {
+        return Vec::new();
+    };
+    // The events of the flight are at the instants of samples.
+    let at_sample = |name: &'static str, t: f64| {
+        let i = usize::min(samples.partition_point(|(t_sample, _, _)| *t_sample < t), samples.len() - 1);
+        let (_, (x, y), _) = samples[i];
+        ShotEvent { name, t, x, y }
+    };
+    let mut events = vec![at_sample("release", samples[0].0), at_sample("apex", summary.apex_t)];
+    if let Some(entry) = summary.entry {
+        events.push(at_sample("score", entry.t));
+    }
+    events.push(at_sample("closest", summary.closest_t));
+    events.push(at_sample("landing", summary.flight_sec));
+    if let Some(hit) = first_hit(samples, & scenario.obstacles, scenario.ball.radius) {
+        events.push(ShotEvent { name: "obstacle_contact", t: hit.t, x: hit.x, y: hit.y });
+    }
+    for hit in target_results(samples, & scenario.targets).iter().filter_map(|result| result.hit) {
+        events.push(ShotEvent { name: "target_hit", t: hit.t, x: hit.x, y: hit.y });
+    }
+
+    if let Some(rebound) = (!trajectory_2d.0).then(|| rebound::simulate_rebound(scenario)).flatten() {
+        for contact in & rebound.contacts {
+            let name = match contact.kind {
+                ContactKind::FrontRim | ContactKind::BackRim => "rim_contact",
+                ContactKind::Backboard => "backboard_contact",
+                ContactKind::Floor => "bounce",
+            };
+            events.push(ShotEvent { name, t: contact.t, x: contact.x, y: contact.y });
+        }
+        if let (true, Some((t, (x, y)))) = (rebound.flag_rolled_in, rebound.path.last()) {
+            events.push(ShotEvent { name: "score", t: *t, x: *x, y: *y });
+        }
+    }
+    events.sort_by(|event_1, event_2| event_1.t.total_cmp(& event_2.t));
+    events
+}
+
+/// The JSON record of the event, with precision decimal places.
+pub fn event_json(event: & ShotEvent, precision: usize) -> String {
+    format!("{{\"type\":\"event\",\"event\":\"{}\",\"t\":{:.4$},\"x\":{:.4$},\"y\":{:.4$}}}",
+            event.name, event.t, event.x, event.y, precision)
+}
+
+/// Writes the samples and the events of the shot, a record per line, with precision decimal
+/// places, and its result.
+pub fn write_event_log(writer: & mut impl Write, scenario: & ScenarioFile, trajectory_2d: & Trajectory,
+                       result: ShotResult, precision: usize) -> io::Result<()> {
+    let samples = & trajectory_2d.1;
+    let events = shot_events(scenario, trajectory_2d);
+    let mut next_event = 0;
+    for (i, (t, _, _)) in samples.iter().enumerate() {
+        writeln!(writer, "{}", sample_json(samples, i, precision))?;
+        while next_event < events.len() && events[next_event].t <= *t {
+            writeln!(writer, "{}", event_json(& events[next_event], precision))?;
+            next_event += 1;
+        }
+    }
+    // The rebound goes on after the last sample of the shot.
+    for event in & events[next_event..] {
+        writeln!(writer, "{}", event_json(event, precision))?;
+    }
+    writeln!(writer, "{}", result.json())?;
+    writer.flush()
+}
@@ -0,0 +1,118 @@
+//! The equations of the shot with the numbers substituted, for a few instants, to follow the
+//! calculations of the physics book step by step:
+//!
+//! ```text
+//! v_0_x = v_0 * cos(teta_0) = 10.00 * cos(45.00°) = 7.07 m/s
+//! ball_pos_y = y_0 + v_0_y * t - 1/2 * g * t^2 = 1.50 + 7.07 * 0.72 - 1/2 * 9.81 * 0.72^2 = 4.05 m
+//! ```
+
+use std::fmt::Write;
+
+use crate::i18n::{Lang, Messages, Num};
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{euclidean_distance, ShotSummary, Trajectory, GRAVITY, MIN_BALL_DELTA_TO_BASKET_CENTER};
+use crate::units::degrees_to_radians;
+
+/// The instants of the explanation.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum InstantKind {
+    Throw,
+    Apex,
+    Closest,
+    Last,
+}
+
+impl InstantKind {
+    pub fn title(self, messages: & Messages) -> &'static str {
+        match self {
+            InstantKind::Throw => messages.instant_throw,
+            InstantKind::Apex => messages.instant_apex,
+            InstantKind::Closest => messages.instant_closest,
+            InstantKind::Last => messages.instant_last,
+        }
+    }
+}
+
+/// An instant of the explanation, with the values of the equations at it.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyInstant {
+    pub kind: InstantKind,
+    pub t: f64,
+    pub ball_x: f64,
+    pub ball_y: f64,
+    pub v_y: f64,
+    /// Distance to the center of the basket.
+    pub dist: f64,
+}
+
+/// The initial velocity decomposed in (v_0_x, v_0_y).
+pub fn velocity_components(scenario: & ScenarioFile) -> (f64, f64) {
+    let teta_0 = degrees_to_radians(scenario.throw.teta_0_deg);
+    (scenario.throw.v_0 * f64::cos(teta_0), scenario.throw.v_0 * f64::sin(teta_0))
+}
+
+/// The instant of the top of the arc, if the ball goes up.
+pub fn apex_time(scenario: & ScenarioFile) -> Option<f64> {
+    let t_apex = velocity_components(scenario).1 / GRAVITY;
+    (t_apex > 0.0).then_some(t_apex)
+}
+
+/// The throw, the top of the arc if it's in the simulated time, the closest instant to the
+/// basket and the last instant above the floor, of the trajectory simulated from the scenario.
+pub fn key_instants(scenario: & ScenarioFile, trajectory_2d: & Trajectory) -> Vec<KeyInstant> {
+    let (x_0, y_0) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let (v_0_x, v_0_y) = velocity_components(scenario);
+    let key_instant = |kind: InstantKind, t: f64| {
+        let ball_x = x_0 + v_0_x * t;
+        let ball_y = y_0 + v_0_y * t - (1.0/2.0) * GRAVITY * t * t;
+        let dist = euclidean_distance(ball_x, ball_y, 0.0, basket_pos_x, basket_pos_y, 0.0);
+        KeyInstant { kind, t, ball_x, ball_y, v_y: v_0_y - GRAVITY * t, dist }
+    };
+
+    let mut instants = vec![key_instant(InstantKind::Throw, 0.0)];
+    let summary = ShotSummary::new(trajectory_2d, basket_pos_x, basket_pos_y);
+    let t_last = summary.map_or(0.0, |summary| summary.flight_sec);
+    if let Some(t_apex) = apex_time(scenario).filter(|t_apex| *t_apex <= t_last) {
+        instants.push(key_instant(InstantKind::Apex, t_apex));
+    }
+    if let Some(summary) = summary {
+        instants.push(key_instant(InstantKind::Closest, summary.closest_t));
+        instants.push(key_instant(InstantKind::Last, summary.flight_sec));
+    }
+    instants
+}
+
+/// The explanation of the key instants of the trajectory simulated from the scenario.
+pub fn explain_shot(scenario: & ScenarioFile, trajectory_2d: & Trajectory, lang: Lang) -> String {
+    let messages = lang.messages();
+    let (x_0, y_0) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let (v_0, teta_0_deg) = (scenario.throw.v_0, scenario.throw.teta_0_deg);
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let (v_0_x, v_0_y) = velocity_components(scenario);
+
+    let mut text = String::with_capacity(2_000);
+    let _ = writeln!(text, "  {}", messages.decomposed_velocity);
+    let _ = writeln!(text, "    v_0_x = v_0 * cos(teta_0) = {:0.2} * cos({:0.2}°) = {:0.2} m/s", Num(v_0), Num(teta_0_deg), Num(v_0_x));
+    let _ = writeln!(text, "    v_0_y = v_0 * sin(teta_0) = {:0.2} * sin({:0.2}°) = {:0.2} m/s", Num(v_0), Num(teta_0_deg), Num(v_0_y));
+
+    for instant in key_instants(scenario, trajectory_2d) {
+        let t = instant.t;
+        if instant.kind == InstantKind::Apex {
+            let _ = writeln!(text, "\n  {}", messages.apex_explained);
+            let _ = writeln!(text, "    v_y = v_0_y - g * t = 0  =>  t = v_0_y / g = {:0.2} / {:0.2} = {:0.2} s", Num(v_0_y), Num(GRAVITY), Num(t));
+        }
+        let _ = writeln!(text, "\n  {}, t = {:0.2} s:", instant.kind.title(messages), Num(t));
+        let _ = writeln!(text, "    ball_pos_x = x_0 + v_0_x * t = {:0.2} + {:0.2} * {:0.2} = {:0.2} m", Num(x_0), Num(v_0_x), Num(t), Num(instant.ball_x));
+        let _ = writeln!(text, "    ball_pos_y = y_0 + v_0_y * t - 1/2 * g * t^2 = {:0.2} + {:0.2} * {:0.2} - 1/2 * {:0.2} * {:0.2}^2 = {:0.2} m",
+                         Num(y_0), Num(v_0_y), Num(t), Num(GRAVITY), Num(t), Num(instant.ball_y));
+        let _ = writeln!(text, "    v_y = v_0_y - g * t = {:0.2} - {:0.2} * {:0.2} = {:0.2} m/s", Num(v_0_y), Num(GRAVITY), Num(t), Num(instant.v_y));
+        let _ = writeln!(text, "    dist = sqrt((ball_pos_x - basket_pos_x)^2 + (ball_pos_y - basket_pos_y)^2) = sqrt(({:0.2} - {:0.2})^2 + ({:0.2} - {:0.2})^2) = {:0.2} m",
+                         Num(instant.ball_x), Num(basket_pos_x), Num(instant.ball_y), Num(basket_pos_y), Num(instant.dist));
+        let flag_in = instant.dist <= MIN_BALL_DELTA_TO_BASKET_CENTER;
+        let _ = writeln!(text, "    {:0.2} m {} {:0.2} m, {}", Num(instant.dist),
+                         if flag_in {"<="} else {">"}, Num(MIN_BALL_DELTA_TO_BASKET_CENTER),
+                         if flag_in {messages.ball_is_in} else {messages.ball_is_not_in});
+    }
+    text
+}
@@ -0,0 +1,88 @@
+//! The frames of reference of the positions of the shot. The world frame is the one of the
+//! simulation, x along the court and y up from the floor. The shooter frame has the origin at
+//! the release point, dx along the shot and dy up. The hoop frame has the origin at the center
+//! of the rim, d the horizontal distance before it and h the height above it, the frame that
+//! the coaches think in, the ball comes down to d = 0 and h = 0.
+
+use crate::scenario::ScenarioFile;
+use crate::trajectory::Trajectory;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Frame {
+    World,
+    Shooter,
+    Hoop,
+}
+
+impl Frame {
+    pub fn from_name(name: & str) -> Option<Frame> {
+        match name {
+            "world" => Some(Frame::World),
+            "shooter" => Some(Frame::Shooter),
+            "hoop" => Some(Frame::Hoop),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Frame::World => "world",
+            Frame::Shooter => "shooter",
+            Frame::Hoop => "hoop",
+        }
+    }
+
+    /// The short names of the horizontal and the vertical coordinates, like "x" and "y".
+    pub fn axis_names(self) -> (&'static str, &'static str) {
+        match self {
+            Frame::World => ("x", "y"),
+            Frame::Shooter => ("dx", "dy"),
+            Frame::Hoop => ("d", "h"),
+        }
+    }
+
+    /// The labels of the axes of the plots.
+    pub fn axis_labels(self) -> (&'static str, &'static str) {
+        match self {
+            Frame::World => ("x (m)", "y (m)"),
+            Frame::Shooter => ("dx from the release (m)", "dy above the release (m)"),
+            Frame::Hoop => ("d to the rim (m)", "h above the rim (m)"),
+        }
+    }
+}
+
+/// The frame with the release point and the center of the rim, in the world frame.
+#[derive(Clone, Copy, Debug)]
+pub struct FrameTransform {
+    pub frame: Frame,
+    pub release: (f64, f64),
+    pub basket: (f64, f64),
+}
+
+impl FrameTransform {
+    pub fn new(frame: Frame, scenario: & ScenarioFile) -> FrameTransform {
+        FrameTransform {
+            frame,
+            release: (scenario.throw.pos_0_x, scenario.throw.pos_0_y),
+            basket: (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y),
+        }
+    }
+
+    /// The point (x, y) of the world frame in this frame.
+    pub fn point(& self, (x, y): (f64, f64)) -> (f64, f64) {
+        // The shot can go to the left, the horizontal coordinates are along it.
+        let direction = if self.basket.0 < self.release.0 {-1.0} else {1.0};
+        match self.frame {
+            Frame::World => (x, y),
+            Frame::Shooter => ((x - self.release.0) * direction, y - self.release.1),
+            Frame::Hoop => ((self.basket.0 - x) * direction, y - self.basket.1),
+        }
+    }
+
+    /// The trajectory with its positions in this frame, the instants don't change.
+    pub fn trajectory(& self, trajectory_2d: & Trajectory) -> Trajectory {
+        (trajectory_2d.0, trajectory_2d.1.iter()
+            .map(|(t, position, flag_enter_instant)| (*t, self.point(*position), *flag_enter_instant))
+            .collect())
+    }
+}
@@ -0,0 +1,79 @@
+//! The overhand free throw against the underhand one, the "granny shot", from the same line.
+//! The underhand release is lower, so the ball needs more speed and a steeper angle, but it
+//! comes down steeper into the basket. The tolerance windows are how much the speed and the
+//! angle can miss the ideal ones and the ball still goes in, with the criterion of the
+//! simulation, the center of the ball within 10 cm of the center of the basket.
+
+use crate::batch::classify_batch;
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{required_speed, GRAVITY};
+use crate::units::{degrees_to_radians, radians_to_degrees};
+
+// The windows are searched around the ideal release, in this many candidates.
+const WINDOW_NUM_CANDIDATES: usize = 2_001;
+const SPEED_SEARCH_FRAC: f64 = 0.05;    // ± 5% of the ideal speed.
+const ANGLE_SEARCH_DEG: f64 = 20.0;     // ± 20° of the release angle.
+// Enough steps for the ball to move about 1 cm between instants.
+const WINDOW_NUM_STEPS: u32 = 2_000;
+
+/// The release of a shot, with the speed that goes through the center of the basket.
+#[derive(Clone, Copy, Debug)]
+pub struct ReleaseAnalysis {
+    pub release_height: f64,
+    pub teta_0_deg: f64,
+    pub v_0: f64,
+    /// Degrees below the horizontal when the ball reaches the center of the basket.
+    pub entry_angle_deg: f64,
+    /// The smallest and the largest speeds that score at the release angle.
+    pub speed_window: (f64, f64),
+    /// The smallest and the largest angles that score at the ideal speed.
+    pub angle_window: (f64, f64),
+}
+
+/// The analysis of the release of the scenario, its speed is replaced by the ideal one. None
+/// when no speed reaches the basket at its angle.
+pub fn analyze_release(scenario: & ScenarioFile) -> Option<ReleaseAnalysis> {
+    let (x_0, y_0) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let teta_0_deg = scenario.throw.teta_0_deg;
+    let teta_0 = degrees_to_radians(teta_0_deg);
+    let v_0 = required_speed(x_0, y_0, teta_0, basket_pos_x, basket_pos_y)?;
+
+    let v_0_x = v_0 * f64::cos(teta_0);
+    let t_basket = (basket_pos_x - x_0) / v_0_x;
+    let v_y = v_0 * f64::sin(teta_0) - GRAVITY * t_basket;
+    let entry_angle_deg = radians_to_degrees(f64::atan2(-v_y, v_0_x));
+
+    let mut scenario = scenario.clone();
+    scenario.simulation.num_steps = WINDOW_NUM_STEPS;
+    scenario.simulation.simulation_sec = f64::max(scenario.simulation.simulation_sec, 2.0 * t_basket);
+    let speed_window = scoring_window(v_0 * (1.0 - SPEED_SEARCH_FRAC), v_0 * (1.0 + SPEED_SEARCH_FRAC),
+                                      |speed| (teta_0_deg, speed), & scenario);
+    let angle_window = scoring_window(teta_0_deg - ANGLE_SEARCH_DEG, teta_0_deg + ANGLE_SEARCH_DEG,
+                                      |angle| (angle, v_0), & scenario);
+    Some(ReleaseAnalysis {
+        release_height: y_0,
+        teta_0_deg,
+        v_0,
+        entry_angle_deg,
+        speed_window: speed_window.unwrap_or((v_0, v_0)),
+        angle_window: angle_window.unwrap_or((teta_0_deg, teta_0_deg)),
+    })
+}
+
+// The run of the values that score around the middle of [min, max], each value becomes the
+// candidate (teta_0 in degrees, v_0) of the sweep.
+fn scoring_window(min: f64, max: f64, candidate: impl Fn(f64) -> (f64, f64), scenario: & ScenarioFile) -> Option<(f64, f64)> {
+    let values: Vec<f64> = (0..WINDOW_NUM_CANDIDATES)
+        .map(|i| min + (max - min) * i as f64 / (WINDOW_NUM_CANDIDATES - 1) as f64)
+        .collect();
+    let candidates: Vec<(f64, f64)> = values.iter().map(|value| candidate(*value)).collect();
+    let scored = classify_batch(scenario, & candidates);
+    let middle = WINDOW_NUM_CANDIDATES / 2;
+    if !scored[middle] {
+        return None;
+    }
+    let first = (0..middle).rev().find(|&i| !scored[i]).map_or(0, |i| i + 1);
+    let last = (middle..WINDOW_NUM_CANDIDATES).find(|&i| !scored[i]).map_or(WINDOW_NUM_CANDIDATES - 1, |i| i - 1);
+    Some((values[first], values[last]))
+}
@@ -0,0 +1,106 @@
+//! The gallery of a batch, a folder with the figure of each scenario, shot_0001.svg,
+//! shot_0002.svg, ..., and an index.html page with their thumbnails, the inputs and the results,
+//! to browse a batch of dozens of scenarios. A click on a thumbnail opens the figure, with its
+//! animation. The scenarios that can't be simulated have their error instead of a figure.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::batch::SimResult;
+use crate::plot_svg::{plot_trajectory_svg, SvgPlotOptions};
+use crate::scenario::ScenarioFile;
+use crate::svg_gen::{escape_attr, escape_text};
+
+/// Width in pixels of the thumbnails of the page.
+pub const THUMBNAIL_WIDTH: u32 = 300;
+
+/// The name of the figure of the i-th scenario, from 0, in the folder.
+pub fn figure_filename(i: usize) -> String {
+    format!("shot_{:04}.svg", i + 1)
+}
+
+/// Writes the figures of the scenarios and the index.html page to the folder, it's created if
+/// it doesn't exist. The results are the ones of the batch, in the order of the scenarios.
+pub fn write_gallery(folder: & str, scenarios: & [ScenarioFile], results: & [SimResult],
+                     options: & SvgPlotOptions, page_title: & str) -> Result<(), String> {
+    let folder_path = Path::new(folder);
+    fs::create_dir_all(folder_path).map_err(|error| format!("Can't create the folder {}: {}", folder, error))?;
+    for (i, (scenario, result)) in scenarios.iter().zip(results).enumerate() {
+        if result.is_err() {
+            continue;
+        }
+        let trajectory_2d = scenario.simulate_2d();
+        let options = SvgPlotOptions { obstacles: scenario.obstacles.clone(), targets: scenario.targets.clone(), ..options.clone() };
+        let svg = plot_trajectory_svg(& trajectory_2d, Some((scenario.scene.basket_pos_x, scenario.scene.basket_pos_y)),
+                                      & options, &shot_label(i, scenario));
+        let filename = folder_path.join(figure_filename(i));
+        fs::write(& filename, svg.to_file_string())
+            .map_err(|error| format!("Can't write the file {}: {}", filename.display(), error))?;
+    }
+    let filename = folder_path.join("index.html");
+    fs::write(& filename, gallery_html(scenarios, results, page_title))
+        .map_err(|error| format!("Can't write the file {}: {}", filename.display(), error))
+}
+
+/// The index page, a card for each scenario with its thumbnail, its inputs and its results.
+pub fn gallery_html(scenarios: & [ScenarioFile], results: & [SimResult], page_title: & str) -> String {
+    let mut cards = String::with_capacity(scenarios.len() * 600);
+    for (i, (scenario, result)) in scenarios.iter().zip(results).enumerate() {
+        let label = shot_label(i, scenario);
+        let _ = writeln!(cards, "<figure>");
+        match result {
+            Ok(summary) => {
+                let filename = figure_filename(i);
+                let _ = writeln!(cards, "<a href=\"{0}\"><img src=\"{0}\" width=\"{1}\" alt=\"{2}\"></a>",
+                                 filename, THUMBNAIL_WIDTH, escape_attr(&label));
+                let _ = writeln!(cards, "<figcaption><b>{}</b><br>", escape_text(&label));
+                let _ = writeln!(cards, "basket at ({:0.2}, {:0.2}) m, released at ({:0.2}, {:0.2}) m<br>",
+                                 scenario.scene.basket_pos_x, scenario.scene.basket_pos_y,
+                                 scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+                let _ = writeln!(cards, "<span class=\"{0}\">{0}</span>, apex {1:0.2} m, range {2:0.2} m, flight {3:0.2} s, closest {4:0.2} m</figcaption>",
+                                 if summary.flag_scored {"scored"} else {"missed"},
+                                 summary.apex_y, summary.range_x, summary.flight_sec, summary.closest_dist);
+            },
+            Err(error) => {
+                let _ = writeln!(cards, "<figcaption><b>{}</b><br><span class=\"error\">{}</span></figcaption>",
+                                 escape_text(&label), escape_text(error));
+            },
+        }
+        let _ = writeln!(cards, "</figure>");
+    }
+    let num_scored = results.iter().filter(|result| matches!(result, Ok(summary) if summary.flag_scored)).count();
+    GALLERY_HTML
+        .replace("__TITLE__", &escape_text(page_title))
+        .replace("__THUMBNAIL_WIDTH__", &THUMBNAIL_WIDTH.to_string())
+        .replace("__SUMMARY__", &format!("{} of {} scenarios scored.", num_scored, scenarios.len()))
+        .replace("__CARDS__", &cards)
+}
+
+fn shot_label(i: usize, scenario: & ScenarioFile) -> String {
+    format!("Shot {}: v_0 = {:0.2} m/s & teta_0 = {:0.2}°", i + 1, scenario.throw.v_0, scenario.throw.teta_0_deg)
+}
+
+const GALLERY_HTML: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>__TITLE__</title>
+<style>
+body { font-family: sans-serif; }
+main { display: flex; flex-wrap: wrap; gap: 12px; }
+figure { margin: 0; width: __THUMBNAIL_WIDTH__px; font-size: 13px; }
+img { display: block; border: 1px solid #888; }
+.scored { color: #080; font-weight: bold; }
+.missed { color: #a00; font-weight: bold; }
+.error { color: #a00; }
+</style>
+</head>
+<body>
+<h1>__TITLE__</h1>
+<p>__SUMMARY__</p>
+<main>
+__CARDS__</main>
+</body>
+</html>
+";
@@ -0,0 +1,119 @@
+//! A self-contained HTML page with the scenario and sliders of the angle and of the speed. A
+//! small script simulates the shot without the air again in the browser, with the equations of
+//! trajectory::basketball_2d(), and redraws it while the sliders are dragged, nothing to install.
+
+use crate::plot_svg::Theme;
+use crate::scenario::ScenarioFile;
+use crate::svg_gen::escape_text;
+use crate::trajectory::{GRAVITY, MIN_BALL_DELTA_TO_BASKET_CENTER};
+
+/// The page of the scenario, drawn with the colors of the theme.
+pub fn sliders_html(scenario: & ScenarioFile, theme: & Theme, page_title: & str) -> String {
+    // The JSON is inside a script element, it can't close it.
+    let scenario_json = scenario.to_json().replace("</", "<\\/");
+    HTML_SLIDERS
+        .replace("__TITLE__", &escape_text(page_title))
+        .replace("__SCENARIO__", &scenario_json)
+        .replace("__GRAVITY__", &GRAVITY.to_string())
+        .replace("__MIN_DELTA__", &MIN_BALL_DELTA_TO_BASKET_CENTER.to_string())
+        .replace("__BACKGROUND__", &theme.background.to_string())
+        .replace("__TRAJECTORY__", &theme.trajectory.to_string())
+        .replace("__SCORE__", &theme.score_marker.to_string())
+        .replace("__BASKET__", &theme.basket.to_string())
+        .replace("__TEXT__", &theme.text.to_string())
+}
+
+// The figure is redrawn in the coordinates of the court, the viewBox fits the arc and the basket.
+const HTML_SLIDERS: &str = "<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>__TITLE__</title>
+</head>
+<body style=\"font-family: sans-serif;\">
+<div>
+<label>teta_0 <input id=\"teta\" type=\"range\" min=\"1\" max=\"89\" step=\"0.1\" style=\"width: 300px\"></label>
+<span id=\"teta_value\"></span>
+</div>
+<div>
+<label>v_0 <input id=\"speed\" type=\"range\" min=\"1\" max=\"25\" step=\"0.01\" style=\"width: 300px\"></label>
+<span id=\"speed_value\"></span>
+</div>
+<p id=\"result\"></p>
+<svg id=\"figure\" width=\"600\" height=\"360\" xmlns=\"http://www.w3.org/2000/svg\" style=\"background: __BACKGROUND__\">
+<line id=\"floor\" stroke=\"__TEXT__\" stroke-width=\"1\" vector-effect=\"non-scaling-stroke\" />
+<line id=\"basket\" stroke=\"__BASKET__\" stroke-width=\"4\" vector-effect=\"non-scaling-stroke\" />
+<polyline id=\"arc\" fill=\"none\" stroke=\"__TRAJECTORY__\" stroke-width=\"2\" vector-effect=\"non-scaling-stroke\" />
+<circle id=\"enter\" r=\"0.12\" fill=\"__SCORE__\" />
+</svg>
+<script id=\"scenario\" type=\"application/json\">
+__SCENARIO__
+</script>
+<script>
+(function () {
+    var scenario = JSON.parse(document.getElementById('scenario').textContent);
+    var GRAVITY = __GRAVITY__;
+    var MIN_DELTA = __MIN_DELTA__;
+    var throw_0 = scenario.throw, scene = scenario.scene, simulation = scenario.simulation;
+    var teta = document.getElementById('teta');
+    var speed = document.getElementById('speed');
+    teta.value = throw_0.teta_0_deg;
+    speed.value = throw_0.v_0;
+    var svg = document.getElementById('figure');
+    function set(id, attrs) {
+        var elem = document.getElementById(id);
+        Object.keys(attrs).forEach(function (name) { elem.setAttribute(name, attrs[name]); });
+    }
+    // The samples above the floor, like basketball_2d(), with y up.
+    function simulate(v_0, teta_0) {
+        var v_0_x = v_0 * Math.cos(teta_0), v_0_y = v_0 * Math.sin(teta_0);
+        var points = [], enter = null;
+        for (var i = 0; i < simulation.num_steps; i++) {
+            var t = simulation.simulation_sec * i / (simulation.num_steps - 1);
+            var x = throw_0.pos_0_x + v_0_x * t;
+            var y = throw_0.pos_0_y + v_0_y * t - 0.5 * GRAVITY * t * t;
+            if (y < 0) {
+                continue;
+            }
+            points.push([x, y]);
+            if (enter === null && Math.hypot(x - scene.basket_pos_x, y - scene.basket_pos_y) <= MIN_DELTA) {
+                enter = [t, x, y];
+            }
+        }
+        return { points: points, enter: enter };
+    }
+    function draw() {
+        var v_0 = Number(speed.value), teta_0_deg = Number(teta.value);
+        document.getElementById('teta_value').textContent = teta_0_deg.toFixed(1) + '°';
+        document.getElementById('speed_value').textContent = v_0.toFixed(2) + ' m/s';
+        var shot = simulate(v_0, teta_0_deg * Math.PI / 180);
+        var x_min = Math.min(throw_0.pos_0_x, scene.basket_pos_x), x_max = Math.max(throw_0.pos_0_x, scene.basket_pos_x);
+        var y_max = scene.basket_pos_y;
+        shot.points.forEach(function (p) {
+            x_min = Math.min(x_min, p[0]);
+            x_max = Math.max(x_max, p[0]);
+            y_max = Math.max(y_max, p[1]);
+        });
+        var margin = 0.5;
+        // The SVG YY axis is down, the figure draws -y.
+        svg.setAttribute('viewBox', [x_min - margin, -y_max - margin, x_max - x_min + 2 * margin, y_max + 2 * margin].join(' '));
+        set('floor', { x1: x_min - margin, y1: 0, x2: x_max + margin, y2: 0 });
+        set('basket', { x1: scene.basket_pos_x - 0.23, y1: -scene.basket_pos_y, x2: scene.basket_pos_x + 0.23, y2: -scene.basket_pos_y });
+        set('arc', { points: shot.points.map(function (p) { return p[0].toFixed(3) + ',' + (-p[1]).toFixed(3); }).join(' ') });
+        var result = document.getElementById('result');
+        if (shot.enter) {
+            set('enter', { cx: shot.enter[1], cy: -shot.enter[2], visibility: 'visible' });
+            result.textContent = 'The ball entered the basket at t = ' + shot.enter[0].toFixed(2) + ' s.';
+        } else {
+            set('enter', { visibility: 'hidden' });
+            result.textContent = 'The ball missed the basket.';
+        }
+    }
+    teta.addEventListener('input', draw);
+    speed.addEventListener('input', draw);
+    draw();
+})();
+</script>
+</body>
+</html>
+";
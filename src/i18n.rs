@@ -0,0 +1,886 @@
+//! The text of the output in English and in Portuguese. Each language is a table of Messages,
+//! a new language is a new table and a name in Lang, the code that prints doesn't change.
+//!
+//! The sentences with values have a "{}" for each one, filled in order by fill().
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The language of the printed text.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lang {
+    En,
+    Pt,
+}
+
+impl Lang {
+    pub fn from_name(name: & str) -> Option<Lang> {
+        match name {
+            "en" => Some(Lang::En),
+            "pt" => Some(Lang::Pt),
+            _ => None,
+        }
+    }
+
+    /// The language of the locale, the first of LC_ALL, LC_MESSAGES and LANG that is set,
+    /// like "pt_PT.UTF-8". English if it isn't one of the languages.
+    pub fn from_env() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_name(&locale.get(..2)?.to_ascii_lowercase()))
+            .unwrap_or(Lang::En)
+    }
+
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Lang::En => & EN,
+            Lang::Pt => & PT,
+        }
+    }
+}
+
+/// All the printed text of a language.
+pub struct Messages {
+    pub title: &'static str,
+    pub data: &'static str,
+    pub throw_position: &'static str,
+    pub meters: &'static str,
+    pub initial_velocity: &'static str,
+    pub meters_per_second: &'static str,
+    pub km_per_hour: &'static str,
+    pub teta_0_degrees: &'static str,
+    pub phi_0_degrees: &'static str,
+    pub basket_position: &'static str,
+    pub simulation_time: &'static str,
+    pub seconds_to_simulate: &'static str,
+    pub num_steps_points: &'static str,
+    pub output_svg: &'static str,
+    pub none: &'static str,
+    pub trajectory: &'static str,
+    pub entered_the_basket: &'static str,
+    /// The values of a yes or no answer, like if the ball entered the basket.
+    pub flag_true: &'static str,
+    pub flag_false: &'static str,
+    pub ball_entered_the_basket: &'static str,
+    pub scored: &'static str,
+    pub missed: &'static str,
+    /// The summary line, with the result, the apex, the range, the flight time, the closest
+    /// distance to the basket and its instant.
+    pub summary_line: &'static str,
+    pub results_title: &'static str,
+    pub results_scored_at: &'static str,
+    pub results_missed: &'static str,
+    pub results_apex: &'static str,
+    pub results_range: &'static str,
+    pub results_flight: &'static str,
+    /// The speed and the angle below the horizontal when the ball entered the basket.
+    pub results_entry: &'static str,
+    pub results_closest: &'static str,
+    /// The arc length to the closest instant, the straight line and the arc length of the flight.
+    pub results_distance: &'static str,
+    /// The points, the distance to the basket, the distance to the three-point line, inside or
+    /// outside, and the league.
+    pub results_value: &'static str,
+    pub value_inside: &'static str,
+    pub value_outside: &'static str,
+    pub results_on_line: &'static str,
+    /// The kind of the arc, the release angle and the angle coming down at the height of the rim.
+    pub results_arc: &'static str,
+    pub results_arc_none: &'static str,
+    pub arc_flat: &'static str,
+    pub arc_medium: &'static str,
+    pub arc_high: &'static str,
+    /// The advices, with the entry angle and the advised release angle.
+    pub advice_flat: &'static str,
+    pub advice_medium: &'static str,
+    pub advice_high: &'static str,
+    pub no_samples_above_floor: &'static str,
+    /// The points of the tracking in the simulated time, all the points and the RMS error.
+    pub tracking_line: &'static str,
+    pub tracking_none: &'static str,
+    pub batch_title: &'static str,
+    pub basket: &'static str,
+    pub batch_scored: &'static str,
+    pub streamed: &'static str,
+    pub equations: &'static str,
+    pub decomposed_velocity: &'static str,
+    pub apex_explained: &'static str,
+    pub instant_throw: &'static str,
+    pub instant_apex: &'static str,
+    pub instant_closest: &'static str,
+    pub instant_last: &'static str,
+    pub ball_is_in: &'static str,
+    pub ball_is_not_in: &'static str,
+    pub drag_title: &'static str,
+    pub drag_ideal: &'static str,
+    pub drag_with_drag: &'static str,
+    pub drag_difference: &'static str,
+    pub drag_result: &'static str,
+    pub drag_apex: &'static str,
+    pub drag_range: &'static str,
+    pub drag_flight: &'static str,
+    pub drag_closest: &'static str,
+    pub quiz_round: &'static str,
+    /// The throw position, the speed, the angle and the position of the basket.
+    pub quiz_shot: &'static str,
+    pub quiz_ask_scored: &'static str,
+    /// The first letter of the answers yes and no, in lowercase.
+    pub quiz_yes: &'static str,
+    pub quiz_no: &'static str,
+    pub quiz_ask_apex: &'static str,
+    pub quiz_reveal: &'static str,
+    pub quiz_right: &'static str,
+    pub quiz_wrong: &'static str,
+    pub quiz_apex_close: &'static str,
+    pub quiz_apex_far: &'static str,
+    pub quiz_score: &'static str,
+    pub quiz_final: &'static str,
+    pub pass_title: &'static str,
+    /// The distance to the teammate and the height of the catch point.
+    pub pass_target: &'static str,
+    pub pass_kind: &'static str,
+    pub pass_angle: &'static str,
+    pub pass_speed: &'static str,
+    pub pass_catch_t: &'static str,
+    pub pass_apex: &'static str,
+    pub pass_caught: &'static str,
+    pub pass_caught_yes: &'static str,
+    pub pass_caught_no: &'static str,
+    pub pass_chest: &'static str,
+    pub pass_lob: &'static str,
+    pub pass_unreachable: &'static str,
+    pub mc_title: &'static str,
+    /// The number of shots and the noise of the speed and of the angle.
+    pub mc_noise: &'static str,
+    /// The shots made, all the shots and the percentage.
+    pub mc_open: &'static str,
+    pub mc_contested: &'static str,
+    /// The distance and the reach of the defender, and the mean clearance of the ball.
+    pub mc_defender: &'static str,
+    pub ft_title: &'static str,
+    /// The distance from the free throw line to the basket.
+    pub ft_distance: &'static str,
+    pub ft_overhand: &'static str,
+    pub ft_underhand: &'static str,
+    pub ft_release_height: &'static str,
+    pub ft_release_angle: &'static str,
+    pub ft_speed: &'static str,
+    pub ft_entry_angle: &'static str,
+    pub ft_speed_window: &'static str,
+    pub ft_angle_window: &'static str,
+    pub ft_note: &'static str,
+    pub rebound_title: &'static str,
+    pub contact_front_rim: &'static str,
+    pub contact_back_rim: &'static str,
+    pub contact_backboard: &'static str,
+    pub contact_floor: &'static str,
+    /// The instant, the kind of the bounce and the position.
+    pub rebound_contact: &'static str,
+    /// The XX position, the distance to the basket, in front or behind, and the instant.
+    pub rebound_landing: &'static str,
+    pub rebound_in_front: &'static str,
+    pub rebound_behind: &'static str,
+    pub rebound_rolled_in: &'static str,
+    pub rebound_none: &'static str,
+    pub rebound_scored: &'static str,
+    /// The release time, the flight time, the total, the budget and the time to spare or over.
+    pub timing_fits: &'static str,
+    pub timing_too_slow: &'static str,
+    pub timing_too_slow_scored: &'static str,
+    pub pass_bounce: &'static str,
+    /// The distance from the passer to the bounce and the instant.
+    pub pass_bounce_at: &'static str,
+    pub balls_title: &'static str,
+    pub balls_ball: &'static str,
+    pub balls_speed: &'static str,
+    pub balls_angle: &'static str,
+    pub balls_apex: &'static str,
+    pub balls_closest: &'static str,
+    pub balls_result: &'static str,
+    /// The ball that scored, or the balls like "1, 3".
+    pub balls_winner: &'static str,
+    pub balls_winners: &'static str,
+    pub balls_nobody: &'static str,
+    /// The run file of the replay.
+    pub replay_note: &'static str,
+    pub random_title: &'static str,
+    pub random_seed: &'static str,
+    pub random_distance: &'static str,
+    pub random_height: &'static str,
+    pub random_ideal: &'static str,
+    /// The shots that scored and all the shots.
+    pub random_scored: &'static str,
+    pub compare_title: &'static str,
+    pub compare_scenarios_same: &'static str,
+    pub compare_scenarios_differ: &'static str,
+    /// The stride of the printed samples.
+    pub compare_positions: &'static str,
+    pub compare_delta: &'static str,
+    /// The largest distance and its instant.
+    pub compare_max: &'static str,
+    pub compare_no_overlap: &'static str,
+    pub compare_summary_same: &'static str,
+    pub compare_summary_differ: &'static str,
+    pub drag_wind: &'static str,
+    /// The temperature, the humidity and the density of the air.
+    pub drag_air: &'static str,
+    pub coriolis_title: &'static str,
+    /// The latitude, the azimuth and the instant.
+    pub coriolis_setup: &'static str,
+    pub coriolis_side: &'static str,
+    pub coriolis_along: &'static str,
+    pub coriolis_up: &'static str,
+    /// How many times the radius of the rim is larger than the deflection.
+    pub coriolis_note: &'static str,
+    /// The name of the axis.
+    pub velocity_component: &'static str,
+    /// The height, the standing reach and the jump of the player, and the release height.
+    pub player_release: &'static str,
+    pub bench_title: &'static str,
+    pub bench_name: &'static str,
+    pub bench_samples: &'static str,
+    pub bench_time: &'static str,
+    pub bench_rate: &'static str,
+    pub bench_allocations: &'static str,
+    pub bench_note: &'static str,
+    pub bench_no_allocations: &'static str,
+    pub mr_title: &'static str,
+    pub mr_profile: &'static str,
+    pub mr_speed: &'static str,
+    pub mr_made: &'static str,
+    pub mr_rate: &'static str,
+    pub correction_short: &'static str,
+    pub correction_long: &'static str,
+    pub correction_low: &'static str,
+    pub correction_faster: &'static str,
+    pub correction_slower: &'static str,
+    pub correction_raise: &'static str,
+    pub correction_lower: &'static str,
+    pub correction_or: &'static str,
+    pub results_result: &'static str,
+    pub result_swish: &'static str,
+    pub result_rim_in: &'static str,
+    pub result_backboard_in: &'static str,
+    pub result_rim_out: &'static str,
+    pub result_airball: &'static str,
+    pub result_blocked: &'static str,
+    pub result_short: &'static str,
+    pub result_long: &'static str,
+    pub batch_resumed: &'static str,
+    pub noise_gaussian: &'static str,
+    pub noise_uniform: &'static str,
+    pub noise_truncated: &'static str,
+    pub result_obstacle: &'static str,
+    pub results_obstacle: &'static str,
+    pub frame_shooter: &'static str,
+    pub frame_hoop: &'static str,
+    pub batch_gallery: &'static str,
+    pub results_targets: &'static str,
+    pub results_target_hit: &'static str,
+    pub results_target_missed: &'static str,
+    /// The speed of the running teammate of the passes.
+    pub pass_runner: &'static str,
+    pub pass_lead: &'static str,
+    /// The header of the table of the energy of the rebound, the columns and the rows.
+    pub rebound_energy_title: &'static str,
+    pub rebound_energy_columns: &'static str,
+    pub rebound_energy_release: &'static str,
+    pub rebound_energy_end: &'static str,
+    /// The automatic stop of the simulation, the instant and the reason.
+    pub auto_stop_line: &'static str,
+    pub rebound_stop: &'static str,
+    pub stop_scored: &'static str,
+    pub stop_floor: &'static str,
+    pub stop_at_rest: &'static str,
+    pub stop_out_of_court: &'static str,
+    pub stop_time_limit: &'static str,
+    /// The columns of the summary table of a batch.
+    pub batch_column_name: &'static str,
+    pub batch_column_angle: &'static str,
+    pub batch_column_speed: &'static str,
+    pub batch_column_result: &'static str,
+    pub batch_column_closest: &'static str,
+    pub batch_column_flight: &'static str,
+    pub batch_error: &'static str,
+    /// The errors of the scenarios, below the table, the number and the error.
+    pub batch_error_line: &'static str,
+    pub batch_csv: &'static str,
+    /// The goals of the other sports and what the ball did at them.
+    pub results_goal: &'static str,
+    pub goal_soccer: &'static str,
+    pub goal_volleyball: &'static str,
+    pub goal_dartboard: &'static str,
+    pub goal_short: &'static str,
+    pub goal_soccer_scored: &'static str,
+    pub goal_soccer_over: &'static str,
+    pub goal_volleyball_in: &'static str,
+    pub goal_volleyball_net: &'static str,
+    pub goal_volleyball_out: &'static str,
+    pub goal_volleyball_in_air: &'static str,
+    pub goal_dartboard_hit: &'static str,
+    pub goal_dartboard_miss: &'static str,
+    pub post_score_line: &'static str,
+    pub post_score_in_net: &'static str,
+}
+
+pub static EN: Messages = Messages {
+    title: "Did the basketball go into the basket?",
+    data: "Data:",
+    throw_position: "Player throw position:",
+    meters: "meters",
+    initial_velocity: "Initial velocity vector:",
+    meters_per_second: "Meters per second",
+    km_per_hour: "Km per hour",
+    teta_0_degrees: "degrees = angle in degrees XX axis to YY axis.",
+    phi_0_degrees: "degrees = angle in degrees ZZ axis to XX axis.",
+    basket_position: "Basket position:",
+    simulation_time: "Test the simulation for how many seconds?",
+    seconds_to_simulate: "Seconds to simulate",
+    num_steps_points: "Divide the simulation seconds into N equal points.",
+    output_svg: "Output SVG",
+    none: "none",
+    trajectory: "Trajectory",
+    entered_the_basket: "Entered the basket",
+    flag_true: "true",
+    flag_false: "false",
+    ball_entered_the_basket: "ball entered the basket",
+    scored: "SCORED",
+    missed: "MISSED",
+    summary_line: "{} | apex: {} m | range: {} m | flight: {} s | closest to the basket: {} m at t: {} s",
+    results_title: "Results:",
+    results_scored_at: "Scored: yes, at t: {} s",
+    results_missed: "Scored: no",
+    results_apex: "Apex: {} m at t: {} s",
+    results_range: "Range: {} m",
+    results_flight: "Flight time: {} s",
+    results_entry: "Entry: {} m/s at {}° below the horizontal",
+    results_closest: "Closest to the basket center: {} m at t: {} s",
+    results_distance: "Distance through the air to the closest instant: {} m, {} m in a straight line from the release to the basket, {} m in the whole flight",
+    results_value: "Shot value: {} points, from {} m of the basket, {} m {} the {} three-point line",
+    value_inside: "inside",
+    value_outside: "outside",
+    results_on_line: "Warning: the feet of the shooter may be on the three-point line, it's a 2 point shot.",
+    results_arc: "Arc: {}, released at {}° and coming down at {}° at the height of the rim",
+    results_arc_none: "Arc: the ball doesn't come down through the height of the rim, throw it harder",
+    arc_flat: "flat",
+    arc_medium: "medium",
+    arc_high: "high",
+    advice_flat: "Advice: entry angle {}° is too flat for reliable makes; try ≥ {}° release",
+    advice_medium: "Advice: entry angle {}° gives the ball a good window into the rim",
+    advice_high: "Advice: entry angle {}° is very steep, the shot needs more strength and is harder to control; try ≤ {}° release",
+    no_samples_above_floor: "No samples above the floor.",
+    tracking_line: "Tracking: {} of {} points in the simulated time | RMS error to the simulation: {} m",
+    tracking_none: "Tracking: none of the {} points is in the simulated time.",
+    batch_title: "Batch of {} scenarios:",
+    basket: "basket",
+    batch_scored: "Scored {} of {}.",
+    streamed: "Streamed {} samples to {} -> {}",
+    equations: "Equations",
+    decomposed_velocity: "Decomposed velocity, the angle teta_0 from the floor:",
+    apex_explained: "The top of the arc is when the ball stops going up, v_y = 0:",
+    instant_throw: "The throw",
+    instant_apex: "The top of the arc",
+    instant_closest: "The closest instant to the basket",
+    instant_last: "The last instant above the floor",
+    ball_is_in: "the ball is in the basket.",
+    ball_is_not_in: "the ball isn't in the basket.",
+    drag_title: "With and without the air drag",
+    drag_ideal: "Ideal",
+    drag_with_drag: "With drag",
+    drag_difference: "Difference",
+    drag_result: "Result",
+    drag_apex: "Apex (m)",
+    drag_range: "Range (m)",
+    drag_flight: "Flight (s)",
+    drag_closest: "Closest (m)",
+    quiz_round: "Round {} of {}",
+    quiz_shot: "The player throws from x = {} m, y = {} m with v_0 = {} m/s at teta_0 = {}°, the basket is at x = {} m, y = {} m.",
+    quiz_ask_scored: "Will it go into the basket? [y/n]: ",
+    quiz_yes: "y",
+    quiz_no: "n",
+    quiz_ask_apex: "How high will it go, in meters? (Enter to skip): ",
+    quiz_reveal: "{}! The top of the arc was at {} m.",
+    quiz_right: "Right, +1 point.",
+    quiz_wrong: "Wrong.",
+    quiz_apex_close: "The height was {} m off, +1 point.",
+    quiz_apex_far: "The height was {} m off.",
+    quiz_score: "Score: {} of {} points.",
+    quiz_final: "Final score: {} of {} points in {} rounds.",
+    pass_title: "Passes to the teammate",
+    pass_target: "The teammate is {} m away, catching the ball at {} m of height, within {} m of the hands.",
+    pass_kind: "Pass",
+    pass_angle: "Angle (°)",
+    pass_speed: "Speed (m/s)",
+    pass_catch_t: "Catch (s)",
+    pass_apex: "Apex (m)",
+    pass_caught: "Caught",
+    pass_caught_yes: "yes",
+    pass_caught_no: "no",
+    pass_chest: "chest",
+    pass_lob: "lob",
+    pass_unreachable: "it doesn't reach the teammate",
+    mc_title: "Monte Carlo",
+    mc_noise: "{} shots with the noise of the release, {} in v_0 and {} in teta_0.",
+    mc_open: "Open: {} of {} made, {}%",
+    mc_contested: "Contested: {} of {} made, {}%",
+    mc_defender: "The defender is {} m in front with the hand at {} m, the mean clearance of the ball over the hand is {} m.",
+    ft_title: "Free throws, overhand and underhand",
+    ft_distance: "The basket is {} m in front of the free throw line, the same for both.",
+    ft_overhand: "Overhand",
+    ft_underhand: "Underhand",
+    ft_release_height: "Release height (m)",
+    ft_release_angle: "Release angle (°)",
+    ft_speed: "Ideal speed (m/s)",
+    ft_entry_angle: "Entry angle (°)",
+    ft_speed_window: "Speed window (m/s)",
+    ft_angle_window: "Angle window (°)",
+    ft_note: "The windows are the ranges of the speed and of the angle that still score, the wider the more forgiving.",
+    rebound_title: "Rebound",
+    contact_front_rim: "front of the rim",
+    contact_back_rim: "back of the rim",
+    contact_backboard: "backboard",
+    contact_floor: "floor",
+    rebound_contact: "{} s: {} at x = {} m, y = {} m",
+    rebound_landing: "The rebound lands at x = {} m, {} m {} the basket, at {} s.",
+    rebound_in_front: "in front of",
+    rebound_behind: "behind",
+    rebound_rolled_in: "The ball bounced in, the miss is a make after all.",
+    rebound_none: "The ball doesn't touch the rim nor the backboard, there's no rebound off the basket.",
+    rebound_scored: "The shot went in, there's no rebound.",
+    timing_fits: "Timing: release {} s + flight {} s = {} s, it fits in the budget of {} s with {} s to spare.",
+    timing_too_slow: "Timing: release {} s + flight {} s = {} s, too slow for the budget of {} s by {} s.",
+    timing_too_slow_scored: "The shot scores, but too late.",
+    pass_bounce: "bounce",
+    pass_bounce_at: "The bounce pass hits the floor {} m in front of the passer, at {} s.",
+    balls_title: "Balls shot at once",
+    balls_ball: "Ball",
+    balls_speed: "Speed (m/s)",
+    balls_angle: "Angle (°)",
+    balls_apex: "Apex (m)",
+    balls_closest: "Closest (m)",
+    balls_result: "Result",
+    balls_winner: "Who scores? The ball {}.",
+    balls_winners: "Who scores? The balls {}.",
+    balls_nobody: "Who scores? Nobody.",
+    replay_note: "Replay of the run {}, the trajectory isn't simulated again.",
+    random_title: "Random shots",
+    random_seed: "Seed {}, the same seed gives the same shots.",
+    random_distance: "Distance (m)",
+    random_height: "Height (m)",
+    random_ideal: "Ideal (m/s)",
+    random_scored: "{} of the {} shots scored.",
+    compare_title: "Comparison of the runs",
+    compare_scenarios_same: "The runs have the same scenario.",
+    compare_scenarios_differ: "The runs have different scenarios.",
+    compare_positions: "Positions of B at the instants of A, one in every {} samples:",
+    compare_delta: "Delta (m)",
+    compare_max: "The largest difference is {} m, at t = {} s.",
+    compare_no_overlap: "The runs have no instants in common.",
+    compare_summary_same: "The results and the values of the shot are the same.",
+    compare_summary_differ: "The values that differ:",
+    drag_wind: "The shot with drag is in the wind of the scenario.",
+    drag_air: "Air at {} °C and {}% humidity, {} kg/m^3.",
+    coriolis_title: "The Coriolis effect",
+    coriolis_setup: "At the latitude {}° with the shot towards {}° from the north, at t = {} s the ball moved:",
+    coriolis_side: "To the side: {} mm, to the right of the shooter when positive.",
+    coriolis_along: "Along the shot: {} mm.",
+    coriolis_up: "Up: {} mm.",
+    coriolis_note: "The radius of the rim is {} times larger, the rotation of the Earth doesn't matter at the scale of the court.",
+    velocity_component: "component along the {} axis",
+    player_release: "A player of {} m, with a standing reach of {} m and a jump of {} m, releases the ball at {} m.",
+    bench_title: "Benchmark",
+    bench_name: "Benchmark",
+    bench_samples: "Samples",
+    bench_time: "Time (s)",
+    bench_rate: "Samples/s",
+    bench_allocations: "Allocations",
+    bench_note: "The drag and the rim have a tenth of the samples, the figure is the one of a tenth of them. Build with --release.",
+    bench_no_allocations: "The allocations are counted with the feature bench, cargo run --release --features bench -- bench.",
+    mr_title: "Make rate by distance",
+    mr_profile: "{} shots at each distance, released at {} m and {}°, with the speed through the center of the basket and the noise {} in v_0 and {} in teta_0.",
+    mr_speed: "v_0 (m/s)",
+    mr_made: "Made",
+    mr_rate: "Rate (%)",
+    correction_short: "Correction: the ball passed {} m short of the basket at the height of the rim",
+    correction_long: "Correction: the ball passed {} m long of the basket at the height of the rim",
+    correction_low: "Correction: the top of the arc is {} m below the rim",
+    correction_faster: "increase v_0 by {} m/s",
+    correction_slower: "decrease v_0 by {} m/s",
+    correction_raise: "raise the angle to {}°",
+    correction_lower: "lower the angle to {}°",
+    correction_or: " or ",
+    results_result: "Result: {}",
+    result_swish: "swish, nothing but net",
+    result_rim_in: "in off the rim",
+    result_backboard_in: "in off the backboard",
+    result_rim_out: "rim out",
+    result_airball: "airball, below the rim",
+    result_blocked: "blocked by the defender",
+    result_short: "short, without touching the rim",
+    result_long: "long, without touching the rim",
+    batch_resumed: "Resumed {} of the {} scenarios from the checkpoint {}.",
+    noise_gaussian: "normal with σ = {}",
+    noise_uniform: "uniform within ±{}",
+    noise_truncated: "normal with σ = {} within ±{}",
+    result_obstacle: "hit an obstacle",
+    results_obstacle: "Obstacle: the ball hits \"{}\" at t = {} s, x = {} m, y = {} m.",
+    frame_shooter: "Positions from the release point, dx along the shot and dy up.",
+    frame_hoop: "Positions from the center of the rim, d the distance before it and h the height above it.",
+    batch_gallery: "Gallery of the scenarios in {}.",
+    results_targets: "Targets: {} of {} hit.",
+    results_target_hit: "{}: hit at t = {} s, x = {} m, y = {} m.",
+    results_target_missed: "{}: missed, the center of the ball passes {} m from its center.",
+    pass_runner: "The teammate runs at {} m/s along the pass, the ball and the teammate reach the catch point at the same instant.",
+    pass_lead: "Lead (m)",
+    rebound_energy_title: "The energy of the ball, kinetic and potential, 0 J at rest on the floor:",
+    rebound_energy_columns: "Energy (J)",
+    rebound_energy_release: "release",
+    rebound_energy_end: "end",
+    auto_stop_line: "The simulation stops at {} s, {}.",
+    rebound_stop: "The rebound ends at {} s, {}.",
+    stop_scored: "the ball entered the basket",
+    stop_floor: "the ball reached the floor",
+    stop_at_rest: "the ball is at rest",
+    stop_out_of_court: "the ball left the court",
+    stop_time_limit: "at the time limit",
+    batch_column_name: "Scenario",
+    batch_column_angle: "Angle (°)",
+    batch_column_speed: "Speed (m/s)",
+    batch_column_result: "Result",
+    batch_column_closest: "Closest (m)",
+    batch_column_flight: "Flight (s)",
+    batch_error: "ERROR",
+    batch_error_line: "Scenario {}: {}",
+    batch_csv: "Summary of the batch in {}.",
+    results_goal: "{}: {}",
+    goal_soccer: "Soccer goal",
+    goal_volleyball: "Volleyball net",
+    goal_dartboard: "Dartboard",
+    goal_short: "missed, the ball comes down before it.",
+    goal_soccer_scored: "GOAL, the top of the ball passes {} m under the crossbar.",
+    goal_soccer_over: "missed, over the crossbar by {} m.",
+    goal_volleyball_in: "IN, the ball clears the net by {} m and lands {} m past it.",
+    goal_volleyball_net: "into the net, {} m too low.",
+    goal_volleyball_out: "OUT, the ball clears the net by {} m and lands {} m past it, beyond the end line.",
+    goal_volleyball_in_air: "the ball clears the net by {} m and is still in the air at the end of the simulation.",
+    goal_dartboard_hit: "HIT, {} m from the center, {} points.",
+    goal_dartboard_miss: "missed, {} m from the center, off the board.",
+    post_score_line: "After the basket the ball leaves the net at {} s, the drop ends at {} s, {}.",
+    post_score_in_net: "After the basket the drop ends at {} s in the net, {}.",
+};
+
+pub static PT: Messages = Messages {
+    title: "A bola de basquetebol entrou no cesto?",
+    data: "Dados:",
+    throw_position: "Posição de lançamento do jogador:",
+    meters: "metros",
+    initial_velocity: "Vetor da velocidade inicial:",
+    meters_per_second: "Metros por segundo",
+    km_per_hour: "Km por hora",
+    teta_0_degrees: "graus = ângulo em graus do eixo XX para o eixo YY.",
+    phi_0_degrees: "graus = ângulo em graus do eixo ZZ para o eixo XX.",
+    basket_position: "Posição do cesto:",
+    simulation_time: "Simular durante quantos segundos?",
+    seconds_to_simulate: "Segundos a simular",
+    num_steps_points: "Dividir os segundos da simulação em N pontos iguais.",
+    output_svg: "SVG de saída",
+    none: "nenhum",
+    trajectory: "Trajetória",
+    entered_the_basket: "Entrou no cesto",
+    flag_true: "sim",
+    flag_false: "não",
+    ball_entered_the_basket: "a bola entrou no cesto",
+    scored: "CESTO",
+    missed: "FALHOU",
+    summary_line: "{} | altura máxima: {} m | alcance: {} m | voo: {} s | mais perto do cesto: {} m em t: {} s",
+    results_title: "Resultados:",
+    results_scored_at: "Encestou: sim, em t: {} s",
+    results_missed: "Encestou: não",
+    results_apex: "Altura máxima: {} m em t: {} s",
+    results_range: "Alcance: {} m",
+    results_flight: "Tempo de voo: {} s",
+    results_entry: "Entrada: {} m/s a {}° abaixo da horizontal",
+    results_closest: "Mais perto do centro do cesto: {} m em t: {} s",
+    results_distance: "Distância pelo ar até ao instante mais perto: {} m, {} m em linha reta do lançamento ao cesto, {} m em todo o voo",
+    results_value: "Valor do lançamento: {} pontos, a {} m do cesto, a {} m {} linha de três pontos da {}",
+    value_inside: "dentro da",
+    value_outside: "fora da",
+    results_on_line: "Aviso: os pés do jogador podem estar na linha de três pontos, é um lançamento de 2 pontos.",
+    results_arc: "Arco: {}, lançada a {}° e a descer a {}° à altura do aro",
+    results_arc_none: "Arco: a bola não desce pela altura do aro, lance-a com mais força",
+    arc_flat: "baixo",
+    arc_medium: "médio",
+    arc_high: "alto",
+    advice_flat: "Conselho: o ângulo de entrada de {}° é demasiado baixo para encestar com segurança; experimente lançar a ≥ {}°",
+    advice_medium: "Conselho: o ângulo de entrada de {}° dá à bola uma boa abertura para o aro",
+    advice_high: "Conselho: o ângulo de entrada de {}° é muito inclinado, o lançamento precisa de mais força e é mais difícil de controlar; experimente lançar a ≤ {}°",
+    no_samples_above_floor: "Nenhuma amostra acima do chão.",
+    tracking_line: "Medições: {} de {} pontos no tempo simulado | erro RMS em relação à simulação: {} m",
+    tracking_none: "Medições: nenhum dos {} pontos está no tempo simulado.",
+    batch_title: "Lote de {} cenários:",
+    basket: "cesto",
+    batch_scored: "Encestou {} de {}.",
+    streamed: "Escritas {} amostras em {} -> {}",
+    equations: "Equações",
+    decomposed_velocity: "Velocidade decomposta, o ângulo teta_0 a partir do chão:",
+    apex_explained: "O topo do arco é quando a bola deixa de subir, v_y = 0:",
+    instant_throw: "O lançamento",
+    instant_apex: "O topo do arco",
+    instant_closest: "O instante mais perto do cesto",
+    instant_last: "O último instante acima do chão",
+    ball_is_in: "a bola está no cesto.",
+    ball_is_not_in: "a bola não está no cesto.",
+    drag_title: "Com e sem a resistência do ar",
+    drag_ideal: "Ideal",
+    drag_with_drag: "Com o ar",
+    drag_difference: "Diferença",
+    drag_result: "Resultado",
+    drag_apex: "Altura (m)",
+    drag_range: "Alcance (m)",
+    drag_flight: "Voo (s)",
+    drag_closest: "Perto (m)",
+    quiz_round: "Ronda {} de {}",
+    quiz_shot: "O jogador lança de x = {} m, y = {} m com v_0 = {} m/s e teta_0 = {}°, o cesto está em x = {} m, y = {} m.",
+    quiz_ask_scored: "Vai entrar no cesto? [s/n]: ",
+    quiz_yes: "s",
+    quiz_no: "n",
+    quiz_ask_apex: "Até que altura vai subir, em metros? (Enter para saltar): ",
+    quiz_reveal: "{}! O topo do arco foi a {} m.",
+    quiz_right: "Certo, +1 ponto.",
+    quiz_wrong: "Errado.",
+    quiz_apex_close: "A altura teve um erro de {} m, +1 ponto.",
+    quiz_apex_far: "A altura teve um erro de {} m.",
+    quiz_score: "Pontuação: {} de {} pontos.",
+    quiz_final: "Pontuação final: {} de {} pontos em {} rondas.",
+    pass_title: "Passes ao colega de equipa",
+    pass_target: "O colega está a {} m, apanha a bola a {} m de altura, até {} m das mãos.",
+    pass_kind: "Passe",
+    pass_angle: "Ângulo (°)",
+    pass_speed: "Veloc. (m/s)",
+    pass_catch_t: "Receção (s)",
+    pass_apex: "Alt. máx. (m)",
+    pass_caught: "Apanhada",
+    pass_caught_yes: "sim",
+    pass_caught_no: "não",
+    pass_chest: "de peito",
+    pass_lob: "em arco",
+    pass_unreachable: "não chega ao colega",
+    mc_title: "Monte Carlo",
+    mc_noise: "{} lançamentos com o ruído do lançamento, {} em v_0 e {} em teta_0.",
+    mc_open: "Livres: {} de {} encestados, {}%",
+    mc_contested: "Contestados: {} de {} encestados, {}%",
+    mc_defender: "O defensor está a {} m à frente com a mão a {} m, a folga média da bola acima da mão é de {} m.",
+    ft_title: "Lances livres, por cima e por baixo",
+    ft_distance: "O cesto está a {} m à frente da linha de lance livre, a mesma para os dois.",
+    ft_overhand: "Por cima",
+    ft_underhand: "Por baixo",
+    ft_release_height: "Altura de lançamento (m)",
+    ft_release_angle: "Ângulo de lançamento (°)",
+    ft_speed: "Velocidade ideal (m/s)",
+    ft_entry_angle: "Ângulo de entrada (°)",
+    ft_speed_window: "Janela da velocidade (m/s)",
+    ft_angle_window: "Janela do ângulo (°)",
+    ft_note: "As janelas são as gamas da velocidade e do ângulo que ainda encestam, quanto mais largas mais tolerantes.",
+    rebound_title: "Ressalto",
+    contact_front_rim: "frente do aro",
+    contact_back_rim: "trás do aro",
+    contact_backboard: "tabela",
+    contact_floor: "chão",
+    rebound_contact: "{} s: {} em x = {} m, y = {} m",
+    rebound_landing: "O ressalto cai em x = {} m, a {} m {} do cesto, aos {} s.",
+    rebound_in_front: "à frente",
+    rebound_behind: "atrás",
+    rebound_rolled_in: "A bola ressaltou para dentro, afinal o falhanço é um cesto.",
+    rebound_none: "A bola não toca no aro nem na tabela, não há ressalto no cesto.",
+    rebound_scored: "O lançamento entrou, não há ressalto.",
+    timing_fits: "Tempo: lançamento {} s + voo {} s = {} s, cabe no tempo de {} s com {} s de folga.",
+    timing_too_slow: "Tempo: lançamento {} s + voo {} s = {} s, demasiado lento para o tempo de {} s por {} s.",
+    timing_too_slow_scored: "O lançamento encesta, mas tarde demais.",
+    pass_bounce: "picado",
+    pass_bounce_at: "O passe picado bate no chão a {} m à frente do passador, aos {} s.",
+    balls_title: "Bolas lançadas ao mesmo tempo",
+    balls_ball: "Bola",
+    balls_speed: "Velocidade (m/s)",
+    balls_angle: "Ângulo (°)",
+    balls_apex: "Topo (m)",
+    balls_closest: "Mais perto (m)",
+    balls_result: "Resultado",
+    balls_winner: "Quem encesta? A bola {}.",
+    balls_winners: "Quem encesta? As bolas {}.",
+    balls_nobody: "Quem encesta? Ninguém.",
+    replay_note: "Repetição da execução {}, a trajetória não é simulada outra vez.",
+    random_title: "Lançamentos aleatórios",
+    random_seed: "Semente {}, a mesma semente dá os mesmos lançamentos.",
+    random_distance: "Distância (m)",
+    random_height: "Altura (m)",
+    random_ideal: "Ideal (m/s)",
+    random_scored: "{} dos {} lançamentos encestaram.",
+    compare_title: "Comparação das execuções",
+    compare_scenarios_same: "As execuções têm o mesmo cenário.",
+    compare_scenarios_differ: "As execuções têm cenários diferentes.",
+    compare_positions: "Posições de B nos instantes de A, uma em cada {} amostras:",
+    compare_delta: "Dif. (m)",
+    compare_max: "A maior diferença é {} m, em t = {} s.",
+    compare_no_overlap: "As execuções não têm instantes em comum.",
+    compare_summary_same: "Os resultados e os valores do lançamento são iguais.",
+    compare_summary_differ: "Os valores que diferem:",
+    drag_wind: "O lançamento com o ar tem o vento do cenário.",
+    drag_air: "Ar a {} °C e {}% de humidade, {} kg/m^3.",
+    coriolis_title: "O efeito de Coriolis",
+    coriolis_setup: "Na latitude {}° com o lançamento para {}° do norte, em t = {} s a bola deslocou-se:",
+    coriolis_side: "Para o lado: {} mm, para a direita do jogador quando positivo.",
+    coriolis_along: "Ao longo do lançamento: {} mm.",
+    coriolis_up: "Para cima: {} mm.",
+    coriolis_note: "O raio do aro é {} vezes maior, a rotação da Terra não importa à escala do campo.",
+    velocity_component: "componente segundo o eixo {}",
+    player_release: "Um jogador de {} m, com um alcance de {} m e um salto de {} m, lança a bola a {} m.",
+    bench_title: "Desempenho",
+    bench_name: "Teste",
+    bench_samples: "Amostras",
+    bench_time: "Tempo (s)",
+    bench_rate: "Amostras/s",
+    bench_allocations: "Alocações",
+    bench_note: "O arrasto e o aro têm um décimo das amostras, a figura é a de um décimo delas. Compile com --release.",
+    bench_no_allocations: "As alocações são contadas com a feature bench, cargo run --release --features bench -- bench.",
+    mr_title: "Taxa de acerto pela distância",
+    mr_profile: "{} lançamentos a cada distância, a {} m e {}°, com a velocidade pelo centro do cesto e o ruído {} em v_0 e {} em teta_0.",
+    mr_speed: "v_0 (m/s)",
+    mr_made: "Cestos",
+    mr_rate: "Taxa (%)",
+    correction_short: "Correção: a bola passou {} m antes do cesto à altura do aro",
+    correction_long: "Correção: a bola passou {} m depois do cesto à altura do aro",
+    correction_low: "Correção: o topo do arco fica {} m abaixo do aro",
+    correction_faster: "aumente v_0 em {} m/s",
+    correction_slower: "diminua v_0 em {} m/s",
+    correction_raise: "suba o ângulo para {}°",
+    correction_lower: "baixe o ângulo para {}°",
+    correction_or: " ou ",
+    results_result: "Resultado: {}",
+    result_swish: "cesto limpo, sem tocar no aro",
+    result_rim_in: "entrou depois de tocar no aro",
+    result_backboard_in: "entrou depois de tocar na tabela",
+    result_rim_out: "bateu no aro e saiu",
+    result_airball: "bola no ar, abaixo do aro",
+    result_blocked: "bloqueado pelo defensor",
+    result_short: "curto, sem tocar no aro",
+    result_long: "longo, sem tocar no aro",
+    batch_resumed: "Retomados {} dos {} cenários do checkpoint {}.",
+    noise_gaussian: "normal com σ = {}",
+    noise_uniform: "uniforme dentro de ±{}",
+    noise_truncated: "normal com σ = {} dentro de ±{}",
+    result_obstacle: "bateu num obstáculo",
+    results_obstacle: "Obstáculo: a bola bate em \"{}\" em t = {} s, x = {} m, y = {} m.",
+    frame_shooter: "Posições a partir do ponto de lançamento, dx ao longo do lançamento e dy para cima.",
+    frame_hoop: "Posições a partir do centro do aro, d a distância antes dele e h a altura acima dele.",
+    batch_gallery: "Galeria dos cenários em {}.",
+    results_targets: "Alvos: {} de {} atingidos.",
+    results_target_hit: "{}: atingido em t = {} s, x = {} m, y = {} m.",
+    results_target_missed: "{}: falhado, o centro da bola passa a {} m do seu centro.",
+    pass_runner: "O colega corre a {} m/s ao longo do passe, a bola e o colega chegam ao ponto de receção no mesmo instante.",
+    pass_lead: "Avanço (m)",
+    rebound_energy_title: "A energia da bola, cinética e potencial, 0 J parada no chão:",
+    rebound_energy_columns: "Energia (J)",
+    rebound_energy_release: "lançamento",
+    rebound_energy_end: "fim",
+    auto_stop_line: "A simulação para aos {} s, {}.",
+    rebound_stop: "O ressalto acaba aos {} s, {}.",
+    stop_scored: "a bola entrou no cesto",
+    stop_floor: "a bola chegou ao chão",
+    stop_at_rest: "a bola está parada",
+    stop_out_of_court: "a bola saiu do campo",
+    stop_time_limit: "no limite de tempo",
+    batch_column_name: "Cenário",
+    batch_column_angle: "Ângulo (°)",
+    batch_column_speed: "Velocidade (m/s)",
+    batch_column_result: "Resultado",
+    batch_column_closest: "Mais perto (m)",
+    batch_column_flight: "Voo (s)",
+    batch_error: "ERRO",
+    batch_error_line: "Cenário {}: {}",
+    batch_csv: "Resumo do lote em {}.",
+    results_goal: "{}: {}",
+    goal_soccer: "Baliza de futebol",
+    goal_volleyball: "Rede de voleibol",
+    goal_dartboard: "Alvo de dardos",
+    goal_short: "falhou, a bola cai antes.",
+    goal_soccer_scored: "GOLO, o topo da bola passa {} m abaixo da barra.",
+    goal_soccer_over: "falhou, por cima da barra por {} m.",
+    goal_volleyball_in: "DENTRO, a bola passa a rede por {} m e cai {} m depois dela.",
+    goal_volleyball_net: "na rede, {} m baixa demais.",
+    goal_volleyball_out: "FORA, a bola passa a rede por {} m e cai {} m depois dela, para lá da linha final.",
+    goal_volleyball_in_air: "a bola passa a rede por {} m e ainda está no ar no fim da simulação.",
+    goal_dartboard_hit: "ACERTOU, a {} m do centro, {} pontos.",
+    goal_dartboard_miss: "falhou, a {} m do centro, fora do alvo.",
+    post_score_line: "Depois do cesto a bola sai da rede aos {} s, a queda termina aos {} s, {}.",
+    post_score_in_net: "Depois do cesto a queda termina aos {} s dentro da rede, {}.",
+};
+
+/// The template with each "{}" replaced by the next value.
+pub fn fill(template: & str, values: & [& dyn fmt::Display]) -> String {
+    use std::fmt::Write;
+
+    let mut text = String::with_capacity(template.len() + values.len() * 8);
+    let mut values = values.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        text.push_str(first);
+    }
+    for part in parts {
+        if let Some(value) = values.next() {
+            let _ = write!(text, "{}", value);
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+static FLAG_DECIMAL_COMMA: AtomicBool = AtomicBool::new(false);
+
+/// The printed reports write the numbers with a decimal comma, like 3,05, the exported files
+/// always keep the point.
+pub fn set_decimal_comma(flag_decimal_comma: bool) {
+    FLAG_DECIMAL_COMMA.store(flag_decimal_comma, Ordering::Relaxed);
+}
+
+pub fn is_decimal_comma() -> bool {
+    FLAG_DECIMAL_COMMA.load(Ordering::Relaxed)
+}
+
+/// The separator of the numbers of a list, like the coordinates (x, y), a semicolon with the
+/// decimal comma.
+pub fn list_separator() -> &'static str {
+    if is_decimal_comma() {"; "} else {", "}
+}
+
+/// A number of a report, formatted like a f64 with the width, the alignment, the sign and the
+/// precision of the format, and with the decimal comma of set_decimal_comma().
+#[derive(Clone, Copy, Debug)]
+pub struct Num(pub f64);
+
+impl fmt::Display for Num {
+    fn fmt(& self, f: & mut fmt::Formatter) -> fmt::Result {
+        let text = match (f.precision(), f.sign_plus()) {
+            (Some(precision), true) => format!("{:+.*}", precision, self.0),
+            (Some(precision), false) => format!("{:.*}", precision, self.0),
+            (None, true) => format!("{:+}", self.0),
+            (None, false) => format!("{}", self.0),
+        };
+        let text = if is_decimal_comma() { text.replace('.', ",") } else { text };
+        // Formatter::pad() would cut the text to the precision.
+        let padding = f.width().unwrap_or(0).saturating_sub(text.chars().count());
+        let (left, right) = match f.align() {
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Right) | None => (padding, 0),
+        };
+        let fill = f.fill().to_string();
+        write!(f, "{}{}{}", fill.repeat(left), text, fill.repeat(right))
+    }
+}
+
+/// A title between two lines of asterisks, the width of the title.
+pub fn banner(title: & str) -> String {
+    let stars = "*".repeat(title.chars().count() + 6);
+    format!("{0}\n** {1} **\n{0}", stars, title)
+}
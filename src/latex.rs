@@ -0,0 +1,135 @@
+//! A LaTeX document with the worked solution of a shot, to hand out in a class: the data, the
+//! equations of the movement, the derivation with the numbers of the shot, the summary table
+//! and the figure of the trajectory, drawn with TikZ. It compiles with pdflatex.
+
+use std::fmt::Write;
+
+use crate::explain::{apex_time, key_instants, velocity_components};
+use crate::i18n::EN;
+use crate::plot_svg::marker_stride;
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{basket_backboard_x, ShotSummary, Trajectory,
+                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, GRAVITY, MIN_BALL_DELTA_TO_BASKET_CENTER, RIM_DIAMETER};
+
+// Size of the figure in cm and the maximum number of points of its curve.
+const FIGURE_WIDTH_CM: f64 = 12.0;
+const FIGURE_HEIGHT_CM: f64 = 7.0;
+const FIGURE_MAX_POINTS: usize = 300;
+
+/// The document of the trajectory simulated from the scenario.
+pub fn latex_worked_solution(scenario: & ScenarioFile, trajectory_2d: & Trajectory) -> String {
+    let (x_0, y_0) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let (v_0, teta_0_deg) = (scenario.throw.v_0, scenario.throw.teta_0_deg);
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let (v_0_x, v_0_y) = velocity_components(scenario);
+
+    let mut tex = String::with_capacity(10_000);
+    tex.push_str("\\documentclass[a4paper,11pt]{article}\n\
+                  \\usepackage[utf8]{inputenc}\n\
+                  \\usepackage{amsmath}\n\
+                  \\usepackage{booktabs}\n\
+                  \\usepackage{tikz}\n\
+                  \\title{Did the basketball go into the basket?}\n\
+                  \\date{}\n\
+                  \\begin{document}\n\
+                  \\maketitle\n\n");
+
+    tex.push_str("\\section*{Data}\n\\begin{tabular}{lr}\n\\toprule\n");
+    let _ = writeln!(tex, "Throw position $(x_0, y_0)$ & $({:.2}, {:.2})\\,\\mathrm{{m}}$ \\\\", x_0, y_0);
+    let _ = writeln!(tex, "Initial speed $v_0$ & ${:.2}\\,\\mathrm{{m/s}}$ \\\\", v_0);
+    let _ = writeln!(tex, "Angle from the floor $\\theta_0$ & ${:.2}^\\circ$ \\\\", teta_0_deg);
+    let _ = writeln!(tex, "Center of the basket & $({:.2}, {:.2})\\,\\mathrm{{m}}$ \\\\", basket_pos_x, basket_pos_y);
+    let _ = writeln!(tex, "Gravity $g$ & ${:.3}\\,\\mathrm{{m/s^2}}$ \\\\", GRAVITY);
+    tex.push_str("\\bottomrule\n\\end{tabular}\n\n");
+
+    tex.push_str("\\section*{Equations}\n\
+                  The uniformly accelerated movement, decomposed in the horizontal and the vertical components:\n\
+                  \\begin{align*}\n\
+                  v_{0x} &= v_0 \\cos\\theta_0 & v_{0y} &= v_0 \\sin\\theta_0 \\\\\n\
+                  x(t) &= x_0 + v_{0x}\\, t & y(t) &= y_0 + v_{0y}\\, t - \\tfrac{1}{2} g t^2 \\\\\n\
+                  & & v_y(t) &= v_{0y} - g t\n\
+                  \\end{align*}\n");
+    let _ = writeln!(tex, "The ball enters the basket when its distance to the center of the basket is at most ${:.2}\\,\\mathrm{{m}}$:\n\
+                           \\[ d(t) = \\sqrt{{(x(t) - x_b)^2 + (y(t) - y_b)^2}} \\]\n",
+                     MIN_BALL_DELTA_TO_BASKET_CENTER);
+
+    tex.push_str("\\section*{Solution}\n\\begin{align*}\n");
+    let _ = writeln!(tex, "v_{{0x}} &= {:.2} \\cos {:.2}^\\circ = {:.2}\\,\\mathrm{{m/s}} \\\\", v_0, teta_0_deg, v_0_x);
+    let _ = write!(tex, "v_{{0y}} &= {:.2} \\sin {:.2}^\\circ = {:.2}\\,\\mathrm{{m/s}}", v_0, teta_0_deg, v_0_y);
+    if let Some(t_apex) = apex_time(scenario) {
+        let _ = write!(tex, " \\\\\nv_y(t) = 0 \\;\\Rightarrow\\; t &= \\frac{{v_{{0y}}}}{{g}} = \\frac{{{:.2}}}{{{:.2}}} = {:.2}\\,\\mathrm{{s}}",
+                       v_0_y, GRAVITY, t_apex);
+    }
+    tex.push_str("\n\\end{align*}\n");
+    for instant in key_instants(scenario, trajectory_2d) {
+        let t = instant.t;
+        let _ = writeln!(tex, "\\paragraph{{{}, $t = {:.2}\\,\\mathrm{{s}}$}}", instant.kind.title(& EN), t);
+        tex.push_str("\\begin{align*}\n");
+        let _ = writeln!(tex, "x &= {:.2} + {:.2} \\cdot {:.2} = {:.2}\\,\\mathrm{{m}} \\\\", x_0, v_0_x, t, instant.ball_x);
+        let _ = writeln!(tex, "y &= {:.2} + {:.2} \\cdot {:.2} - \\tfrac{{1}}{{2}} \\cdot {:.2} \\cdot {:.2}^2 = {:.2}\\,\\mathrm{{m}} \\\\",
+                         y_0, v_0_y, t, GRAVITY, t, instant.ball_y);
+        let _ = writeln!(tex, "v_y &= {:.2} - {:.2} \\cdot {:.2} = {:.2}\\,\\mathrm{{m/s}} \\\\", v_0_y, GRAVITY, t, instant.v_y);
+        let _ = writeln!(tex, "d &= \\sqrt{{({:.2} - {:.2})^2 + ({:.2} - {:.2})^2}} = {:.2}\\,\\mathrm{{m}}",
+                         instant.ball_x, basket_pos_x, instant.ball_y, basket_pos_y, instant.dist);
+        tex.push_str("\\end{align*}\n");
+    }
+
+    tex.push_str("\n\\section*{Summary}\n");
+    match ShotSummary::new(trajectory_2d, basket_pos_x, basket_pos_y) {
+        Some(summary) => {
+            tex.push_str("\\begin{tabular}{lr}\n\\toprule\n");
+            let _ = writeln!(tex, "Result & {} \\\\", if summary.flag_scored {"scored"} else {"missed"});
+            let _ = writeln!(tex, "Top of the arc & ${:.2}\\,\\mathrm{{m}}$ \\\\", summary.apex_y);
+            let _ = writeln!(tex, "Range & ${:.2}\\,\\mathrm{{m}}$ \\\\", summary.range_x);
+            let _ = writeln!(tex, "Flight time & ${:.2}\\,\\mathrm{{s}}$ \\\\", summary.flight_sec);
+            let _ = writeln!(tex, "Closest to the basket & ${:.2}\\,\\mathrm{{m}}$ at $t = {:.2}\\,\\mathrm{{s}}$ \\\\",
+                             summary.closest_dist, summary.closest_t);
+            tex.push_str("\\bottomrule\n\\end{tabular}\n\n");
+            write_figure(& mut tex, trajectory_2d, basket_pos_x, basket_pos_y);
+        },
+        None => tex.push_str("The ball starts below the floor.\n\n"),
+    }
+    tex.push_str("\\end{document}\n");
+    tex
+}
+
+/// Writes the document to a file.
+pub fn save_latex(filename: & str, scenario: & ScenarioFile, trajectory_2d: & Trajectory) -> Result<(), String> {
+    std::fs::write(filename, latex_worked_solution(scenario, trajectory_2d))
+        .map_err(|error| format!("Can't write the LaTeX file {}: {}", filename, error))
+}
+
+// The trajectory, the floor and the basket in meters, scaled to fit the figure.
+fn write_figure(tex: & mut String, trajectory_2d: & Trajectory, basket_pos_x: f64, basket_pos_y: f64) {
+    let samples = & trajectory_2d.1;
+    let backboard_x = basket_backboard_x(basket_pos_x);
+    let backboard_bottom = basket_pos_y - BACKBOARD_BELOW_RIM;
+    let (mut x_min, mut x_max, mut y_max) = (f64::min(0.0, basket_pos_x), backboard_x, backboard_bottom + BACKBOARD_HEIGHT);
+    for (_t, (x, y), _flag_enter_instant) in samples {
+        x_min = f64::min(x_min, *x);
+        x_max = f64::max(x_max, *x);
+        y_max = f64::max(y_max, *y);
+    }
+    let scale = f64::min(FIGURE_WIDTH_CM / f64::max(x_max - x_min, 1e-9), FIGURE_HEIGHT_CM / f64::max(y_max, 1e-9));
+
+    tex.push_str("\\begin{figure}[h]\n\\centering\n");
+    let _ = writeln!(tex, "\\begin{{tikzpicture}}[x={:.4}cm, y={:.4}cm]", scale, scale);
+    let _ = writeln!(tex, "\\draw[gray] ({:.3}, 0) -- ({:.3}, 0);", x_min, x_max);
+    let _ = writeln!(tex, "\\draw[very thick, orange] ({:.3}, {:.3}) -- ({:.3}, {:.3});",
+                     basket_pos_x - RIM_DIAMETER / 2.0, basket_pos_y, basket_pos_x + RIM_DIAMETER / 2.0, basket_pos_y);
+    let _ = writeln!(tex, "\\draw[thick] ({:.3}, {:.3}) -- ({:.3}, {:.3});",
+                     backboard_x, backboard_bottom, backboard_x, backboard_bottom + BACKBOARD_HEIGHT);
+    // The curve through at most FIGURE_MAX_POINTS samples, and always the last one.
+    let stride = marker_stride(samples.len(), FIGURE_MAX_POINTS);
+    tex.push_str("\\draw[blue] plot coordinates {");
+    for (i, (_t, (x, y), _flag_enter_instant)) in samples.iter().enumerate() {
+        if i % stride == 0 || i + 1 == samples.len() {
+            let _ = write!(tex, " ({:.3}, {:.3})", x, y);
+        }
+    }
+    tex.push_str(" };\n");
+    for (_t, (x, y), _flag_enter_instant) in samples.iter().filter(|sample| sample.2) {
+        let _ = writeln!(tex, "\\fill[green!60!black] ({:.3}, {:.3}) circle (2pt);", x, y);
+    }
+    tex.push_str("\\end{tikzpicture}\n\\caption{The trajectory of the ball, in meters.}\n\\end{figure}\n\n");
+}
@@ -0,0 +1,60 @@
+//! Basketball trajectory, the simulation of the shot and its figures in SVG and in text mode.
+//!
+//! The binary is the command line tool, the library is also used by the WebAssembly
+//! bindings, see the wasm module with the feature "wasm".
+
+pub mod advice;
+pub mod batch;
+pub mod bench;
+pub mod compare;
+pub mod coriolis;
+pub mod court;
+pub mod display_cmd;
+pub mod event_log;
+pub mod explain;
+pub mod frames;
+pub mod free_throw;
+pub mod gallery;
+pub mod html_sliders;
+pub mod i18n;
+pub mod latex;
+pub mod monte_carlo;
+pub mod notebook;
+pub mod obstacles;
+pub mod output_names;
+pub mod pass;
+pub mod player;
+pub mod plausibility;
+pub mod plot_svg;
+pub mod post_score;
+pub mod quiz;
+pub mod random_shot;
+pub mod rebound;
+pub mod replay;
+pub mod rim;
+pub mod rng;
+pub mod scenario;
+pub mod shot_result;
+pub mod simulator;
+pub mod sports;
+pub mod stream;
+pub mod svg_gen;
+pub mod sweep;
+pub mod targets;
+pub mod telemetry;
+pub mod termination;
+pub mod timing;
+pub mod tracking;
+pub mod trajectory;
+pub mod units;
+pub mod video;
+pub mod wind;
+
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "scripting")]
+pub mod script;
+
+#[cfg(feature = "websocket")]
+pub mod stream_ws;
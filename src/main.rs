@@ -1,417 +1,1418 @@
-/// Name: Basketball trajectory in Rust
-/// 
-/// Author: João Nuno Carvalho
-/// Date:   2022.01.06
-/// Description: This tries to answer the question if the ball that a basket
-///              player throws with a V_0 velocity vector and a Teta angle
-///              will enter the basket in a parabolic trajectory?
-///              I made this to illustrate to my daughter that the equations
-///              in her physics book could came out "alive" if they were
-///              calculated with a simple program. I applied to basket, a
-///              game that my daughter likes. It calculates for each instant t
-///              and in the end it draws the trajectory of the ball in a
-///              SVG animation and in text mode.
-///
-/// License: MIT Open Source License.
-/// 
-/// Equations in 2D:
-/// 
-///    Uniformed accelerated movement:          
-///              s = s_0 + v_0 * t - 1/2 * g * t^2
-/// 
-///    Decomposed movement into is components XX and YY:
-///              v_0_x = v_0 * cos(teta_0)
-///              v_0_y = v_0 * sin(teta_0)
-/// 
-///              ball_pos_x = x_0 + v_0_x * t
-///              ball_pos_y = y_0 + v_0_y * t - 1/2 * GRAVITY * t^2 
-///
-///    Euclidean distance 3D:
-///              dist = sqrt( (p_x - q_x)^2 + (p_y - q_y)^2 + (p_z - q_z)^2 )
-///   
-/// References: 
-///    Projectile motion
-///    https://en.wikipedia.org/wiki/Projectile_motion
-
-
-mod svg_gen;
-
-use crate::svg_gen::Color;
-
-const GRAVITY: f64 = 9.807; // m / s^2 - Meters per second square.
-const MIN_BALL_DELTA_TO_BASKET_CENTER: f64 = 0.1; // 10 cm
-
-type Trajectory = (bool, Vec<(f64, (f64, f64), bool)>);
+//! Name: Basketball trajectory in Rust
+//! 
+//! Author: João Nuno Carvalho
+//! Date:   2022.01.06
+//! Description: This tries to answer the question if the ball that a basket
+//!              player throws with a V_0 velocity vector and a Teta angle
+//!              will enter the basket in a parabolic trajectory?
+//!              I made this to illustrate to my daughter that the equations
+//!              in her physics book could came out "alive" if they were
+//!              calculated with a simple program. I applied to basket, a
+//!              game that my daughter likes. It calculates for each instant t
+//!              and in the end it draws the trajectory of the ball in a
+//!              SVG animation and in text mode.
+//!
+//! License: MIT Open Source License.
+//! 
+//! Equations in 2D:
+//! 
+//!    Uniformed accelerated movement:          
+//!              s = s_0 + v_0 * t - 1/2 * g * t^2
+//! 
+//!    Decomposed movement into is components XX and YY:
+//!              v_0_x = v_0 * cos(teta_0)
+//!              v_0_y = v_0 * sin(teta_0)
+//! 
+//!              ball_pos_x = x_0 + v_0_x * t
+//!              ball_pos_y = y_0 + v_0_y * t - 1/2 * GRAVITY * t^2 
+//!
+//!    Euclidean distance 3D:
+//!              dist = sqrt( (p_x - q_x)^2 + (p_y - q_y)^2 + (p_z - q_z)^2 )
+//!   
+//! References: 
+//!    Projectile motion
+//!    https://en.wikipedia.org/wiki/Projectile_motion
+
+mod cli;
+
+// Counts the allocations for the bench subcommand.
+#[cfg(feature = "bench")]
+#[global_allocator]
+static GLOBAL_ALLOCATOR: bench::CountingAllocator = bench::CountingAllocator;
+
+use basketball_trajectory::advice::{ArcClass, MissCorrection};
+use basketball_trajectory::batch;
+use basketball_trajectory::bench;
+use basketball_trajectory::compare;
+use basketball_trajectory::coriolis;
+use basketball_trajectory::court::{self, shot_value_in_plane, League};
+use basketball_trajectory::display_cmd::{self, DisplayCMD};
+use basketball_trajectory::event_log;
+use basketball_trajectory::explain;
+use basketball_trajectory::free_throw::{self, ReleaseAnalysis};
+use basketball_trajectory::html_sliders;
+use basketball_trajectory::frames::{Frame, FrameTransform};
+use basketball_trajectory::gallery;
+use basketball_trajectory::i18n::{self, Lang, Messages, Num};
+use basketball_trajectory::latex;
+use basketball_trajectory::monte_carlo::{self, Defender};
+use basketball_trajectory::obstacles::{self, Obstacle, ObstacleHit};
+use basketball_trajectory::output_names::OutputNamer;
+use basketball_trajectory::pass::{self, PassKind, PassSolution};
+use basketball_trajectory::plausibility::plausibility_warnings;
+use basketball_trajectory::player::Player;
+use basketball_trajectory::plot_svg::{plot_anaglyph_svg, plot_balls_svg, plot_energy_svg, plot_frame_svg, plot_make_rate_svg, plot_phase_svg, plot_small_multiples_svg, plot_speed_map_svg, plot_trajectory_svg,
+                                        SvgPlotOptions, SMALL_MULTIPLES_ANGLES};
+use basketball_trajectory::quiz;
+use basketball_trajectory::random_shot;
+use basketball_trajectory::post_score::{self, PostScore};
+use basketball_trajectory::rebound::{self, Rebound};
+use basketball_trajectory::replay::RunFile;
+use basketball_trajectory::rng::{self, Rng};
+use basketball_trajectory::shot_result::ShotResult;
+use basketball_trajectory::scenario::{BatchFile, ScenarioFile, FREE_THROW_DISTANCE};
+use basketball_trajectory::stream;
+use basketball_trajectory::svg_gen;
+use basketball_trajectory::targets::target_results;
+use basketball_trajectory::telemetry;
+use basketball_trajectory::termination;
+use basketball_trajectory::timing::ShotTiming;
+use basketball_trajectory::tracking;
+use basketball_trajectory::trajectory::{arc_length, basket_backboard_x, basketball_2d, basketball_3d, cumulative_distance, sample_acceleration, sample_velocity, ShotSummary, Trajectory,
+                                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, RIM_DIAMETER};
+use basketball_trajectory::units;
+use basketball_trajectory::video;
+
+// Size of the text plot in a terminal.
+const CMD_MAX_COLS: usize = 200;
+const CMD_MAX_ROWS: usize = 100;
+const CMD_FRAME_COLS: usize = 12;                // Labels of the YY axis, the borders and "x (m)".
+const CMD_FRAME_ROWS: usize = 5;                 // Borders, labels of the XX axis and the prompt.
+// Rows of the positions printed by compare, the samples between them are skipped.
+const COMPARE_MAX_ROWS: usize = 40;
 
 fn main() {
-    println!("********************************************");
-    println!("** Did the basketball go into the basket? **");
-    println!("********************************************");
+    let mut cmd_args = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Some(cmd_args)) => cmd_args,
+        Ok(None) => {
+            print!("{}", cli::usage());
+            return;
+        },
+        Err(error) => {
+            eprintln!("{}\n", error);
+            eprint!("{}", cli::usage());
+            std::process::exit(1);
+        },
+    };
+    let lang = cmd_args.lang.unwrap_or_else(Lang::from_env);
+    let messages = lang.messages();
+    i18n::set_decimal_comma(cmd_args.flag_decimal_comma);
+    // The names of the output files, an output that exists stops the program before any is written.
+    let output_namer = OutputNamer::new(cmd_args.output_suffix, cmd_args.flag_force);
+    if let Err(error) = output_namer.name_all([& mut cmd_args.svg_phase_filename, & mut cmd_args.svg_energy_filename, & mut cmd_args.svg_frame_filename,
+                                               & mut cmd_args.svg_anaglyph_filename, & mut cmd_args.svg_make_rate_filename,
+                                               & mut cmd_args.html_sliders_filename, & mut cmd_args.svg_speed_map_filename,
+                                               & mut cmd_args.svg_grid_filename,
+                                               & mut cmd_args.latex_filename, & mut cmd_args.save_scenario_filename,
+                                               & mut cmd_args.save_run_filename, & mut cmd_args.svg_compare_filename,
+                                               & mut cmd_args.event_log_filename, & mut cmd_args.batch_csv_filename,
+                                               & mut cmd_args.stream_csv_filename, & mut cmd_args.stream_svg_filename]) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+    // The script replaces the run of the shot.
+    #[cfg(feature = "scripting")]
+    if let Some(script_filename) = & cmd_args.script_filename {
+        if let Err(error) = basketball_trajectory::script::run_script_file(script_filename) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    // The batch replaces the run of the shot.
+    if let Some(batch_filename) = & cmd_args.batch_filename {
+        match BatchFile::load(batch_filename) {
+            Ok(batch_file) => {
+                if let Err(error) = print_batch(& batch_file.scenarios, & cmd_args, messages) {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            },
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            },
+        }
+        return;
+    }
+    // The quiz replaces the run of the shot.
+    if let Some(quiz_rounds) = cmd_args.quiz_rounds {
+        let mut rng = cmd_args.seed.map_or_else(Rng::from_time, Rng::new);
+        if let Err(error) = quiz::run_quiz(std::io::stdin().lock(), std::io::stdout(), quiz_rounds, & mut rng, messages) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    // The random shots replace the run of the shot.
+    if let Some(num_shots) = cmd_args.random_shots {
+        print_random_shots(num_shots, cmd_args.seed, messages);
+        return;
+    }
+    // The benchmark replaces the run of the shot.
+    if let Some(num_samples) = cmd_args.bench_samples {
+        print_bench(num_samples, messages);
+        return;
+    }
+    // The comparison of two runs replaces the run of the shot.
+    if let Some((run_a_filename, run_b_filename)) = & cmd_args.compare_filenames {
+        if let Err(error) = print_compare(run_a_filename, run_b_filename, & cmd_args, messages) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+    // Read before the output, an invalid file stops the program.
+    let tracking_points = match & cmd_args.tracking_filename {
+        Some(tracking_filename) => match tracking::read_tracking_csv(tracking_filename) {
+            Ok(points) => match cmd_args.tracking_smoothing {
+                Some(smoothing) => tracking::smooth(& points, smoothing, cmd_args.tracking_window),
+                None => points,
+            },
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            },
+        },
+        None => Vec::new(),
+    };
+    // The replay has the scenario and the trajectory of a previous run.
+    let run = match & cmd_args.replay_filename {
+        Some(replay_filename) => match RunFile::load(replay_filename) {
+            Ok(run) => Some(run),
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            },
+        },
+        None => None,
+    };
+    let mut scenario = match (& run, & cmd_args.scenario_filename) {
+        (Some(run), _) => run.scenario.clone(),
+        (None, Some(scenario_filename)) => match ScenarioFile::load(scenario_filename) {
+            Ok(scenario) => scenario,
+            Err(error) => {
+                eprintln!("{}", error);
+                std::process::exit(1);
+            },
+        },
+        (None, None) => cmd_args.preset_scenario.clone().unwrap_or_default(),
+    };
+    if scenario.is_newer_version() {
+        eprintln!("Warning: the scenario is of the newer version {}, its new fields are ignored.", scenario.version);
+    }
+    // The outputs of the command line replace the ones of the scenario.
+    if cmd_args.html_filename.is_some() {
+        scenario.outputs.html_filename = cmd_args.html_filename.clone();
+    }
+    if cmd_args.cmd_filename.is_some() {
+        scenario.outputs.text_filename = cmd_args.cmd_filename.clone();
+    }
+    if let Some(temperature_c) = cmd_args.temperature_c {
+        scenario.air.temperature_c = temperature_c;
+    }
+    if let Some(humidity_percent) = cmd_args.humidity_percent {
+        scenario.air.humidity_percent = humidity_percent;
+    }
+    // The samples of the wind can be in any order in the command line.
+    if !cmd_args.wind.is_empty() {
+        scenario.wind = cmd_args.wind.clone();
+        scenario.wind.sort_by(|sample_1, sample_2| sample_1.t.total_cmp(& sample_2.t));
+        if let Err(error) = scenario.validate() {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+    if !cmd_args.targets.is_empty() {
+        scenario.targets = cmd_args.targets.clone();
+    }
+    // The samples of a replay are the ones of the run.
+    if let (Some(num_steps), None) = (cmd_args.num_steps, & run) {
+        scenario.simulation.num_steps = num_steps;
+    }
+    if cmd_args.flag_auto_stop && run.is_none() {
+        scenario.simulation.auto_stop = true;
+    }
+    if let (Some(post_score_sec), None) = (cmd_args.post_score_sec, & run) {
+        scenario.simulation.post_score_sec = Some(post_score_sec);
+    }
+    if let (Some(v_0), None) = (cmd_args.v_0, & run) {
+        scenario.throw.v_0 = v_0;
+    }
+    if let (Some(teta_0_deg), None) = (cmd_args.teta_0_deg, & run) {
+        scenario.throw.teta_0_deg = teta_0_deg;
+    }
+    if let (Some(distance), None) = (cmd_args.basket_distance, & run) {
+        scenario.scene.basket_pos_x = scenario.throw.pos_0_x + distance;
+    }
+    if let (Some(pressure_bar), None) = (cmd_args.ball_pressure, & run) {
+        scenario.ball.pressure_bar = pressure_bar;
+    }
+    if let (Some((v_x, v_y, v_z)), None) = (cmd_args.velocity, & run) {
+        scenario.throw.set_velocity(v_x, v_y, v_z);
+    }
+    let player = cmd_args.player_height.filter(|_| run.is_none())
+        .map(|height| Player { height, standing_reach: cmd_args.standing_reach, jump_height: cmd_args.jump_height });
+    if let Some(player) = player {
+        scenario.throw.pos_0_y = player.release_height();
+    }
+    // The implausible inputs are simulated anyway, with --strict they stop the program.
+    let warnings = plausibility_warnings(& scenario);
+    for warning in & warnings {
+        eprintln!("{}: {}", if cmd_args.flag_strict {"Error"} else {"Warning"}, warning);
+    }
+    if cmd_args.flag_strict && !warnings.is_empty() {
+        std::process::exit(1);
+    }
+    if let Some(save_scenario_filename) = & cmd_args.save_scenario_filename {
+        if let Err(error) = scenario.save(save_scenario_filename) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    // The comparison of the free throws replaces the run of the shot.
+    if cmd_args.flag_free_throws {
+        print_free_throws(messages);
+        return;
+    }
+    // The passes replace the run of the shot.
+    if let Some(pass_distance) = cmd_args.pass_distance {
+        print_passes(& scenario, pass_distance, cmd_args.catch_height, cmd_args.runner_speed, messages);
+        return;
+    }
+
+    // The Monte Carlo simulation replaces the run of the shot.
+    if let Some(num_shots) = cmd_args.monte_carlo_shots {
+        let defender = cmd_args.defender_distance.map(|distance| Defender { distance, reach: cmd_args.defender_reach });
+        if let Err(error) = print_monte_carlo(& scenario, num_shots, defender, cmd_args.seed, messages) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // The make rate by distance replaces the run of the shot.
+    if let Some(num_shots) = cmd_args.make_rate_shots {
+        if let Err(error) = print_make_rate(& scenario, num_shots, & cmd_args, messages) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // The balls shot at once replace the run of the shot.
+    if !cmd_args.balls.is_empty() {
+        if let Err(error) = print_balls(& scenario, & cmd_args, & output_namer, messages) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // The streaming replaces the run of the shot, the samples aren't kept in memory.
+    if cmd_args.stream_csv_filename.is_some() || cmd_args.stream_svg_filename.is_some() {
+        if let Err(error) = stream_files(& scenario, & cmd_args, messages) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    // The outputs of the scenario are only written by the shot and the balls.
+    if let Err(error) = output_namer.name_all([& mut scenario.outputs.svg_filename, & mut scenario.outputs.html_filename,
+                                               & mut scenario.outputs.text_filename]) {
+        eprintln!("{}", error);
+        std::process::exit(1);
+    }
+
+    println!("{}", i18n::banner(messages.title));
+    if let Some(player) = player {
+        let number = |value: f64| format!("{:0.2}", Num(value));
+        println!("{}\n", i18n::fill(messages.player_release, &[& number(player.height), & number(player.standing_reach()),
+                                                                & number(player.jump_height), & number(player.release_height())]));
+    }
+    if let Some(replay_filename) = & cmd_args.replay_filename {
+        println!("{}\n", i18n::fill(messages.replay_note, &[replay_filename]));
+    }
+    if scenario.simulation.auto_stop && run.is_none() {
+        let (stop_sec, stop_reason) = termination::auto_stop(& scenario);
+        println!("{}\n", i18n::fill(messages.auto_stop_line, &[& format!("{:0.2}", Num(stop_sec)), & stop_reason.name(messages)]));
+    }
     
     // Player throw position.
-    let pos_0_x: f64 = 0.0;   // m - meters
-    let pos_0_y: f64 = 1.5;   // m - meters 
-    let pos_0_z: f64 = 0.0;   // m - meters
+    let pos_0_x: f64 = scenario.throw.pos_0_x;   // m - meters
+    let pos_0_y: f64 = scenario.throw.pos_0_y;   // m - meters 
+    let pos_0_z: f64 = scenario.throw.pos_0_z;   // m - meters
     
     // Initial velocity vector.
-    let v_0:    f64 = 10.0;   // m/s - Meters per second
-    let teta_0: f64 = 45.0;   // teta degrees =  angle in degrees XX axis to YY axis.
-    let phi_0:  f64 =  0.0;   // phi  degrees =  angle in degrees ZZ axis to XX axis.  
+    let v_0:    f64 = scenario.throw.v_0;          // m/s - Meters per second
+    let teta_0: f64 = scenario.throw.teta_0_deg;   // teta degrees =  angle in degrees XX axis to YY axis.
+    let phi_0:  f64 = scenario.throw.phi_0_deg;    // phi  degrees =  angle in degrees ZZ axis to XX axis.  
     
     // Basket position.
-    let basket_pos_x: f64 = 8.0;   // m - meters
-    let basket_pos_y: f64 = 3.05;  // m - meters
-    let basket_pos_z: f64 = 5.0;   // m - meters
+    let basket_pos_x: f64 = scenario.scene.basket_pos_x;   // m - meters
+    let basket_pos_y: f64 = scenario.scene.basket_pos_y;   // m - meters
+    let basket_pos_z: f64 = scenario.scene.basket_pos_z;   // m - meters
 
     // Test the simulation for how many seconds?
-    let simulation_sec: f64 = 3.0;  // s - Seconds to simulate.
-    let num_steps: u32      = 60;   // Divide the simulation seconds into N equal points.
+    let simulation_sec: f64 = scenario.simulated_sec();            // s - Seconds to simulate.
+    let num_steps: u32      = scenario.simulation.num_steps;       // Divide the simulation seconds into N equal points.
 
-    let svg_trajectory_filename = "basketball_trajectory.svg";
+    let svg_trajectory_filename = scenario.outputs.svg_filename.as_deref();
     let svg_file_path = "./";
     let svg_x_max: f32 = 500.0;   // Max XX Coordinate.
     let svg_y_max: f32 = 300.0; // 500.0; //300.0;   // Max YY Coordinate.
 
+    // The report has 2 decimal places without --precision.
+    let report_precision = cmd_args.precision.unwrap_or(2);
     print_initial_data(pos_0_x, pos_0_y, pos_0_z, 
                        v_0, teta_0, phi_0,
                        basket_pos_x, basket_pos_y, basket_pos_z,
                        simulation_sec, num_steps,
-                       svg_trajectory_filename);
+                       svg_trajectory_filename, scenario.throw.velocity(), report_precision, messages);
 
-    let num_rows = 50; // 80;
-    let num_cols = 80;
-    let rows_meters = 10.0; // m - meters
-    let cols_meters = 10.0; // m - meters
-    let mut display_cmd: DisplayCMD = DisplayCMD::new(num_rows, num_cols, rows_meters, cols_meters);
+    // The simulation takes the angle in radians.
+    let trajectory_2d = match run {
+        Some(run) => run.trajectory(),
+        None => basketball_2d(pos_0_x, pos_0_y, 
+                              v_0, units::degrees_to_radians(teta_0),
+                              basket_pos_x, basket_pos_y,
+                              simulation_sec, num_steps),
+    };
+    if let Some(save_run_filename) = & cmd_args.save_run_filename {
+        let run = match cmd_args.run_keyframes_tolerance {
+            Some(tolerance) => RunFile::with_keyframes(& scenario, & trajectory_2d, tolerance),
+            None => RunFile::new(& scenario, & trajectory_2d),
+        };
+        if let Err(error) = run.save(save_run_filename) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
 
-    let trajectory_2d = basketball_2d(pos_0_x, pos_0_y, 
-                                      v_0, teta_0,
-                                      basket_pos_x, basket_pos_y,
-                                      simulation_sec, num_steps);
+    // The grid fills the terminal, without the frame and the labels, up to a maximum size.
+    // The default of 80 x 50 is for when the output isn't a terminal.
+    let (num_cols, num_rows) = match (cmd_args.cmd_cols, cmd_args.cmd_rows, display_cmd::terminal_size()) {
+        (cols, rows, Some((term_cols, term_rows))) =>
+            (cols.unwrap_or(usize::clamp(term_cols.saturating_sub(CMD_FRAME_COLS), 20, CMD_MAX_COLS)),
+             rows.unwrap_or(usize::clamp(term_rows.saturating_sub(CMD_FRAME_ROWS), 10, CMD_MAX_ROWS))),
+        (cols, rows, None) => (cols.unwrap_or(80), rows.unwrap_or(50)),
+    };
+    // The size in meters of the text plot fits the trajectory and the basket, unless
+    // it's given in the command line.
+    let backboard_x = basket_backboard_x(basket_pos_x);
+    let backboard_top = basket_pos_y - BACKBOARD_BELOW_RIM + BACKBOARD_HEIGHT;
+    let (x_max, y_max) = trajectory_2d.1.iter()
+        .fold((backboard_x, backboard_top), |(x_max, y_max), (_t, (x, y), _)| (f64::max(x_max, *x), f64::max(y_max, *y)));
+    let mut display_cmd = match (cmd_args.cmd_rows_meters, cmd_args.cmd_cols_meters) {
+        (None, None) => DisplayCMD::fit_to_scene(num_rows, num_cols, x_max, y_max),
+        (rows_meters, cols_meters) => DisplayCMD::new(num_rows, num_cols,
+                                                      rows_meters.unwrap_or(y_max * 1.05),
+                                                      cols_meters.unwrap_or(x_max * 1.05)),
+    };
+    display_cmd.set_axes(cmd_args.flag_cmd_axes);
+    display_cmd.set_render_mode(cmd_args.cmd_render_mode);
+    let mut charset = cmd_args.cmd_charset;
+    charset.ball = cmd_args.cmd_ball_char.unwrap_or(charset.ball);
+    charset.background = cmd_args.cmd_background_char.unwrap_or(charset.background);
+    display_cmd.set_charset(charset);
 
-    print_trajectory_2d(& trajectory_2d, & mut display_cmd);
+    let flag_color = cmd_args.color_mode.is_enabled();
+    display_cmd.set_color(flag_color);
+    let frame_transform = FrameTransform::new(cmd_args.frame, & scenario);
+    print_trajectory_2d(& frame_transform.trajectory(& trajectory_2d), cmd_args.frame, flag_color, report_precision, messages);
+    let defender = cmd_args.defender_distance.map(|distance| Defender { distance, reach: cmd_args.defender_reach });
+    let obstacle_hit = obstacles::first_hit(& trajectory_2d.1, & scenario.obstacles, scenario.ball.radius);
+    let shot_result = ShotResult::classify(& scenario, trajectory_2d.0, defender, obstacle_hit);
+    print_results(& trajectory_2d, & scenario, shot_result, obstacle_hit, cmd_args.league, cmd_args.time_budget, cmd_args.release_time,
+                  report_precision, messages);
+    // The animations go on after a made basket, with the drop through the net.
+    let post_score = scenario.simulation.post_score_sec
+        .and_then(|post_score_sec| post_score::simulate_post_score(& scenario, & trajectory_2d, post_score_sec));
+    if let Some(post_score) = & post_score {
+        print_post_score(post_score, report_precision, messages);
+    }
+    let trajectory_animation = match & post_score {
+        Some(post_score) => post_score::continue_trajectory(& trajectory_2d, post_score),
+        None => trajectory_2d.clone(),
+    };
+    // The playback only in a terminal, in a pipe or in a file the escape codes would be garbage.
+    if cmd_args.flag_cmd_play && std::io::IsTerminal::is_terminal(& std::io::stdout()) {
+        // At the frame rate, or a frame for each sample.
+        let frames = match cmd_args.fps {
+            Some(fps) => video::resample_frames(& trajectory_animation, fps, 1.0),
+            None => trajectory_animation.clone(),
+        };
+        play_trajectory_cmd(& frames, & mut display_cmd, basket_pos_x, basket_pos_y, & scenario.obstacles);
+    } else {
+        draw_court_cmd(& mut display_cmd, basket_pos_x, basket_pos_y, & scenario.obstacles);
+        draw_trajectory_cmd(& mut display_cmd, & trajectory_animation.1, true);
+        display_cmd.print();
+    }
+    println!("{}", status_line_cmd(& trajectory_2d, basket_pos_x, basket_pos_y, messages));
+    if !tracking_points.is_empty() {
+        match tracking::rms_error(& trajectory_2d.1, & tracking_points) {
+            Some((rms, num_points)) => println!("  {}", i18n::fill(messages.tracking_line,
+                                                                   &[& num_points, & tracking_points.len(), & format!("{:0.3}", Num(rms))])),
+            None => println!("  {}", i18n::fill(messages.tracking_none, &[& tracking_points.len()])),
+        }
+    }
+    if cmd_args.flag_compare_drag {
+        print_drag_comparison(& scenario, & trajectory_2d, messages);
+    }
+    let rebound = cmd_args.flag_rebound.then(|| print_rebound(& scenario, & trajectory_2d, messages)).flatten();
+    if let Some(latitude_deg) = cmd_args.coriolis_latitude {
+        print_coriolis(& scenario, & trajectory_2d, latitude_deg, cmd_args.azimuth_deg, messages);
+    }
+    if cmd_args.flag_explain {
+        println!("\n{}", i18n::banner(messages.equations));
+        print!("{}", explain::explain_shot(& scenario, & trajectory_2d, lang));
+    }
+    if let Some(cmd_filename) = & scenario.outputs.text_filename {
+        // Without the colors in the file.
+        display_cmd.set_color(false);
+        let _ = svg_gen::write_file(cmd_filename, "", & display_cmd.render());
+    }
 
-    let svg = plot_trajectory_svg(& trajectory_2d,
-                                      basket_pos_x, basket_pos_y,
-                                      svg_x_max, svg_y_max);
+    let value = shot_value_in_plane(cmd_args.league, pos_0_x, basket_pos_x);
+    let svg_label = format!("v_0 = {:0.2} m/s & teta_0 = {:0.2}° -> {}, {} point shot",
+                            v_0, teta_0, if trajectory_2d.0 {"entered the basket"} else {"missed the basket"}, value.points);
+    let svg_plot_options = SvgPlotOptions {
+        width: svg_x_max,
+        height: svg_y_max,
+        margin: cmd_args.svg_margin,
+        flag_draw_markers: cmd_args.flag_svg_markers,
+        theme: cmd_args.theme.clone(),
+        animation_mode: cmd_args.animation_mode,
+        trail_sec: cmd_args.svg_trail_sec,
+        playback: cmd_args.playback,
+        max_markers: cmd_args.svg_max_markers,
+        simplify_tolerance: cmd_args.svg_simplify_tolerance,
+        measured_points: tracking_points,
+        rebound_points: rebound.map(|rebound| rebound.path).unwrap_or_default(),
+        obstacles: scenario.obstacles.clone(),
+        targets: scenario.targets.clone(),
+        precision: cmd_args.precision.unwrap_or(svg_gen::DEFAULT_SVG_PRECISION),
+        output_mode: cmd_args.svg_output_mode,
+    };
+    let mut svg = plot_trajectory_svg(& trajectory_animation,
+                                      Some((basket_pos_x, basket_pos_y)),
+                                      & svg_plot_options,
+                                      &svg_label);
+    svg.set_responsive(cmd_args.flag_svg_responsive);
    
     // let file_str = svg.to_file_string();
     // println!("{}", file_str);
 
-    let _ = svg.to_file(svg_trajectory_filename, svg_file_path);
-}
+    if let Some(svg_trajectory_filename) = svg_trajectory_filename {
+        let _ = svg.to_file(svg_trajectory_filename, svg_file_path);
+    }
+
+    if let Some(html_filename) = & scenario.outputs.html_filename {
+        let _ = svg.to_html_file(html_filename, svg_file_path, "Basketball trajectory",
+                                 cmd_args.flag_html_controls, cmd_args.flag_html_pan_zoom,
+                                 svg_plot_options.animation_dur_sec(trajectory_animation.1.last().map_or(0.0, |sample| sample.0)));
+    }
+
+    if let Some(phase_filename) = & cmd_args.svg_phase_filename {
+        let phase_label = format!("Phase space, v_0 = {:0.2} m/s & teta_0 = {:0.2}°", v_0, teta_0);
+        let mut phase_svg = plot_phase_svg(& trajectory_2d, & svg_plot_options, &phase_label);
+        phase_svg.set_responsive(cmd_args.flag_svg_responsive);
+        let _ = phase_svg.to_file(phase_filename, svg_file_path);
+    }
+
+    if let Some(frames_folder) = & cmd_args.svg_frames_folder {
+        // The frames of a previous video aren't replaced, the old ones after the last frame would stay.
+        if !cmd_args.flag_force && std::path::Path::new(frames_folder).join(video::frame_filename(0)).exists() {
+            eprintln!("The frames in {} exist, use --force to overwrite them.", frames_folder);
+            std::process::exit(1);
+        }
+        if let Err(error) = video::write_svg_frames(frames_folder, & trajectory_animation, (basket_pos_x, basket_pos_y), & svg_plot_options,
+                                                    cmd_args.fps.unwrap_or(video::DEFAULT_FPS), cmd_args.playback.unwrap_or(1.0)) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    if let Some(frame_filename) = & cmd_args.svg_frame_filename {
+        let frame_label = format!("Trajectory in the {} frame, v_0 = {:0.2} m/s & teta_0 = {:0.2}°", cmd_args.frame.name(), v_0, teta_0);
+        let mut frame_svg = plot_frame_svg(& trajectory_2d, & frame_transform, & svg_plot_options, &frame_label);
+        frame_svg.set_responsive(cmd_args.flag_svg_responsive);
+        let _ = frame_svg.to_file(frame_filename, svg_file_path);
+    }
+
+    if let Some(energy_filename) = & cmd_args.svg_energy_filename {
+        let energy_label = format!("Energy, v_0 = {:0.2} m/s & teta_0 = {:0.2}°", v_0, teta_0);
+        let trajectory_drag = cmd_args.flag_compare_drag.then(|| scenario.simulate_2d_drag());
+        let mut energy_svg = plot_energy_svg(& trajectory_2d, trajectory_drag.as_ref(), scenario.ball.mass,
+                                             & svg_plot_options, &energy_label);
+        energy_svg.set_responsive(cmd_args.flag_svg_responsive);
+        let _ = energy_svg.to_file(energy_filename, svg_file_path);
+    }
+
+    if let Some(anaglyph_filename) = & cmd_args.svg_anaglyph_filename {
+        let anaglyph_label = format!("Anaglyph, v_0 = {:0.2} m/s & teta_0 = {:0.2}° & phi_0 = {:0.2}°", v_0, teta_0, phi_0);
+        let trajectory_3d = basketball_3d(pos_0_x, pos_0_y, pos_0_z,
+                                          v_0, units::degrees_to_radians(teta_0), units::degrees_to_radians(phi_0),
+                                          basket_pos_x, basket_pos_y, basket_pos_z,
+                                          simulation_sec, num_steps);
+        let mut anaglyph_svg = plot_anaglyph_svg(& trajectory_3d, (basket_pos_x, basket_pos_y, basket_pos_z),
+                                                 & svg_plot_options, &anaglyph_label);
+        anaglyph_svg.set_responsive(cmd_args.flag_svg_responsive);
+        let _ = anaglyph_svg.to_file(anaglyph_filename, svg_file_path);
+    }
+
+    if let Some(speed_map_filename) = & cmd_args.svg_speed_map_filename {
+        let speed_map_label = format!("Speed to score, teta_0 = {:0.2}° & release at {:0.2} m, {}", teta_0, pos_0_y, cmd_args.league.name());
+        let cells = court::required_speed_grid(pos_0_y, units::degrees_to_radians(teta_0), (basket_pos_x, basket_pos_y, basket_pos_z),
+                                               court::SPEED_MAP_CELL_SIZE);
+        let mut speed_map_svg = plot_speed_map_svg(& cells, court::SPEED_MAP_CELL_SIZE, (basket_pos_x, basket_pos_z), cmd_args.league,
+                                                   & svg_plot_options, &speed_map_label);
+        speed_map_svg.set_responsive(cmd_args.flag_svg_responsive);
+        let _ = speed_map_svg.to_file(speed_map_filename, svg_file_path);
+    }
+
+    if let Some(grid_filename) = & cmd_args.svg_grid_filename {
+        // The shot at each angle, with its speed and the rest of the scenario.
+        let (angle_from, angle_to, angle_step) = cmd_args.grid_angles.unwrap_or(SMALL_MULTIPLES_ANGLES);
+        let num_angles = f64::floor((angle_to - angle_from) / angle_step + 1e-9) as usize + 1;
+        let (trajectories, captions): (Vec<Trajectory>, Vec<String>) = (0..num_angles).map(|i| {
+            let angle = angle_from + i as f64 * angle_step;
+            let mut scenario_angle = scenario.clone();
+            scenario_angle.throw.teta_0_deg = angle;
+            (scenario_angle.simulate_2d(), format!("teta_0 = {:0.1}°", angle))
+        }).unzip();
+        let grid_label = format!("Angles from {:0.1}° to {:0.1}° in steps of {:0.1}°, v_0 = {:0.2} m/s", angle_from, angle_to, angle_step, v_0);
+        let mut grid_svg = plot_small_multiples_svg(& trajectories, & captions, (basket_pos_x, basket_pos_y), & svg_plot_options, &grid_label);
+        grid_svg.set_responsive(cmd_args.flag_svg_responsive);
+        let _ = grid_svg.to_file(grid_filename, svg_file_path);
+    }
+
+    if let Some(sliders_filename) = & cmd_args.html_sliders_filename {
+        let _ = svg_gen::write_file(sliders_filename, svg_file_path,
+                                    & html_sliders::sliders_html(& scenario, & cmd_args.theme, "Basketball trajectory"));
+    }
+
+    if let Some(latex_filename) = & cmd_args.latex_filename {
+        if let Err(error) = latex::save_latex(latex_filename, & scenario, & trajectory_2d) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    let json_precision = cmd_args.precision.unwrap_or(telemetry::DEFAULT_JSON_PRECISION);
+    if let Some(event_log_filename) = & cmd_args.event_log_filename {
+        let result = match event_log_filename.as_str() {
+            "-" => event_log::write_event_log(& mut std::io::stderr().lock(), & scenario, & trajectory_2d, shot_result, json_precision),
+            _ => std::fs::File::create(event_log_filename).and_then(|file| {
+                event_log::write_event_log(& mut std::io::BufWriter::new(file), & scenario, & trajectory_2d, shot_result, json_precision)
+            }),
+        };
+        if let Err(error) = result {
+            eprintln!("Can't write the event log {}: {}", event_log_filename, error);
+            std::process::exit(1);
+        }
+    }
 
-fn conv_meters_sec_to_km_hour(vel: f64) -> f64 {
-    (vel * 3_600.0) / 1_000.0 
+    if let Some(udp_addr) = & cmd_args.udp_addr {
+        if let Err(error) = telemetry::send_trajectory_udp(udp_addr, & trajectory_2d.1, cmd_args.udp_format, json_precision) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    // Last, it waits for the client.
+    #[cfg(feature = "websocket")]
+    if let Some(ws_addr) = & cmd_args.ws_addr {
+        if let Err(error) = basketball_trajectory::stream_ws::stream_trajectory_ws(ws_addr, & trajectory_2d,
+                                                                                  basket_pos_x, basket_pos_y, json_precision) {
+            eprintln!("{}", error);
+            std::process::exit(1);
+        }
+    }
+
+    if cmd_args.flag_exit_code {
+        std::process::exit(shot_result.exit_code());
+    }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn print_initial_data(pos_0_x: f64, pos_0_y: f64, pos_0_z: f64, 
                       v_0: f64, teta_0: f64, phi_0: f64,
                       basket_pos_x: f64, basket_pos_y: f64, basket_pos_z: f64,
                       simulation_sec: f64, num_steps: u32,
-                      svg_trajectory_filename: & str) {
+                      svg_trajectory_filename: Option<& str>,
+                      (v_0_x, v_0_y, v_0_z): (f64, f64, f64),
+                      precision: usize, messages: & Messages) {
 
-    println!("Data:");
+    println!("{}", messages.data);
     
-    println!("\n  Player throw position:");
-    println!("    pos_0_x: {:0.2} m - meters", pos_0_x);
-    println!("    pos_0_y: {:0.2} m - meters", pos_0_y);
-    println!("    pos_0_z: {:0.2} m - meters", pos_0_z);
+    println!("\n  {}", messages.throw_position);
+    println!("    pos_0_x: {:0.*} m - {}", precision, Num(pos_0_x), messages.meters);
+    println!("    pos_0_y: {:0.*} m - {}", precision, Num(pos_0_y), messages.meters);
+    println!("    pos_0_z: {:0.*} m - {}", precision, Num(pos_0_z), messages.meters);
     
-    println!("\n  Initial velocity vector:");
-    println!("    v_0: {:0.2} m/s - Meters per second", v_0);
-    println!("    v_0: {:0.2} Km/h - Km per hour", conv_meters_sec_to_km_hour(v_0));
-    println!("    teta_0: {:0.2} degrees = angle in degrees XX axis to YY axis.", teta_0);
-    println!("    phi_0: {:0.2} degrees = angle in degrees ZZ axis to XX axis.", phi_0);  
+    println!("\n  {}", messages.initial_velocity);
+    println!("    v_0: {:0.*} m/s - {}", precision, Num(v_0), messages.meters_per_second);
+    println!("    v_0: {:0.*} Km/h - {}", precision, Num(units::meters_sec_to_km_hour(v_0)), messages.km_per_hour);
+    println!("    teta_0: {:0.*} {}", precision, Num(teta_0), messages.teta_0_degrees);
+    println!("    phi_0: {:0.*} {}", precision, Num(phi_0), messages.phi_0_degrees);  
+    println!("    v_0_x: {:0.*} m/s - {}", precision, Num(v_0_x), i18n::fill(messages.velocity_component, &[& "XX"]));
+    println!("    v_0_y: {:0.*} m/s - {}", precision, Num(v_0_y), i18n::fill(messages.velocity_component, &[& "YY"]));
+    println!("    v_0_z: {:0.*} m/s - {}", precision, Num(v_0_z), i18n::fill(messages.velocity_component, &[& "ZZ"]));
     
-    println!("\n  Basket position:");
-    println!("    basket_pos_x: {:0.2} m - meters", basket_pos_x);
-    println!("    basket_pos_y: {:0.2} m - meters", basket_pos_y);
-    println!("    basket_pos_z: {:0.2} m - meters", basket_pos_z);
+    println!("\n  {}", messages.basket_position);
+    println!("    basket_pos_x: {:0.*} m - {}", precision, Num(basket_pos_x), messages.meters);
+    println!("    basket_pos_y: {:0.*} m - {}", precision, Num(basket_pos_y), messages.meters);
+    println!("    basket_pos_z: {:0.*} m - {}", precision, Num(basket_pos_z), messages.meters);
 
-    println!("\n  Test the simulation for how many seconds?");
-    println!("    simulation_sec: {:0.2} s - Seconds to simulate", simulation_sec);
-    println!("    num_steps: {:0.2}        - Divide the simulation seconds into N equal points.", num_steps );
+    println!("\n  {}", messages.simulation_time);
+    println!("    simulation_sec: {:0.*} s - {}", precision, Num(simulation_sec), messages.seconds_to_simulate);
+    println!("    num_steps: {:0.2}        - {}", num_steps, messages.num_steps_points);
 
-    println!("\n  Output SVG");
-    println!("    svg_trajectory_filename = {}", svg_trajectory_filename);
+    println!("\n  {}", messages.output_svg);
+    println!("    svg_trajectory_filename = {}", svg_trajectory_filename.unwrap_or(messages.none));
 }
 
-fn basketball_2d(pos_0_x: f64, pos_0_y: f64, 
-                 v_0: f64, teta_0: f64,
-                 basket_pos_x: f64, basket_pos_y: f64,
-                 simulation_sec: f64, num_steps: u32)
-                 -> Trajectory {
-    
-    // The velocity is positive and not zero.
-    assert!(v_0 > 0.0);
-    // We will simulate a non negative and a non zero time.
-    assert!(simulation_sec > 0.0);
-    // We will simulate at least 2 steps.
-    assert!(num_steps > 2);
-
-    let v_0_x = v_0 * f64::cos(teta_0);
-    let v_0_y = v_0 * f64::sin(teta_0);
-
-    let x_0 =  pos_0_x;
-    let y_0 = pos_0_y;
-
-    let time_steps = get_time_steps(simulation_sec, num_steps);
-
-    let mut trajectory_2d: Vec<(f64, (f64, f64), bool)> = Vec::new();
-
-    let mut flag_into_the_basket = false;
-
-    for t in time_steps {
-        let ball_x = x_0 + v_0_x * t;
-        let ball_y = y_0 + v_0_y * t - (1.0/2.0) * GRAVITY * t * t; 
-        let dist = euclidean_distance(
-            ball_x, ball_y, 0.0,
-             basket_pos_x, basket_pos_y, 0.0);
-        let mut flag_enter_instant = false;
-        if dist <= MIN_BALL_DELTA_TO_BASKET_CENTER {
-            flag_into_the_basket = true;
-            flag_enter_instant = true;
-        }
-        if ball_y >= 0.0 {
-            trajectory_2d.push( (t, (ball_x, ball_y), flag_enter_instant) );
+/// Draws the floor, the rim and the backboard at their positions in meters.
+fn draw_court_cmd(display_cmd: & mut DisplayCMD, basket_pos_x: f64, basket_pos_y: f64, obstacles: & [Obstacle]) {
+    let charset = *display_cmd.charset();
+    let floor_x_max = display_cmd.cols_meters();
+    display_cmd.draw_hline_meters(charset.floor, 0.0, 0.0, floor_x_max);
+    let rim_x_0 = basket_pos_x - RIM_DIAMETER / 2.0;
+    let rim_x_1 = basket_pos_x + RIM_DIAMETER / 2.0;
+    display_cmd.draw_hline_meters(charset.rim, basket_pos_y, rim_x_0, rim_x_1);
+    display_cmd.set_pixel_meters(charset.rim_edge, basket_pos_y, rim_x_0, false);
+    display_cmd.set_pixel_meters(charset.rim_edge, basket_pos_y, rim_x_1, false);
+    let backboard_bottom = basket_pos_y - BACKBOARD_BELOW_RIM;
+    display_cmd.draw_vline_meters(charset.backboard, basket_backboard_x(basket_pos_x),
+                                  backboard_bottom, backboard_bottom + BACKBOARD_HEIGHT);
+    // The outline of the rectangles.
+    for obstacle in obstacles {
+        for ((x_0, y_0), (x_1, y_1)) in obstacle.edges() {
+            display_cmd.draw_line_meters(charset.obstacle, y_0, x_0, y_1, x_1);
         }
     }
-    (flag_into_the_basket, trajectory_2d)
 }
 
-/*
-fn basketball_3d(pos_0_x: f64, pos_0_y: f64, pos_0_z: f64, 
-                 v_0: f64, teta_0: f64, phi_0: f64,
-                 basket_pos_x: f64, basket_pos_y: f64, basket_pos_z: f64,
-                 simulation_sec: f64, num_steps: u32)
-                 -> (bool, Vec<(f64, (f64, f64, f64))) {
+/// The list of the instants, the positions of the trajectory are in the frame.
+fn print_trajectory_2d(trajectory_2d: & Trajectory, frame: Frame, flag_color: bool, precision: usize, messages: & Messages) {
+    println!("\n{}", i18n::banner(messages.trajectory));
+    println!("  {}: {}", messages.entered_the_basket, if trajectory_2d.0 {messages.flag_true} else {messages.flag_false});
+    match frame {
+        Frame::World => (),
+        Frame::Shooter => println!("  {}", messages.frame_shooter),
+        Frame::Hoop => println!("  {}", messages.frame_hoop),
+    }
+    println!();
 
+    let samples = & trajectory_2d.1;
+    let distances = cumulative_distance(samples);
+    let (x_name, y_name) = frame.axis_names();
+    for (i, (t, (x, y), flag_enter_instant)) in samples.iter().enumerate() {
+        let (v_x, v_y) = sample_velocity(samples, i);
+        let (a_x, a_y) = sample_acceleration(samples, i);
+        let (color_start, color_end) = match (flag_color, *flag_enter_instant) {
+            (true, true) => (display_cmd::ANSI_SCORE, display_cmd::ANSI_RESET),
+            (true, false) => (display_cmd::ANSI_BALL, display_cmd::ANSI_RESET),
+            (false, _) => ("", ""),
+        };
+        let round = |value: f64| Num(round_to_zero(value, precision));
+        println!("  {}t: {:0.12$} s, {13}: {:0.12$} m, {14}: {:0.12$} m, s: {:0.12$} m, v{13}: {:0.12$} m/s, v{14}: {:0.12$} m/s, v: {:0.12$} m/s, a{13}: {:0.12$} m/s², a{14}: {:0.12$} m/s², {}{} ",
+                 color_start, Num(*t), Num(*x), Num(*y), Num(distances[i]), round(v_x), round(v_y), Num(f64::hypot(v_x, v_y)), round(a_x), round(a_y),
+                 if *flag_enter_instant {messages.ball_entered_the_basket} else {""}, color_end, precision, x_name, y_name);
+    }
+    println!();
 }
-*/
-
-fn get_time_steps(simulation_sec: f64, num_steps: u32) -> Vec<f64> {
-    let inner_steps = num_steps - 1;
-    let delta_t = simulation_sec / inner_steps as f64;
-    let mut time_steps_vec: Vec<f64> = Vec::new();
-    // Add the first instant 0.0 s.
-    time_steps_vec.push(0.0);
-    for step in 1..(inner_steps + 1) {
-        time_steps_vec.push(delta_t * step as f64);    
-    }
-    // Add the last instant simulation_sec s.
-    time_steps_vec.push(simulation_sec);
-    time_steps_vec
+
+// The finite differences leave rounding errors, like -0.00 for a constant velocity, with the
+// precision decimal places.
+fn round_to_zero(value: f64, precision: usize) -> f64 {
+    if f64::abs(value) < 0.5 * f64::powi(10.0, -(precision as i32)) {0.0} else {value}
 }
 
-fn euclidean_distance(p_x: f64, p_y: f64, p_z: f64,
-                      q_x: f64, q_y: f64, q_z: f64)
-                      -> f64 {
-    f64::sqrt((p_x - q_x).powi(2) + (p_y - q_y).powi(2) + (p_z - q_z).powi(2))
+/// The key numbers of the shot, its value, the advice for its arc and the check of the time budget,
+/// after the list of the instants.
+#[allow(clippy::too_many_arguments)]
+fn print_results(trajectory_2d: & Trajectory, scenario: & ScenarioFile, shot_result: ShotResult, obstacle_hit: Option<ObstacleHit>,
+                 league: League, time_budget: Option<f64>, release_time: f64, precision: usize, messages: & Messages) {
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let Some(summary) = ShotSummary::new(trajectory_2d, basket_pos_x, basket_pos_y) else {
+        return;
+    };
+    let number = |value: f64| format!("{:0.*}", precision, Num(value));
+    println!("  {}", messages.results_title);
+    match summary.entry {
+        Some(entry) => {
+            println!("    {}", i18n::fill(messages.results_scored_at, &[& number(entry.t)]));
+            println!("    {}", i18n::fill(messages.results_entry, &[& number(entry.speed), & format!("{:0.1}", Num(entry.angle_deg))]));
+        },
+        None => println!("    {}", messages.results_missed),
+    }
+    // The goal of another sport replaces the result of the basket.
+    match & scenario.goal {
+        Some(goal) => {
+            let outcome = goal.outcome(& trajectory_2d.1, scenario.ball.radius);
+            println!("    {}", i18n::fill(messages.results_goal, &[& goal.name(messages), & outcome.describe(precision, messages)]));
+        },
+        None => println!("    {}", i18n::fill(messages.results_result, &[& shot_result.label(messages)])),
+    }
+    if let Some(hit) = obstacle_hit {
+        println!("    {}", i18n::fill(messages.results_obstacle, &[& scenario.obstacles[hit.index].label(hit.index), & number(hit.t),
+                                                                   & number(hit.x), & number(hit.y)]));
+    }
+    if !scenario.targets.is_empty() {
+        let results = target_results(& trajectory_2d.1, & scenario.targets);
+        let num_hit = results.iter().filter(|result| result.hit.is_some()).count();
+        println!("    {}", i18n::fill(messages.results_targets, &[& num_hit, & results.len()]));
+        for (i, (target, result)) in scenario.targets.iter().zip(& results).enumerate() {
+            match result.hit {
+                Some(hit) => println!("      {}", i18n::fill(messages.results_target_hit, &[& target.label(i), & number(hit.t),
+                                                                                           & number(hit.x), & number(hit.y)])),
+                None => println!("      {}", i18n::fill(messages.results_target_missed, &[& target.label(i), & number(result.closest)])),
+            }
+        }
+    }
+    println!("    {}", i18n::fill(messages.results_apex, &[& number(summary.apex_y), & number(summary.apex_t)]));
+    println!("    {}", i18n::fill(messages.results_range, &[& number(summary.range_x)]));
+    println!("    {}", i18n::fill(messages.results_flight, &[& number(summary.flight_sec)]));
+    println!("    {}", i18n::fill(messages.results_closest, &[& number(summary.closest_dist), & number(summary.closest_t)]));
+    let distances = cumulative_distance(& trajectory_2d.1);
+    let i_closest = trajectory_2d.1.partition_point(|(t, _, _)| *t < summary.closest_t);
+    let straight_line = f64::hypot(basket_pos_x - scenario.throw.pos_0_x, basket_pos_y - scenario.throw.pos_0_y);
+    println!("    {}", i18n::fill(messages.results_distance, &[& number(distances[i_closest]), & number(straight_line),
+                                                               & number(arc_length(& trajectory_2d.1))]));
+    let value = shot_value_in_plane(league, scenario.throw.pos_0_x, basket_pos_x);
+    println!("    {}", i18n::fill(messages.results_value,
+                                  &[& value.points, & number(value.distance), & number(f64::abs(value.line_distance)),
+                                    & if value.line_distance > 0.0 {messages.value_outside} else {messages.value_inside},
+                                    & league.name()]));
+    if value.flag_on_line {
+        println!("    {}", messages.results_on_line);
+    }
+    let correction = (!trajectory_2d.0).then(|| MissCorrection::new(scenario)).flatten();
+    match ArcClass::new(scenario.throw.teta_0_deg, trajectory_2d, basket_pos_y) {
+        Some(arc) => {
+            println!("    {}", i18n::fill(messages.results_arc, &[& arc.kind.name(messages), & format!("{:0.1}", Num(arc.release_angle_deg)),
+                                                                 & format!("{:0.1}", Num(arc.entry_angle_deg))]));
+            // The angle of the correction is already the one of the medium arc, when one scores.
+            if correction.is_none_or(|correction| correction.teta_0_deg.is_none()) {
+                println!("    {}", arc.advice(messages));
+            }
+        },
+        None => println!("    {}", messages.results_arc_none),
+    }
+    if let Some(correction) = correction {
+        println!("    {}", correction.suggestion(messages, scenario.throw.teta_0_deg));
+    }
+    if let Some(budget) = time_budget {
+        let timing = ShotTiming::new(& summary, release_time, budget);
+        let times: [& dyn std::fmt::Display; 5] = [& number(timing.release_time), & number(timing.flight_time),
+                                                   & number(timing.total()), & number(timing.budget),
+                                                   & number(f64::abs(timing.margin()))];
+        if timing.fits() {
+            println!("    {}", i18n::fill(messages.timing_fits, &times));
+        } else {
+            println!("    {}", i18n::fill(messages.timing_too_slow, &times));
+            if summary.flag_scored {
+                println!("    {}", messages.timing_too_slow_scored);
+            }
+        }
+    }
+    println!();
 }
 
-fn print_trajectory_2d(trajectory_2d: & Trajectory, display_cmd: & mut DisplayCMD) {
-    println!("\n****************");
-    println!("** Trajectory **");
-    println!("****************");
-    println!("  Entered the basket: {}", trajectory_2d.0);
-    println!("");
+/// Draws the path of the samples and the ball, in all the samples or only in the last one.
+fn draw_trajectory_cmd(display_cmd: & mut DisplayCMD, samples: & [(f64, (f64, f64), bool)], flag_all_balls: bool) {
+    let ball_ch = display_cmd.charset().ball;
+    let path_ch = display_cmd.charset().path;
+    // The path first, so the samples of the ball are drawn over it.
+    for pair in samples.windows(2) {
+        let (_, (x_0, y_0), _) = pair[0];
+        let (_, (x_1, y_1), _) = pair[1];
+        display_cmd.draw_line_meters(path_ch, y_0, x_0, y_1, x_1);
+    }
+    for (i, (_t, (x, y), flag_enter_instant)) in samples.iter().enumerate() {
+        if flag_all_balls || *flag_enter_instant || i + 1 == samples.len() {
+            let ch = if flag_all_balls || i + 1 == samples.len() { ball_ch } else { path_ch };
+            display_cmd.set_pixel_meters(ch, *y, *x, *flag_enter_instant);
+        }
+    }
+}
 
-    for (t, (x, y), flag_enter_instant) in & trajectory_2d.1 {
-        println!("  t: {:0.2} s, x: {:0.2} m, y: {:0.2} m, {} ", t, x, y, if *flag_enter_instant {"ball entered the basket"} else {""} );
-        display_cmd.set_pixel_meters('O', *y, *x, *flag_enter_instant);
+/// One line with the result and the key numbers of the shot, for under the text plot.
+fn status_line_cmd(trajectory_2d: & Trajectory, basket_pos_x: f64, basket_pos_y: f64, messages: & Messages) -> String {
+    match ShotSummary::new(trajectory_2d, basket_pos_x, basket_pos_y) {
+        Some(summary) => format!("  {}", summary_text(& summary, messages)),
+        None => format!("  {}", messages.no_samples_above_floor),
     }
-    println!("");
+}
 
-    display_cmd.print();
+fn summary_text(summary: & ShotSummary, messages: & Messages) -> String {
+    let number = |value: f64| format!("{:0.2}", Num(value));
+    i18n::fill(messages.summary_line,
+               &[& if summary.flag_scored {messages.scored} else {messages.missed},
+                 & number(summary.apex_y), & number(summary.range_x), & number(summary.flight_sec),
+                 & number(summary.closest_dist), & number(summary.closest_t)])
 }
 
-struct DisplayCMD {
-    buf: Vec<char>,
-    num_rows: usize,
-    num_cols: usize,
-    rows_meters: f64,
-    cols_meters: f64,
+/// Simulates the shot with the air drag and prints a table with the ideal one.
+fn print_drag_comparison(scenario: & ScenarioFile, trajectory_2d: & Trajectory, messages: & Messages) {
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let trajectory_drag = scenario.simulate_2d_drag();
+    let (Some(ideal), Some(drag)) = (ShotSummary::new(trajectory_2d, basket_pos_x, basket_pos_y),
+                                     ShotSummary::new(& trajectory_drag, basket_pos_x, basket_pos_y)) else {
+        return;
+    };
+    let result = |summary: & ShotSummary| if summary.flag_scored {messages.scored} else {messages.missed};
+    println!("\n{}", i18n::banner(messages.drag_title));
+    println!("  {}", i18n::fill(messages.drag_air, &[& Num(scenario.air.temperature_c), & Num(scenario.air.humidity_percent),
+                                                      & format!("{:0.3}", Num(scenario.air.density()))]));
+    if !scenario.wind.is_empty() {
+        println!("  {}", messages.drag_wind);
+    }
+    println!("  {:<14} {:>10} {:>10} {:>11}", "", messages.drag_ideal, messages.drag_with_drag, messages.drag_difference);
+    println!("  {:<14} {:>10} {:>10}", messages.drag_result, result(& ideal), result(& drag));
+    for (label, value_ideal, value_drag) in [(messages.drag_apex, ideal.apex_y, drag.apex_y),
+                                             (messages.drag_range, ideal.range_x, drag.range_x),
+                                             (messages.drag_flight, ideal.flight_sec, drag.flight_sec),
+                                             (messages.drag_closest, ideal.closest_dist, drag.closest_dist)] {
+        println!("  {:<14} {:>10.2} {:>10.2} {:>+11.2}", label, Num(value_ideal), Num(value_drag), Num(value_drag - value_ideal));
+    }
+}
+
+/// Prints the deflection of the shot by the Coriolis effect at the closest instant to the basket.
+fn print_coriolis(scenario: & ScenarioFile, trajectory_2d: & Trajectory, latitude_deg: f64, azimuth_deg: f64, messages: & Messages) {
+    let Some(summary) = ShotSummary::new(trajectory_2d, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y) else {
+        return;
+    };
+    let deflection = coriolis::coriolis_deflection(scenario, latitude_deg, azimuth_deg, summary.closest_t);
+    // Without the sign of the ones that round to 0.
+    let millimeters = |value: f64| format!("{:+0.3}", Num(if f64::abs(value) < 0.0000005 {0.0} else {value * 1000.0}));
+    println!("\n{}", i18n::banner(messages.coriolis_title));
+    println!("  {}", i18n::fill(messages.coriolis_setup, &[& Num(latitude_deg), & Num(azimuth_deg), & format!("{:0.2}", Num(deflection.t))]));
+    println!("    {}", i18n::fill(messages.coriolis_side, &[& millimeters(deflection.side)]));
+    println!("    {}", i18n::fill(messages.coriolis_along, &[& millimeters(deflection.along)]));
+    println!("    {}", i18n::fill(messages.coriolis_up, &[& millimeters(deflection.up)]));
+    let largest = f64::max(f64::abs(deflection.side), f64::max(f64::abs(deflection.along), f64::abs(deflection.up)));
+    if largest > 0.0 {
+        println!("  {}", i18n::fill(messages.coriolis_note, &[& format!("{:0.0}", RIM_DIAMETER / 2.0 / largest)]));
+    }
 }
 
-impl DisplayCMD {
-    fn new(num_rows: usize, num_cols: usize, rows_meters: f64, cols_meters: f64) -> Self {
-        DisplayCMD { 
-            buf: vec![' '; num_rows * num_cols],
-            num_rows,
-            num_cols,
-            rows_meters,
-            cols_meters, 
+/// Prints when the ball of a made basket leaves the net and where the drop ends.
+fn print_post_score(post_score: & PostScore, precision: usize, messages: & Messages) {
+    let number = |value: f64| format!("{:0.*}", precision, Num(value));
+    let t_end = post_score.path.last().map_or(0.0, |(t, _position)| *t);
+    let line = match post_score.t_net_exit {
+        Some(t_net_exit) => i18n::fill(messages.post_score_line, &[& number(t_net_exit), & number(t_end), & post_score.stop_reason.name(messages)]),
+        None => i18n::fill(messages.post_score_in_net, &[& number(t_end), & post_score.stop_reason.name(messages)]),
+    };
+    println!("  {}", line);
+}
+
+/// Simulates the carom of a miss and prints the bounces and where the rebound lands, and
+/// returns it for the SVG.
+fn print_rebound(scenario: & ScenarioFile, trajectory_2d: & Trajectory, messages: & Messages) -> Option<Rebound> {
+    println!("\n{}", i18n::banner(messages.rebound_title));
+    if trajectory_2d.0 {
+        println!("  {}", messages.rebound_scored);
+        return None;
+    }
+    let Some(rebound) = rebound::simulate_rebound(scenario) else {
+        println!("  {}", messages.rebound_none);
+        return None;
+    };
+    let number = |value: f64| format!("{:0.2}", Num(value));
+    for contact in & rebound.contacts {
+        println!("  {}", i18n::fill(messages.rebound_contact, &[& number(contact.t), & contact.kind.name(messages),
+                                                                & number(contact.x), & number(contact.y)]));
+    }
+    if rebound.flag_rolled_in {
+        println!("  {}", messages.rebound_rolled_in);
+    } else if let Some(landing) = rebound.landing() {
+        let basket_pos_x = scenario.scene.basket_pos_x;
+        println!("  {}", i18n::fill(messages.rebound_landing,
+                                    &[& number(landing.x), & number(f64::abs(landing.x - basket_pos_x)),
+                                      & if landing.x < basket_pos_x {messages.rebound_in_front} else {messages.rebound_behind},
+                                      & number(landing.t)]));
+    }
+    if let Some(stop_reason) = rebound.stop_reason {
+        let t_end = rebound.path.last().map_or(0.0, |(t, _position)| *t);
+        println!("  {}", i18n::fill(messages.rebound_stop, &[& number(t_end), & stop_reason.name(messages)]));
+    }
+    // Each bounce takes its part of the energy of the release, what's left is the one at the end.
+    println!("\n  {}\n", messages.rebound_energy_title);
+    println!("  {:<30} {:>12} {:>8}", "", messages.rebound_energy_columns, "%");
+    let initial_energy = rebound.initial_energy;
+    let percent = |energy: f64| format!("{:0.1}", Num(energy / initial_energy * 100.0));
+    println!("  {:<30} {:>12.2} {:>8}", messages.rebound_energy_release, Num(initial_energy), percent(initial_energy));
+    for contact in & rebound.contacts {
+        println!("  {:<30} {:>12.2} {:>8}", format!("{} s: {}", number(contact.t), contact.kind.name(messages)),
+                 Num(-contact.energy_lost), percent(-contact.energy_lost));
+    }
+    println!("  {:<30} {:>12.2} {:>8}", messages.rebound_energy_end, Num(rebound.final_energy), percent(rebound.final_energy));
+    Some(rebound)
+}
+
+/// Simulates the shots with the noise of the release, and with the defender, and prints the make rates.
+fn print_monte_carlo(scenario: & ScenarioFile, num_shots: usize, defender: Option<Defender>, seed: Option<u64>,
+                     messages: & Messages) -> Result<(), String> {
+    scenario.validate()?;
+    if let Some(defender) = defender {
+        if scenario.throw.pos_0_x + defender.distance >= scenario.scene.basket_pos_x {
+            return Err(format!("The defender, {:0.2} m in front of the shooter, must be before the basket.", defender.distance));
         }
     }
+    let mut rng = seed.map_or_else(Rng::from_time, Rng::new);
+    let noise = scenario.noise;
+    let result = monte_carlo::run_monte_carlo(scenario, num_shots, noise, defender, & mut rng);
+    let percent = |rate: f64| format!("{:0.1}", Num(rate * 100.0));
+    println!("{}", i18n::banner(messages.mc_title));
+    println!("  {}\n", i18n::fill(messages.mc_noise, &[& num_shots, & noise.v_0.describe(" m/s", messages),
+                                                       & noise.teta_0_deg.describe("°", messages)]));
+    println!("  {}", i18n::fill(messages.mc_open, &[& result.num_made_open, & num_shots, & percent(result.open_rate())]));
+    if let Some(defender) = defender {
+        println!("  {}", i18n::fill(messages.mc_defender, &[& format!("{:0.2}", Num(defender.distance)), & format!("{:0.2}", Num(defender.reach)),
+                                                            & format!("{:0.2}", Num(result.mean_clearance))]));
+        println!("  {}", i18n::fill(messages.mc_contested, &[& result.num_made_contested, & num_shots,
+                                                             & percent(result.contested_rate())]));
+    }
+    Ok(())
+}
+
+/// Simulates the shots with the noise of the release at each distance, prints the make rates and
+/// writes their chart.
+fn print_make_rate(scenario: & ScenarioFile, num_shots: usize, cmd_args: & cli::CmdArgs, messages: & Messages) -> Result<(), String> {
+    scenario.validate()?;
+    let mut rng = cmd_args.seed.map_or_else(Rng::from_time, Rng::new);
+    let noise = scenario.noise;
+    let rates = monte_carlo::make_rate_by_distance(scenario, num_shots, noise, & mut rng);
+    println!("{}", i18n::banner(messages.mr_title));
+    println!("  {}\n", i18n::fill(messages.mr_profile, &[& num_shots, & format!("{:0.2}", Num(scenario.throw.pos_0_y)),
+                                                         & format!("{:0.1}", Num(scenario.throw.teta_0_deg)),
+                                                         & noise.v_0.describe(" m/s", messages), & noise.teta_0_deg.describe("°", messages)]));
+    println!("  {:>14} {:>12} {:>10} {:>10}", messages.random_distance, messages.mr_speed, messages.mr_made, messages.mr_rate);
+    for rate in & rates {
+        let v_0 = rate.v_0.map_or("-".to_string(), |v_0| format!("{:0.2}", Num(v_0)));
+        println!("  {:>14.1} {:>12} {:>10} {:>10.1}", Num(rate.distance), v_0, rate.result.num_made_open, Num(rate.result.open_rate() * 100.0));
+    }
+
+    if let Some(make_rate_filename) = & cmd_args.svg_make_rate_filename {
+        let points: Vec<(f64, f64)> = rates.iter().map(|rate| (rate.distance, rate.result.open_rate() * 100.0)).collect();
+        let label = format!("Make rate, {} shots at each distance & teta_0 = {:0.2}°", num_shots, scenario.throw.teta_0_deg);
+        let options = SvgPlotOptions { theme: cmd_args.theme.clone(), margin: cmd_args.svg_margin,
+                                      precision: cmd_args.precision.unwrap_or(svg_gen::DEFAULT_SVG_PRECISION),
+                                      output_mode: cmd_args.svg_output_mode, ..SvgPlotOptions::default() };
+        let mut svg = plot_make_rate_svg(& points, & options, &label);
+        svg.set_responsive(cmd_args.flag_svg_responsive);
+        svg.to_file(make_rate_filename, "./")?;
+    }
+    Ok(())
+}
+
+/// The analysis of the overhand and of the underhand free throws, side by side.
+fn print_free_throws(messages: & Messages) {
+    let analyses: Vec<Option<ReleaseAnalysis>> = ["free-throw", "free-throw-underhand"].iter()
+        .map(|name| ScenarioFile::preset(name).and_then(|scenario| free_throw::analyze_release(& scenario)))
+        .collect();
+    let [Some(overhand), Some(underhand)] = analyses[..] else {
+        return;
+    };
+    println!("{}", i18n::banner(messages.ft_title));
+    println!("  {}\n", i18n::fill(messages.ft_distance, &[& format!("{:0.3}", Num(FREE_THROW_DISTANCE))]));
+    println!("  {:<28} {:>12} {:>12}", "", messages.ft_overhand, messages.ft_underhand);
+    let window_width = |window: (f64, f64)| window.1 - window.0;
+    for (label, value_overhand, value_underhand) in
+            [(messages.ft_release_height, overhand.release_height, underhand.release_height),
+             (messages.ft_release_angle, overhand.teta_0_deg, underhand.teta_0_deg),
+             (messages.ft_speed, overhand.v_0, underhand.v_0),
+             (messages.ft_entry_angle, overhand.entry_angle_deg, underhand.entry_angle_deg),
+             (messages.ft_speed_window, window_width(overhand.speed_window), window_width(underhand.speed_window)),
+             (messages.ft_angle_window, window_width(overhand.angle_window), window_width(underhand.angle_window))] {
+        println!("  {:<28} {:>12.3} {:>12.3}", label, Num(value_overhand), Num(value_underhand));
+    }
+    println!("\n  {}", messages.ft_note);
+}
 
-    fn set_pixel(& mut self, ch: char, row: usize, col: usize) {
-        // println!("row: {}, col: {}", row, col);
-        assert!(row < self.num_rows);
-        assert!(col < self.num_cols);
-        self.buf[row*self.num_cols + col] = ch;
-    }
-
-    fn set_pixel_meters(& mut self, ch: char, row_meters_p: f64, col_meters_p: f64, flag_enter_instant: bool) {
-        assert!(row_meters_p <= self.rows_meters);
-        assert!(col_meters_p <= self.cols_meters);
-        let row = (row_meters_p * (self.num_rows - 1) as f64) / self.rows_meters;
-        let col = (col_meters_p * (self.num_cols - 1) as f64) / self.cols_meters;
-        let row = f64::round(row) as usize;
-        let col = f64::round(col) as usize;
-        let mut ch = ch;
-        if flag_enter_instant {
-            ch = '=';
-            self.set_pixel(ch, row, col - 2);
-            self.set_pixel(ch, row, col - 1);
-            self.set_pixel(ch, row, col + 1);
-            self.set_pixel(ch, row, col + 2);
-            ch = '*';
+/// Makes the random shots with the seed, or one from the clock, simulates them and prints a table.
+fn print_random_shots(num_shots: usize, seed: Option<u64>, messages: & Messages) {
+    let seed = seed.unwrap_or_else(rng::time_seed);
+    let mut rng = Rng::new(seed);
+    println!("{}", i18n::banner(messages.random_title));
+    println!("  {}\n", i18n::fill(messages.random_seed, &[& seed]));
+    println!("  {:>4} {:>14} {:>12} {:>12} {:>16} {:>12} {:>10} {:>14} {:>10}", "#", messages.random_distance, messages.random_height,
+             messages.balls_angle, messages.balls_speed, messages.random_ideal, messages.balls_apex, messages.balls_closest,
+             messages.balls_result);
+    let mut num_scored = 0;
+    for i in 1..=num_shots {
+        let (scenario, ideal_v_0) = random_shot::random_scenario(& mut rng);
+        let trajectory_2d = scenario.simulate_2d();
+        let throw = & scenario.throw;
+        let ideal = ideal_v_0.map_or("-".to_string(), |v_0| format!("{:0.2}", Num(v_0)));
+        match ShotSummary::new(& trajectory_2d, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y) {
+            Some(summary) => {
+                num_scored += summary.flag_scored as usize;
+                println!("  {:>4} {:>14.2} {:>12.2} {:>12.1} {:>16.2} {:>12} {:>10.2} {:>14.2} {:>10}", i,
+                         Num(scenario.scene.basket_pos_x - throw.pos_0_x), Num(throw.pos_0_y), Num(throw.teta_0_deg), Num(throw.v_0), ideal,
+                         Num(summary.apex_y), Num(summary.closest_dist), if summary.flag_scored {messages.scored} else {messages.missed});
+            },
+            None => println!("  {:>4} {:>14.2} {:>12.2} {:>12.1} {:>16.2} {:>12}", i,
+                             Num(scenario.scene.basket_pos_x - throw.pos_0_x), Num(throw.pos_0_y), Num(throw.teta_0_deg), Num(throw.v_0), ideal),
         }
-        self.set_pixel(ch, row, col);
+    }
+    println!("\n  {}", i18n::fill(messages.random_scored, &[& num_scored, & num_shots]));
+}
 
+/// Runs the benchmarks and prints their throughput and allocations.
+fn print_bench(num_samples: u32, messages: & Messages) {
+    println!("{}", i18n::banner(messages.bench_title));
+    println!("  {:<18} {:>12} {:>12} {:>16} {:>14} {:>14}", messages.bench_name, messages.bench_samples, messages.bench_time,
+             messages.bench_rate, messages.bench_allocations, "Bytes");
+    for result in bench::run_benches(num_samples) {
+        // Without the counting allocator the allocations aren't known.
+        let (num_allocations, num_bytes) = if cfg!(feature = "bench") {
+            (result.num_allocations.to_string(), result.num_bytes.to_string())
+        } else {
+            ("-".to_string(), "-".to_string())
+        };
+        println!("  {:<18} {:>12} {:>12.4} {:>16.0} {:>14} {:>14}", result.name, result.num_samples, Num(result.seconds),
+                 Num(result.samples_per_sec()), num_allocations, num_bytes);
     }
+    println!("\n  {}", messages.bench_note);
+    if !cfg!(feature = "bench") {
+        println!("  {}", messages.bench_no_allocations);
+    }
+}
+
+/// Prints the differences of the positions of two runs, at the instants of the first one, and of
+/// their summaries, and writes the SVG of the two arcs.
+fn print_compare(run_a_filename: & str, run_b_filename: & str, cmd_args: & cli::CmdArgs, messages: & Messages) -> Result<(), String> {
+    let run_a = RunFile::load(run_a_filename)?;
+    let run_b = RunFile::load(run_b_filename)?;
+    let (trajectory_a, trajectory_b) = (run_a.trajectory(), run_b.trajectory());
+    println!("{}", i18n::banner(messages.compare_title));
+    println!("  A: {}\n  B: {}", run_a_filename, run_b_filename);
+    println!("  {}\n", if run_a.scenario == run_b.scenario {messages.compare_scenarios_same} else {messages.compare_scenarios_differ});
 
-    fn get_pixel(& self, row: usize, col: usize) -> char {
-        assert!(row < self.num_rows);
-        assert!(col < self.num_cols);
-        self.buf[row*self.num_cols + col]
+    let deltas = compare::sample_deltas(& trajectory_a, & trajectory_b);
+    match deltas.iter().max_by(|delta_1, delta_2| delta_1.distance().total_cmp(& delta_2.distance())) {
+        None => println!("  {}", messages.compare_no_overlap),
+        Some(max) => {
+            let stride = deltas.len().div_ceil(COMPARE_MAX_ROWS);
+            println!("  {}", i18n::fill(messages.compare_positions, &[& stride]));
+            println!("  {:>8} {:>9} {:>9} {:>9} {:>9} {:>10} {:>10} {:>10}", "t (s)", "x A (m)", "y A (m)", "x B (m)", "y B (m)",
+                     "dx (m)", "dy (m)", messages.compare_delta);
+            // Without the sign of the differences that round to 0.
+            let number = |value: f64| Num(if f64::abs(value) < 0.00005 {0.0} else {value});
+            for delta in deltas.iter().step_by(stride) {
+                let (d_x, d_y) = delta.delta();
+                println!("  {:>8.3} {:>9.3} {:>9.3} {:>9.3} {:>9.3} {:>+10.4} {:>+10.4} {:>10.4}", Num(delta.t), Num(delta.a.0), Num(delta.a.1),
+                         Num(delta.b.0), Num(delta.b.1), number(d_x), number(d_y), Num(delta.distance()));
+            }
+            println!("\n  {}", i18n::fill(messages.compare_max, &[& format!("{:0.4}", Num(max.distance())), & format!("{:0.3}", Num(max.t))]));
+        },
     }
 
-    fn print(& self) {
-        for row in (0..self.num_rows).rev() {
-            for col in 0..self.num_cols {
-                print!("{}", self.get_pixel(row, col));
+    let summary = |run: & RunFile, trajectory: & Trajectory| ShotSummary::new(trajectory, run.scenario.scene.basket_pos_x, run.scenario.scene.basket_pos_y);
+    if let (Some(a), Some(b)) = (summary(& run_a, & trajectory_a), summary(& run_b, & trajectory_b)) {
+        let result = |summary: & ShotSummary| if summary.flag_scored {messages.scored} else {messages.missed};
+        let values: Vec<(&str, f64, f64)> = [(messages.drag_apex, a.apex_y, b.apex_y),
+                                             (messages.drag_range, a.range_x, b.range_x),
+                                             (messages.drag_flight, a.flight_sec, b.flight_sec),
+                                             (messages.drag_closest, a.closest_dist, b.closest_dist)]
+            .into_iter()
+            .filter(|(_, value_a, value_b)| f64::abs(value_b - value_a) > compare::SAME_TOLERANCE)
+            .collect();
+        if a.flag_scored == b.flag_scored && values.is_empty() {
+            println!("\n  {}", messages.compare_summary_same);
+        } else {
+            println!("\n  {}", messages.compare_summary_differ);
+            println!("  {:<14} {:>10} {:>10} {:>11}", "", "A", "B", messages.drag_difference);
+            if a.flag_scored != b.flag_scored {
+                println!("  {:<14} {:>10} {:>10}", messages.drag_result, result(& a), result(& b));
+            }
+            for (label, value_a, value_b) in values {
+                println!("  {:<14} {:>10.4} {:>10.4} {:>+11.4}", label, Num(value_a), Num(value_b), Num(value_b - value_a));
             }
-            println!("");
         }
     }
-}
 
-fn plot_trajectory_svg(trajectory_2d: & Trajectory,
-                       basket_pos_x: f64, basket_pos_y: f64,
-                       svg_x_max: f32, svg_y_max: f32 ) -> svg_gen::SVG {
+    if let Some(svg_filename) = & cmd_args.svg_compare_filename {
+        let svg_plot_options = SvgPlotOptions {
+            margin: cmd_args.svg_margin,
+            theme: cmd_args.theme.clone(),
+            animation_mode: cmd_args.animation_mode,
+            playback: cmd_args.playback,
+            simplify_tolerance: cmd_args.svg_simplify_tolerance,
+            precision: cmd_args.precision.unwrap_or(svg_gen::DEFAULT_SVG_PRECISION),
+            output_mode: cmd_args.svg_output_mode,
+            ..SvgPlotOptions::default()
+        };
+        let name = |letter: &str, filename: &str, trajectory: & Trajectory|
+            format!("{}: {} -> {}", letter, filename, if trajectory.0 {"entered the basket"} else {"missed the basket"});
+        let names = [name("A", run_a_filename, & trajectory_a), name("B", run_b_filename, & trajectory_b)];
+        let basket_pos = (run_a.scenario.scene.basket_pos_x, run_a.scenario.scene.basket_pos_y);
+        let mut svg = plot_balls_svg(& [trajectory_a, trajectory_b], & names, basket_pos, & svg_plot_options, "Comparison of two runs");
+        svg.set_responsive(cmd_args.flag_svg_responsive);
+        svg.to_file(svg_filename, "./")?;
+    }
+    Ok(())
+}
 
-    debug_assert!(svg_x_max > 0.0);
-    debug_assert!(svg_y_max > 0.0);
+/// Simulates the balls of the command line shot at once, prints a table of them and writes the
+/// SVG of the scenario with all of them.
+fn print_balls(scenario: & ScenarioFile, cmd_args: & cli::CmdArgs, output_namer: & OutputNamer, messages: & Messages)
+               -> Result<(), String> {
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    // Before the table, an output that exists stops it.
+    let svg_filename = scenario.outputs.svg_filename.as_deref().map(|filename| output_namer.name(filename)).transpose()?;
+    let mut trajectories: Vec<Trajectory> = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+    println!("{}", i18n::banner(messages.balls_title));
+    println!("  {:<6} {:>16} {:>12} {:>10} {:>14} {:>10}", messages.balls_ball, messages.balls_speed, messages.balls_angle,
+             messages.balls_apex, messages.balls_closest, messages.balls_result);
+    let mut winners: Vec<String> = Vec::new();
+    for (i, (v_0, teta_0_deg)) in cmd_args.balls.iter().enumerate() {
+        let mut ball = scenario.clone();
+        ball.throw.v_0 = *v_0;
+        ball.throw.teta_0_deg = *teta_0_deg;
+        ball.validate()?;
+        let trajectory_2d = ball.simulate_2d();
+        let summary = ShotSummary::new(& trajectory_2d, basket_pos_x, basket_pos_y)
+            .ok_or("The ball starts below the floor.".to_string())?;
+        println!("  {:<6} {:>16.2} {:>12.1} {:>10.2} {:>14.2} {:>10}", i + 1, Num(*v_0), Num(*teta_0_deg), Num(summary.apex_y),
+                 Num(summary.closest_dist), if summary.flag_scored {messages.scored} else {messages.missed});
+        if summary.flag_scored {
+            winners.push((i + 1).to_string());
+        }
+        names.push(format!("Ball {}: {:0.2} m/s & {:0.1}° -> {}", i + 1, v_0, teta_0_deg,
+                           if summary.flag_scored {"entered the basket"} else {"missed the basket"}));
+        trajectories.push(trajectory_2d);
+    }
+    match winners.len() {
+        0 => println!("\n  {}", messages.balls_nobody),
+        1 => println!("\n  {}", i18n::fill(messages.balls_winner, &[& winners[0]])),
+        _ => println!("\n  {}", i18n::fill(messages.balls_winners, &[& winners.join(", ")])),
+    }
 
-    use std::fmt::Write;
+    if let Some(svg_filename) = svg_filename {
+        let svg_plot_options = SvgPlotOptions {
+            margin: cmd_args.svg_margin,
+            theme: cmd_args.theme.clone(),
+            animation_mode: cmd_args.animation_mode,
+            playback: cmd_args.playback,
+            simplify_tolerance: cmd_args.svg_simplify_tolerance,
+            precision: cmd_args.precision.unwrap_or(svg_gen::DEFAULT_SVG_PRECISION),
+            output_mode: cmd_args.svg_output_mode,
+            ..SvgPlotOptions::default()
+        };
+        let label = format!("{} balls shot at once", cmd_args.balls.len());
+        let mut svg = plot_balls_svg(& trajectories, & names, (basket_pos_x, basket_pos_y), & svg_plot_options, &label);
+        svg.set_responsive(cmd_args.flag_svg_responsive);
+        svg.to_file(&svg_filename, "./")?;
+    }
+    Ok(())
+}
 
-    let mut svg = svg_gen::SVG::new(svg_x_max, svg_y_max, Some(Color::Black));
+/// Solves the passes to the teammate and prints a table of them, with the lead of each one when
+/// the teammate runs.
+fn print_passes(scenario: & ScenarioFile, distance: f64, catch_height: f64, runner_speed: Option<f64>, messages: & Messages) {
+    println!("{}", i18n::banner(messages.pass_title));
+    println!("  {}", i18n::fill(messages.pass_target, &[& format!("{:0.2}", Num(distance)), & format!("{:0.2}", Num(catch_height)),
+                                                       & format!("{:0.2}", Num(pass::CATCH_RADIUS))]));
+    if let Some(runner_speed) = runner_speed {
+        println!("  {}", i18n::fill(messages.pass_runner, &[& format!("{:0.2}", Num(runner_speed))]));
+    }
+    println!();
+    print!("  {:<10} {:>10} {:>12} {:>12} {:>13} {:>9}", messages.pass_kind, messages.pass_angle, messages.pass_speed,
+           messages.pass_catch_t, messages.pass_apex, messages.pass_caught);
+    // The passes to the teammate that stands are the ones without a lead.
+    let solutions: Vec<(PassKind, Option<PassSolution>, Option<f64>)> = match runner_speed {
+        Some(runner_speed) => {
+            println!(" {:>10}", messages.pass_lead);
+            pass::solve_intercepts(scenario, distance, catch_height, runner_speed).into_iter()
+                .map(|(kind, intercept)| (kind, intercept.map(|intercept| intercept.solution), intercept.map(|intercept| intercept.lead)))
+                .collect()
+        },
+        None => {
+            println!();
+            pass::solve_passes(scenario, distance, catch_height).into_iter()
+                .map(|(kind, solution)| (kind, solution, None))
+                .collect()
+        },
+    };
+    for (kind, solution, lead) in & solutions {
+        match solution {
+            Some(solution) => {
+                print!("  {:<10} {:>10.1} {:>12.2} {:>12.2} {:>13.2} {:>9}", kind.name(messages),
+                       Num(solution.teta_0_deg), Num(solution.v_0), Num(solution.catch_t), Num(solution.apex_y),
+                       if solution.flag_caught {messages.pass_caught_yes} else {messages.pass_caught_no});
+                match lead {
+                    Some(lead) => println!(" {:>+10.2}", Num(*lead)),
+                    None => println!(),
+                }
+            },
+            None => println!("  {:<10} {}", kind.name(messages), messages.pass_unreachable),
+        }
+    }
+    for (t, x) in solutions.iter().filter_map(|(_kind, solution, _lead)| solution.and_then(|solution| solution.bounce)) {
+        println!("\n  {}", i18n::fill(messages.pass_bounce_at, &[& format!("{:0.2}", Num(x - scenario.throw.pos_0_x)), & format!("{:0.2}", Num(t))]));
+    }
+}
 
-    // NOTE: Copied the SVG file output value to sublime, selected the text and see the number
-    //       of bytes, single byte characters.
-    const FINAL_SVG_TEXT_SIZE: usize = 10_000;
-    let mut elem_str = String::with_capacity(FINAL_SVG_TEXT_SIZE);
+/// Simulates all the scenarios and prints a line for each one, with the checkpoint file of
+/// --checkpoint.
+fn print_batch(scenarios: & [ScenarioFile], cmd_args: & cli::CmdArgs, messages: & Messages) -> Result<(), String> {
+    // The gallery of a previous batch isn't replaced, the figures would be mixed.
+    if let Some(gallery_folder) = & cmd_args.gallery_folder {
+        let index_path = std::path::Path::new(gallery_folder).join("index.html");
+        if !cmd_args.flag_force && index_path.exists() {
+            return Err(format!("The gallery {} exists, use --force to overwrite it.", index_path.display()));
+        }
+    }
+    for (i, scenario) in scenarios.iter().enumerate() {
+        for warning in plausibility_warnings(scenario) {
+            if cmd_args.flag_strict {
+                return Err(format!("Scenario {}: {}", i + 1, warning));
+            }
+            eprintln!("Warning: scenario {}: {}", i + 1, warning);
+        }
+    }
+    let results = match & cmd_args.checkpoint_filename {
+        Some(checkpoint_filename) => {
+            // A new checkpoint doesn't replace the one of a run that can be resumed.
+            if !cmd_args.flag_resume && !cmd_args.flag_force && std::path::Path::new(checkpoint_filename).exists() {
+                return Err(format!("The checkpoint file {} exists, use --resume to continue it or --force to start again.",
+                                   checkpoint_filename));
+            }
+            let (results, num_resumed) = batch::simulate_batch_checkpoint(scenarios, checkpoint_filename, cmd_args.flag_resume)?;
+            if cmd_args.flag_resume {
+                println!("{}", i18n::fill(messages.batch_resumed, &[& num_resumed, & scenarios.len(), & checkpoint_filename]));
+            }
+            results
+        },
+        None => batch::simulate_batch(scenarios),
+    };
+    println!("{}\n", i18n::fill(messages.batch_title, &[& scenarios.len()]));
+    print_batch_table(scenarios, & results, messages);
+    let num_scored = results.iter().filter(|result| matches!(result, Ok(summary) if summary.flag_scored)).count();
+    println!("  {}", i18n::fill(messages.batch_scored, &[& num_scored, & scenarios.len()]));
+    if let Some(gallery_folder) = & cmd_args.gallery_folder {
+        let svg_plot_options = SvgPlotOptions {
+            margin: cmd_args.svg_margin,
+            theme: cmd_args.theme.clone(),
+            animation_mode: cmd_args.animation_mode,
+            playback: cmd_args.playback,
+            simplify_tolerance: cmd_args.svg_simplify_tolerance,
+            precision: cmd_args.precision.unwrap_or(svg_gen::DEFAULT_SVG_PRECISION),
+            output_mode: cmd_args.svg_output_mode,
+            ..SvgPlotOptions::default()
+        };
+        gallery::write_gallery(gallery_folder, scenarios, & results, & svg_plot_options, "Basketball trajectory batch")?;
+        println!("  {}", i18n::fill(messages.batch_gallery, &[& std::path::Path::new(gallery_folder).join("index.html").display()]));
+    }
+    if let Some(csv_filename) = & cmd_args.batch_csv_filename {
+        let csv = batch::summary_csv(scenarios, & results, cmd_args.precision.unwrap_or(stream::DEFAULT_CSV_PRECISION));
+        std::fs::write(csv_filename, csv).map_err(|error| format!("Can't write the file {}: {}", csv_filename, error))?;
+        println!("  {}", i18n::fill(messages.batch_csv, &[csv_filename]));
+    }
+    Ok(())
+}
 
-    let mut x_max: f64 = f64::MIN;
-    let mut y_max: f64 = f64::MIN;
-    // Find x_max and y_max in the trajectory.
-    for (t, (x, y), flag_enter_instant) in & trajectory_2d.1 {    
-        if *x > x_max {
-            x_max = *x;
+/// Prints a line for each scenario of the batch, the columns aligned, and then the errors.
+fn print_batch_table(scenarios: & [ScenarioFile], results: & [batch::SimResult], messages: & Messages) {
+    let names: Vec<String> = scenarios.iter().enumerate().map(|(i, scenario)| batch::scenario_name(i, scenario)).collect();
+    // The columns are as wide as their headers and their cells.
+    let name_width = names.iter().map(|name| name.chars().count()).fold(messages.batch_column_name.chars().count(), usize::max);
+    let result_width = [messages.scored, messages.missed, messages.batch_error].iter()
+        .map(|text| text.chars().count()).fold(messages.batch_column_result.chars().count(), usize::max);
+    let [angle_width, speed_width, closest_width, flight_width] = [messages.batch_column_angle, messages.batch_column_speed,
+        messages.batch_column_closest, messages.batch_column_flight].map(|header| usize::max(header.chars().count(), 8));
+    println!("  {:<name_width$}  {:>angle_width$}  {:>speed_width$}  {:<result_width$}  {:>closest_width$}  {:>flight_width$}",
+             messages.batch_column_name, messages.batch_column_angle, messages.batch_column_speed,
+             messages.batch_column_result, messages.batch_column_closest, messages.batch_column_flight);
+    println!("  {}", "-".repeat(name_width + angle_width + speed_width + result_width + closest_width + flight_width + 10));
+    for ((scenario, result), name) in scenarios.iter().zip(results).zip(& names) {
+        let (result_text, closest, flight) = match result {
+            Ok(summary) => (if summary.flag_scored {messages.scored} else {messages.missed},
+                            format!("{:0.2}", Num(summary.closest_dist)), format!("{:0.2}", Num(summary.flight_sec))),
+            Err(_) => (messages.batch_error, String::new(), String::new()),
+        };
+        let line = format!("  {:<name_width$}  {:>angle_width$.2}  {:>speed_width$.2}  {:<result_width$}  {:>closest_width$}  {:>flight_width$}",
+                           name, Num(scenario.throw.teta_0_deg), Num(scenario.throw.v_0), result_text, closest, flight);
+        println!("{}", line.trim_end());
+    }
+    for (i, result) in results.iter().enumerate() {
+        if let Err(error) = result {
+            println!("  {}", i18n::fill(messages.batch_error_line, &[& (i + 1), error]));
         }
-        if *y > y_max {
-            y_max = *y;
+    }
+}
+
+/// Writes the streamed files, each one simulates the shot again.
+fn stream_files(scenario: & ScenarioFile, cmd_args: & cli::CmdArgs, messages: & Messages) -> Result<(), String> {
+    let print_summary = |filename: & str, summary: stream::StreamSummary| {
+        println!("{}", i18n::fill(messages.streamed, &[& summary.num_samples, & filename,
+                                                       & if summary.flag_scored {messages.scored} else {messages.missed}]));
+    };
+    if let Some(csv_filename) = & cmd_args.stream_csv_filename {
+        print_summary(csv_filename, stream::stream_scenario_csv(scenario, csv_filename, cmd_args.precision.unwrap_or(stream::DEFAULT_CSV_PRECISION))?);
+    }
+    if let Some(svg_filename) = & cmd_args.stream_svg_filename {
+        let svg_plot_options = SvgPlotOptions {
+            margin: cmd_args.svg_margin,
+            theme: cmd_args.theme.clone(),
+            precision: cmd_args.precision.unwrap_or(svg_gen::DEFAULT_SVG_PRECISION),
+            output_mode: cmd_args.svg_output_mode,
+            ..SvgPlotOptions::default()
+        };
+        print_summary(svg_filename, stream::stream_scenario_svg(scenario, svg_filename, & svg_plot_options)?);
+    }
+    Ok(())
+}
+
+/// Redraws the text plot for each instant at the simulated rate, the ball flies across the
+/// terminal. Each frame is printed over the previous one with the ANSI cursor movements.
+fn play_trajectory_cmd(trajectory_2d: & Trajectory, display_cmd: & mut DisplayCMD, basket_pos_x: f64, basket_pos_y: f64,
+                       obstacles: & [Obstacle]) {
+    use std::io::Write;
+
+    let samples = & trajectory_2d.1;
+    let mut stdout = std::io::stdout();
+    // Hides the cursor while playing.
+    print!("\x1b[?25l");
+    let mut num_lines_previous = 0;
+    let mut t_previous = samples.first().map_or(0.0, |sample| sample.0);
+    for i in 0..samples.len() {
+        let t = samples[i].0;
+        std::thread::sleep(std::time::Duration::from_secs_f64(f64::max(t - t_previous, 0.0)));
+        t_previous = t;
+
+        display_cmd.clear();
+        draw_court_cmd(display_cmd, basket_pos_x, basket_pos_y, obstacles);
+        draw_trajectory_cmd(display_cmd, & samples[..=i], i + 1 == samples.len());
+        let frame = display_cmd.render();
+        if num_lines_previous > 0 {
+            // Back to the first line of the previous frame.
+            print!("\x1b[{}F", num_lines_previous);
         }
+        print!("{}", frame);
+        let _ = stdout.flush();
+        num_lines_previous = frame.lines().count();
     }
-    let max_x_y = f64::max(x_max, y_max);
-    let scale_factor = svg_x_max as f64 / max_x_y;
-
-    /*
-        <circle id="circle" cx="0" cy="0" r="3" fill="yellow" />
-      
-        <animateMotion
-                xlink:href="#circle"
-                dur="3s"
-                begin="0s"
-                fill="freeze"
-                repeatCount="indefinite">
-            <mpath xlink:href="#motionPath" />
-        </animateMotion>
-    */
-
-    for (t, (x, y), flag_enter_instant) in & trajectory_2d.1 {    
-        // Draw the circle.
-        // <circle cx="150" cy="100" r="2" fill="blue" />
-        let _ = write!(elem_str, 
-                "<circle cx=\"{0:.2}\" cy=\"{1:.2}\" r=\"{2:.2}\" fill=\"{3}\" />\n",
-                x * scale_factor,
-                svg_y_max as f64 - y * scale_factor,
-                2.0,
-                if *flag_enter_instant {"green"} else {"blue"}
-            );
-    }
-
-    // Draw the basket.
-    // "<rect x="100" y="200" width="20" height="5" style="fill:green;stroke:green;stroke-width:1.0" />\n",
-    let _ = write!(elem_str,
-              "<rect x=\"{0:.2}\" y=\"{1:.2}\" width=\"{2:.2}\" height=\"{3:.2}\" style=\"fill:green;stroke:green;stroke-width:{4:.2}\" />\n",
-              basket_pos_x * scale_factor - 10.0,
-              svg_y_max as f64 - basket_pos_y * scale_factor - 2.0,
-              20.0,
-              4.0,
-              1.0);
-
-    // Get the position zero of the trajectory of the basket ball.
-    let x_0 = trajectory_2d.1[0].1.0 * scale_factor; 
-    let y_0 = svg_y_max as f64 - trajectory_2d.1[0].1.1 * scale_factor;
-
-    // Motion path.
-    // <path id="motionPath" fill="none" stroke="#000000" d="M0,0L100,100L200,200" />
-    let _ = write!(elem_str, 
-            "<path id=\"motionPath\" fill=\"none\" d=\"M{0:.2},{1:.2}\n",
-            x_0,
-            y_0);
-
-    let mut flag_skip_first = true;
-    for (t, (x, y), flag_enter_instant) in & trajectory_2d.1 {    
-        //if flag_skip_first {
-        //    flag_skip_first = false;
-        //    continue;
-        //}
-        // Draw the circle.
-        // "L100,200\n"
-        let _ = write!(elem_str, 
-                // "L{0:.2},{1:.2}\n",
-                // "L{0},{1}\n",
-                "L{0:.2},{1:.2}\n",
-                x * scale_factor,
-                svg_y_max as f64 - y * scale_factor);
-
-    }
-    let _ = write!(elem_str, "\" />\n" );
-
-    // "<circle id="circle" cx="%.2f" cy="%.2f" r="3" fill="yellow" />\n"
-    let _ = write!(elem_str, 
-        "<circle id=\"circle\" cx=\"{0:.2}\" cy=\"{1:.2}\" r=\"{2}\" fill=\"yellow\" />\n",
-        0.0,
-        0.0,
-        3);
-
-    /*
-        <animateMotion
-                xlink:href="#circle"
-                dur="3s"
-                begin="0s"
-                fill="freeze"
-                repeatCount="indefinite">
-            <mpath xlink:href="#motionPath" />
-        </animateMotion>
-    */
-    let _ = write!(elem_str,
-            "<animateMotion
-                xlink:href=\"#circle\"
-                dur=\"3s\"
-                begin=\"0s\"
-                fill=\"freeze\"
-                repeatCount=\"indefinite\">
-                <mpath xlink:href=\"#motionPath\" />
-            </animateMotion>"
-            );
-
-    svg.add_elem(elem_str);
-
-    svg
+    print!("\x1b[?25h");
+    let _ = stdout.flush();
 }
 
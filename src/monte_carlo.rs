@@ -0,0 +1,236 @@
+//! Monte Carlo simulation of the make rate of a shot. Each shot is the shot of the scenario with
+//! a noise in the speed and in the angle of the release, like the shots of a real player. The
+//! noise of each one is normal by default, or the distribution of the noise section of the
+//! scenario file:
+//!
+//! ```text
+//! [noise.v_0]
+//! distribution = "truncated-gaussian"
+//! sigma = 0.1
+//! limit = 0.15
+//!
+//! [noise.teta_0_deg]
+//! distribution = "uniform"
+//! half_width = 2.0
+//! ```
+//!
+//! A defender in front of the shooter contests the shot: the less the ball clears the hand of the
+//! defender, the lower the chance that a shot that goes in isn't blocked or disturbed. The chance
+//! is a logistic curve of the clearance, about 13% when the ball grazes the fingertips and 99%
+//! with half a meter over them.
+
+use serde::{Deserialize, Serialize};
+
+use crate::batch::classify_batch;
+use crate::i18n::{self, Messages, Num};
+use crate::rng::Rng;
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{required_speed, GRAVITY};
+use crate::units::degrees_to_radians;
+
+// Enough steps for the ball to move only a few cm between instants.
+const MONTE_CARLO_NUM_STEPS: u32 = 600;
+// The distances to the basket of the make rate table, from 2 m to 10 m.
+pub const MAKE_RATE_DISTANCES: [f64; 9] = [2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0]; // m
+// The clearance with a chance of 50% and the width of the logistic curve.
+const CONTEST_MID_CLEARANCE: f64 = 0.15;   // m
+const CONTEST_CLEARANCE_SCALE: f64 = 0.08; // m
+
+/// The distribution of the error of a value of the release, around the value aimed.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "distribution", rename_all = "kebab-case")]
+pub enum Noise {
+    /// Normal, with the standard deviation sigma.
+    Gaussian { sigma: f64 },
+    /// The same chance for all the errors from -half_width to half_width.
+    Uniform { half_width: f64 },
+    /// Normal, without the errors beyond the limit, a shooter that never misses by more.
+    TruncatedGaussian { sigma: f64, limit: f64 },
+}
+
+impl Noise {
+    /// The value with the error.
+    pub fn sample(& self, value: f64, rng: & mut Rng) -> f64 {
+        match * self {
+            Noise::Gaussian { sigma } => rng.normal(value, sigma),
+            Noise::Uniform { half_width } => rng.range(value - half_width, value + half_width),
+            Noise::TruncatedGaussian { sigma, limit } => {
+                if sigma == 0.0 {
+                    return value;
+                }
+                // Drawn again until it's inside, about one in 12 draws with the smallest limit.
+                loop {
+                    let error = rng.normal(0.0, sigma);
+                    if error.abs() <= limit {
+                        return value + error;
+                    }
+                }
+            },
+        }
+    }
+
+    /// The name is the one of the field in the errors.
+    pub fn validate(& self, name: & str) -> Result<(), String> {
+        let is_size = |value: f64| value >= 0.0 && value.is_finite();
+        match * self {
+            Noise::Gaussian { sigma } if !is_size(sigma) =>
+                Err(format!("{}.sigma is {}, expected a non negative number.", name, sigma)),
+            Noise::Uniform { half_width } if !is_size(half_width) =>
+                Err(format!("{}.half_width is {}, expected a non negative number.", name, half_width)),
+            Noise::TruncatedGaussian { sigma, .. } if !is_size(sigma) =>
+                Err(format!("{}.sigma is {}, expected a non negative number.", name, sigma)),
+            Noise::TruncatedGaussian { sigma, limit } if !(is_size(limit) && limit >= sigma / 10.0) =>
+                Err(format!("{}.limit is {}, expected at least a tenth of sigma.", name, limit)),
+            _ => Ok(()),
+        }
+    }
+
+    /// The distribution in the printed text, like "normal with σ = 0.08 m/s".
+    pub fn describe(& self, unit: & str, messages: & Messages) -> String {
+        match * self {
+            Noise::Gaussian { sigma } => i18n::fill(messages.noise_gaussian, &[& format!("{:0.2}{}", Num(sigma), unit)]),
+            Noise::Uniform { half_width } => i18n::fill(messages.noise_uniform, &[& format!("{:0.2}{}", Num(half_width), unit)]),
+            Noise::TruncatedGaussian { sigma, limit } =>
+                i18n::fill(messages.noise_truncated, &[& format!("{:0.2}{}", Num(sigma), unit), & format!("{:0.2}{}", Num(limit), unit)]),
+        }
+    }
+}
+
+/// The noise of the speed and of the angle of the release.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReleaseNoise {
+    pub v_0: Noise,         // m/s
+    pub teta_0_deg: Noise,  // degrees
+}
+
+impl Default for ReleaseNoise {
+    fn default() -> Self {
+        ReleaseNoise { v_0: Noise::Gaussian { sigma: 0.08 }, teta_0_deg: Noise::Gaussian { sigma: 1.0 } }
+    }
+}
+
+impl ReleaseNoise {
+    pub fn is_default(& self) -> bool {
+        * self == ReleaseNoise::default()
+    }
+
+    pub fn validate(& self) -> Result<(), String> {
+        self.v_0.validate("noise.v_0")?;
+        self.teta_0_deg.validate("noise.teta_0_deg")
+    }
+}
+
+/// A defender between the shooter and the basket, at distance meters in front of the shooter,
+/// with the hand up at the height reach.
+#[derive(Clone, Copy, Debug)]
+pub struct Defender {
+    pub distance: f64,  // m
+    pub reach: f64,     // m
+}
+
+impl Defender {
+    pub const DEFAULT_REACH: f64 = 2.9; // m - A jump with the arm up.
+
+    /// How much the bottom of the ball passes over the hand, negative when it's below it.
+    /// None when the ball doesn't reach the defender.
+    pub fn clearance(& self, scenario: & ScenarioFile, v_0: f64, teta_0_deg: f64) -> Option<f64> {
+        let teta_0 = degrees_to_radians(teta_0_deg);
+        let v_0_x = v_0 * f64::cos(teta_0);
+        if v_0_x <= 0.0 {
+            return None;
+        }
+        let t = self.distance / v_0_x;
+        let ball_y = scenario.throw.pos_0_y + v_0 * f64::sin(teta_0) * t - (1.0/2.0) * GRAVITY * t * t;
+        Some(ball_y - scenario.ball.radius - self.reach)
+    }
+}
+
+/// The chance, from 0 to 1, that a shot that goes in isn't stopped by the defender.
+pub fn contest_factor(clearance: f64) -> f64 {
+    1.0 / (1.0 + f64::exp(-(clearance - CONTEST_MID_CLEARANCE) / CONTEST_CLEARANCE_SCALE))
+}
+
+/// The shots made without and with the defender, and the mean clearance over its hand.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MonteCarloResult {
+    pub num_shots: usize,
+    pub num_made_open: usize,
+    pub num_made_contested: usize,
+    pub mean_clearance: f64,
+}
+
+impl MonteCarloResult {
+    pub fn open_rate(& self) -> f64 {
+        self.num_made_open as f64 / usize::max(self.num_shots, 1) as f64
+    }
+
+    pub fn contested_rate(& self) -> f64 {
+        self.num_made_contested as f64 / usize::max(self.num_shots, 1) as f64
+    }
+}
+
+/// Simulates num_shots shots of the scenario with the release noise. Without a defender the
+/// contested shots are the open ones.
+pub fn run_monte_carlo(scenario: & ScenarioFile, num_shots: usize, noise: ReleaseNoise,
+                       defender: Option<Defender>, rng: & mut Rng) -> MonteCarloResult {
+    let mut scenario = scenario.clone();
+    scenario.simulation.num_steps = u32::max(scenario.simulation.num_steps, MONTE_CARLO_NUM_STEPS);
+    let candidates: Vec<(f64, f64)> = (0..num_shots)
+        .map(|_| (noise.teta_0_deg.sample(scenario.throw.teta_0_deg, rng),
+                  noise.v_0.sample(scenario.throw.v_0, rng)))
+        .collect();
+    let scored = classify_batch(& scenario, & candidates);
+
+    let mut result = MonteCarloResult { num_shots, ..MonteCarloResult::default() };
+    let mut clearance_sum = 0.0;
+    for ((teta_0_deg, v_0), flag_scored) in candidates.iter().zip(scored) {
+        // The draw is made for every shot, so the sequence doesn't depend on the result.
+        let draw = rng.next_f64();
+        let clearance = defender.and_then(|defender| defender.clearance(& scenario, *v_0, *teta_0_deg));
+        clearance_sum += clearance.unwrap_or(0.0);
+        if flag_scored {
+            result.num_made_open += 1;
+            if clearance.is_none_or(|clearance| draw < contest_factor(clearance)) {
+                result.num_made_contested += 1;
+            }
+        }
+    }
+    result.mean_clearance = clearance_sum / usize::max(num_shots, 1) as f64;
+    result
+}
+
+/// The make rate of the shooter at a distance from the basket, with the speed of the shots
+/// without the noise, None when no speed reaches the basket with the angle.
+#[derive(Clone, Copy, Debug)]
+pub struct DistanceRate {
+    pub distance: f64,
+    pub v_0: Option<f64>,
+    pub result: MonteCarloResult,
+}
+
+/// The make rate at each distance of MAKE_RATE_DISTANCES of the shooter of the scenario, with its
+/// release height and angle and the speed that takes the ball through the center of the basket,
+/// like a player that aims, and the noise of the release.
+pub fn make_rate_by_distance(scenario: & ScenarioFile, num_shots: usize, noise: ReleaseNoise,
+                             rng: & mut Rng) -> Vec<DistanceRate> {
+    MAKE_RATE_DISTANCES.iter()
+        .map(|distance| {
+            let mut scenario = scenario.clone();
+            scenario.throw.pos_0_x = scenario.scene.basket_pos_x - distance;
+            let v_0 = required_speed(scenario.throw.pos_0_x, scenario.throw.pos_0_y, degrees_to_radians(scenario.throw.teta_0_deg),
+                                     scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+            let result = match v_0 {
+                Some(v_0) => {
+                    scenario.throw.v_0 = v_0;
+                    // Long enough for the ball to reach the basket.
+                    let flight_sec = distance / (v_0 * f64::cos(degrees_to_radians(scenario.throw.teta_0_deg)));
+                    scenario.simulation.simulation_sec = f64::max(scenario.simulation.simulation_sec, 1.5 * flight_sec);
+                    run_monte_carlo(& scenario, num_shots, noise, None, rng)
+                },
+                None => MonteCarloResult { num_shots, ..MonteCarloResult::default() },
+            };
+            DistanceRate { distance: *distance, v_0, result }
+        })
+        .collect()
+}
@@ -0,0 +1,27 @@
+//! Inline figures in the Rust Jupyter kernel, evcxr.
+//!
+//! evcxr shows a value with its method evcxr_display(), when it exists. The SVG has it, and the
+//! trajectory has it with the trait EvcxrDisplay in scope:
+//!
+//! ```text
+//! :dep basketball_trajectory = { path = "." }
+//! use basketball_trajectory::notebook::EvcxrDisplay;
+//! use basketball_trajectory::trajectory::basketball_2d;
+//! basketball_2d(0.0, 1.5, 10.0, 1.0, 8.0, 3.05, 3.0, 60)
+//! ```
+
+use crate::plot_svg::{plot_trajectory_svg, SvgPlotOptions};
+use crate::trajectory::Trajectory;
+
+pub trait EvcxrDisplay {
+    fn evcxr_display(& self);
+}
+
+impl EvcxrDisplay for Trajectory {
+    /// The animated figure of the trajectory. Without the basket, it isn't part of the trajectory,
+    /// for the basket use plot_trajectory_svg() and display the SVG.
+    fn evcxr_display(& self) {
+        let label = format!("{} samples -> {}", self.1.len(), if self.0 {"entered the basket"} else {"missed the basket"});
+        plot_trajectory_svg(self, None, & SvgPlotOptions::default(), &label).evcxr_display();
+    }
+}
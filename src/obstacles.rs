@@ -0,0 +1,172 @@
+//! Obstacles over the court in the plane of the shot, like a ceiling beam, a wall or the branch
+//! of a tree on an outdoor court. They are in the scenario file, in TOML each one in an
+//! [[obstacles]] table, a rectangle between two corners or a segment between two ends:
+//!
+//! ```text
+//! [[obstacles]]
+//! name = "ceiling beam"
+//! kind = "rectangle"
+//! x_1 = 4.0
+//! y_1 = 5.5
+//! x_2 = 5.0
+//! y_2 = 5.8
+//! ```
+//!
+//! The ball hits an obstacle when its surface touches it, the ball has its radius, the
+//! obstacles don't have a thickness.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ObstacleKind {
+    /// The corners (x_1, y_1) and (x_2, y_2), the ball can't be inside.
+    Rectangle,
+    /// The ends (x_1, y_1) and (x_2, y_2).
+    Segment,
+}
+
+/// An obstacle, the positions in meters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub kind: ObstacleKind,
+    pub x_1: f64,
+    pub y_1: f64,
+    pub x_2: f64,
+    pub y_2: f64,
+}
+
+/// The first contact of the ball with an obstacle, the index of the obstacle and the instant
+/// and the position of the center of the ball.
+#[derive(Clone, Copy, Debug)]
+pub struct ObstacleHit {
+    pub index: usize,
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+type Point = (f64, f64);
+
+impl Obstacle {
+    /// The name, or "obstacle" and its number from 1.
+    pub fn label(& self, index: usize) -> String {
+        if self.name.is_empty() { format!("obstacle {}", index + 1) } else { self.name.clone() }
+    }
+
+    /// The sides of the rectangle, or the segment.
+    pub fn edges(& self) -> Vec<(Point, Point)> {
+        let (p_1, p_2) = ((self.x_1, self.y_1), (self.x_2, self.y_2));
+        match self.kind {
+            ObstacleKind::Rectangle => {
+                let (p_12, p_21) = ((self.x_1, self.y_2), (self.x_2, self.y_1));
+                vec![(p_1, p_12), (p_12, p_2), (p_2, p_21), (p_21, p_1)]
+            },
+            ObstacleKind::Segment => vec![(p_1, p_2)],
+        }
+    }
+
+    fn contains(& self, (x, y): Point) -> bool {
+        self.kind == ObstacleKind::Rectangle
+            && (f64::min(self.x_1, self.x_2)..=f64::max(self.x_1, self.x_2)).contains(&x)
+            && (f64::min(self.y_1, self.y_2)..=f64::max(self.y_1, self.y_2)).contains(&y)
+    }
+}
+
+/// The positions that can't be drawn or hit, they must be finite.
+pub fn validate_obstacles(obstacles: & [Obstacle]) -> Result<(), String> {
+    match obstacles.iter().position(|obstacle| ![obstacle.x_1, obstacle.y_1, obstacle.x_2, obstacle.y_2].iter().all(|value| value.is_finite())) {
+        Some(i) => Err(format!("obstacles[{}] has a position that isn't a finite number.", i)),
+        None => Ok(()),
+    }
+}
+
+/// The first obstacle that the ball of the radius hits between the samples, until it enters the
+/// basket. None when the flight is clear.
+pub fn first_hit(samples: & [(f64, (f64, f64), bool)], obstacles: & [Obstacle], ball_radius: f64) -> Option<ObstacleHit> {
+    if samples.is_empty() {
+        return None;
+    }
+    let edges: Vec<Vec<(Point, Point)>> = obstacles.iter().map(Obstacle::edges).collect();
+    // A single sample is a path without length.
+    let num_paths = usize::max(samples.len(), 2) - 1;
+    for i in 0..num_paths {
+        let (t_0, p_0, flag_enter_0) = samples[i];
+        if flag_enter_0 {
+            return None;
+        }
+        let (t_1, p_1, _) = samples[usize::min(i + 1, samples.len() - 1)];
+        // The first of the obstacles along this path, by the fraction of the path.
+        let hit = obstacles.iter().enumerate().filter_map(|(index, obstacle)| {
+            if obstacle.contains(p_0) {
+                return Some((index, 0.0));
+            }
+            edges[index].iter()
+                .filter_map(|edge| first_touch((p_0, p_1), * edge, ball_radius))
+                .map(|frac| (index, frac))
+                .min_by(|hit_1, hit_2| hit_1.1.total_cmp(& hit_2.1))
+        }).min_by(|hit_1, hit_2| hit_1.1.total_cmp(& hit_2.1));
+        if let Some((index, frac)) = hit {
+            return Some(ObstacleHit {
+                index,
+                t: t_0 + (t_1 - t_0) * frac,
+                x: p_0.0 + (p_1.0 - p_0.0) * frac,
+                y: p_0.1 + (p_1.1 - p_0.1) * frac,
+            });
+        }
+    }
+    None
+}
+
+// The fraction of the path where the ball of the radius first touches the edge, None if it
+// doesn't. The distance to the edge only goes down and then up along the path, the first touch
+// is found by bisection before the closest point.
+fn first_touch(path: (Point, Point), edge: (Point, Point), radius: f64) -> Option<f64> {
+    let (distance, frac_closest) = segment_distance(path, edge);
+    if distance > radius {
+        return None;
+    }
+    let (a, b) = path;
+    let distance_at = |frac: f64| point_distance((a.0 + (b.0 - a.0) * frac, a.1 + (b.1 - a.1) * frac), edge).0;
+    if distance_at(0.0) <= radius {
+        return Some(0.0);
+    }
+    let (mut frac_out, mut frac_in) = (0.0, frac_closest);
+    for _ in 0..40 {
+        let frac = (frac_out + frac_in) / 2.0;
+        if distance_at(frac) <= radius { frac_in = frac } else { frac_out = frac }
+    }
+    Some(frac_in)
+}
+
+// The distance from the point to the segment, and the fraction of the segment of the closest point.
+fn point_distance(p: Point, (a, b): (Point, Point)) -> (f64, f64) {
+    let (d_x, d_y) = (b.0 - a.0, b.1 - a.1);
+    let length_square = d_x * d_x + d_y * d_y;
+    let frac = if length_square > 0.0 { (((p.0 - a.0) * d_x + (p.1 - a.1) * d_y) / length_square).clamp(0.0, 1.0) } else { 0.0 };
+    (f64::hypot(a.0 + d_x * frac - p.0, a.1 + d_y * frac - p.1), frac)
+}
+
+// The distance between the path and the edge, and the fraction of the path of the closest point.
+fn segment_distance(path: (Point, Point), edge: (Point, Point)) -> (f64, f64) {
+    let ((a, b), (c, d)) = (path, edge);
+    let cross = |u: Point, v: Point| u.0 * v.1 - u.1 * v.0;
+    let (r, s) = ((b.0 - a.0, b.1 - a.1), (d.0 - c.0, d.1 - c.1));
+    let denominator = cross(r, s);
+    if denominator != 0.0 {
+        let a_c = (c.0 - a.0, c.1 - a.1);
+        let (frac_path, frac_edge) = (cross(a_c, s) / denominator, cross(a_c, r) / denominator);
+        if (0.0..=1.0).contains(&frac_path) && (0.0..=1.0).contains(&frac_edge) {
+            return (0.0, frac_path);
+        }
+    }
+    // Without a crossing the closest points include an end of one of them.
+    let (distance_c, frac_c) = point_distance(c, path);
+    let (distance_d, frac_d) = point_distance(d, path);
+    [point_distance(a, edge).0, point_distance(b, edge).0, distance_c, distance_d].into_iter()
+        .zip([0.0, 1.0, frac_c, frac_d])
+        .min_by(|closest_1, closest_2| closest_1.0.total_cmp(& closest_2.0))
+        .unwrap_or((f64::INFINITY, 0.0))
+}
@@ -0,0 +1,104 @@
+//! The names of the output files. A run doesn't overwrite the files of an earlier one, unless it's
+//! forced, and a suffix, the time of the run or a counter, gives each run its own files.
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputSuffix {
+    None,
+    /// The UTC time of the run, like basketball_trajectory_20220106-153000.svg.
+    Timestamp,
+    /// The first free number, like basketball_trajectory_1.svg.
+    Counter,
+}
+
+impl OutputSuffix {
+    pub fn from_name(name: & str) -> Option<OutputSuffix> {
+        match name {
+            "none"      => Some(OutputSuffix::None),
+            "timestamp" => Some(OutputSuffix::Timestamp),
+            "counter"   => Some(OutputSuffix::Counter),
+            _ => None,
+        }
+    }
+}
+
+/// Names the output files of a run, all of them with the same time.
+pub struct OutputNamer {
+    suffix: OutputSuffix,
+    flag_force: bool,
+    timestamp: String,
+}
+
+impl OutputNamer {
+    pub fn new(suffix: OutputSuffix, flag_force: bool) -> OutputNamer {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+        OutputNamer { suffix, flag_force, timestamp: utc_timestamp(secs) }
+    }
+
+    /// The name of the file to write, an error when the file exists and it can't be overwritten.
+    /// The name "-" is kept.
+    pub fn name(& self, filename: & str) -> Result<String, String> {
+        // The standard output or error isn't a file.
+        if filename == "-" {
+            return Ok(filename.to_string());
+        }
+        let base = match self.suffix {
+            OutputSuffix::None => {
+                if Path::new(filename).exists() && !self.flag_force {
+                    return Err(format!("The output file {} exists, use --force to overwrite it or --output-suffix to write a new one.",
+                                       filename));
+                }
+                return Ok(filename.to_string());
+            },
+            OutputSuffix::Timestamp => {
+                let name = with_suffix(filename, &format!("_{}", self.timestamp));
+                if !Path::new(&name).exists() {
+                    return Ok(name);
+                }
+                // Two runs in the same second.
+                name
+            },
+            OutputSuffix::Counter => filename.to_string(),
+        };
+        let name = (1..).map(|i| with_suffix(&base, &format!("_{}", i)))
+            .find(|name| !Path::new(name).exists())
+            .unwrap_or_default();
+        Ok(name)
+    }
+
+    /// Names the files in place, the None ones aren't written.
+    pub fn name_all<'a>(& self, filenames: impl IntoIterator<Item = &'a mut Option<String>>) -> Result<(), String> {
+        for filename in filenames.into_iter().flatten() {
+            *filename = self.name(filename)?;
+        }
+        Ok(())
+    }
+}
+
+/// The filename with the suffix before the extension, "shot.svg" and "_1" give "shot_1.svg".
+pub fn with_suffix(filename: & str, suffix: & str) -> String {
+    let name_start = filename.rfind('/').map_or(0, |i| i + 1);
+    match filename[name_start..].rfind('.') {
+        // Not the dot of a hidden file, like ".shot".
+        Some(i) if i > 0 => format!("{}{}{}", &filename[..name_start + i], suffix, &filename[name_start + i..]),
+        _ => format!("{}{}", filename, suffix),
+    }
+}
+
+/// The UTC date and time of the seconds since 1970, as YYYYMMDD-hhmmss.
+pub fn utc_timestamp(secs: u64) -> String {
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+    // The civil date of the days since 1970-01-01, in eras of 400 years that start in March.
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_from_march + 2) / 5 + 1;
+    let month = if month_from_march < 10 {month_from_march + 3} else {month_from_march - 9};
+    let year = year_of_era + era * 400 + if month <= 2 {1} else {0};
+    format!("{:04}{:02}{:02}-{:02}{:02}{:02}", year, month, day, secs_of_day / 3_600, secs_of_day % 3_600 / 60, secs_of_day % 60)
+}
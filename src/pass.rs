@@ -0,0 +1,256 @@
+//! Passes to a teammate, the target is the point where the teammate catches the ball instead of
+//! the basket. The chest pass is fast and almost flat, the lob goes over the defenders. Each one
+//! is solved for the speed that reaches the catch point and simulated, it's caught when the
+//! ball passes within the reach of the hands.
+//!
+//! The bounce pass hits the floor once, at 2/3 of the way like the coaches teach, and loses speed
+//! in the bounce like the rebounds. It's two parabolas, solved for the speed and the angle that
+//! reach the catch point exactly.
+//!
+//! A teammate running at a constant speed along the line of the pass is caught at the point they
+//! reach at the instant of the catch, solve_intercepts() finds it and the lead of each pass, how
+//! far ahead of the teammate it's thrown.
+
+use crate::i18n::Messages;
+use crate::rebound::{pressure_restitution, FLOOR_RESTITUTION};
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{required_speed, ShotSummary, GRAVITY};
+use crate::units::{degrees_to_radians, radians_to_degrees};
+
+pub const CATCH_HEIGHT: f64 = 1.5;    // m - The chest of the teammate.
+pub const CATCH_RADIUS: f64 = 0.3;    // m - The reach of the hands around the catch point.
+const PASS_NUM_STEPS: u32 = 600;
+const PASS_EXTRA_TIME: f64 = 1.25;
+pub const BOUNCE_FRACTION: f64 = 2.0 / 3.0;   // Of the way to the teammate.
+// The time from the release to the bounce is searched in this range, the first solution is the fastest pass.
+const BOUNCE_MIN_SEC: f64 = 0.01;
+const BOUNCE_MAX_SEC: f64 = 3.0;
+const BOUNCE_NUM_CANDIDATES: usize = 300;
+const BOUNCE_NUM_BISECTIONS: usize = 60;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PassKind {
+    Chest,
+    Lob,
+    Bounce,
+}
+
+impl PassKind {
+    pub const ALL: [PassKind; 3] = [PassKind::Chest, PassKind::Lob, PassKind::Bounce];
+
+    /// The angle of the release from the floor, in degrees. None for the bounce pass, its angle
+    /// is solved.
+    pub fn teta_0_deg(self) -> Option<f64> {
+        match self {
+            PassKind::Chest => Some(15.0),
+            PassKind::Lob => Some(55.0),
+            PassKind::Bounce => None,
+        }
+    }
+
+    pub fn name(self, messages: & Messages) -> &'static str {
+        match self {
+            PassKind::Chest => messages.pass_chest,
+            PassKind::Lob => messages.pass_lob,
+            PassKind::Bounce => messages.pass_bounce,
+        }
+    }
+}
+
+/// A pass that reaches the catch point, and what the simulation says of it.
+#[derive(Clone, Copy, Debug)]
+pub struct PassSolution {
+    pub kind: PassKind,
+    pub teta_0_deg: f64,
+    pub v_0: f64,
+    /// The instant that the ball is closest to the catch point, and the distance.
+    pub catch_t: f64,
+    pub catch_dist: f64,
+    pub apex_y: f64,
+    pub flag_caught: bool,
+    /// The instant and the XX position of the bounce on the floor, for the bounce pass.
+    pub bounce: Option<(f64, f64)>,
+}
+
+/// The scenario of the pass, from the throw position of the scenario to the teammate at
+/// distance meters, with the catch point at catch_height in the place of the basket. None for
+/// the bounce pass, it isn't a single parabola.
+pub fn pass_scenario(scenario: & ScenarioFile, distance: f64, catch_height: f64, kind: PassKind) -> Option<ScenarioFile> {
+    let mut pass = scenario.clone();
+    pass.scene.basket_pos_x = scenario.throw.pos_0_x + distance;
+    pass.scene.basket_pos_y = catch_height;
+    pass.throw.teta_0_deg = kind.teta_0_deg()?;
+    let teta_0 = degrees_to_radians(pass.throw.teta_0_deg);
+    pass.throw.v_0 = required_speed(pass.throw.pos_0_x, pass.throw.pos_0_y, teta_0,
+                                    pass.scene.basket_pos_x, pass.scene.basket_pos_y)?;
+    // A little after the ball reaches the teammate.
+    let catch_t = distance / (pass.throw.v_0 * f64::cos(teta_0));
+    pass.simulation.simulation_sec = PASS_EXTRA_TIME * catch_t;
+    pass.simulation.num_steps = PASS_NUM_STEPS;
+    Some(pass)
+}
+
+/// The chest pass, the lob and the bounce pass to the teammate, None for the one that can't
+/// reach the catch point.
+pub fn solve_passes(scenario: & ScenarioFile, distance: f64, catch_height: f64) -> Vec<(PassKind, Option<PassSolution>)> {
+    PassKind::ALL.iter().map(|&kind| (kind, solve_pass(scenario, distance, catch_height, kind))).collect()
+}
+
+/// The pass of the kind to the teammate at distance meters, None when it can't reach the catch
+/// point.
+pub fn solve_pass(scenario: & ScenarioFile, distance: f64, catch_height: f64, kind: PassKind) -> Option<PassSolution> {
+    if kind == PassKind::Bounce {
+        return solve_bounce_pass(scenario, distance, catch_height);
+    }
+    let pass = pass_scenario(scenario, distance, catch_height, kind)?;
+    let trajectory_2d = pass.simulate_2d();
+    let summary = ShotSummary::new(& trajectory_2d, pass.scene.basket_pos_x, pass.scene.basket_pos_y)?;
+    Some(PassSolution {
+        kind,
+        teta_0_deg: pass.throw.teta_0_deg,
+        v_0: pass.throw.v_0,
+        catch_t: summary.closest_t,
+        catch_dist: summary.closest_dist,
+        apex_y: summary.apex_y,
+        flag_caught: summary.closest_dist <= CATCH_RADIUS,
+        bounce: None,
+    })
+}
+
+/// A pass to the running teammate, thrown lead meters ahead of where they are at the release.
+#[derive(Clone, Copy, Debug)]
+pub struct Intercept {
+    pub solution: PassSolution,
+    pub lead: f64,
+}
+
+/// The passes to the teammate at distance meters at the release, running at runner_speed m/s
+/// away from the passer, negative towards them. The ball and the teammate reach the catch point
+/// at the same instant. None for the pass that can't, like a teammate that runs to the passer
+/// faster than the ball comes.
+pub fn solve_intercepts(scenario: & ScenarioFile, distance: f64, catch_height: f64, runner_speed: f64)
+                        -> Vec<(PassKind, Option<Intercept>)> {
+    PassKind::ALL.iter().map(|&kind| {
+        let intercept = intercept_time(scenario, distance, catch_height, runner_speed, kind).and_then(|catch_t| {
+            let lead = runner_speed * catch_t;
+            let solution = (distance + lead > 0.0).then(|| solve_pass(scenario, distance + lead, catch_height, kind)).flatten()?;
+            Some(Intercept { solution, lead })
+        });
+        (kind, intercept)
+    }).collect()
+}
+
+// The instant of the catch of the running teammate. With the angle of the release fixed the
+// ball at x_0 + distance + runner_speed * t and at catch_height is a quadratic in t. The time of
+// the bounce pass doesn't depend on the distance, only its horizontal speed does.
+fn intercept_time(scenario: & ScenarioFile, distance: f64, catch_height: f64, runner_speed: f64, kind: PassKind) -> Option<f64> {
+    let Some(teta_0_deg) = kind.teta_0_deg() else {
+        return solve_bounce_pass(scenario, distance, catch_height).map(|solution| solution.catch_t);
+    };
+    let tan_teta_0 = f64::tan(degrees_to_radians(teta_0_deg));
+    // g/2 t^2 - runner_speed * tan(teta_0) * t - (y_0 - catch_height + distance * tan(teta_0)) = 0
+    let b = runner_speed * tan_teta_0;
+    let c = scenario.throw.pos_0_y - catch_height + distance * tan_teta_0;
+    let discriminant = b * b + 2.0 * GRAVITY * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    let catch_t = (b + f64::sqrt(discriminant)) / GRAVITY;
+    (catch_t > 0.0).then_some(catch_t)
+}
+
+/// The bounce pass to the teammate at distance meters, the ball bounces once at BOUNCE_FRACTION
+/// of the way and reaches catch_height at the teammate. None when even the fastest pass comes
+/// up lower than the catch height, or the ball starts on the floor.
+pub fn solve_bounce_pass(scenario: & ScenarioFile, distance: f64, catch_height: f64) -> Option<PassSolution> {
+    // The center of the ball is a radius above the floor in the bounce.
+    let radius = scenario.ball.radius;
+    let y_0 = scenario.throw.pos_0_y;
+    let floor_restitution = pressure_restitution(FLOOR_RESTITUTION, scenario.ball.pressure_bar);
+    if y_0 <= radius || catch_height <= radius {
+        return None;
+    }
+    // With t_1 from the release to the bounce, the speed of the ball up after the bounce and the
+    // height at the teammate t_2 later. The horizontal speed is the same in the whole pass.
+    let t_2_of = |t_1: f64| t_1 * (1.0 - BOUNCE_FRACTION) / BOUNCE_FRACTION;
+    let v_y_bounce_of = |t_1: f64| floor_restitution * ((y_0 - radius) / t_1 + GRAVITY * t_1 / 2.0);
+    let catch_error = |t_1: f64| {
+        let t_2 = t_2_of(t_1);
+        radius + v_y_bounce_of(t_1) * t_2 - (1.0/2.0) * GRAVITY * t_2 * t_2 - catch_height
+    };
+
+    let candidates: Vec<f64> = (0..BOUNCE_NUM_CANDIDATES)
+        .map(|i| BOUNCE_MIN_SEC + (BOUNCE_MAX_SEC - BOUNCE_MIN_SEC) * i as f64 / (BOUNCE_NUM_CANDIDATES - 1) as f64)
+        .collect();
+    let i = (1..BOUNCE_NUM_CANDIDATES)
+        .find(|&i| f64::signum(catch_error(candidates[i - 1])) != f64::signum(catch_error(candidates[i])))?;
+    let (mut t_low, mut t_high) = (candidates[i - 1], candidates[i]);
+    for _ in 0..BOUNCE_NUM_BISECTIONS {
+        let t_middle = (t_low + t_high) / 2.0;
+        if f64::signum(catch_error(t_middle)) == f64::signum(catch_error(t_low)) {
+            t_low = t_middle;
+        } else {
+            t_high = t_middle;
+        }
+    }
+    let t_1 = (t_low + t_high) / 2.0;
+    let t_2 = t_2_of(t_1);
+    let v_x = BOUNCE_FRACTION * distance / t_1;
+    let v_y_0 = (radius - y_0 + (1.0/2.0) * GRAVITY * t_1 * t_1) / t_1;
+    let v_y_bounce = v_y_bounce_of(t_1);
+
+    // The top of the first parabola when it goes up, and of the second before the catch.
+    let apex_1 = if v_y_0 > 0.0 { y_0 + v_y_0 * v_y_0 / (2.0 * GRAVITY) } else { y_0 };
+    let apex_2 = if v_y_bounce / GRAVITY < t_2 { radius + v_y_bounce * v_y_bounce / (2.0 * GRAVITY) } else { catch_height };
+    Some(PassSolution {
+        kind: PassKind::Bounce,
+        teta_0_deg: radians_to_degrees(f64::atan2(v_y_0, v_x)),
+        v_0: f64::hypot(v_x, v_y_0),
+        catch_t: t_1 + t_2,
+        catch_dist: f64::abs(catch_error(t_1)),
+        apex_y: f64::max(apex_1, apex_2),
+        flag_caught: f64::abs(catch_error(t_1)) <= CATCH_RADIUS,
+        bounce: Some((t_1, scenario.throw.pos_0_x + BOUNCE_FRACTION * distance)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passes_reach_the_teammate() {
+        let scenario = ScenarioFile::default();
+        let passes = solve_passes(& scenario, 6.0, CATCH_HEIGHT);
+        assert_eq!(passes.iter().map(|(kind, _)| *kind).collect::<Vec<_>>(), PassKind::ALL);
+        for (kind, solution) in & passes {
+            let solution = solution.unwrap();
+            assert_eq!(solution.kind, *kind);
+            assert!(solution.flag_caught && solution.catch_dist <= CATCH_RADIUS, "{:?}", solution);
+            assert_eq!(solution.bounce.is_some(), *kind == PassKind::Bounce);
+        }
+        let [chest, lob] = [0, 1].map(|i| passes[i].1.unwrap());
+        assert_eq!(chest.teta_0_deg, 15.0);
+        assert_eq!(lob.teta_0_deg, 55.0);
+        // The chest pass is the flat and fast one, the lob goes higher and takes longer.
+        assert!(chest.apex_y < lob.apex_y && chest.catch_t < lob.catch_t);
+    }
+
+    #[test]
+    fn chest_pass_too_flat_for_a_high_catch() {
+        // 15° from 1.5 m rises about 0.5 m in 2 m, it can't reach a catch at 3 m, the lob can.
+        let passes = solve_passes(& ScenarioFile::default(), 2.0, 3.0);
+        assert!(passes[0].1.is_none());
+        assert!(passes[1].1.is_some());
+    }
+
+    #[test]
+    fn bounce_pass_bounces_at_two_thirds() {
+        let scenario = ScenarioFile::default();
+        let bounce = solve_pass(& scenario, 6.0, CATCH_HEIGHT, PassKind::Bounce).unwrap();
+        assert!(bounce.flag_caught);
+        let (t_bounce, bounce_x) = bounce.bounce.unwrap();
+        assert!(f64::abs(bounce_x - (scenario.throw.pos_0_x + BOUNCE_FRACTION * 6.0)) < 0.01, "{}", bounce_x);
+        assert!(t_bounce > 0.0 && t_bounce < bounce.catch_t);
+    }
+}
@@ -0,0 +1,64 @@
+//! The checks of the inputs that can be simulated but aren't a basketball shot, like a release
+//! at 5 m or a ball at 40 m/s, usually a typo or a value in the wrong unit. They are warnings,
+//! the shot is simulated anyway, unless --strict turns them into errors. The checks of the
+//! basket and of the ball are skipped with the goal of another sport.
+
+use crate::scenario::ScenarioFile;
+use crate::trajectory::GRAVITY;
+
+pub const MAX_RELEASE_HEIGHT: f64 = 3.0;  // m - Higher than the release of the tallest players.
+pub const MAX_SPEED: f64 = 20.0;          // m/s - Faster than a throw across the whole court.
+pub const MAX_SIMULATION_FACTOR: f64 = 5.0; // Times the flight until the floor.
+pub const BALL_RADIUS_RANGE: (f64, f64) = (0.05, 0.2); // m
+pub const BALL_MASS_RANGE: (f64, f64) = (0.1, 2.0);    // kg
+pub const BALL_PRESSURE_RANGE: (f64, f64) = (0.2, 1.2); // bar
+
+/// The warnings of the scenario, empty when all the inputs are plausible.
+pub fn plausibility_warnings(scenario: & ScenarioFile) -> Vec<String> {
+    let throw = & scenario.throw;
+    let scene = & scenario.scene;
+    let mut warnings = Vec::new();
+    if throw.pos_0_y > MAX_RELEASE_HEIGHT {
+        warnings.push(format!("throw.pos_0_y is {} m, a release above {} m is higher than any player.", throw.pos_0_y, MAX_RELEASE_HEIGHT));
+    }
+    if throw.pos_0_y < 0.0 {
+        warnings.push(format!("throw.pos_0_y is {} m, the release is below the floor.", throw.pos_0_y));
+    }
+    if throw.v_0 > MAX_SPEED {
+        warnings.push(format!("throw.v_0 is {} m/s, faster than {} m/s is more than a player can throw.", throw.v_0, MAX_SPEED));
+    }
+    if !(-90.0..=90.0).contains(&throw.teta_0_deg) {
+        warnings.push(format!("throw.teta_0_deg is {}°, the ball is thrown backwards.", throw.teta_0_deg));
+    }
+    // The basket and the ball of another sport aren't the ones of basketball.
+    let flag_basketball = scenario.goal.is_none();
+    if flag_basketball && scene.basket_pos_y < throw.pos_0_y {
+        warnings.push(format!("scene.basket_pos_y is {} m, the basket is below the release at {} m.", scene.basket_pos_y, throw.pos_0_y));
+    }
+    if let (Some(flight_sec), false) = (flight_to_floor(scenario), scenario.simulation.auto_stop) {
+        if scenario.simulation.simulation_sec > MAX_SIMULATION_FACTOR * flight_sec {
+            warnings.push(format!("simulation.simulation_sec is {} s, more than {} times the {:0.2} s of the flight until the floor.",
+                                  scenario.simulation.simulation_sec, MAX_SIMULATION_FACTOR, flight_sec));
+        }
+    }
+    if flag_basketball {
+        if !(BALL_RADIUS_RANGE.0..=BALL_RADIUS_RANGE.1).contains(&scenario.ball.radius) {
+            warnings.push(format!("ball.radius is {} m, a basketball has about 0.12 m.", scenario.ball.radius));
+        }
+        if !(BALL_MASS_RANGE.0..=BALL_MASS_RANGE.1).contains(&scenario.ball.mass) {
+            warnings.push(format!("ball.mass is {} kg, a basketball has about 0.62 kg.", scenario.ball.mass));
+        }
+        if !(BALL_PRESSURE_RANGE.0..=BALL_PRESSURE_RANGE.1).contains(&scenario.ball.pressure_bar) {
+            warnings.push(format!("ball.pressure_bar is {} bar, a basketball is inflated to about 0.55 bar.", scenario.ball.pressure_bar));
+        }
+    }
+    warnings
+}
+
+// The instant the ball without drag reaches the floor, None if it's released below it.
+fn flight_to_floor(scenario: & ScenarioFile) -> Option<f64> {
+    let (_v_x, v_y, _v_z) = scenario.throw.velocity();
+    let pos_0_y = scenario.throw.pos_0_y;
+    (pos_0_y >= 0.0).then(|| (v_y + f64::sqrt(v_y * v_y + 2.0 * GRAVITY * pos_0_y)) / GRAVITY)
+        .filter(|flight_sec| *flight_sec > 0.0)
+}
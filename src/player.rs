@@ -0,0 +1,30 @@
+//! The release point of a jump shot from the body of the player, instead of the height of the
+//! release. The ball leaves the hands above the head, a bit below the standing reach, the tips
+//! of the fingers with the arm up, and higher by the height of the jump.
+
+/// The standing reach of the adults is about this times the height.
+pub const STANDING_REACH_RATIO: f64 = 1.33;
+/// From the standing reach down to the center of the ball at the release, the arm isn't
+/// straight up and the ball is in front of the fingers.
+pub const RELEASE_BELOW_REACH: f64 = 0.25; // m
+
+/// The body of the player, in meters.
+#[derive(Clone, Copy, Debug)]
+pub struct Player {
+    pub height: f64,
+    /// Measured, or STANDING_REACH_RATIO times the height.
+    pub standing_reach: Option<f64>,
+    /// 0 for a set shot.
+    pub jump_height: f64,
+}
+
+impl Player {
+    pub fn standing_reach(& self) -> f64 {
+        self.standing_reach.unwrap_or(STANDING_REACH_RATIO * self.height)
+    }
+
+    /// The height of the center of the ball when it leaves the hands.
+    pub fn release_height(& self) -> f64 {
+        self.standing_reach() + self.jump_height - RELEASE_BELOW_REACH
+    }
+}
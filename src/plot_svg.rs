@@ -0,0 +1,1189 @@
+//! The figure of the trajectory in SVG, with the animation of the ball.
+
+use crate::court::{three_point_line, League, SpeedCell, BASKET_FROM_BASELINE, COURT_WIDTH, HALF_COURT_LENGTH, KEY_WIDTH};
+use crate::frames::FrameTransform;
+use crate::obstacles::{Obstacle, ObstacleKind};
+use crate::scenario::FREE_THROW_DISTANCE;
+use crate::svg_gen::{escape_attr, palette, simplify_polyline, Color, OutputMode, Style, DEFAULT_SVG_PRECISION, SVG};
+use crate::targets::Target;
+use crate::trajectory::{basket_backboard_x, euclidean_distance, sample_speed, sample_velocity, Trajectory, Trajectory3D,
+                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, BACKBOARD_WIDTH, GRAVITY, RIM_DIAMETER};
+
+pub const SVG_ANIMATION_DUR_SEC: f32 = 3.0; // s - Duration of one loop of the SVG animation.
+// The angles of the small multiples, from, to and the step.
+pub const SMALL_MULTIPLES_ANGLES: (f64, f64, f64) = (35.0, 60.0, 5.0); // °
+pub const SMALL_MULTIPLES_MAX_PANELS: usize = 100;
+
+// Bytes of the text of each instant, measured in the SVG file and rounded up, so the buffers
+// are allocated once with their final size.
+const MARKER_BYTES: usize = 140;      // A circle with its tooltip.
+const TRAIL_BYTES: usize = 220;       // A circle of the trail with its fade animation.
+const MOTION_PATH_BYTES: usize = 34;  // A point of the motion path, its keyTime and its keyPoint.
+const KEYFRAME_BYTES: usize = 60;     // A CSS keyframe of the ball.
+
+// The anaglyph, the eyes are further apart than real ones to see the depth of the whole court.
+const ANAGLYPH_EYE_SEPARATION: f64 = 0.6;                      // m
+// From the middle of the shot, in lengths of the shot, behind, above and to the left.
+const ANAGLYPH_CAMERA_OFFSET: (f64, f64, f64) = (-0.9, 0.4, -0.8);
+const ANAGLYPH_RIM_POINTS: usize = 24;
+
+/// Colors of the SVG figure.
+#[derive(Clone)]
+pub struct Theme {
+    pub background: Color,
+    pub trajectory: Color,
+    // The instant that the ball entered the basket.
+    pub score_marker: Color,
+    // The animated ball.
+    pub ball: Color,
+    pub basket: Color,
+    pub text: Color,
+    // The measured positions, from the tracking data.
+    pub measured: Color,
+    // The carom after the ball hits the rim or the backboard.
+    pub rebound: Color,
+    // The obstacles of the scenario, like a beam.
+    pub obstacle: Color,
+}
+
+impl Theme {
+    /// Black background, good for the screen.
+    pub fn dark() -> Self {
+        Theme {
+            background: Color::Black,
+            trajectory: Color::Blue,
+            score_marker: Color::Green,
+            ball: Color::Yellow,
+            basket: Color::Green,
+            text: Color::White,
+            measured: Color::Red,
+            rebound: Color::Rgb(255, 140, 0),
+            obstacle: Color::Rgb(150, 150, 150),
+        }
+    }
+
+    /// White background, good for printing on paper.
+    pub fn light() -> Self {
+        Theme {
+            background: Color::White,
+            trajectory: Color::Rgb(0, 90, 181),
+            score_marker: Color::Rgb(0, 140, 60),
+            ball: Color::Rgb(230, 110, 0),
+            basket: Color::Rgb(200, 50, 0),
+            text: Color::Black,
+            measured: Color::Rgb(150, 0, 150),
+            rebound: Color::Rgb(210, 100, 0),
+            obstacle: Color::Rgb(100, 100, 100),
+        }
+    }
+
+    pub fn from_name(name: & str) -> Option<Theme> {
+        match name {
+            "dark"  => Some(Theme::dark()),
+            "light" => Some(Theme::light()),
+            _ => None,
+        }
+    }
+}
+
+/// How the ball is animated in the SVG.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnimationMode {
+    /// SVG animateMotion, it's deprecated in some renderers.
+    Smil,
+    /// CSS keyframes with transform, supported by more viewers.
+    Css,
+}
+
+impl AnimationMode {
+    pub fn from_name(name: & str) -> Option<AnimationMode> {
+        match name {
+            "smil" => Some(AnimationMode::Smil),
+            "css"  => Some(AnimationMode::Css),
+            _ => None,
+        }
+    }
+}
+
+/// Options of the SVG figure.
+#[derive(Clone)]
+pub struct SvgPlotOptions {
+    pub width: f32,   // Max XX Coordinate.
+    pub height: f32,  // Max YY Coordinate.
+    // Empty space around the scene, in pixels.
+    pub margin: f32,
+    pub flag_draw_markers: bool,
+    pub theme: Theme,
+    pub animation_mode: AnimationMode,
+    // Fade out time of the animated trail, in simulated seconds.
+    pub trail_sec: Option<f64>,
+    // Speed of the animation against the simulated time, 0.25 is 4 times slower. Without it
+    // a loop of the animation takes SVG_ANIMATION_DUR_SEC seconds.
+    pub playback: Option<f64>,
+    // Maximum number of circles for the instants, and for the trail. The instant
+    // that the ball entered the basket is always drawn.
+    pub max_markers: usize,
+    // Maximum distance in pixels from the simplified arc line to the instants.
+    // The motion path of the animation always has all the instants.
+    pub simplify_tolerance: f32,
+    // Measured positions (t, (x, y)) of a real shot, drawn as hollow circles over the trajectory.
+    pub measured_points: Vec<(f64, (f64, f64))>,
+    // The path (t, (x, y)) of the rebound, drawn dashed from the first bounce.
+    pub rebound_points: Vec<(f64, (f64, f64))>,
+    // The obstacles of the scenario, they are in the figure.
+    pub obstacles: Vec<Obstacle>,
+    // The targets other than the basket, drawn as rings of their tolerance.
+    pub targets: Vec<Target>,
+    // Decimal places of the coordinates.
+    pub precision: usize,
+    // The layout of the SVG text, it's written as the elements are added.
+    pub output_mode: OutputMode,
+}
+
+impl Default for SvgPlotOptions {
+    fn default() -> Self {
+        SvgPlotOptions {
+            width: 500.0,
+            height: 300.0,
+            margin: 20.0,
+            flag_draw_markers: true,
+            theme: Theme::dark(),
+            animation_mode: AnimationMode::Smil,
+            trail_sec: None,
+            playback: None,
+            max_markers: 200,
+            simplify_tolerance: 0.1,
+            measured_points: Vec::new(),
+            rebound_points: Vec::new(),
+            obstacles: Vec::new(),
+            targets: Vec::new(),
+            precision: DEFAULT_SVG_PRECISION,
+            output_mode: OutputMode::Pretty,
+        }
+    }
+}
+
+impl SvgPlotOptions {
+    /// The seconds of a loop of the animation of the instants until t_last, in simulated seconds.
+    pub fn animation_dur_sec(& self, t_last: f64) -> f32 {
+        match self.playback {
+            // In milliseconds, the keyTimes keep the timing of the simulation.
+            Some(playback) if t_last > 0.0 => (f64::round(t_last / playback * 1000.0) / 1000.0) as f32,
+            _ => SVG_ANIMATION_DUR_SEC,
+        }
+    }
+}
+
+// Draw only one of each stride instants, so that there are at most max_markers circles.
+pub fn marker_stride(num_samples: usize, max_markers: usize) -> usize {
+    if max_markers == 0 {
+        return usize::MAX;
+    }
+    usize::max(1, num_samples.div_ceil(max_markers))
+}
+
+/// The figure of the trajectory, with the basket at basket_pos (x, y) if it's given.
+pub fn plot_trajectory_svg(trajectory_2d: & Trajectory,
+                       basket_pos: Option<(f64, f64)>,
+                       options: & SvgPlotOptions,
+                       label: & str) -> SVG {
+
+    let svg_x_max = options.width;
+    let svg_y_max = options.height;
+    let theme = & options.theme;
+    let animation_mode = options.animation_mode;
+    let trail_sec = options.trail_sec;
+    let animation_dur_sec = options.animation_dur_sec(trajectory_2d.1.last().map_or(0.0, |sample| sample.0));
+    let flag_draw_markers = options.flag_draw_markers;
+
+    debug_assert!(svg_x_max > 0.0);
+    debug_assert!(svg_y_max > 0.0);
+
+    use std::fmt::Write;
+
+    let mut svg = SVG::new(svg_x_max, svg_y_max, Some(theme.background.clone()));
+
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    // The label is user text, the SVG builder escapes it.
+    svg.set_title(label);
+
+    let num_samples = trajectory_2d.1.len();
+    let marker_stride = marker_stride(num_samples, options.max_markers);
+    let num_markers = if flag_draw_markers { num_samples.div_ceil(marker_stride) } else { 0 };
+    let num_trail = if trail_sec.is_some() { num_samples.div_ceil(marker_stride) } else { 0 };
+    // The trail and the animation of the ball.
+    svg.reserve(1_000 + num_trail * TRAIL_BYTES + num_samples * MOTION_PATH_BYTES);
+
+    // Find the bounds of the scene, the trajectory, the basket and the floor.
+    let (mut x_min, mut x_max, mut y_max) = match basket_pos {
+        Some((basket_pos_x, basket_pos_y)) => (basket_pos_x, basket_pos_x, basket_pos_y),
+        None => trajectory_2d.1.first().map_or((0.0, 0.0, 0.0), |(_t, (x, _y), _)| (*x, *x, 0.0)),
+    };
+    let y_min: f64 = 0.0;
+    for (_t, (x, y), _flag_enter_instant) in & trajectory_2d.1 {    
+        x_min = f64::min(x_min, *x);
+        x_max = f64::max(x_max, *x);
+        y_max = f64::max(y_max, *y);
+    }
+    for (_t, (x, y)) in options.measured_points.iter().chain(& options.rebound_points) {
+        x_min = f64::min(x_min, *x);
+        x_max = f64::max(x_max, *x);
+        y_max = f64::max(y_max, *y);
+    }
+    for obstacle in & options.obstacles {
+        x_min = f64::min(x_min, f64::min(obstacle.x_1, obstacle.x_2));
+        x_max = f64::max(x_max, f64::max(obstacle.x_1, obstacle.x_2));
+        y_max = f64::max(y_max, f64::max(obstacle.y_1, obstacle.y_2));
+    }
+    for target in & options.targets {
+        x_min = f64::min(x_min, target.x - target.tolerance);
+        x_max = f64::max(x_max, target.x + target.tolerance);
+        y_max = f64::max(y_max, target.y + target.tolerance);
+    }
+    // A single scale for both axis, so the arc isn't distorted, that fits the scene
+    // inside the canvas minus the margins.
+    let margin = f64::min(options.margin as f64, f64::min(svg_x_max as f64, svg_y_max as f64) / 4.0);
+    let plot_width  = svg_x_max as f64 - 2.0 * margin;
+    let plot_height = svg_y_max as f64 - 2.0 * margin;
+    let scale_factor = f64::min(plot_width / f64::max(x_max - x_min, 1e-9),
+                                plot_height / f64::max(y_max - y_min, 1e-9));
+    // From meters to SVG coordinates, the SVG YY axis points down.
+    let floor_svg_y = svg_y_max as f64 - margin;
+    let to_svg_x = |x: f64| margin + (x - x_min) * scale_factor;
+    let to_svg_y = |y: f64| floor_svg_y - (y - y_min) * scale_factor;
+
+    /*
+        <circle id="circle" cx="0" cy="0" r="3" fill="yellow" />
+      
+        <animateMotion
+                xlink:href="#circle"
+                dur="3s"
+                begin="0s"
+                fill="freeze"
+                repeatCount="indefinite">
+            <mpath xlink:href="#motionPath" />
+        </animateMotion>
+    */
+
+    // Clip the trajectory to the court, above the floor and inside the plot frame.
+    let court_clip_id = svg.add_clip_rect(0.0, 0.0, svg_x_max, floor_svg_y as f32);
+
+    // Draw the arc as a single line.
+    // <polyline points="0,224 13,202 27,182" fill="none" stroke="blue" stroke-width="1.5" />
+    let arc_points: Vec<(f32, f32)> = trajectory_2d.1.iter()
+        .map(|(_t, (x, y), _flag_enter_instant)| (to_svg_x(*x) as f32, to_svg_y(*y) as f32))
+        .collect();
+    svg.begin_clip_group(&court_clip_id);
+    // With thousands of instants most points are inside the same pixel, simplify the line.
+    let arc_line_points = simplify_polyline(&arc_points, options.simplify_tolerance);
+    svg.add_polyline(&arc_line_points, & Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5));
+
+    // Draw a circle for each instant, the spacing between them shows the speed.
+    // The instant that the ball entered the basket is always marked.
+    // The circles are written to the buffer of the SVG and the tooltip buffer is reused, with
+    // many instants an element for each one would be an allocation for each one.
+    let sample_style       = Style::new().fill(theme.trajectory.clone());
+    let enter_sample_style = Style::new().fill(theme.score_marker.clone());
+    svg.reserve((num_markers + 1) * MARKER_BYTES);
+    let mut tooltip = String::with_capacity(MARKER_BYTES);
+    for (i, (t, (x_m, y_m), flag_enter_instant)) in trajectory_2d.1.iter().enumerate() {
+        if (!flag_draw_markers || i % marker_stride != 0) && !*flag_enter_instant {
+            continue;
+        }
+        // Draw the circle, with the values of the instant as a tooltip.
+        // <circle cx="150" cy="100" r="2" fill="blue"><title>t: 0.10 s ...</title></circle>
+        let (x, y) = arc_points[i];
+        tooltip.clear();
+        let _ = write!(tooltip, "t: {0:.5$} s, x: {1:.5$} m, y: {2:.5$} m, speed: {3:.5$} m/s{4}",
+                       t, x_m, y_m, sample_speed(& trajectory_2d.1, i),
+                       if *flag_enter_instant {", ball entered the basket"} else {""}, options.precision);
+        svg.add_circle_with_title(x, y, 2.0, if *flag_enter_instant {& enter_sample_style} else {& sample_style}, &tooltip);
+    }
+
+    // The rebound, from the first bounce.
+    if !options.rebound_points.is_empty() {
+        let rebound_points: Vec<(f32, f32)> = options.rebound_points.iter()
+            .map(|(_t, (x, y))| (to_svg_x(*x) as f32, to_svg_y(*y) as f32))
+            .collect();
+        svg.add_polyline(& simplify_polyline(&rebound_points, options.simplify_tolerance),
+                         & Style::new().stroke(theme.rebound.clone()).stroke_width(1.5).dash_array(&[5.0, 3.0]));
+    }
+
+    // The measured points over the simulated ones.
+    let measured_style = Style::new().stroke(theme.measured.clone()).stroke_width(1.0);
+    for (t, (x_m, y_m)) in & options.measured_points {
+        tooltip.clear();
+        let _ = write!(tooltip, "Measured t: {0:.3$} s, x: {1:.3$} m, y: {2:.3$} m", t, x_m, y_m, options.precision);
+        svg.add_circle_with_title(to_svg_x(*x_m) as f32, to_svg_y(*y_m) as f32, 3.0, & measured_style, &tooltip);
+    }
+    svg.end_group();
+
+    // The obstacles, the rectangles filled and the segments as thick lines.
+    let obstacle_style = Style::new().fill(theme.obstacle.clone()).stroke(theme.obstacle.clone()).stroke_width(3.0);
+    for obstacle in & options.obstacles {
+        let (x_1, y_1) = (to_svg_x(obstacle.x_1) as f32, to_svg_y(obstacle.y_1) as f32);
+        let (x_2, y_2) = (to_svg_x(obstacle.x_2) as f32, to_svg_y(obstacle.y_2) as f32);
+        match obstacle.kind {
+            ObstacleKind::Rectangle => svg.add_rect(f32::min(x_1, x_2), f32::min(y_1, y_2), (x_2 - x_1).abs(), (y_2 - y_1).abs(), & obstacle_style),
+            ObstacleKind::Segment => svg.add_line(x_1, y_1, x_2, y_2, & obstacle_style),
+        }
+    }
+
+    // The targets, at least the size of the basket in the figure.
+    let target_style = Style::new().stroke(theme.basket.clone()).stroke_width(2.0);
+    for target in & options.targets {
+        svg.add_circle(to_svg_x(target.x) as f32, to_svg_y(target.y) as f32,
+                       f64::max(target.tolerance * scale_factor, 3.0) as f32, & target_style);
+    }
+
+    // Draw the basket.
+    // <rect x="100" y="200" width="20" height="5" fill="green" stroke="green" stroke-width="1.0" />
+    if let Some((basket_pos_x, basket_pos_y)) = basket_pos {
+        let basket_style = Style::new().fill(theme.basket.clone()).stroke(theme.basket.clone()).stroke_width(1.0);
+        svg.add_rect((to_svg_x(basket_pos_x) - 10.0) as f32,
+                     (to_svg_y(basket_pos_y) - 2.0) as f32,
+                     20.0,
+                     4.0,
+                     & basket_style);
+    }
+
+    // Fading trail, a circle at each instant that appears when the ball passes over it and
+    // fades out during trail_sec seconds of simulated time. All circles share the same fade
+    // animation, delayed by the instant of each one.
+    let ball_color = escape_attr(&theme.ball.to_string()).into_owned();
+    if let Some(trail_sec) = trail_sec {
+        let t_last = trajectory_2d.1.last().map_or(0.0, |sample| sample.0);
+        let fade_frac = if t_last > 0.0 { f64::min(trail_sec / t_last, 1.0) } else { 1.0 };
+        if animation_mode == AnimationMode::Css {
+            svg.add_style(&format!("@keyframes trail_fade {{ 0% {{ opacity: 0.8; }} {0:.2}% {{ opacity: 0; }} 100% {{ opacity: 0; }} }}\n\
+                                    .trail {{ opacity: 0; animation: trail_fade {1}s linear infinite; }}\n",
+                                   fade_frac * 100.0,
+                                   animation_dur_sec));
+        }
+        for (i, (t, _pos, _flag_enter_instant)) in trajectory_2d.1.iter().enumerate().step_by(marker_stride) {
+            let (x, y) = arc_points[i];
+            let delay = if t_last > 0.0 { t / t_last * animation_dur_sec as f64 } else { 0.0 };
+            match animation_mode {
+                AnimationMode::Smil => {
+                    svg.begin_elem(format_args!("<circle cx=\"{0:.3$}\" cy=\"{1:.3$}\" r=\"2.50\" fill=\"{2}\" opacity=\"0\">",
+                                                x, y, ball_color, options.precision));
+                    svg.add_elem(format_args!("<animate attributeName=\"opacity\" values=\"0.8;0;0\" keyTimes=\"0;{0:.4};1\" dur=\"{1}s\" \
+                                               begin=\"{2:.3}s\" repeatCount=\"indefinite\" />",
+                                              fade_frac, animation_dur_sec, delay));
+                    svg.end_elem("circle");
+                },
+                AnimationMode::Css => {
+                    svg.add_elem(format_args!(
+                        "<circle class=\"trail\" cx=\"{0:.4$}\" cy=\"{1:.4$}\" r=\"2.50\" fill=\"{2}\" style=\"animation-delay: {3:.3}s\" />",
+                        x, y,
+                        ball_color,
+                        delay,
+                        options.precision));
+                },
+            }
+        }
+    }
+
+    match animation_mode {
+        AnimationMode::Smil => {
+            // Get the position zero of the trajectory of the basket ball.
+            let x_0 = to_svg_x(trajectory_2d.1[0].1.0); 
+            let y_0 = to_svg_y(trajectory_2d.1[0].1.1);
+
+            // Motion path.
+            // <path id="motionPath" fill="none" stroke="#000000" d="M0,0 L100,100 L200,200" />
+            let mut path_d = String::with_capacity(num_samples * MOTION_PATH_BYTES);
+            let _ = write!(path_d, "M{0:.2$},{1:.2$}", x_0, y_0, options.precision);
+            for (_t, (x, y), _flag_enter_instant) in & trajectory_2d.1 {
+                let _ = write!(path_d, " L{0:.2$},{1:.2$}", to_svg_x(*x), to_svg_y(*y), options.precision);
+            }
+            svg.add_elem(format_args!("<path id=\"motionPath\" fill=\"none\" d=\"{}\" />", path_d));
+
+            // <circle id="circle" cx="0.00" cy="0.00" r="3" fill="yellow" />
+            svg.add_elem(format_args!("<circle id=\"circle\" cx=\"0.00\" cy=\"0.00\" r=\"3\" fill=\"{}\" />", ball_color));
+
+            /*
+                <animateMotion
+                        xlink:href="#circle"
+                        dur="3s"
+                        begin="0s"
+                        fill="freeze"
+                        repeatCount="indefinite">
+                    <mpath xlink:href="#motionPath" />
+                </animateMotion>
+            */
+            // The ball is at the fraction keyPoints of the path length at the fraction
+            // keyTimes of the duration, so it moves with the timing of the simulation.
+            let t_last = trajectory_2d.1.last().map_or(0.0, |sample| sample.0);
+            let mut path_len_vec: Vec<f64> = Vec::with_capacity(num_samples);
+            path_len_vec.push(0.0);
+            for i in 1..arc_points.len() {
+                let (x_0, y_0) = arc_points[i - 1];
+                let (x_1, y_1) = arc_points[i];
+                let len = euclidean_distance(x_0 as f64, y_0 as f64, 0.0, x_1 as f64, y_1 as f64, 0.0);
+                path_len_vec.push(path_len_vec[i - 1] + len);
+            }
+            let total_len = path_len_vec.last().copied().unwrap_or(0.0);
+            let mut key_times = String::with_capacity(num_samples * 7);
+            let mut key_points = String::with_capacity(num_samples * 7);
+            for (i, (t, _pos, _flag_enter_instant)) in trajectory_2d.1.iter().enumerate() {
+                let separator = if i > 0 {";"} else {""};
+                let _ = write!(key_times, "{}{:.4}", separator, if t_last > 0.0 { t / t_last } else { 0.0 });
+                let _ = write!(key_points, "{}{:.4}", separator, if total_len > 0.0 { path_len_vec[i] / total_len } else { 0.0 });
+            }
+
+            svg.begin_elem(format_args!("<animateMotion xlink:href=\"#circle\" dur=\"{0}s\" begin=\"0s\" fill=\"freeze\" calcMode=\"linear\" \
+                                         keyTimes=\"{1}\" keyPoints=\"{2}\" repeatCount=\"indefinite\">",
+                                        animation_dur_sec, key_times, key_points));
+            svg.add_elem("<mpath xlink:href=\"#motionPath\" />");
+            svg.end_elem("animateMotion");
+        },
+        AnimationMode::Css => {
+            // The ball moves with a CSS keyframe for each instant, the time between keyframes is
+            // proportional to the simulated time.
+            //   @keyframes ball_flight { 0.00% { transform: translate(0.00px, 224.11px); } ... }
+            let t_last = trajectory_2d.1.last().map_or(0.0, |sample| sample.0);
+            let mut css_str = String::with_capacity(200 + num_samples * KEYFRAME_BYTES);
+            css_str.push_str("@keyframes ball_flight {\n");
+            for (t, (x, y), _flag_enter_instant) in & trajectory_2d.1 {
+                let percent = if t_last > 0.0 { t / t_last * 100.0 } else { 0.0 };
+                let _ = writeln!(css_str, "    {0:.2}% {{ transform: translate({1:.3$}px, {2:.3$}px); }}",
+                                 percent,
+                                 to_svg_x(*x),
+                                 to_svg_y(*y),
+                                 options.precision);
+            }
+            css_str.push_str("}\n");
+            let _ = writeln!(css_str, "#circle {{ animation: ball_flight {0}s linear infinite; }}", animation_dur_sec);
+            svg.add_style(&css_str);
+
+            svg.add_elem(format_args!("<circle id=\"circle\" cx=\"0.00\" cy=\"0.00\" r=\"3\" fill=\"{}\" />", ball_color));
+        },
+    }
+
+
+    svg.add_text(8.0, 16.0, 12.0, & Style::new().fill(theme.text.clone()), label);
+
+    svg
+}
+
+/// The figure of several balls shot at once at the basket at basket_pos (x, y), each one with the
+/// color of the qualitative palette and its name in the legend. The balls are animated together,
+/// with the same clock, a ball that lands first waits at its last instant for the others. The
+/// markers, the trail and the tracking points of the options aren't drawn.
+pub fn plot_balls_svg(trajectories: & [Trajectory], names: & [String], basket_pos: (f64, f64),
+                      options: & SvgPlotOptions, label: & str) -> SVG {
+    use std::fmt::Write;
+
+    let theme = & options.theme;
+    let (svg_x_max, svg_y_max) = (options.width, options.height);
+    let mut svg = SVG::new(svg_x_max, svg_y_max, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+
+    // The bounds of the scene, all the trajectories, the basket and the floor.
+    let (basket_pos_x, basket_pos_y) = basket_pos;
+    let (mut x_min, mut x_max, mut y_max) = (basket_pos_x, basket_pos_x, basket_pos_y);
+    let mut t_max: f64 = 0.0;
+    for (t, (x, y), _flag_enter_instant) in trajectories.iter().flat_map(|trajectory_2d| & trajectory_2d.1) {
+        x_min = f64::min(x_min, *x);
+        x_max = f64::max(x_max, *x);
+        y_max = f64::max(y_max, *y);
+        t_max = f64::max(t_max, *t);
+    }
+    let animation_dur_sec = options.animation_dur_sec(t_max);
+    // Room for the legend above the scene.
+    let legend_height = 14.0 * trajectories.len() as f64;
+    let margin = f64::min(options.margin as f64, f64::min(svg_x_max as f64, svg_y_max as f64) / 4.0);
+    let plot_width  = svg_x_max as f64 - 2.0 * margin;
+    let plot_height = svg_y_max as f64 - 2.0 * margin - legend_height;
+    let scale_factor = f64::min(plot_width / f64::max(x_max - x_min, 1e-9),
+                                plot_height / f64::max(y_max, 1e-9));
+    let floor_svg_y = svg_y_max as f64 - margin;
+    let to_svg_x = |x: f64| margin + (x - x_min) * scale_factor;
+    let to_svg_y = |y: f64| floor_svg_y - y * scale_factor;
+
+    let basket_style = Style::new().fill(theme.basket.clone()).stroke(theme.basket.clone()).stroke_width(1.0);
+    svg.add_rect((to_svg_x(basket_pos_x) - 10.0) as f32, (to_svg_y(basket_pos_y) - 2.0) as f32, 20.0, 4.0, & basket_style);
+
+    let colors = palette::qualitative_n(trajectories.len());
+    let mut animations: Vec<BallAnimation> = Vec::with_capacity(trajectories.len());
+    let mut css_str = String::new();
+    for (i, ((trajectory_2d, name), color)) in trajectories.iter().zip(names).zip(& colors).enumerate() {
+        let samples = & trajectory_2d.1;
+        if samples.is_empty() {
+            continue;
+        }
+        let points: Vec<(f32, f32)> = samples.iter()
+            .map(|(_t, (x, y), _flag_enter_instant)| (to_svg_x(*x) as f32, to_svg_y(*y) as f32))
+            .collect();
+        svg.add_polyline(& simplify_polyline(&points, options.simplify_tolerance),
+                         & Style::new().stroke(color.clone()).stroke_width(1.5));
+        if let Some(i_enter) = samples.iter().position(|(_t, _pos, flag_enter_instant)| *flag_enter_instant) {
+            let (x, y) = points[i_enter];
+            svg.add_circle_with_title(x, y, 3.0, & Style::new().fill(theme.score_marker.clone()),
+                                      &format!("{} entered the basket at t: {:.*} s", name, options.precision, samples[i_enter].0));
+        }
+        svg.add_text(8.0, (margin + 24.0 + 14.0 * i as f64) as f32, 11.0, & Style::new().fill(color.clone()), name);
+
+        // The fractions of the time of the animation, the same clock for all the balls.
+        let mut times: Vec<f64> = samples.iter().map(|(t, _pos, _flag_enter_instant)| if t_max > 0.0 { t / t_max } else { 0.0 }).collect();
+        let mut points = points;
+        if times.last().is_some_and(|last| *last < 1.0) {
+            times.push(1.0);
+            points.push(points[points.len() - 1]);
+        }
+        animations.push((i, points, times, escape_attr(&color.to_string()).into_owned()));
+    }
+    // The balls over all the arcs.
+    for (i, points, times, ball_color) in & animations {
+        match options.animation_mode {
+            AnimationMode::Smil => {
+                let mut path_len_vec: Vec<f64> = vec![0.0];
+                for j in 1..points.len() {
+                    let ((x_0, y_0), (x_1, y_1)) = (points[j - 1], points[j]);
+                    path_len_vec.push(path_len_vec[j - 1] + euclidean_distance(x_0 as f64, y_0 as f64, 0.0, x_1 as f64, y_1 as f64, 0.0));
+                }
+                let total_len = path_len_vec.last().copied().unwrap_or(0.0);
+                let mut path_d = format!("M{:.*},{:.*}", options.precision, points[0].0, options.precision, points[0].1);
+                for (x, y) in points {
+                    let _ = write!(path_d, " L{:.*},{:.*}", options.precision, x, options.precision, y);
+                }
+                svg.add_elem(format_args!("<path id=\"motionPath{}\" fill=\"none\" d=\"{}\" />", i, path_d));
+                svg.add_elem(format_args!("<circle id=\"ball{}\" cx=\"0.00\" cy=\"0.00\" r=\"3\" fill=\"{}\" />", i, ball_color));
+                let key_times: Vec<String> = times.iter().map(|time| format!("{:.4}", time)).collect();
+                let key_points: Vec<String> = path_len_vec.iter()
+                    .map(|len| format!("{:.4}", if total_len > 0.0 { len / total_len } else { 0.0 }))
+                    .collect();
+                svg.begin_elem(format_args!("<animateMotion xlink:href=\"#ball{0}\" dur=\"{1}s\" begin=\"0s\" fill=\"freeze\" calcMode=\"linear\" \
+                                             keyTimes=\"{2}\" keyPoints=\"{3}\" repeatCount=\"indefinite\">",
+                                            i, animation_dur_sec, key_times.join(";"), key_points.join(";")));
+                svg.add_elem(format_args!("<mpath xlink:href=\"#motionPath{}\" />", i));
+                svg.end_elem("animateMotion");
+            },
+            AnimationMode::Css => {
+                let _ = writeln!(css_str, "@keyframes ball_flight{} {{", i);
+                for (time, (x, y)) in times.iter().zip(points) {
+                    let _ = writeln!(css_str, "    {:.2}% {{ transform: translate({:.*}px, {:.*}px); }}", time * 100.0,
+                                     options.precision, x, options.precision, y);
+                }
+                let _ = writeln!(css_str, "}}\n#ball{0} {{ animation: ball_flight{0} {1}s linear infinite; }}", i, animation_dur_sec);
+                svg.add_elem(format_args!("<circle id=\"ball{}\" cx=\"0.00\" cy=\"0.00\" r=\"3\" fill=\"{}\" />", i, ball_color));
+            },
+        }
+    }
+    if !css_str.is_empty() {
+        svg.add_style(&css_str);
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & Style::new().fill(theme.text.clone()), label);
+    svg
+}
+
+/// The phase space plot of the trajectory, the vertical velocity v_y against the height y, of
+/// the instants above the floor. The shot without the air drag is a parabola lying on its side,
+/// the top of the arc is where it crosses v_y = 0. Only the size, the margin, the simplification,
+/// the precision and the theme of the options are used.
+pub fn plot_phase_svg(trajectory_2d: & Trajectory, options: & SvgPlotOptions, label: & str) -> SVG {
+    let theme = & options.theme;
+    let mut svg = SVG::new(options.width, options.height, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+    let text_style = Style::new().fill(theme.text.clone());
+
+    // (y, v_y, flag_enter_instant) of the instants above the floor.
+    let samples = & trajectory_2d.1;
+    let phase_points: Vec<(f64, f64, bool)> = samples.iter().enumerate()
+        .filter(|(_i, (_t, (_x, y), _flag_enter_instant))| *y >= 0.0)
+        .map(|(i, (_t, (_x, y), flag_enter_instant))| (*y, sample_velocity(samples, i).1, *flag_enter_instant))
+        .collect();
+
+    // The bounds always have the floor and v_y = 0.
+    let (mut y_max, mut v_y_min, mut v_y_max) = (0.0_f64, 0.0_f64, 0.0_f64);
+    for (y, v_y, _flag_enter_instant) in & phase_points {
+        y_max = f64::max(y_max, *y);
+        v_y_min = f64::min(v_y_min, *v_y);
+        v_y_max = f64::max(v_y_max, *v_y);
+    }
+    let frame = PlotFrame::new(options, (0.0, y_max), (v_y_min, v_y_max));
+    frame.add_axes(& mut svg, theme, "y (m)", "v_y (m/s)");
+
+    // The dashed line of v_y = 0, the top of the arc.
+    let zero_svg_y = frame.to_svg_y(0.0) as f32;
+    let (left, right, top, bottom) = (frame.left as f32, frame.right as f32, frame.top as f32, frame.bottom as f32);
+    svg.add_line(left, zero_svg_y, right, zero_svg_y,
+                 & Style::new().stroke(theme.text.clone()).stroke_width(1.0).dash_array(&[4.0, 4.0]));
+    if zero_svg_y - top > 12.0 && bottom - zero_svg_y > 12.0 {
+        svg.add_text(left - 40.0, zero_svg_y + 4.0, 10.0, & text_style, &format!("{:.*}", options.precision, 0.0));
+    }
+
+    let line_points: Vec<(f32, f32)> = phase_points.iter()
+        .map(|(y, v_y, _flag_enter_instant)| (frame.to_svg_x(*y) as f32, frame.to_svg_y(*v_y) as f32))
+        .collect();
+    let line_points = simplify_polyline(&line_points, options.simplify_tolerance);
+    svg.add_polyline(&line_points, & Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5));
+
+    // The instant that the ball entered the basket.
+    let enter_style = Style::new().fill(theme.score_marker.clone());
+    for (y, v_y, _flag_enter_instant) in phase_points.iter().filter(|point| point.2) {
+        let tooltip = format!("y: {:.*} m, v_y: {:.*} m/s, ball entered the basket", options.precision, y, options.precision, v_y);
+        svg.add_circle_with_title(frame.to_svg_x(*y) as f32, frame.to_svg_y(*v_y) as f32, 3.0, & enter_style, &tooltip);
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & text_style, label);
+    svg
+}
+
+/// The trajectory in the frame of the transform, with the axes of the frame, the dashed lines
+/// through its origin, the release point and the center of the rim, of the instants above the
+/// floor. Only the size, the margin, the simplification, the precision and the theme of the
+/// options are used.
+pub fn plot_frame_svg(trajectory_2d: & Trajectory, transform: & FrameTransform, options: & SvgPlotOptions, label: & str) -> SVG {
+    let theme = & options.theme;
+    let mut svg = SVG::new(options.width, options.height, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+    let text_style = Style::new().fill(theme.text.clone());
+
+    let points: Vec<((f64, f64), bool)> = trajectory_2d.1.iter()
+        .filter(|(_t, (_x, y), _flag_enter_instant)| *y >= 0.0)
+        .map(|(_t, position, flag_enter_instant)| (transform.point(*position), *flag_enter_instant))
+        .collect();
+    let (release, basket) = (transform.point(transform.release), transform.point(transform.basket));
+
+    // The bounds always have the origin, the release and the rim.
+    let (mut x_range, mut y_range) = ((0.0_f64, 0.0_f64), (0.0_f64, 0.0_f64));
+    for (x, y) in points.iter().map(|point| point.0).chain([release, basket]) {
+        x_range = (f64::min(x_range.0, x), f64::max(x_range.1, x));
+        y_range = (f64::min(y_range.0, y), f64::max(y_range.1, y));
+    }
+    let frame = PlotFrame::new(options, x_range, y_range);
+    let (x_label, y_label) = transform.frame.axis_labels();
+    frame.add_axes(& mut svg, theme, x_label, y_label);
+
+    let origin_style = Style::new().stroke(theme.text.clone()).stroke_width(1.0).dash_array(&[4.0, 4.0]);
+    let (origin_svg_x, origin_svg_y) = (frame.to_svg_x(0.0) as f32, frame.to_svg_y(0.0) as f32);
+    svg.add_line(frame.left as f32, origin_svg_y, frame.right as f32, origin_svg_y, & origin_style);
+    svg.add_line(origin_svg_x, frame.top as f32, origin_svg_x, frame.bottom as f32, & origin_style);
+
+    let line_points: Vec<(f32, f32)> = points.iter()
+        .map(|((x, y), _flag_enter_instant)| (frame.to_svg_x(*x) as f32, frame.to_svg_y(*y) as f32))
+        .collect();
+    let line_points = simplify_polyline(&line_points, options.simplify_tolerance);
+    svg.add_polyline(&line_points, & Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5));
+
+    let (x_name, y_name) = transform.frame.axis_names();
+    let mut add_point = |(x, y): (f64, f64), style: & Style, name: & str| {
+        let tooltip = format!("{}, {}: {:.*} m, {}: {:.*} m", name, x_name, options.precision, x, y_name, options.precision, y);
+        svg.add_circle_with_title(frame.to_svg_x(x) as f32, frame.to_svg_y(y) as f32, 3.0, style, &tooltip);
+    };
+    add_point(release, & Style::new().fill(theme.text.clone()), "release");
+    add_point(basket, & Style::new().fill(theme.basket.clone()), "center of the rim");
+    for (position, _flag_enter_instant) in points.iter().filter(|point| point.1) {
+        add_point(*position, & Style::new().fill(theme.score_marker.clone()), "ball entered the basket");
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & text_style, label);
+    svg
+}
+
+/// The plot of the kinetic, the potential and the total energy of the ball over the time, of
+/// the instants above the floor, with the mass in kg. Without the air drag the total is a flat
+/// line, the energy is conserved. With the trajectory with the air drag, its total energy is
+/// also drawn, dashed, and it goes down. The options are used like in plot_phase_svg().
+pub fn plot_energy_svg(trajectory_2d: & Trajectory, trajectory_drag: Option<& Trajectory>, ball_mass: f64,
+                       options: & SvgPlotOptions, label: & str) -> SVG {
+    let theme = & options.theme;
+    let mut svg = SVG::new(options.width, options.height, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+
+    // The instants above the floor.
+    let energy_points = |trajectory: & Trajectory| -> Vec<EnergyPoint> {
+        let samples = & trajectory.1;
+        samples.iter().enumerate()
+            .filter(|(_i, (_t, (_x, y), _flag_enter_instant))| *y >= 0.0)
+            .map(|(i, (t, (_x, y), _flag_enter_instant))| {
+                let (v_x, v_y) = sample_velocity(samples, i);
+                (*t, 0.5 * ball_mass * (v_x * v_x + v_y * v_y), ball_mass * GRAVITY * y)
+            })
+            .collect()
+    };
+    let ideal_points = energy_points(trajectory_2d);
+    let drag_points = trajectory_drag.map(energy_points).unwrap_or_default();
+
+    let (mut t_max, mut energy_max) = (0.0_f64, 0.0_f64);
+    for (t, kinetic, potential) in ideal_points.iter().chain(& drag_points) {
+        t_max = f64::max(t_max, *t);
+        energy_max = f64::max(energy_max, kinetic + potential);
+    }
+    let frame = PlotFrame::new(options, (0.0, t_max), (0.0, energy_max));
+    frame.add_axes(& mut svg, theme, "t (s)", "E (J)");
+
+    // The kinetic, the potential and the total energy of an instant.
+    let series = ["kinetic", "potential", "total"];
+    let energy = |point: & EnergyPoint, i_series: usize| match i_series {
+        0 => point.1,
+        1 => point.2,
+        _ => point.1 + point.2,
+    };
+    let mut add_line = |points: & [EnergyPoint], i_series: usize, style: Style| {
+        let line_points: Vec<(f32, f32)> = points.iter()
+            .map(|point| (frame.to_svg_x(point.0) as f32, frame.to_svg_y(energy(point, i_series)) as f32))
+            .collect();
+        svg.add_polyline(& simplify_polyline(&line_points, options.simplify_tolerance), & style);
+    };
+    let colors = palette::qualitative_n(series.len());
+    for (i_series, color) in colors.iter().enumerate() {
+        add_line(& ideal_points, i_series, Style::new().stroke(color.clone()).stroke_width(1.5));
+    }
+    if !drag_points.is_empty() {
+        add_line(& drag_points, 2, Style::new().stroke(colors[2].clone()).stroke_width(1.5).dash_array(&[6.0, 4.0]));
+    }
+
+    // The legend, in a row above the plot after the label of the axis, in the colors of the lines.
+    let mut legend: Vec<(& str, & Color)> = series.into_iter().zip(& colors).collect();
+    if !drag_points.is_empty() {
+        legend.push(("total, with the air drag", & colors[2]));
+    }
+    let mut legend_x = frame.left as f32 + 70.0;
+    for (name, color) in legend {
+        svg.add_text(legend_x, frame.top as f32 - 6.0, 11.0, & Style::new().fill(color.clone()), name);
+        legend_x += name.len() as f32 * 6.0 + 14.0;
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & Style::new().fill(theme.text.clone()), label);
+    svg
+}
+
+/// The anaglyph of the shot in 3D, the view of the left eye in red and the one of the right eye
+/// in cyan, to see the depth of the arc with red and cyan glasses, red on the left eye. The
+/// camera is behind the shooter, to the left and above, looking at the middle of the shot, and
+/// sees the floor, the rim and the backboard of the basket at basket_pos (x, y, z) too. The
+/// background is white and the two colors are multiplied where they overlap. Only the size, the
+/// margin and the simplification of the options are used.
+pub fn plot_anaglyph_svg(trajectory_3d: & Trajectory3D, basket_pos: (f64, f64, f64),
+                         options: & SvgPlotOptions, label: & str) -> SVG {
+    let mut svg = SVG::new(options.width, options.height, Some(Color::White));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+    svg.add_style("polyline, polygon, line { mix-blend-mode: multiply; }");
+
+    let samples = & trajectory_3d.1;
+    let (basket_pos_x, basket_pos_y, basket_pos_z) = basket_pos;
+    let (pos_0_x, _pos_0_y, pos_0_z) = samples.first().map_or((0.0, 0.0, 0.0), |(_t, pos, _flag_enter_instant)| *pos);
+
+    // The lines of the scene in 3D, the floor grid every meter, the rim, the backboard and the arc.
+    let (x_min, x_max) = (f64::min(pos_0_x, basket_pos_x).floor() - 1.0, basket_backboard_x(basket_pos_x).ceil() + 1.0);
+    let (z_min, z_max) = (f64::min(pos_0_z, basket_pos_z).floor() - 2.0, f64::max(pos_0_z, basket_pos_z).ceil() + 2.0);
+    let mut grid_lines: Vec<Vec<Point3D>> = Vec::new();
+    for i in 0..=(x_max - x_min) as usize {
+        grid_lines.push(vec![(x_min + i as f64, 0.0, z_min), (x_min + i as f64, 0.0, z_max)]);
+    }
+    for i in 0..=(z_max - z_min) as usize {
+        grid_lines.push(vec![(x_min, 0.0, z_min + i as f64), (x_max, 0.0, z_min + i as f64)]);
+    }
+    let rim: Vec<Point3D> = (0..=ANAGLYPH_RIM_POINTS)
+        .map(|i| {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / ANAGLYPH_RIM_POINTS as f64;
+            (basket_pos_x + RIM_DIAMETER / 2.0 * f64::cos(angle), basket_pos_y, basket_pos_z + RIM_DIAMETER / 2.0 * f64::sin(angle))
+        })
+        .collect();
+    let backboard_x = basket_backboard_x(basket_pos_x);
+    let (board_bottom, board_top) = (basket_pos_y - BACKBOARD_BELOW_RIM, basket_pos_y - BACKBOARD_BELOW_RIM + BACKBOARD_HEIGHT);
+    let (board_left, board_right) = (basket_pos_z - BACKBOARD_WIDTH / 2.0, basket_pos_z + BACKBOARD_WIDTH / 2.0);
+    let backboard = vec![(backboard_x, board_bottom, board_left), (backboard_x, board_top, board_left),
+                         (backboard_x, board_top, board_right), (backboard_x, board_bottom, board_right),
+                         (backboard_x, board_bottom, board_left)];
+    let arc: Vec<Point3D> = samples.iter().map(|(_t, pos, _flag_enter_instant)| *pos).collect();
+
+    // The camera, a distance from the middle of the shot proportional to its length, with the
+    // forward, the right and the up directions of the view. The eyes are on the right direction.
+    let target = ((pos_0_x + basket_pos_x) / 2.0, basket_pos_y / 2.0, (pos_0_z + basket_pos_z) / 2.0);
+    let shot_length = f64::max(euclidean_distance(pos_0_x, 0.0, pos_0_z, basket_pos_x, 0.0, basket_pos_z), 1.0);
+    let camera = (target.0 + ANAGLYPH_CAMERA_OFFSET.0 * shot_length, target.1 + ANAGLYPH_CAMERA_OFFSET.1 * shot_length,
+                  target.2 + ANAGLYPH_CAMERA_OFFSET.2 * shot_length);
+    let forward = normalize(sub(target, camera));
+    let right = normalize(cross(forward, (0.0, 1.0, 0.0)));
+    let up = cross(right, forward);
+    // The eyes look in parallel, each image is shifted to put the target on the screen, the
+    // things behind it are seen behind the screen and the ones in front of it in front.
+    let project = |point: Point3D, eye_offset: f64| -> (f64, f64) {
+        let eye = (camera.0 + right.0 * eye_offset, camera.1 + right.1 * eye_offset, camera.2 + right.2 * eye_offset);
+        let to_point = sub(point, eye);
+        let to_target = sub(target, eye);
+        let depth = f64::max(dot(to_point, forward), 1e-3);
+        (dot(to_point, right) / depth - dot(to_target, right) / dot(to_target, forward), dot(to_point, up) / depth)
+    };
+    let eyes = [(-ANAGLYPH_EYE_SEPARATION / 2.0, Color::Rgb(255, 0, 0)), (ANAGLYPH_EYE_SEPARATION / 2.0, Color::Rgb(0, 255, 255))];
+
+    // The same scale for the two eyes, to fit all the lines.
+    let mut lines = grid_lines;
+    lines.push(backboard);
+    lines.push(rim);
+    lines.push(arc);
+    let (mut u_min, mut u_max, mut v_min, mut v_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+    for (eye_offset, _color) in & eyes {
+        for (u, v) in lines.iter().flatten().map(|point| project(*point, *eye_offset)) {
+            (u_min, u_max, v_min, v_max) = (f64::min(u_min, u), f64::max(u_max, u), f64::min(v_min, v), f64::max(v_max, v));
+        }
+    }
+    let margin = f64::min(options.margin as f64, f64::min(options.width as f64, options.height as f64) / 4.0);
+    let (plot_width, plot_height) = (options.width as f64 - 2.0 * margin, options.height as f64 - 2.0 * margin - 20.0);
+    let scale_factor = f64::min(plot_width / f64::max(u_max - u_min, 1e-9), plot_height / f64::max(v_max - v_min, 1e-9));
+    let (center_u, center_v) = ((u_min + u_max) / 2.0, (v_min + v_max) / 2.0);
+    let to_svg = |(u, v): (f64, f64)| -> (f32, f32) {
+        ((options.width as f64 / 2.0 + (u - center_u) * scale_factor) as f32,
+         (options.height as f64 / 2.0 + 10.0 - (v - center_v) * scale_factor) as f32)
+    };
+
+    let num_lines = lines.len();
+    for (eye_offset, color) in & eyes {
+        for (i, line) in lines.iter().enumerate() {
+            let points: Vec<(f32, f32)> = line.iter().map(|point| to_svg(project(*point, *eye_offset))).collect();
+            // The arc is the last line, the thickest one.
+            let stroke_width = if i == num_lines - 1 {2.0} else {1.0};
+            svg.add_polyline(& simplify_polyline(&points, options.simplify_tolerance),
+                             & Style::new().stroke(color.clone()).stroke_width(stroke_width));
+        }
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & Style::new().fill(Color::Black), label);
+    svg
+}
+
+type Point3D = (f64, f64, f64);
+
+fn sub(a: Point3D, b: Point3D) -> Point3D {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn dot(a: Point3D, b: Point3D) -> f64 {
+    a.0 * b.0 + a.1 * b.1 + a.2 * b.2
+}
+
+fn cross(a: Point3D, b: Point3D) -> Point3D {
+    (a.1 * b.2 - a.2 * b.1, a.2 * b.0 - a.0 * b.2, a.0 * b.1 - a.1 * b.0)
+}
+
+fn normalize(a: Point3D) -> Point3D {
+    let length = f64::max(dot(a, a).sqrt(), 1e-12);
+    (a.0 / length, a.1 / length, a.2 / length)
+}
+
+/// The chart of the make rate in percent against the distance to the basket, from the points
+/// (distance, rate). The options are used like in plot_phase_svg().
+pub fn plot_make_rate_svg(points: & [(f64, f64)], options: & SvgPlotOptions, label: & str) -> SVG {
+    let theme = & options.theme;
+    let mut svg = SVG::new(options.width, options.height, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+
+    let distance_max = points.iter().fold(0.0_f64, |distance_max, (distance, _rate)| f64::max(distance_max, *distance));
+    let frame = PlotFrame::new(options, (0.0, distance_max), (0.0, 100.0));
+    frame.add_axes(& mut svg, theme, "distance (m)", "made (%)");
+
+    let line_points: Vec<(f32, f32)> = points.iter()
+        .map(|(distance, rate)| (frame.to_svg_x(*distance) as f32, frame.to_svg_y(*rate) as f32))
+        .collect();
+    svg.add_polyline(&line_points, & Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5));
+    let marker_style = Style::new().fill(theme.score_marker.clone());
+    for ((distance, rate), (x, y)) in points.iter().zip(& line_points) {
+        svg.add_circle_with_title(*x, *y, 3.0, & marker_style, &format!("{:0.1} m: {:0.1}%", distance, rate));
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & Style::new().fill(theme.text.clone()), label);
+    svg
+}
+
+/// The heatmap of the speed that scores from each cell of the half court, seen from above with
+/// the basket at basket_pos (x, z) at the top, over the lines of the court and the three-point
+/// line of the league. The slowest speed is dark and the fastest is yellow, the cells without a
+/// speed aren't drawn. The height of the figure follows the court, only the width, the margin,
+/// the precision and the theme of the options are used.
+pub fn plot_speed_map_svg(cells: & [SpeedCell], cell_size: f64, basket_pos: (f64, f64), league: League,
+                          options: & SvgPlotOptions, label: & str) -> SVG {
+    let theme = & options.theme;
+    let (basket_x, basket_z) = basket_pos;
+    let margin = options.margin as f64;
+    // Room for the label above the court and for the legend below it.
+    let scale_factor = (options.width as f64 - 2.0 * margin) / COURT_WIDTH;
+    let (top, legend_height) = (margin + 20.0, 40.0);
+    let svg_height = top + HALF_COURT_LENGTH * scale_factor + legend_height + margin;
+    let mut svg = SVG::new(options.width, svg_height as f32, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+    let baseline_x = basket_x + BASKET_FROM_BASELINE;
+    let to_svg = |(x, z): (f64, f64)| -> (f32, f32) {
+        ((margin + (z - basket_z + COURT_WIDTH / 2.0) * scale_factor) as f32, (top + (baseline_x - x) * scale_factor) as f32)
+    };
+
+    let (v_0_min, v_0_max) = cells.iter().filter_map(|cell| cell.v_0)
+        .fold((f64::MAX, f64::MIN), |(v_0_min, v_0_max), v_0| (f64::min(v_0_min, v_0), f64::max(v_0_max, v_0)));
+    let cell_svg_size = (cell_size * scale_factor) as f32;
+    for cell in cells {
+        if let Some(v_0) = cell.v_0 {
+            let (x, y) = to_svg((cell.x + cell_size / 2.0, cell.z - cell_size / 2.0));
+            let color = palette::sequential((v_0 - v_0_min) / f64::max(v_0_max - v_0_min, 1e-9));
+            // A bit larger than the cell, without gaps between the cells.
+            svg.add_rect(x, y, cell_svg_size + 0.5, cell_svg_size + 0.5, & Style::new().fill(color));
+        }
+    }
+
+    // The lines of the court, the three-point line, the key and the basket.
+    let line_style = Style::new().stroke(theme.text.clone()).stroke_width(1.5);
+    let (court_left, court_top) = to_svg((baseline_x, basket_z - COURT_WIDTH / 2.0));
+    let (court_right, court_bottom) = to_svg((baseline_x - HALF_COURT_LENGTH, basket_z + COURT_WIDTH / 2.0));
+    svg.add_polygon(&[(court_left, court_top), (court_right, court_top), (court_right, court_bottom), (court_left, court_bottom)],
+                    & line_style);
+    let three_point: Vec<(f32, f32)> = three_point_line(league, basket_x, basket_z).into_iter().map(to_svg).collect();
+    svg.add_polyline(&three_point, & line_style);
+    let free_throw_x = basket_x - FREE_THROW_DISTANCE;
+    let key: Vec<(f32, f32)> = [(baseline_x, basket_z - KEY_WIDTH / 2.0), (free_throw_x, basket_z - KEY_WIDTH / 2.0),
+                                (free_throw_x, basket_z + KEY_WIDTH / 2.0), (baseline_x, basket_z + KEY_WIDTH / 2.0)]
+        .into_iter().map(to_svg).collect();
+    svg.add_polyline(&key, & line_style);
+    let (basket_svg_x, basket_svg_y) = to_svg((basket_x, basket_z));
+    svg.add_circle(basket_svg_x, basket_svg_y, (RIM_DIAMETER / 2.0 * scale_factor) as f32,
+                   & Style::new().stroke(theme.basket.clone()).stroke_width(2.0));
+
+    // The legend, the colors from the slowest to the fastest speed.
+    if v_0_min <= v_0_max {
+        let text_style = Style::new().fill(theme.text.clone());
+        let (legend_y, legend_width) = (court_bottom + 12.0, (options.width as f64 - 2.0 * margin - 120.0) as f32);
+        const LEGEND_STEPS: usize = 40;
+        for i in 0..LEGEND_STEPS {
+            let x = margin as f32 + 60.0 + legend_width * i as f32 / LEGEND_STEPS as f32;
+            svg.add_rect(x, legend_y, legend_width / LEGEND_STEPS as f32 + 0.5, 12.0,
+                         & Style::new().fill(palette::sequential(i as f64 / (LEGEND_STEPS - 1) as f64)));
+        }
+        svg.add_text(margin as f32, legend_y + 10.0, 11.0, & text_style, &format!("{:.*} m/s", options.precision, v_0_min));
+        svg.add_text(margin as f32 + 66.0 + legend_width, legend_y + 10.0, 11.0, & text_style, &format!("{:.*} m/s", options.precision, v_0_max));
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & Style::new().fill(theme.text.clone()), label);
+    svg
+}
+
+/// The small multiples of a sweep, a grid of small figures, one for each trajectory with its
+/// caption and if it entered the basket at basket_pos (x, y). All the panels have the same
+/// scale, so the arcs can be compared side by side. The height of the figure follows the rows,
+/// only the width, the margin, the simplification, the precision and the theme of the options
+/// are used.
+pub fn plot_small_multiples_svg(trajectories: & [Trajectory], captions: & [String], basket_pos: (f64, f64),
+                                options: & SvgPlotOptions, label: & str) -> SVG {
+    let theme = & options.theme;
+    let num_cols = usize::max(f64::ceil(f64::sqrt(trajectories.len() as f64)) as usize, 1);
+    let num_rows = trajectories.len().div_ceil(num_cols);
+
+    // The bounds of the scene, the same in all the panels, with the backboard.
+    let (basket_pos_x, basket_pos_y) = basket_pos;
+    let backboard_x = basket_backboard_x(basket_pos_x);
+    let backboard_top = basket_pos_y - BACKBOARD_BELOW_RIM + BACKBOARD_HEIGHT;
+    let (mut x_min, mut x_max, mut y_max) = (f64::min(basket_pos_x, backboard_x), f64::max(basket_pos_x, backboard_x), backboard_top);
+    for (_t, (x, y), _flag_enter_instant) in trajectories.iter().flat_map(|trajectory_2d| & trajectory_2d.1) {
+        x_min = f64::min(x_min, *x);
+        x_max = f64::max(x_max, *x);
+        y_max = f64::max(y_max, *y);
+    }
+    // Room for the label above the grid and for the caption above each panel.
+    let margin = options.margin as f64;
+    let (top, caption_height, gap) = (margin + 20.0, 30.0, 10.0);
+    let panel_width = (options.width as f64 - 2.0 * margin - gap * (num_cols - 1) as f64) / num_cols as f64;
+    let scale_factor = panel_width / f64::max(x_max - x_min, 1e-9);
+    let panel_height = caption_height + y_max * scale_factor;
+    let svg_height = top + num_rows as f64 * (panel_height + gap) - gap + margin;
+    let mut svg = SVG::new(options.width, svg_height as f32, Some(theme.background.clone()));
+    svg.set_precision(options.precision);
+    svg.set_output_mode(options.output_mode);
+    svg.set_title(label);
+
+    let text_style = Style::new().fill(theme.text.clone());
+    let floor_style = Style::new().stroke(theme.text.clone()).stroke_width(1.0);
+    let backboard_style = Style::new().stroke(theme.basket.clone()).stroke_width(2.0);
+    let basket_style = Style::new().fill(theme.basket.clone()).stroke(theme.basket.clone()).stroke_width(1.0);
+    let trajectory_style = Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5);
+    for (i, (trajectory_2d, caption)) in trajectories.iter().zip(captions).enumerate() {
+        let left = margin + (i % num_cols) as f64 * (panel_width + gap);
+        let panel_top = top + (i / num_cols) as f64 * (panel_height + gap);
+        let floor_svg_y = panel_top + panel_height;
+        let to_svg = |x: f64, y: f64| ((left + (x - x_min) * scale_factor) as f32, (floor_svg_y - y * scale_factor) as f32);
+
+        let (result, result_color) = if trajectory_2d.0 {("made", theme.score_marker.clone())} else {("missed", theme.text.clone())};
+        svg.add_text(left as f32, (panel_top + 12.0) as f32, 11.0, & text_style, caption);
+        svg.add_text(left as f32, (panel_top + 25.0) as f32, 11.0, & Style::new().fill(result_color), result);
+        let (floor_x_0, floor_y) = to_svg(x_min, 0.0);
+        svg.add_line(floor_x_0, floor_y, floor_x_0 + panel_width as f32, floor_y, & floor_style);
+        let (board_x, board_bottom) = to_svg(backboard_x, basket_pos_y - BACKBOARD_BELOW_RIM);
+        let (_, board_top) = to_svg(backboard_x, backboard_top);
+        svg.add_line(board_x, board_bottom, board_x, board_top, & backboard_style);
+        let (rim_x_0, rim_y) = to_svg(basket_pos_x - RIM_DIAMETER / 2.0, basket_pos_y);
+        svg.add_rect(rim_x_0, rim_y - 1.0, (RIM_DIAMETER * scale_factor) as f32, 2.0, & basket_style);
+
+        let samples = & trajectory_2d.1;
+        let points: Vec<(f32, f32)> = samples.iter().map(|(_t, (x, y), _flag_enter_instant)| to_svg(*x, *y)).collect();
+        svg.add_polyline(& simplify_polyline(&points, options.simplify_tolerance), & trajectory_style);
+        if let Some(i_enter) = samples.iter().position(|(_t, _pos, flag_enter_instant)| *flag_enter_instant) {
+            let (x, y) = points[i_enter];
+            svg.add_circle_with_title(x, y, 2.5, & Style::new().fill(theme.score_marker.clone()),
+                                      &format!("{}, entered the basket at t: {:.*} s", caption, options.precision, samples[i_enter].0));
+        }
+    }
+
+    svg.add_text(8.0, 16.0, 12.0, & text_style, label);
+    svg
+}
+
+// The index of a ball, the points and the fractions of the time of its animation, and its color.
+type BallAnimation = (usize, Vec<(f32, f32)>, Vec<f64>, String);
+
+// (t, kinetic, potential) of an instant, in s and J.
+type EnergyPoint = (f64, f64, f64);
+
+// The area of a plot with two axes, each with its own scale, and the conversions from the
+// values to the SVG coordinates. It leaves room on the left and at the bottom for the numbers.
+struct PlotFrame {
+    left: f64,
+    right: f64,
+    top: f64,
+    bottom: f64,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+    // Decimal places of the numbers at the ends of the axes.
+    precision: usize,
+}
+
+impl PlotFrame {
+    fn new(options: & SvgPlotOptions, x_range: (f64, f64), y_range: (f64, f64)) -> PlotFrame {
+        let margin = f64::min(options.margin as f64, f64::min(options.width as f64, options.height as f64) / 4.0);
+        PlotFrame {
+            left: margin + 40.0,
+            right: options.width as f64 - margin,
+            top: margin + 20.0,
+            bottom: options.height as f64 - margin - 20.0,
+            x_range,
+            y_range,
+            precision: options.precision,
+        }
+    }
+
+    fn to_svg_x(& self, x: f64) -> f64 {
+        self.left + (x - self.x_range.0) * (self.right - self.left) / f64::max(self.x_range.1 - self.x_range.0, 1e-9)
+    }
+
+    fn to_svg_y(& self, y: f64) -> f64 {
+        self.bottom - (y - self.y_range.0) * (self.bottom - self.top) / f64::max(self.y_range.1 - self.y_range.0, 1e-9)
+    }
+
+    // The axes along the bottom and the left side, with their labels and the numbers at the ends.
+    fn add_axes(& self, svg: & mut SVG, theme: & Theme, x_label: & str, y_label: & str) {
+        let axis_style = Style::new().stroke(theme.text.clone()).stroke_width(1.0);
+        let text_style = Style::new().fill(theme.text.clone());
+        let (left, right, top, bottom) = (self.left as f32, self.right as f32, self.top as f32, self.bottom as f32);
+        svg.add_line(left, bottom, right, bottom, & axis_style);
+        svg.add_line(left, top, left, bottom, & axis_style);
+        svg.add_text((left + right) / 2.0 - 15.0, bottom + 16.0, 11.0, & text_style, x_label);
+        svg.add_text(left - 4.0, bottom + 16.0, 10.0, & text_style, &format!("{:.*}", self.precision, self.x_range.0));
+        svg.add_text(right - 24.0, bottom + 16.0, 10.0, & text_style, &format!("{:.*}", self.precision, self.x_range.1));
+        svg.add_text(left + 6.0, top - 6.0, 11.0, & text_style, y_label);
+        svg.add_text(left - 40.0, top + 4.0, 10.0, & text_style, &format!("{:.*}", self.precision, self.y_range.1));
+        svg.add_text(left - 40.0, bottom + 4.0, 10.0, & text_style, &format!("{:.*}", self.precision, self.y_range.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::ScenarioFile;
+
+    #[test]
+    fn labels_with_the_precision() {
+        let trajectory_2d = ScenarioFile::default().simulate_2d();
+        let options = SvgPlotOptions { precision: 3, ..SvgPlotOptions::default() };
+        let svg_str = plot_trajectory_svg(& trajectory_2d, Some((8.0, 3.05)), & options, "Shot").to_file_string();
+        assert!(svg_str.contains("<title>t: 0.000 s, x: 0.000 m, y: 1.500 m, speed: 10.000 m/s</title>"), "{}", svg_str);
+        // The numbers at the ends of the axes.
+        let svg_str = plot_phase_svg(& trajectory_2d, & options, "Phase").to_file_string();
+        assert!(svg_str.contains(">0.000</text>"), "{}", svg_str);
+        let options = SvgPlotOptions { precision: 1, ..options };
+        let svg_str = plot_phase_svg(& trajectory_2d, & options, "Phase").to_file_string();
+        assert!(svg_str.contains(">0.0</text>") && !svg_str.contains(">0.00</text>"), "{}", svg_str);
+    }
+
+    // The tags are balanced, and the text and the values of the attributes have no raw markup:
+    // no "<", and "&" only in the references like "&amp;".
+    fn assert_well_formed(svg_str: & str) {
+        let check_text = |text: & str| {
+            assert!(!text.contains('<'), "Raw < in {:?}", text);
+            for (i, _) in text.match_indices('&') {
+                let reference = & text[i..];
+                let len = reference.find(';').unwrap_or_else(|| panic!("Raw & in {:?}", text));
+                assert!(reference[1..len].chars().all(|ch| ch.is_ascii_alphanumeric() || ch == '#'), "Raw & in {:?}", text);
+            }
+        };
+        let mut open_tags: Vec<& str> = Vec::new();
+        let mut rest = svg_str;
+        while let Some(start) = rest.find('<') {
+            check_text(& rest[..start]);
+            rest = & rest[start..];
+            if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+                rest = & cdata[cdata.find("]]>").expect("Unclosed CDATA") + 3..];
+                continue;
+            }
+            // The end of the tag is the first ">" out of the quotes.
+            let mut flag_in_quotes = false;
+            let end = rest.char_indices().find(|& (_, ch)| {
+                if ch == '"' { flag_in_quotes = !flag_in_quotes; }
+                ch == '>' && !flag_in_quotes
+            }).expect("Unclosed tag").0;
+            let tag = & rest[1..end];
+            rest = & rest[end + 1..];
+            for (i, value) in tag.split('"').enumerate() {
+                if i % 2 == 1 {
+                    check_text(value);
+                }
+            }
+            if let Some(name) = tag.strip_prefix('/') {
+                assert_eq!(open_tags.pop(), Some(name.trim()), "Unbalanced </{}>", name);
+            } else if !tag.ends_with('/') && !tag.starts_with('?') && !tag.starts_with('!') {
+                open_tags.push(tag.split_whitespace().next().unwrap());
+            }
+        }
+        check_text(rest);
+        assert!(open_tags.is_empty(), "Unclosed {:?}", open_tags);
+    }
+
+    #[test]
+    fn name_with_markup_is_escaped() {
+        let trajectory_2d = ScenarioFile::default().simulate_2d();
+        let svg_str = plot_trajectory_svg(& trajectory_2d, Some((8.0, 3.05)), & SvgPlotOptions::default(),
+                                          "Shot <b> & \"quoted\"").to_file_string();
+        assert!(svg_str.contains("<title>Shot &lt;b&gt; &amp; \"quoted\"</title>"), "{}", svg_str);
+        assert!(svg_str.contains(">Shot &lt;b&gt; &amp; \"quoted\"</text>"), "{}", svg_str);
+        assert_well_formed(& svg_str);
+    }
+
+    #[test]
+    fn smil_and_css_animations() {
+        let trajectory_2d = ScenarioFile::default().simulate_2d();
+        let render = |animation_mode: AnimationMode| {
+            let options = SvgPlotOptions { animation_mode, ..SvgPlotOptions::default() };
+            plot_trajectory_svg(& trajectory_2d, Some((8.0, 3.05)), & options, "Shot").to_file_string()
+        };
+        let smil_str = render(AnimationMode::Smil);
+        assert!(smil_str.contains("<animateMotion") && !smil_str.contains("@keyframes"), "{}", smil_str);
+        assert_well_formed(& smil_str);
+        let css_str = render(AnimationMode::Css);
+        assert!(css_str.contains("@keyframes ball_flight") && !css_str.contains("<animateMotion"), "{}", css_str);
+        assert!(css_str.contains("#circle { animation: ball_flight"), "{}", css_str);
+        assert_well_formed(& css_str);
+    }
+}
@@ -0,0 +1,108 @@
+//! The ball after a made basket, so the animation doesn't end with the ball in the ring. With
+//! simulation.post_score_sec, or --post-score, the ball drops from its entry through the net and
+//! bounces on the floor, until it's at rest, leaves the court or the seconds end.
+//!
+//! The net holds the ball inside the ring, the part of its speed against the net is lost, and
+//! slows it with a drag proportional to its speed, from the ring down to the bottom of the net,
+//! NET_LENGTH below it. Under the net the ball falls freely and bounces on
+//! the floor with the restitution of the rebounds, until its bounces are too low to see and it
+//! rolls.
+
+use crate::rebound::{pressure_restitution, FLOOR_RESTITUTION};
+use crate::scenario::ScenarioFile;
+use crate::termination::{court_bounds, is_bounce_over, is_out_of_court, StopReason, REST_SPEED};
+use crate::trajectory::{Trajectory, GRAVITY, RIM_DIAMETER};
+use crate::units::degrees_to_radians;
+
+pub const NET_LENGTH: f64 = 0.45;  // m - From the ring to the bottom of the net, FIBA 0.40 to 0.45 m.
+pub const NET_DAMPING: f64 = 8.0;  // 1/s - The drag of the net for each m/s of the speed of the ball.
+const POST_SCORE_DT: f64 = 0.0005; // s - Step of the integration.
+
+/// The drop after the basket.
+#[derive(Clone, Debug)]
+pub struct PostScore {
+    /// The instant the ball leaves the bottom of the net, None if it doesn't in the seconds.
+    pub t_net_exit: Option<f64>,
+    /// The samples (t, (x, y)) after the entry, at the rate of the samples of the shot.
+    pub path: Vec<(f64, (f64, f64))>,
+    pub stop_reason: StopReason,
+}
+
+/// Drops the ball of the made shot of the scenario through the net, from the first sample of the
+/// trajectory that enters the basket, for at most the seconds. None if the shot missed.
+pub fn simulate_post_score(scenario: & ScenarioFile, trajectory_2d: & Trajectory, max_sec: f64) -> Option<PostScore> {
+    let samples = & trajectory_2d.1;
+    let i_entry = samples.iter().position(|(_, _, flag_enter_instant)| *flag_enter_instant)?;
+    let (t_entry, (mut x, mut y), _) = samples[i_entry];
+    let sample_sec = match samples.get(..2) {
+        Some([(t_0, _, _), (t_1, _, _)]) if t_1 > t_0 => t_1 - t_0,
+        _ => scenario.simulation.simulation_sec / scenario.simulation.num_steps as f64,
+    };
+    let teta_0 = degrees_to_radians(scenario.throw.teta_0_deg);
+    let mut v_x = scenario.throw.v_0 * f64::cos(teta_0);
+    let mut v_y = scenario.throw.v_0 * f64::sin(teta_0) - GRAVITY * t_entry;
+
+    let radius = scenario.ball.radius;
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    // The center of the ball inside the ring moves this far from the center of the ring.
+    let max_offset = f64::max(RIM_DIAMETER / 2.0 - radius, 0.0);
+    let floor_restitution = pressure_restitution(FLOOR_RESTITUTION, scenario.ball.pressure_bar);
+    let court_bounds = court_bounds(scenario);
+    let mut path: Vec<(f64, (f64, f64))> = Vec::new();
+    let mut t_net_exit = None;
+    let mut flag_rolling = false;
+    let mut stop_reason = StopReason::TimeLimit;
+    let mut t_next_sample = t_entry + sample_sec;
+    let num_steps = f64::ceil(max_sec / POST_SCORE_DT) as usize;
+
+    for step in 1..=num_steps {
+        let t = t_entry + step as f64 * POST_SCORE_DT;
+        let flag_in_net = y < basket_pos_y && y > basket_pos_y - NET_LENGTH;
+        let (drag_x, drag_y) = if flag_in_net { (NET_DAMPING * v_x, NET_DAMPING * v_y) } else { (0.0, 0.0) };
+        x += v_x * POST_SCORE_DT;
+        v_x -= drag_x * POST_SCORE_DT;
+        if t_net_exit.is_none() && f64::abs(x - basket_pos_x) > max_offset {
+            x = basket_pos_x + f64::clamp(x - basket_pos_x, -max_offset, max_offset);
+            v_x = 0.0;
+        }
+        if !flag_rolling {
+            y += v_y * POST_SCORE_DT;
+            v_y -= (GRAVITY + drag_y) * POST_SCORE_DT;
+        }
+        if t_net_exit.is_none() && y <= basket_pos_y - NET_LENGTH {
+            t_net_exit = Some(t);
+        }
+        if y < radius && v_y < 0.0 {
+            v_y *= -floor_restitution;
+            y = radius;
+            if is_bounce_over(y, v_y, radius) {
+                v_y = 0.0;
+                flag_rolling = true;
+            }
+        }
+
+        let reason = if flag_rolling && f64::abs(v_x) < REST_SPEED { Some(StopReason::AtRest) }
+                     else if is_out_of_court(x, court_bounds) { Some(StopReason::OutOfCourt) }
+                     else { None };
+        if let Some(reason) = reason {
+            path.push((t, (x, y)));
+            stop_reason = reason;
+            break;
+        }
+        if t >= t_next_sample {
+            path.push((t, (x, y)));
+            t_next_sample += sample_sec;
+        }
+    }
+    Some(PostScore { t_net_exit, path, stop_reason })
+}
+
+/// The trajectory of the animation, the one of the shot up to the entry followed by the drop.
+pub fn continue_trajectory(trajectory_2d: & Trajectory, post_score: & PostScore) -> Trajectory {
+    let num_before = trajectory_2d.1.iter().position(|(_, _, flag_enter_instant)| *flag_enter_instant)
+        .map_or(trajectory_2d.1.len(), |i_entry| i_entry + 1);
+    let samples = trajectory_2d.1[..num_before].iter().copied()
+        .chain(post_score.path.iter().map(|(t, position)| (*t, *position, false)))
+        .collect();
+    (trajectory_2d.0, samples)
+}
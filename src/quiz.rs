@@ -0,0 +1,108 @@
+//! The quiz, guess first and then see the simulation. Each round is a random shot close to
+//! the one that scores, the player says if it goes into the basket and, optionally, how high
+//! it goes. One point for the right answer and one for a height within QUIZ_APEX_TOLERANCE.
+
+use std::io::{self, BufRead, Write};
+
+use crate::i18n::{fill, Messages, Num};
+use crate::rng::Rng;
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{required_speed, ShotSummary};
+
+// Enough steps for the ball to move only a few cm between instants.
+const QUIZ_NUM_STEPS: u32 = 600;
+const QUIZ_APEX_TOLERANCE: f64 = 0.25;  // m
+
+/// The points of the rounds played, the quiz ends early at the end of the input.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct QuizScore {
+    pub num_rounds: usize,
+    pub points: usize,
+    pub max_points: usize,
+}
+
+/// A random shot, from up to 4 m in front of the default throw position, with a speed
+/// within 2.5% of the one that goes through the center of the basket.
+pub fn random_quiz_scenario(rng: & mut Rng) -> ScenarioFile {
+    let mut scenario = ScenarioFile::default();
+    scenario.simulation.num_steps = QUIZ_NUM_STEPS;
+    scenario.throw.pos_0_x = rng.range(0.0, 4.0);
+    scenario.throw.teta_0_deg = rng.range(40.0, 60.0);
+    let v_0 = required_speed(scenario.throw.pos_0_x, scenario.throw.pos_0_y, scenario.throw.teta_0_deg.to_radians(),
+                             scenario.scene.basket_pos_x, scenario.scene.basket_pos_y)
+        .unwrap_or(scenario.throw.v_0);
+    scenario.throw.v_0 = v_0 * rng.range(0.975, 1.025);
+    scenario
+}
+
+/// Plays the rounds, reading the answers from input and writing the questions to output.
+pub fn run_quiz<R: BufRead, W: Write>(mut input: R, mut output: W, num_rounds: usize,
+                                      rng: & mut Rng, messages: & Messages) -> io::Result<QuizScore> {
+    let mut score = QuizScore::default();
+    'rounds: for round in 1..=num_rounds {
+        let scenario = random_quiz_scenario(rng);
+        let throw = & scenario.throw;
+        writeln!(output, "\n{}", fill(messages.quiz_round, &[& round, & num_rounds]))?;
+        writeln!(output, "{}", fill(messages.quiz_shot,
+                                    &[& format!("{:0.2}", Num(throw.pos_0_x)), & format!("{:0.2}", Num(throw.pos_0_y)),
+                                      & format!("{:0.2}", Num(throw.v_0)), & format!("{:0.1}", Num(throw.teta_0_deg)),
+                                      & format!("{:0.2}", Num(scenario.scene.basket_pos_x)),
+                                      & format!("{:0.2}", Num(scenario.scene.basket_pos_y))]))?;
+
+        // Make or miss, asked again until the answer is yes or no.
+        let flag_guess_scored = loop {
+            write!(output, "{}", messages.quiz_ask_scored)?;
+            output.flush()?;
+            let Some(answer) = read_answer(& mut input)? else { break 'rounds };
+            match answer.chars().next().map(|ch| ch.to_lowercase().to_string()) {
+                Some(ch) if ch == messages.quiz_yes => break true,
+                Some(ch) if ch == messages.quiz_no => break false,
+                _ => continue,
+            }
+        };
+        // The height is optional, an empty or invalid answer skips it.
+        write!(output, "{}", messages.quiz_ask_apex)?;
+        output.flush()?;
+        let Some(answer) = read_answer(& mut input)? else { break 'rounds };
+        let guess_apex = answer.replace(',', ".").parse::<f64>().ok().filter(|apex| apex.is_finite());
+
+        scenario.validate().map_err(io::Error::other)?;
+        let trajectory_2d = scenario.simulate_2d();
+        let Some(summary) = ShotSummary::new(& trajectory_2d, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y) else {
+            continue;
+        };
+        score.num_rounds += 1;
+        score.max_points += 1;
+        writeln!(output, "{}", fill(messages.quiz_reveal,
+                                    &[& if summary.flag_scored {messages.scored} else {messages.missed},
+                                      & format!("{:0.2}", Num(summary.apex_y))]))?;
+        if flag_guess_scored == summary.flag_scored {
+            score.points += 1;
+            writeln!(output, "{}", messages.quiz_right)?;
+        } else {
+            writeln!(output, "{}", messages.quiz_wrong)?;
+        }
+        if let Some(guess_apex) = guess_apex {
+            score.max_points += 1;
+            let apex_error = f64::abs(guess_apex - summary.apex_y);
+            if apex_error <= QUIZ_APEX_TOLERANCE {
+                score.points += 1;
+                writeln!(output, "{}", fill(messages.quiz_apex_close, &[& format!("{:0.2}", Num(apex_error))]))?;
+            } else {
+                writeln!(output, "{}", fill(messages.quiz_apex_far, &[& format!("{:0.2}", Num(apex_error))]))?;
+            }
+        }
+        writeln!(output, "{}", fill(messages.quiz_score, &[& score.points, & score.max_points]))?;
+    }
+    writeln!(output, "\n{}", fill(messages.quiz_final, &[& score.points, & score.max_points, & score.num_rounds]))?;
+    Ok(score)
+}
+
+// A line without the spaces, None at the end of the input.
+fn read_answer<R: BufRead>(input: & mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if input.read_line(& mut line)? == 0 {
+        return Ok(None);
+    }
+    Ok(Some(line.trim().to_string()))
+}
@@ -0,0 +1,35 @@
+//! Random shots for the practice problems, and to explore the solvers with many inputs. The
+//! distance, the release height and the angle are in the ranges of real shots, and the speed is
+//! close to the one that goes through the center of the basket, so some of them score.
+
+use crate::rng::Rng;
+use crate::scenario::ScenarioFile;
+use crate::trajectory::required_speed;
+use crate::units::degrees_to_radians;
+
+// The ranges of the shots.
+const DISTANCE_RANGE: (f64, f64) = (1.0, 9.0);       // m - From the basket, along the floor.
+const RELEASE_HEIGHT_RANGE: (f64, f64) = (1.6, 2.6); // m
+const ANGLE_RANGE: (f64, f64) = (30.0, 65.0);        // degrees
+const SPEED_ERROR_FRAC: f64 = 0.08;                  // ± 8% of the ideal speed.
+const SPEED_RANGE: (f64, f64) = (4.0, 14.0);         // m/s - When there's no ideal speed.
+// Enough steps for the ball to move only a few cm between instants.
+const RANDOM_NUM_STEPS: u32 = 600;
+
+/// A random shot at the basket of the default scenario, and the speed that goes through its
+/// center, None when no speed does at the angle of the shot.
+pub fn random_scenario(rng: & mut Rng) -> (ScenarioFile, Option<f64>) {
+    let mut scenario = ScenarioFile::default();
+    scenario.simulation.num_steps = RANDOM_NUM_STEPS;
+    let distance = rng.range(DISTANCE_RANGE.0, DISTANCE_RANGE.1);
+    scenario.throw.pos_0_x = scenario.scene.basket_pos_x - distance;
+    scenario.throw.pos_0_y = rng.range(RELEASE_HEIGHT_RANGE.0, RELEASE_HEIGHT_RANGE.1);
+    scenario.throw.teta_0_deg = rng.range(ANGLE_RANGE.0, ANGLE_RANGE.1);
+    let ideal_v_0 = required_speed(scenario.throw.pos_0_x, scenario.throw.pos_0_y, degrees_to_radians(scenario.throw.teta_0_deg),
+                                   scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    // The draw is made for every shot, so the sequence doesn't depend on the ideal speed.
+    let error = rng.range(-SPEED_ERROR_FRAC, SPEED_ERROR_FRAC);
+    let speed = rng.range(SPEED_RANGE.0, SPEED_RANGE.1);
+    scenario.throw.v_0 = ideal_v_0.map_or(speed, |v_0| v_0 * (1.0 + error));
+    (scenario, ideal_v_0)
+}
@@ -0,0 +1,287 @@
+//! The carom of a shot that misses, the second phase of the play. The ball is simulated from the
+//! release with its radius, it bounces off the rim, the backboard and the floor, and the rebound
+//! lands where it first touches the floor.
+//!
+//! In the plane of the shot the rim is two points, the front and the back of the ring, each one
+//! the circle of the section of the rod. The bounces lose energy with a coefficient of restitution,
+//! the part of the speed along the normal that the ball keeps. Between the bounces the energy of
+//! the ball, kinetic and potential from the ball resting on the floor, with its center at the
+//! height of its radius, doesn't change, each bounce loses a part of it.
+//!
+//! The restitutions are the ones of a ball at the nominal pressure. A softer ball loses more of
+//! its speed in a bounce and a harder one keeps more, the restitution scales with the pressure
+//! as (pressure / NOMINAL_BALL_PRESSURE)^RESTITUTION_PRESSURE_EXPONENT, a simple empirical
+//! model of the bounces of the balls at different pressures, up to MAX_RESTITUTION.
+
+use crate::i18n::Messages;
+use crate::scenario::ScenarioFile;
+use crate::termination::{court_bounds, is_bounce_over, is_out_of_court, StopReason, AUTO_STOP_MAX_SEC, REST_SPEED};
+use crate::trajectory::{basket_backboard_x, GRAVITY, BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, RIM_DIAMETER, RIM_TUBE_RADIUS};
+use crate::units::degrees_to_radians;
+
+pub const RIM_RESTITUTION: f64 = 0.6;
+pub const BACKBOARD_RESTITUTION: f64 = 0.7;
+pub const FLOOR_RESTITUTION: f64 = 0.8;     // From 1.8 m a ball bounces to about 1.2 m.
+pub const NOMINAL_BALL_PRESSURE: f64 = 0.55; // bar - The middle of the 0.48 to 0.62 bar of FIBA.
+pub const RESTITUTION_PRESSURE_EXPONENT: f64 = 0.3;
+pub const MAX_RESTITUTION: f64 = 0.95;
+const REBOUND_DT: f64 = 0.0005;             // s - Step of the integration.
+const REBOUND_SAMPLE_SEC: f64 = 0.01;       // s - Between the samples of the path.
+const REBOUND_MAX_SEC: f64 = 6.0;           // s
+// The path ends at this bounce on the floor.
+const REBOUND_NUM_FLOOR_BOUNCES: usize = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ContactKind {
+    FrontRim,
+    BackRim,
+    Backboard,
+    Floor,
+}
+
+impl ContactKind {
+    pub fn name(self, messages: & Messages) -> &'static str {
+        match self {
+            ContactKind::FrontRim => messages.contact_front_rim,
+            ContactKind::BackRim => messages.contact_back_rim,
+            ContactKind::Backboard => messages.contact_backboard,
+            ContactKind::Floor => messages.contact_floor,
+        }
+    }
+}
+
+/// A bounce of the ball, the instant and the position of the center of the ball, and the energy
+/// in J that the bounce took.
+#[derive(Clone, Copy, Debug)]
+pub struct Contact {
+    pub kind: ContactKind,
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+    pub energy_lost: f64,
+}
+
+/// The carom after the ball touched the rim or the backboard.
+#[derive(Clone, Debug)]
+pub struct Rebound {
+    /// The bounces in order, the first one on the rim or the backboard.
+    pub contacts: Vec<Contact>,
+    /// The ball fell through the ring after the bounces, it's a make.
+    pub flag_rolled_in: bool,
+    /// The samples (t, (x, y)) from the first bounce to the end.
+    pub path: Vec<(f64, (f64, f64))>,
+    /// The energy in J, kinetic and potential from the ball resting on the floor, at the release and at the end.
+    pub initial_energy: f64,
+    pub final_energy: f64,
+    /// Why the path ended, with simulation.auto_stop. None when it ends at the second bounce on
+    /// the floor.
+    pub stop_reason: Option<StopReason>,
+}
+
+impl Rebound {
+    /// The first bounce on the floor, where the rebound lands. None if the ball rolled in.
+    pub fn landing(& self) -> Option<& Contact> {
+        self.contacts.iter().find(|contact| contact.kind == ContactKind::Floor)
+    }
+}
+
+/// The restitution of a ball inflated to pressure_bar, from the one at the nominal pressure.
+pub fn pressure_restitution(restitution: f64, pressure_bar: f64) -> f64 {
+    f64::min(restitution * f64::powf(pressure_bar / NOMINAL_BALL_PRESSURE, RESTITUTION_PRESSURE_EXPONENT), MAX_RESTITUTION)
+}
+
+/// Simulates the shot of the scenario with the bounces. None when the ball doesn't touch the rim
+/// or the backboard before the floor, or it goes through the ring without touching them. With
+/// simulation.auto_stop the ball bounces until it rolls, and it ends at rest or out of the court.
+pub fn simulate_rebound(scenario: & ScenarioFile) -> Option<Rebound> {
+    let radius = scenario.ball.radius;
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let rims = [(ContactKind::FrontRim, basket_pos_x - RIM_DIAMETER / 2.0),
+                (ContactKind::BackRim, basket_pos_x + RIM_DIAMETER / 2.0)];
+    let backboard_x = basket_backboard_x(basket_pos_x);
+    let backboard_bottom = basket_pos_y - BACKBOARD_BELOW_RIM;
+    let backboard_top = backboard_bottom + BACKBOARD_HEIGHT;
+    let pressure_bar = scenario.ball.pressure_bar;
+    let (rim_restitution, backboard_restitution, floor_restitution) = (pressure_restitution(RIM_RESTITUTION, pressure_bar),
+        pressure_restitution(BACKBOARD_RESTITUTION, pressure_bar), pressure_restitution(FLOOR_RESTITUTION, pressure_bar));
+
+    let teta_0 = degrees_to_radians(scenario.throw.teta_0_deg);
+    let (mut x, mut y) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let (mut v_x, mut v_y) = (scenario.throw.v_0 * f64::cos(teta_0), scenario.throw.v_0 * f64::sin(teta_0));
+    let mut contacts: Vec<Contact> = Vec::new();
+    let mut path: Vec<(f64, (f64, f64))> = Vec::new();
+    let mut flag_rolled_in = false;
+    let mut t_next_sample = 0.0;
+    let flag_auto_stop = scenario.simulation.auto_stop;
+    let court_bounds = court_bounds(scenario);
+    let mut flag_rolling = false;
+    let mut stop_reason = flag_auto_stop.then_some(StopReason::TimeLimit);
+    let max_sec = if flag_auto_stop {AUTO_STOP_MAX_SEC} else {REBOUND_MAX_SEC};
+    let num_steps = f64::ceil(max_sec / REBOUND_DT) as usize;
+    let mass = scenario.ball.mass;
+    // The potential energy is 0 with the ball resting on the floor.
+    let energy = |y: f64, v_x: f64, v_y: f64| mass * ((1.0/2.0) * (v_x * v_x + v_y * v_y) + GRAVITY * (y - radius));
+    let initial_energy = energy(y, v_x, v_y);
+
+    for step in 1..=num_steps {
+        let t = step as f64 * REBOUND_DT;
+        let y_prev = y;
+        x += v_x * REBOUND_DT;
+        if !flag_rolling {
+            y += v_y * REBOUND_DT - (1.0/2.0) * GRAVITY * REBOUND_DT * REBOUND_DT;
+            v_y -= GRAVITY * REBOUND_DT;
+        }
+        let energy_before = energy(y, v_x, v_y);
+
+        let mut contact = None;
+        for (kind, rim_x) in rims {
+            let (d_x, d_y) = (x - rim_x, y - basket_pos_y);
+            let dist = f64::hypot(d_x, d_y);
+            let v_normal = (v_x * d_x + v_y * d_y) / dist;
+            if dist < radius + RIM_TUBE_RADIUS && v_normal < 0.0 {
+                // The speed along the normal is reflected, the ball is moved out of the rod.
+                let (n_x, n_y) = (d_x / dist, d_y / dist);
+                v_x -= (1.0 + rim_restitution) * v_normal * n_x;
+                v_y -= (1.0 + rim_restitution) * v_normal * n_y;
+                x = rim_x + n_x * (radius + RIM_TUBE_RADIUS);
+                y = basket_pos_y + n_y * (radius + RIM_TUBE_RADIUS);
+                contact = Some(kind);
+            }
+        }
+        if x < backboard_x && x + radius > backboard_x && v_x > 0.0 && y >= backboard_bottom && y <= backboard_top {
+            v_x *= -backboard_restitution;
+            x = backboard_x - radius;
+            contact = Some(ContactKind::Backboard);
+        }
+        if y < radius && v_y < 0.0 {
+            v_y *= -floor_restitution;
+            y = radius;
+            contact = Some(ContactKind::Floor);
+            // The bounces that are too low to see end, the ball rolls on the floor.
+            if flag_auto_stop && is_bounce_over(y, v_y, radius) {
+                v_y = 0.0;
+                flag_rolling = true;
+            }
+        }
+        // Down through the ring, between the two sides of the rim.
+        if y_prev >= basket_pos_y && y < basket_pos_y && f64::abs(x - basket_pos_x) < RIM_DIAMETER / 2.0 {
+            flag_rolled_in = !contacts.is_empty();
+            path.push((t, (x, y)));
+            stop_reason = stop_reason.map(|_| StopReason::Scored);
+            break;
+        }
+
+        if let Some(kind) = contact {
+            // A shot that doesn't touch the basket isn't a rebound, it's an air ball.
+            if contacts.is_empty() && kind == ContactKind::Floor {
+                return None;
+            }
+            contacts.push(Contact { kind, t, x, y, energy_lost: energy_before - energy(y, v_x, v_y) });
+            path.push((t, (x, y)));
+            t_next_sample = t + REBOUND_SAMPLE_SEC;
+            if !flag_auto_stop && contacts.iter().filter(|contact| contact.kind == ContactKind::Floor).count() == REBOUND_NUM_FLOOR_BOUNCES {
+                break;
+            }
+        } else if !contacts.is_empty() && t >= t_next_sample {
+            path.push((t, (x, y)));
+            t_next_sample += REBOUND_SAMPLE_SEC;
+        }
+        if flag_auto_stop && !contacts.is_empty() {
+            let reason = if flag_rolling && f64::abs(v_x) < REST_SPEED { Some(StopReason::AtRest) }
+                         else if is_out_of_court(x, court_bounds) { Some(StopReason::OutOfCourt) }
+                         else { None };
+            if reason.is_some() {
+                path.push((t, (x, y)));
+                stop_reason = reason;
+                break;
+            }
+        }
+    }
+    if contacts.is_empty() {
+        return None;
+    }
+    Some(Rebound { contacts, flag_rolled_in, path, initial_energy, final_energy: energy(y, v_x, v_y), stop_reason })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::FREE_THROW_DISTANCE;
+    use crate::trajectory::required_speed;
+
+    // A free throw at teta_0_deg aimed at (aim_x, aim_y).
+    fn shot_at(aim_x: f64, aim_y: f64, teta_0_deg: f64, pressure_bar: f64) -> ScenarioFile {
+        let mut scenario = ScenarioFile::preset("free-throw").unwrap();
+        scenario.ball.pressure_bar = pressure_bar;
+        let throw = & mut scenario.throw;
+        throw.teta_0_deg = teta_0_deg;
+        throw.v_0 = required_speed(throw.pos_0_x, throw.pos_0_y, degrees_to_radians(teta_0_deg), aim_x, aim_y).unwrap();
+        scenario
+    }
+
+    // The highest point of the path after each bounce on the floor.
+    fn bounce_heights(rebound: & Rebound) -> Vec<f64> {
+        let floor_ts: Vec<f64> = rebound.contacts.iter().filter(|contact| contact.kind == ContactKind::Floor)
+            .map(|contact| contact.t).collect();
+        floor_ts.iter().enumerate().map(|(i, & t_start)| {
+            let t_end = floor_ts.get(i + 1).copied().unwrap_or(f64::INFINITY);
+            rebound.path.iter().filter(|(t, _)| *t >= t_start && *t < t_end).map(|(_, (_, y))| *y).fold(0.0, f64::max)
+        }).collect()
+    }
+
+    #[test]
+    fn front_rim_shot_lands_in_front() {
+        let rebound = simulate_rebound(& shot_at(FREE_THROW_DISTANCE - 0.3, 3.05, 45.0, NOMINAL_BALL_PRESSURE)).unwrap();
+        assert_eq!(rebound.contacts[0].kind, ContactKind::FrontRim);
+        assert!(!rebound.flag_rolled_in);
+        let landing = rebound.landing().unwrap();
+        assert!(landing.x < FREE_THROW_DISTANCE - RIM_DIAMETER / 2.0, "{}", landing.x);
+    }
+
+    #[test]
+    fn backboard_shot_comes_back() {
+        // Straight at the backboard, 50 cm above the rim.
+        let backboard_x = basket_backboard_x(FREE_THROW_DISTANCE);
+        let scenario = shot_at(backboard_x, 3.55, 45.0, NOMINAL_BALL_PRESSURE);
+        let rebound = simulate_rebound(& scenario).unwrap();
+        assert_eq!(rebound.contacts[0].kind, ContactKind::Backboard);
+        let landing = rebound.landing().unwrap();
+        assert!(landing.x < FREE_THROW_DISTANCE && landing.x > scenario.throw.pos_0_x, "{}", landing.x);
+    }
+
+    #[test]
+    fn floor_bounces_get_lower() {
+        let mut scenario = shot_at(basket_backboard_x(FREE_THROW_DISTANCE), 3.55, 45.0, NOMINAL_BALL_PRESSURE);
+        scenario.simulation.auto_stop = true;
+        let heights = bounce_heights(& simulate_rebound(& scenario).unwrap());
+        assert!(heights.len() > 3, "{:?}", heights);
+        assert!(heights.windows(2).all(|pair| pair[1] < pair[0]), "{:?}", heights);
+    }
+
+    #[test]
+    fn energy_balance() {
+        for (aim_x, aim_y) in [(FREE_THROW_DISTANCE - 0.3, 3.05), (basket_backboard_x(FREE_THROW_DISTANCE), 3.55)] {
+            let rebound = simulate_rebound(& shot_at(aim_x, aim_y, 45.0, NOMINAL_BALL_PRESSURE)).unwrap();
+            let energy_lost: f64 = rebound.contacts.iter().map(|contact| contact.energy_lost).sum();
+            let balance = rebound.initial_energy - rebound.final_energy - energy_lost;
+            assert!(balance.abs() < 1e-3 * rebound.initial_energy, "{}", balance);
+        }
+        // The potential energy is measured from the center of the ball at rest on the floor.
+        let scenario = shot_at(FREE_THROW_DISTANCE - 0.3, 3.05, 45.0, NOMINAL_BALL_PRESSURE);
+        let (ball, throw) = (& scenario.ball, & scenario.throw);
+        let expected = ball.mass * ((1.0/2.0) * throw.v_0 * throw.v_0 + GRAVITY * (throw.pos_0_y - ball.radius));
+        assert!((simulate_rebound(& scenario).unwrap().initial_energy - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn softer_ball_bounces_lower() {
+        // Off the front of the rim and onto the floor, the only difference is the pressure.
+        let aim_x = FREE_THROW_DISTANCE - 0.3;
+        let heights: Vec<f64> = [0.3, NOMINAL_BALL_PRESSURE, 0.8].iter().map(|& pressure_bar| {
+            let rebound = simulate_rebound(& shot_at(aim_x, 3.05, 45.0, pressure_bar)).unwrap();
+            assert_eq!(rebound.contacts[0].kind, ContactKind::FrontRim);
+            bounce_heights(& rebound)[0]
+        }).collect();
+        assert!(heights[0] < heights[1] && heights[1] < heights[2], "{:?}", heights);
+    }
+}
@@ -0,0 +1,297 @@
+//! The run file, the scenario and the full trajectory of a simulation, in JSON. The outputs, the
+//! SVG, the text plot and the reports, can be made again from it without simulating, and the
+//! file can be shared with the results.
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "scenario": { ... },
+//!   "scored": false,
+//!   "samples": [ { "t": 0.0, "x": 0.0, "y": 1.5, "entered": false }, ... ]
+//! }
+//! ```
+//!
+//! A long run can be saved as keyframes instead of the samples, the keyframe has the index of
+//! its sample and the velocity of the ball, and the samples between two keyframes are rebuilt on
+//! load with the cubic of the positions and the velocities at its ends, exact for a parabola. The
+//! keyframes are chosen so that each sample is rebuilt within the tolerance, and the samples
+//! where the ball entered the basket are always keyframes:
+//!
+//! ```text
+//! "keyframes": {
+//!   "num_samples": 1000,
+//!   "tolerance": 0.001,
+//!   "frames": [ { "i": 0, "t": 0.0, "x": 0.0, "y": 1.5, "vx": 7.07, "vy": 7.07, "entered": false }, ... ]
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{sample_velocity, Trajectory};
+
+/// Version of the schema written by this build.
+pub const RUN_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunFile {
+    pub version: u32,
+    pub scenario: ScenarioFile,
+    pub scored: bool,
+    /// Empty when the run has the keyframes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub samples: Vec<RunSample>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keyframes: Option<Keyframes>,
+}
+
+/// A sample of the trajectory, the instant, the position and if the ball entered the basket.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunSample {
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+    pub entered: bool,
+}
+
+/// The trajectory stored as some of its samples.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keyframes {
+    /// Samples of the trajectory, with the ones between the keyframes.
+    pub num_samples: usize,
+    /// Maximum distance in meters of a rebuilt sample to the simulated one.
+    pub tolerance: f64,
+    pub frames: Vec<Keyframe>,
+}
+
+/// The i-th sample of the trajectory and the velocity of the ball there, in m/s.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub i: usize,
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+    pub vx: f64,
+    pub vy: f64,
+    pub entered: bool,
+}
+
+impl RunFile {
+    pub fn new(scenario: & ScenarioFile, trajectory_2d: & Trajectory) -> RunFile {
+        RunFile {
+            version: RUN_VERSION,
+            scenario: scenario.clone(),
+            scored: trajectory_2d.0,
+            samples: trajectory_2d.1.iter()
+                .map(|(t, (x, y), flag_enter_instant)| RunSample { t: *t, x: *x, y: *y, entered: *flag_enter_instant })
+                .collect(),
+            keyframes: None,
+        }
+    }
+
+    /// The run with the keyframes of the trajectory instead of its samples, each sample is
+    /// rebuilt within the tolerance in meters.
+    pub fn with_keyframes(scenario: & ScenarioFile, trajectory_2d: & Trajectory, tolerance: f64) -> RunFile {
+        RunFile {
+            version: RUN_VERSION,
+            scenario: scenario.clone(),
+            scored: trajectory_2d.0,
+            samples: Vec::new(),
+            keyframes: Some(Keyframes::new(& trajectory_2d.1, tolerance)),
+        }
+    }
+
+    /// The trajectory of the run, as the simulation returned it, or rebuilt from the keyframes.
+    pub fn trajectory(& self) -> Trajectory {
+        match & self.keyframes {
+            Some(keyframes) => (self.scored, keyframes.samples()),
+            None => (self.scored, self.samples.iter().map(|sample| (sample.t, (sample.x, sample.y), sample.entered)).collect()),
+        }
+    }
+
+    pub fn load(filename: & str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(filename)
+            .map_err(|error| format!("Can't read the run file {}: {}", filename, error))?;
+        let run: RunFile = serde_json::from_str(&text).map_err(|error| format!("{}: Invalid run: {}", filename, error))?;
+        run.validate().map_err(|error| format!("{}: {}", filename, error))?;
+        Ok(run)
+    }
+
+    pub fn save(& self, filename: & str) -> Result<(), String> {
+        // Plain structs of numbers and strings, it can't fail.
+        let text = serde_json::to_string_pretty(self).unwrap_or_default() + "\n";
+        std::fs::write(filename, text).map_err(|error| format!("Can't write the run file {}: {}", filename, error))
+    }
+
+    /// The runs that can't be rendered, the samples must be finite and in the order of the time.
+    pub fn validate(& self) -> Result<(), String> {
+        self.scenario.validate()?;
+        if let Some(keyframes) = & self.keyframes {
+            return keyframes.validate();
+        }
+        if self.samples.is_empty() {
+            return Err("The run has no samples.".to_string());
+        }
+        if let Some(i) = self.samples.iter().position(|sample| !(sample.t.is_finite() && sample.x.is_finite() && sample.y.is_finite())) {
+            return Err(format!("The sample {} isn't a finite number.", i));
+        }
+        if let Some(i) = (1..self.samples.len()).find(|&i| self.samples[i].t < self.samples[i - 1].t) {
+            return Err(format!("The sample {} is before the previous one.", i));
+        }
+        Ok(())
+    }
+}
+
+impl Keyframes {
+    /// The fewest keyframes, about, that rebuild the samples within the tolerance in meters.
+    pub fn new(samples: & [(f64, (f64, f64), bool)], tolerance: f64) -> Keyframes {
+        let frame = |i: usize| {
+            let (t, (x, y), entered) = samples[i];
+            let (vx, vy) = sample_velocity(samples, i);
+            Keyframe { i, t, x, y, vx, vy, entered }
+        };
+        // The keyframes a and b rebuild the samples between them.
+        let fits = |a: & Keyframe, b: usize| {
+            let b = frame(b);
+            (a.i + 1..b.i).all(|i| {
+                let (t, (x, y), entered) = samples[i];
+                let (t_rebuilt, (x_rebuilt, y_rebuilt)) = interpolate(a, & b, i);
+                // The instants too, when the samples aren't evenly spaced.
+                !entered && f64::hypot(x_rebuilt - x, y_rebuilt - y) <= tolerance
+                    && f64::abs(t_rebuilt - t) <= 1e-9 * f64::max(1.0, t.abs())
+            })
+        };
+        let mut frames: Vec<Keyframe> = Vec::new();
+        if let Some(last) = samples.len().checked_sub(1) {
+            frames.push(frame(0));
+            while let Some(a) = frames.last().copied().filter(|a| a.i < last) {
+                // The farthest keyframe from a, doubling the span while it fits and then by
+                // bisection between the last span that fits and the first that doesn't.
+                let mut good = a.i + 1;
+                let mut bad = None;
+                while bad.is_none() && good < last {
+                    let next = usize::min(a.i + 2 * (good - a.i), last);
+                    if fits(& a, next) { good = next } else { bad = Some(next) }
+                }
+                if let Some(mut bad) = bad {
+                    while bad - good > 1 {
+                        let mid = (good + bad) / 2;
+                        if fits(& a, mid) { good = mid } else { bad = mid }
+                    }
+                }
+                frames.push(frame(good));
+            }
+        }
+        Keyframes { num_samples: samples.len(), tolerance, frames }
+    }
+
+    /// The samples of the trajectory, the keyframes and the ones rebuilt between them.
+    pub fn samples(& self) -> Vec<(f64, (f64, f64), bool)> {
+        let mut samples = Vec::with_capacity(self.num_samples);
+        for pair in self.frames.windows(2) {
+            let (a, b) = (& pair[0], & pair[1]);
+            samples.push((a.t, (a.x, a.y), a.entered));
+            samples.extend((a.i + 1..b.i).map(|i| {
+                let (t, position) = interpolate(a, b, i);
+                (t, position, false)
+            }));
+        }
+        if let Some(last) = self.frames.last() {
+            samples.push((last.t, (last.x, last.y), last.entered));
+        }
+        samples
+    }
+
+    /// The keyframes that can't be rebuilt, they go from the first to the last sample in order.
+    pub fn validate(& self) -> Result<(), String> {
+        let (Some(first), Some(last)) = (self.frames.first(), self.frames.last()) else {
+            return Err("The run has no keyframes.".to_string());
+        };
+        if first.i != 0 || last.i + 1 != self.num_samples {
+            return Err(format!("The keyframes must start at the sample 0 and end at the sample {}.", self.num_samples.saturating_sub(1)));
+        }
+        if let Some(i) = self.frames.iter().position(|frame| ![frame.t, frame.x, frame.y, frame.vx, frame.vy].iter().all(|value| value.is_finite())) {
+            return Err(format!("The keyframe {} isn't a finite number.", i));
+        }
+        if let Some(i) = (1..self.frames.len()).find(|&i| self.frames[i].i <= self.frames[i - 1].i || self.frames[i].t < self.frames[i - 1].t) {
+            return Err(format!("The keyframe {} is before the previous one.", i));
+        }
+        Ok(())
+    }
+}
+
+// The instant of the i-th sample, with the samples evenly spaced in time between the keyframes,
+// and the position there on the cubic of the positions and the velocities of the keyframes.
+fn interpolate(a: & Keyframe, b: & Keyframe, i: usize) -> (f64, (f64, f64)) {
+    let s = (i - a.i) as f64 / (b.i - a.i) as f64;
+    let dt = b.t - a.t;
+    let t = a.t + dt * s;
+    // The Hermite basis.
+    let (s_2, s_3) = (s * s, s * s * s);
+    let (h_00, h_10, h_01, h_11) = (2.0 * s_3 - 3.0 * s_2 + 1.0, s_3 - 2.0 * s_2 + s, -2.0 * s_3 + 3.0 * s_2, s_3 - s_2);
+    let x = h_00 * a.x + h_10 * dt * a.vx + h_01 * b.x + h_11 * dt * b.vx;
+    let y = h_00 * a.y + h_10 * dt * a.vy + h_01 * b.y + h_11 * dt * b.vy;
+    (t, (x, y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The largest distance of a rebuilt sample to the simulated one, with the same instants.
+    fn max_error(samples: & [(f64, (f64, f64), bool)], keyframes: & Keyframes) -> f64 {
+        let rebuilt = keyframes.samples();
+        assert_eq!(rebuilt.len(), samples.len());
+        samples.iter().zip(& rebuilt).map(|((t, (x, y), entered), (t_rebuilt, (x_rebuilt, y_rebuilt), entered_rebuilt))| {
+            assert!(f64::abs(t - t_rebuilt) <= 1e-9 * f64::max(1.0, t.abs()));
+            assert_eq!(entered, entered_rebuilt);
+            f64::hypot(x - x_rebuilt, y - y_rebuilt)
+        }).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn keyframes_of_a_parabola() {
+        // The free throw scores, its entry samples are keyframes.
+        let samples = ScenarioFile::preset("free-throw").unwrap().simulate_2d().1;
+        assert!(samples.iter().any(|(_, _, entered)| *entered));
+        let keyframes = Keyframes::new(& samples, 0.001);
+        assert_eq!(keyframes.validate(), Ok(()));
+        assert!(keyframes.frames.len() < samples.len() / 10, "{} keyframes", keyframes.frames.len());
+        assert!(max_error(& samples, & keyframes) <= 0.001);
+    }
+
+    #[test]
+    fn keyframes_with_drag_within_tolerance() {
+        let samples = ScenarioFile::default().simulate_2d_drag().1;
+        for tolerance in [0.01, 0.001, 0.0001] {
+            let keyframes = Keyframes::new(& samples, tolerance);
+            assert!(keyframes.frames.len() < samples.len());
+            assert!(max_error(& samples, & keyframes) <= tolerance, "tolerance {}", tolerance);
+        }
+    }
+
+    #[test]
+    fn keyframes_of_a_bounce_within_tolerance() {
+        // The path turns at the floor, the keyframes must stay close to it.
+        let samples: Vec<(f64, (f64, f64), bool)> = (0..400).map(|i| {
+            let t = i as f64 * 0.005;
+            (t, (3.0 * t, f64::abs(f64::sin(3.0 * t)) * 2.0), false)
+        }).collect();
+        let keyframes = Keyframes::new(& samples, 0.002);
+        assert!(max_error(& samples, & keyframes) <= 0.002);
+    }
+
+    #[test]
+    fn run_file_with_keyframes_round_trip() {
+        let scenario = ScenarioFile::default();
+        let trajectory_2d = scenario.simulate_2d();
+        let run = RunFile::with_keyframes(& scenario, & trajectory_2d, 0.001);
+        let text = serde_json::to_string(& run).unwrap();
+        let loaded: RunFile = serde_json::from_str(& text).unwrap();
+        assert_eq!(loaded.validate(), Ok(()));
+        assert_eq!(loaded.trajectory().1.len(), trajectory_2d.1.len());
+        let mut broken = run.clone();
+        broken.keyframes.as_mut().unwrap().frames.pop();
+        assert!(broken.validate().is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! The shot in 3D with the rim as a torus, the ring of the rim with the section of its rod, and
+//! the backboard as a rectangle. The ball, with its radius, bounces off them, so a shot off the
+//! line can rattle around the rim before it falls in or out. It scores when its center goes down
+//! through the ring, instead of the distance to the center of the basket.
+
+use crate::rebound::{pressure_restitution, BACKBOARD_RESTITUTION, RIM_RESTITUTION};
+use crate::trajectory::{basket_backboard_x, get_time_steps, Trajectory3D,
+                        BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT, BACKBOARD_WIDTH, GRAVITY, RIM_DIAMETER, RIM_TUBE_RADIUS};
+
+const RIM_DT: f64 = 0.0002; // s - Maximum step of the integration, the ball moves about 2 mm.
+
+/// The same as basketball_3d() with the collisions of a ball of radius in m, inflated to
+/// pressure_bar, with the rim and the backboard, at the same instants.
+#[allow(clippy::too_many_arguments)]
+pub fn basketball_3d_rim(pos_0_x: f64, pos_0_y: f64, pos_0_z: f64,
+                         v_0: f64, teta_0: f64, phi_0: f64,
+                         basket_pos_x: f64, basket_pos_y: f64, basket_pos_z: f64,
+                         simulation_sec: f64, num_steps: u32,
+                         ball_radius: f64, pressure_bar: f64)
+                         -> Trajectory3D {
+
+    assert!(v_0 > 0.0);
+    assert!(simulation_sec > 0.0);
+    assert!(num_steps > 2);
+    assert!(ball_radius > 0.0);
+
+    let v_0_horizontal = v_0 * f64::cos(teta_0);
+    let mut pos = (pos_0_x, pos_0_y, pos_0_z);
+    let mut vel = (v_0_horizontal * f64::cos(phi_0), v_0 * f64::sin(teta_0), v_0_horizontal * f64::sin(phi_0));
+    let basket = (basket_pos_x, basket_pos_y, basket_pos_z);
+    let (rim_restitution, backboard_restitution) = (pressure_restitution(RIM_RESTITUTION, pressure_bar),
+                                                    pressure_restitution(BACKBOARD_RESTITUTION, pressure_bar));
+    let mut t_state = 0.0;
+    let mut trajectory_3d: Vec<(f64, (f64, f64, f64), bool)> = Vec::new();
+    let mut flag_into_the_basket = false;
+
+    for t in get_time_steps(simulation_sec, num_steps) {
+        // From the previous instant to this one in equal steps.
+        let num_substeps = f64::ceil((t - t_state) / RIM_DT) as usize;
+        let mut flag_enter_instant = false;
+        for _ in 0..num_substeps {
+            let dt = (t - t_state) / num_substeps as f64;
+            let y_prev = pos.1;
+            pos = (pos.0 + vel.0 * dt, pos.1 + vel.1 * dt - (1.0/2.0) * GRAVITY * dt * dt, pos.2 + vel.2 * dt);
+            vel.1 -= GRAVITY * dt;
+            bounce_rim(& mut pos, & mut vel, basket, ball_radius, rim_restitution);
+            bounce_backboard(& mut pos, & mut vel, basket, ball_radius, backboard_restitution);
+            // Down through the plane of the ring, inside it.
+            let dist_axis = f64::hypot(pos.0 - basket.0, pos.2 - basket.2);
+            if y_prev >= basket.1 && pos.1 < basket.1 && dist_axis < RIM_DIAMETER / 2.0 {
+                flag_enter_instant = true;
+            }
+        }
+        t_state = t;
+        flag_into_the_basket |= flag_enter_instant;
+        if pos.1 >= 0.0 {
+            trajectory_3d.push( (t, pos, flag_enter_instant) );
+        }
+    }
+    (flag_into_the_basket, trajectory_3d)
+}
+
+// The torus is the circle of the center of the rod, in the horizontal plane of the rim, with the
+// rod around it. The point of the rod closest to the ball is on the circle in the direction of
+// the ball from the axis, the normal of the contact goes from it to the center of the ball.
+fn bounce_rim(pos: & mut (f64, f64, f64), vel: & mut (f64, f64, f64), basket: (f64, f64, f64), ball_radius: f64,
+              restitution: f64) {
+    let (d_x, d_z) = (pos.0 - basket.0, pos.2 - basket.2);
+    let dist_axis = f64::hypot(d_x, d_z);
+    // Over the axis all the ring is at the same distance, the ball can't touch it there.
+    if dist_axis < 1e-9 {
+        return;
+    }
+    let ring_radius = RIM_DIAMETER / 2.0;
+    let closest = (basket.0 + d_x / dist_axis * ring_radius, basket.1, basket.2 + d_z / dist_axis * ring_radius);
+    let normal = (pos.0 - closest.0, pos.1 - closest.1, pos.2 - closest.2);
+    let dist = f64::sqrt(normal.0 * normal.0 + normal.1 * normal.1 + normal.2 * normal.2);
+    let contact_dist = ball_radius + RIM_TUBE_RADIUS;
+    if dist >= contact_dist || dist < 1e-9 {
+        return;
+    }
+    let normal = (normal.0 / dist, normal.1 / dist, normal.2 / dist);
+    let v_normal = vel.0 * normal.0 + vel.1 * normal.1 + vel.2 * normal.2;
+    if v_normal < 0.0 {
+        // The speed along the normal is reflected and reduced, the one along the rod is kept.
+        vel.0 -= (1.0 + restitution) * v_normal * normal.0;
+        vel.1 -= (1.0 + restitution) * v_normal * normal.1;
+        vel.2 -= (1.0 + restitution) * v_normal * normal.2;
+    }
+    // Out of the rod.
+    *pos = (closest.0 + normal.0 * contact_dist, closest.1 + normal.1 * contact_dist, closest.2 + normal.2 * contact_dist);
+}
+
+// The front of the backboard, facing the shooter, centered on the basket to the sides.
+fn bounce_backboard(pos: & mut (f64, f64, f64), vel: & mut (f64, f64, f64), basket: (f64, f64, f64), ball_radius: f64,
+                    restitution: f64) {
+    let backboard_x = basket_backboard_x(basket.0);
+    let backboard_bottom = basket.1 - BACKBOARD_BELOW_RIM;
+    if pos.0 < backboard_x && pos.0 + ball_radius > backboard_x && vel.0 > 0.0
+            && pos.1 >= backboard_bottom && pos.1 <= backboard_bottom + BACKBOARD_HEIGHT
+            && f64::abs(pos.2 - basket.2) <= BACKBOARD_WIDTH / 2.0 {
+        vel.0 *= -restitution;
+        pos.0 = backboard_x - ball_radius;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rebound::NOMINAL_BALL_PRESSURE;
+    use crate::trajectory::required_speed;
+
+    const BALL_RADIUS: f64 = 0.12; // m
+    const BASKET: (f64, f64, f64) = (4.6, 3.05, 0.0);
+
+    // The shot from (0, 2, 0) with the speed that takes it through the point at (aim_x, aim_z) in the plane of the ring.
+    fn shot_to(aim_x: f64, aim_z: f64, teta_0_deg: f64) -> Trajectory3D {
+        let dist = f64::hypot(aim_x, aim_z);
+        let teta_0 = teta_0_deg.to_radians();
+        let v_0 = required_speed(0.0, 2.0, teta_0, dist, BASKET.1).unwrap();
+        basketball_3d_rim(0.0, 2.0, 0.0, v_0, teta_0, f64::atan2(aim_z, aim_x),
+                          BASKET.0, BASKET.1, BASKET.2, 3.0, 3001, BALL_RADIUS, NOMINAL_BALL_PRESSURE)
+    }
+
+    // The touches are the groups of samples where the horizontal speed changes, in the flight it's constant.
+    fn num_touches(trajectory_3d: & Trajectory3D) -> usize {
+        let samples = & trajectory_3d.1;
+        let mut num_touches = 0;
+        let mut flag_touching = false;
+        for window in samples.windows(3) {
+            let (p_0, p_1, p_2) = (window[0].1, window[1].1, window[2].1);
+            let change = f64::hypot(p_2.0 - 2.0 * p_1.0 + p_0.0, p_2.2 - 2.0 * p_1.2 + p_0.2);
+            let flag_touch = change > 1e-9;
+            if flag_touch && !flag_touching {
+                num_touches += 1;
+            }
+            flag_touching = flag_touch;
+        }
+        num_touches
+    }
+
+    #[test]
+    fn dropped_on_the_rod_bounces_outward() {
+        // Just outside of the front of the rod, falling at 3 m/s and touching it.
+        let rod_x = BASKET.0 - RIM_DIAMETER / 2.0;
+        let mut pos = (rod_x - 0.05, BASKET.1 + 0.11, BASKET.2);
+        let mut vel = (0.0, -3.0, 0.0);
+        let normal = {
+            let (d_x, d_y) = (pos.0 - rod_x, pos.1 - BASKET.1);
+            let dist = f64::hypot(d_x, d_y);
+            (d_x / dist, d_y / dist)
+        };
+        let v_normal_before = vel.0 * normal.0 + vel.1 * normal.1;
+        bounce_rim(& mut pos, & mut vel, BASKET, BALL_RADIUS, RIM_RESTITUTION);
+
+        // Away from the axis of the basket and up, slower along the normal.
+        assert!(vel.0 < 0.0 && vel.1 > 0.0);
+        let v_normal_after = vel.0 * normal.0 + vel.1 * normal.1;
+        assert!((v_normal_after - (-RIM_RESTITUTION * v_normal_before)).abs() < 1e-9);
+        assert!(v_normal_after.abs() < v_normal_before.abs());
+        let dist = f64::hypot(pos.0 - rod_x, pos.1 - BASKET.1);
+        assert!((dist - (BALL_RADIUS + RIM_TUBE_RADIUS)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn centered_shot_scores_without_touching() {
+        let trajectory_3d = shot_to(BASKET.0, 0.0, 60.0);
+        assert!(trajectory_3d.0);
+        assert_eq!(num_touches(& trajectory_3d), 0);
+    }
+
+    #[test]
+    fn shot_off_the_line_rattles() {
+        // 12 cm to the side of the center, the ball touches the rim several times and falls in.
+        let trajectory_3d = shot_to(BASKET.0, 0.12, 60.0);
+        assert!(num_touches(& trajectory_3d) > 1);
+        assert!(trajectory_3d.0);
+    }
+}
@@ -0,0 +1,50 @@
+//! A small random number generator, SplitMix64, for the shots of the quiz and of the Monte
+//! Carlo simulation. It isn't for cryptography, the same seed gives the same numbers.
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Seeded from the clock, different in each run.
+    pub fn from_time() -> Self {
+        Rng::new(time_seed())
+    }
+
+    pub fn next_u64(& mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    pub fn next_f64(& mut self) -> f64 {
+        // The 53 bits of the mantissa.
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Uniform in [min, max).
+    pub fn range(& mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+
+    /// Normal, with the Box-Muller transform.
+    pub fn normal(& mut self, mean: f64, std_dev: f64) -> f64 {
+        // 1 - u is in (0, 1], its logarithm is finite.
+        let u_1 = 1.0 - self.next_f64();
+        let u_2 = self.next_f64();
+        mean + std_dev * f64::sqrt(-2.0 * f64::ln(u_1)) * f64::cos(2.0 * std::f64::consts::PI * u_2)
+    }
+}
+
+/// A seed from the clock, to print it and repeat the run with the same seed.
+pub fn time_seed() -> u64 {
+    std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_nanos() as u64)
+}
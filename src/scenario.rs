@@ -0,0 +1,598 @@
+//! The scenario file, all the inputs of a shot, to save it and load it again in a later version.
+//!
+//! The file is JSON, or TOML when the filename ends in ".toml", or YAML with the feature "yaml"
+//! when it ends in ".yaml" or ".yml":
+//!
+//! ```text
+//! version = 1
+//!
+//! [scene]
+//! basket_pos_x = 8.0
+//! basket_pos_y = 3.05
+//! basket_pos_z = 5.0
+//!
+//! [throw]
+//! v_0 = 10.0
+//! teta_0_deg = 45.0
+//! ```
+//!
+//! The lengths, the speeds, the angles and the simulated time can also be a text with a unit,
+//! like v_0 = "36km/h" or basket_pos_x = "15ft", see units::parse_quantity(). They are written
+//! as numbers, in meters, m/s, degrees and seconds.
+//!
+//! The schema only grows: a missing field has its default value and an unknown field, from a
+//! newer version, is ignored. The version only changes when the meaning of a field changes.
+//!
+//! The wind, when there's one, is a list of samples, in TOML each one in a [[wind]] table. The
+//! noise of the release of the Monte Carlo simulations is in the [noise] table, see monte_carlo,
+//! the obstacles over the court in [[obstacles]] tables, see obstacles, and the targets other
+//! than the basket in [[targets]] tables, see targets. The goal of another sport, like a soccer
+//! goal, is in the [goal] table, see sports.
+//!
+//! A batch file has a list of scenarios, in TOML each one in a [[scenarios]] table.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::monte_carlo::ReleaseNoise;
+use crate::obstacles::{validate_obstacles, Obstacle, ObstacleKind};
+use crate::rebound::NOMINAL_BALL_PRESSURE;
+use crate::sports::{Goal, DARTBOARD_DISTANCE, DARTBOARD_HEIGHT, DARTBOARD_RADIUS, SOCCER_CROSSBAR_HEIGHT, SOCCER_PENALTY_DISTANCE,
+                    VOLLEYBALL_HALF_COURT, VOLLEYBALL_NET_HEIGHT};
+use crate::targets::{validate_targets, Target};
+use crate::termination::auto_stop;
+use crate::trajectory::{air_density, basketball_2d, basketball_2d_drag, Flight2D, Trajectory};
+use crate::units::serde_quantity;
+use crate::wind::{validate_wind, WindSample};
+
+/// Version of the schema written by this build.
+pub const SCENARIO_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScenarioFile {
+    pub version: u32,
+    /// The name in the summary of a batch, the files without it are written without it.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub scene: Scene,
+    pub ball: Ball,
+    pub throw: Throw,
+    pub simulation: Simulation,
+    pub outputs: Outputs,
+    pub air: Air,
+    /// Without samples there's no wind, and the files don't have the field.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub wind: Vec<WindSample>,
+    /// The files without it have the default noise, and they are written without it.
+    #[serde(skip_serializing_if = "ReleaseNoise::is_default")]
+    pub noise: ReleaseNoise,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub obstacles: Vec<Obstacle>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub targets: Vec<Target>,
+    /// The goal of another sport, see sports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goal: Option<Goal>,
+}
+
+/// The court, the position of the center of the basket in meters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scene {
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub basket_pos_x: f64,
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub basket_pos_y: f64,
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub basket_pos_z: f64,
+}
+
+/// A size 7 ball, only the model with the air drag and the bounces depend on it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Ball {
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub radius: f64,  // m
+    pub mass: f64,    // kg
+    pub pressure_bar: f64, // bar - Inflation, above the air outside, only the bounces depend on it.
+}
+
+/// The air of the court, only the model with the air drag depends on it. The default is the dry
+/// air at 20 °C at the sea level.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Air {
+    pub temperature_c: f64,     // °C
+    pub humidity_percent: f64,  // % - Relative humidity.
+    pub pressure_hpa: f64,      // hPa
+}
+
+impl Air {
+    /// The density of the air in kg/m^3.
+    pub fn density(& self) -> f64 {
+        air_density(self.temperature_c, self.humidity_percent, self.pressure_hpa)
+    }
+}
+
+/// The player throw position in meters, the speed in m/s and the angles in degrees.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Throw {
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub pos_0_x: f64,
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub pos_0_y: f64,
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub pos_0_z: f64,
+    #[serde(deserialize_with = "serde_quantity::speed")]
+    pub v_0: f64,
+    // Angle from the XX axis to the YY axis.
+    #[serde(deserialize_with = "serde_quantity::angle")]
+    pub teta_0_deg: f64,
+    // Angle from the ZZ axis to the XX axis.
+    #[serde(deserialize_with = "serde_quantity::angle")]
+    pub phi_0_deg: f64,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Simulation {
+    // s - Seconds to simulate.
+    #[serde(deserialize_with = "serde_quantity::time")]
+    pub simulation_sec: f64,
+    // Divide the simulation seconds into N equal points.
+    pub num_steps: u32,
+    // Simulate until the ball scores, reaches the floor or leaves the court, see termination.
+    pub auto_stop: bool,
+    // s - After a made basket, drop the ball through the net for at most these seconds, see post_score.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_score_sec: Option<f64>,
+}
+
+/// The files written, without the file none is written.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Outputs {
+    pub svg_filename: Option<String>,
+    pub html_filename: Option<String>,
+    pub text_filename: Option<String>,
+}
+
+/// Many scenarios, for the batch mode.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BatchFile {
+    pub version: u32,
+    pub scenarios: Vec<ScenarioFile>,
+}
+
+impl Default for BatchFile {
+    fn default() -> Self {
+        BatchFile { version: SCENARIO_VERSION, scenarios: Vec::new() }
+    }
+}
+
+impl BatchFile {
+    /// Reads a batch, TOML or YAML by the extension of the filename, otherwise JSON. The
+    /// scenarios are validated when they are simulated, one invalid scenario doesn't stop the others.
+    pub fn load(filename: & str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(filename)
+            .map_err(|error| format!("Can't read the batch file {}: {}", filename, error))?;
+        FileFormat::from_filename(filename).parse(&text).map_err(|error| format!("{}: Invalid batch: {}", filename, error))
+    }
+}
+
+impl Default for ScenarioFile {
+    fn default() -> Self {
+        ScenarioFile {
+            version: SCENARIO_VERSION,
+            name: String::new(),
+            scene: Scene::default(),
+            ball: Ball::default(),
+            throw: Throw::default(),
+            simulation: Simulation::default(),
+            outputs: Outputs::default(),
+            air: Air::default(),
+            wind: Vec::new(),
+            noise: ReleaseNoise::default(),
+            obstacles: Vec::new(),
+            targets: Vec::new(),
+            goal: None,
+        }
+    }
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Scene { basket_pos_x: 8.0, basket_pos_y: 3.05, basket_pos_z: 5.0 }
+    }
+}
+
+impl Default for Ball {
+    fn default() -> Self {
+        Ball { radius: 0.12, mass: 0.62, pressure_bar: NOMINAL_BALL_PRESSURE }
+    }
+}
+
+impl Default for Air {
+    fn default() -> Self {
+        Air { temperature_c: 20.0, humidity_percent: 0.0, pressure_hpa: 1013.25 }
+    }
+}
+
+impl Throw {
+    /// The initial velocity in m/s as (v_x, v_y, v_z), in the axes of the scene.
+    pub fn velocity(& self) -> (f64, f64, f64) {
+        let (teta_0, phi_0) = (self.teta_0_deg.to_radians(), self.phi_0_deg.to_radians());
+        let v_0_horizontal = self.v_0 * f64::cos(teta_0);
+        (v_0_horizontal * f64::cos(phi_0), self.v_0 * f64::sin(teta_0), v_0_horizontal * f64::sin(phi_0))
+    }
+
+    /// Sets v_0 and the angles from the initial velocity in m/s, like the one measured by a
+    /// tracking system.
+    pub fn set_velocity(& mut self, v_x: f64, v_y: f64, v_z: f64) {
+        self.v_0 = f64::sqrt(v_x * v_x + v_y * v_y + v_z * v_z);
+        self.teta_0_deg = f64::atan2(v_y, f64::hypot(v_x, v_z)).to_degrees();
+        self.phi_0_deg = f64::atan2(v_z, v_x).to_degrees();
+    }
+}
+
+impl Default for Throw {
+    fn default() -> Self {
+        Throw { pos_0_x: 0.0, pos_0_y: 1.5, pos_0_z: 0.0, v_0: 10.0, teta_0_deg: 45.0, phi_0_deg: 0.0 }
+    }
+}
+
+impl Default for Simulation {
+    fn default() -> Self {
+        Simulation { simulation_sec: 3.0, num_steps: 60, auto_stop: false, post_score_sec: None }
+    }
+}
+
+impl Default for Outputs {
+    fn default() -> Self {
+        Outputs {
+            svg_filename: Some("basketball_trajectory.svg".to_string()),
+            html_filename: None,
+            text_filename: None,
+        }
+    }
+}
+
+/// The names of the scenarios of ScenarioFile::preset().
+pub const PRESET_NAMES: [& str; 6] = ["default", "free-throw", "free-throw-underhand", "soccer-penalty", "volleyball-serve", "darts"];
+
+/// From the free throw line to the center of the basket, FIBA.
+pub const FREE_THROW_DISTANCE: f64 = 4.225; // m
+
+impl ScenarioFile {
+    /// A scenario by its name, one of PRESET_NAMES. The free throws are the overhand one,
+    /// released above the head, and the underhand one, released at the height of the hips. The
+    /// other sports have their goal, and the basket at the point that they aim at.
+    pub fn preset(name: & str) -> Option<ScenarioFile> {
+        let mut scenario = ScenarioFile::default();
+        let (pos_0_y, v_0, teta_0_deg) = match name {
+            "default" => return Some(scenario),
+            "free-throw" => (2.1, 7.2, 52.0),
+            "free-throw-underhand" => (1.2, 8.0, 60.0),
+            "soccer-penalty" | "volleyball-serve" | "darts" => return Some(ScenarioFile::sport_preset(name)),
+            _ => return None,
+        };
+        scenario.scene.basket_pos_x = FREE_THROW_DISTANCE;
+        scenario.throw.pos_0_y = pos_0_y;
+        scenario.throw.v_0 = v_0;
+        scenario.throw.teta_0_deg = teta_0_deg;
+        scenario.simulation.simulation_sec = 2.0;
+        scenario.simulation.num_steps = 600;
+        Some(scenario)
+    }
+
+    // The presets of the other sports, the ball, the throw and the goal.
+    fn sport_preset(name: & str) -> ScenarioFile {
+        let mut scenario = ScenarioFile::default();
+        scenario.scene.basket_pos_z = scenario.throw.pos_0_z;
+        scenario.simulation.num_steps = 600;
+        let bar = |name: & str, x: f64, y_1: f64, y_2: f64| Obstacle { name: name.to_string(), kind: ObstacleKind::Segment,
+                                                                        x_1: x, y_1, x_2: x, y_2 };
+        match name {
+            "soccer-penalty" => {
+                scenario.ball = Ball { radius: 0.11, mass: 0.43, pressure_bar: 0.9 };
+                scenario.throw.pos_0_y = 0.11;
+                scenario.throw.v_0 = 20.0;
+                scenario.throw.teta_0_deg = 12.0;
+                scenario.scene.basket_pos_x = SOCCER_PENALTY_DISTANCE;
+                scenario.scene.basket_pos_y = SOCCER_CROSSBAR_HEIGHT / 2.0;
+                scenario.simulation.simulation_sec = 1.0;
+                // The crossbar, 12 cm thick.
+                scenario.obstacles = vec![bar("crossbar", SOCCER_PENALTY_DISTANCE + 0.06, SOCCER_CROSSBAR_HEIGHT, SOCCER_CROSSBAR_HEIGHT + 0.12)];
+                scenario.goal = Some(Goal::SoccerGoal { line_x: SOCCER_PENALTY_DISTANCE, crossbar_y: SOCCER_CROSSBAR_HEIGHT });
+            },
+            "volleyball-serve" => {
+                scenario.ball = Ball { radius: 0.105, mass: 0.27, pressure_bar: 0.3 };
+                scenario.throw.pos_0_y = 2.6;
+                scenario.throw.v_0 = 15.0;
+                scenario.throw.teta_0_deg = 15.0;
+                scenario.scene.basket_pos_x = VOLLEYBALL_HALF_COURT;
+                scenario.scene.basket_pos_y = VOLLEYBALL_NET_HEIGHT;
+                scenario.simulation.simulation_sec = 2.0;
+                // The net is 1 m tall under its top.
+                scenario.obstacles = vec![bar("net", VOLLEYBALL_HALF_COURT, VOLLEYBALL_NET_HEIGHT - 1.0, VOLLEYBALL_NET_HEIGHT)];
+                scenario.goal = Some(Goal::VolleyballNet { net_x: VOLLEYBALL_HALF_COURT, net_y: VOLLEYBALL_NET_HEIGHT,
+                                                           court_length: VOLLEYBALL_HALF_COURT });
+            },
+            _ => {
+                scenario.ball = Ball { radius: 0.003, mass: 0.022, pressure_bar: NOMINAL_BALL_PRESSURE };
+                scenario.throw.pos_0_y = 1.75;
+                scenario.throw.v_0 = 6.0;
+                scenario.throw.teta_0_deg = 19.5;
+                scenario.scene.basket_pos_x = DARTBOARD_DISTANCE;
+                scenario.scene.basket_pos_y = DARTBOARD_HEIGHT;
+                scenario.simulation.simulation_sec = 0.6;
+                scenario.goal = Some(Goal::Dartboard { x: DARTBOARD_DISTANCE, y: DARTBOARD_HEIGHT, radius: DARTBOARD_RADIUS });
+            },
+        }
+        scenario
+    }
+
+    pub fn from_json(text: & str) -> Result<Self, String> {
+        serde_json::from_str(text).map_err(|error| format!("Invalid scenario: {}", error))
+    }
+
+    pub fn from_toml(text: & str) -> Result<Self, String> {
+        toml::from_str(text).map_err(|error| format!("Invalid scenario: {}", error))
+    }
+
+    pub fn to_json(& self) -> String {
+        // Plain structs of numbers and strings, it can't fail.
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn to_toml(& self) -> String {
+        toml::to_string_pretty(self).unwrap_or_default()
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(text: & str) -> Result<Self, String> {
+        serde_norway::from_str(text).map_err(|error| format!("Invalid scenario: {}", error))
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(& self) -> String {
+        serde_norway::to_string(self).unwrap_or_default()
+    }
+
+    /// Reads a scenario, TOML or YAML by the extension of the filename, otherwise JSON.
+    pub fn load(filename: & str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(filename)
+            .map_err(|error| format!("Can't read the scenario file {}: {}", filename, error))?;
+        let scenario: ScenarioFile = FileFormat::from_filename(filename).parse(&text)
+            .map_err(|error| format!("{}: Invalid scenario: {}", filename, error))?;
+        scenario.validate().map_err(|error| format!("{}: {}", filename, error))?;
+        Ok(scenario)
+    }
+
+    /// Writes the scenario, TOML or YAML by the extension of the filename, otherwise JSON.
+    pub fn save(& self, filename: & str) -> Result<(), String> {
+        let text = match FileFormat::from_filename(filename) {
+            FileFormat::Json => self.to_json() + "\n",
+            FileFormat::Toml => self.to_toml(),
+            #[cfg(feature = "yaml")]
+            FileFormat::Yaml => self.to_yaml(),
+            #[cfg(not(feature = "yaml"))]
+            FileFormat::Yaml => return Err(format!("Can't write the scenario file {}: {}", filename, YAML_FEATURE_ERROR)),
+        };
+        std::fs::write(filename, text).map_err(|error| format!("Can't write the scenario file {}: {}", filename, error))
+    }
+
+    /// The values that the simulation can't run with.
+    pub fn validate(& self) -> Result<(), String> {
+        if !(self.throw.v_0 > 0.0 && self.throw.v_0.is_finite()) {
+            return Err(format!("throw.v_0 is {}, expected a positive number.", self.throw.v_0));
+        }
+        if !(self.simulation.simulation_sec > 0.0 && self.simulation.simulation_sec.is_finite()) {
+            return Err(format!("simulation.simulation_sec is {}, expected a positive number.", self.simulation.simulation_sec));
+        }
+        if !(self.ball.radius > 0.0 && self.ball.radius.is_finite()) {
+            return Err(format!("ball.radius is {}, expected a positive number.", self.ball.radius));
+        }
+        if !(self.ball.mass > 0.0 && self.ball.mass.is_finite()) {
+            return Err(format!("ball.mass is {}, expected a positive number.", self.ball.mass));
+        }
+        if !(self.ball.pressure_bar > 0.0 && self.ball.pressure_bar.is_finite()) {
+            return Err(format!("ball.pressure_bar is {}, expected a positive number.", self.ball.pressure_bar));
+        }
+        if let Some(post_score_sec) = self.simulation.post_score_sec {
+            if !(post_score_sec > 0.0 && post_score_sec.is_finite()) {
+                return Err(format!("simulation.post_score_sec is {}, expected a positive number.", post_score_sec));
+            }
+        }
+        if self.simulation.num_steps <= 2 {
+            return Err(format!("simulation.num_steps is {}, expected at least 3.", self.simulation.num_steps));
+        }
+        if !(-60.0..=60.0).contains(&self.air.temperature_c) {
+            return Err(format!("air.temperature_c is {}, expected from -60 to 60.", self.air.temperature_c));
+        }
+        if !(0.0..=100.0).contains(&self.air.humidity_percent) {
+            return Err(format!("air.humidity_percent is {}, expected from 0 to 100.", self.air.humidity_percent));
+        }
+        if !(self.air.pressure_hpa > 0.0 && self.air.pressure_hpa.is_finite()) {
+            return Err(format!("air.pressure_hpa is {}, expected a positive number.", self.air.pressure_hpa));
+        }
+        validate_wind(&self.wind)?;
+        self.noise.validate()?;
+        validate_obstacles(&self.obstacles)?;
+        validate_targets(&self.targets)?;
+        if let Some(goal) = & self.goal {
+            goal.validate()?;
+        }
+        Ok(())
+    }
+
+    /// The seconds to simulate, the ones of the automatic stop with simulation.auto_stop.
+    pub fn simulated_sec(& self) -> f64 {
+        if self.simulation.auto_stop { auto_stop(self).0 } else { self.simulation.simulation_sec }
+    }
+
+    /// The shot in 2D, call validate() first, the simulation asserts the same values.
+    pub fn simulate_2d(& self) -> Trajectory {
+        basketball_2d(self.throw.pos_0_x, self.throw.pos_0_y,
+                      self.throw.v_0, self.throw.teta_0_deg.to_radians(),
+                      self.scene.basket_pos_x, self.scene.basket_pos_y,
+                      self.simulated_sec(), self.simulation.num_steps)
+    }
+
+    /// The shot in 2D with the air drag on the ball and the wind, call validate() first.
+    pub fn simulate_2d_drag(& self) -> Trajectory {
+        basketball_2d_drag(self.throw.pos_0_x, self.throw.pos_0_y,
+                           self.throw.v_0, self.throw.teta_0_deg.to_radians(),
+                           self.scene.basket_pos_x, self.scene.basket_pos_y,
+                           self.simulated_sec(), self.simulation.num_steps,
+                           self.ball.radius, self.ball.mass, self.air.density(), &self.wind)
+    }
+
+    /// The instants of the shot in 2D one at a time, for the streaming of the long simulations.
+    pub fn flight_2d(& self) -> Flight2D {
+        Flight2D::new(self.throw.pos_0_x, self.throw.pos_0_y,
+                      self.throw.v_0, self.throw.teta_0_deg.to_radians(),
+                      self.scene.basket_pos_x, self.scene.basket_pos_y,
+                      self.simulated_sec(), self.simulation.num_steps)
+    }
+
+    /// If it was written by a newer version, its new fields were ignored.
+    pub fn is_newer_version(& self) -> bool {
+        self.version > SCENARIO_VERSION
+    }
+}
+
+#[cfg(not(feature = "yaml"))]
+const YAML_FEATURE_ERROR: &str = "the YAML files need the feature yaml, build with --features yaml.";
+
+// The format of a scenario or a batch file, by the extension of its filename.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum FileFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl FileFormat {
+    fn from_filename(filename: & str) -> FileFormat {
+        let filename = filename.to_ascii_lowercase();
+        if filename.ends_with(".toml") {
+            FileFormat::Toml
+        } else if filename.ends_with(".yaml") || filename.ends_with(".yml") {
+            FileFormat::Yaml
+        } else {
+            FileFormat::Json
+        }
+    }
+
+    fn parse<T: DeserializeOwned>(self, text: & str) -> Result<T, String> {
+        match self {
+            FileFormat::Json => serde_json::from_str(text).map_err(|error| error.to_string()),
+            FileFormat::Toml => toml::from_str(text).map_err(|error| error.to_string()),
+            #[cfg(feature = "yaml")]
+            FileFormat::Yaml => serde_norway::from_str(text).map_err(|error| error.to_string()),
+            #[cfg(not(feature = "yaml"))]
+            FileFormat::Yaml => Err(YAML_FEATURE_ERROR.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn presets() -> Vec<ScenarioFile> {
+        PRESET_NAMES.iter().map(|name| ScenarioFile::preset(name).unwrap()).collect()
+    }
+
+    fn temp_filename(name: & str) -> String {
+        std::env::temp_dir().join(format!("basketball_trajectory_{}_{}", std::process::id(), name))
+            .to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn json_and_toml_round_trips() {
+        for scenario in presets() {
+            assert_eq!(ScenarioFile::from_json(& scenario.to_json()).unwrap(), scenario);
+            assert_eq!(ScenarioFile::from_toml(& scenario.to_toml()).unwrap(), scenario);
+        }
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn yaml_round_trip() {
+        for scenario in presets() {
+            assert_eq!(ScenarioFile::from_yaml(& scenario.to_yaml()).unwrap(), scenario);
+        }
+    }
+
+    #[test]
+    fn file_formats_by_extension() {
+        assert_eq!(FileFormat::from_filename("shot.toml"), FileFormat::Toml);
+        assert_eq!(FileFormat::from_filename("SHOT.TOML"), FileFormat::Toml);
+        assert_eq!(FileFormat::from_filename("shot.yaml"), FileFormat::Yaml);
+        assert_eq!(FileFormat::from_filename("shot.yml"), FileFormat::Yaml);
+        assert_eq!(FileFormat::from_filename("shot.json"), FileFormat::Json);
+        assert_eq!(FileFormat::from_filename("shot"), FileFormat::Json);
+        #[cfg(not(feature = "yaml"))]
+        assert_eq!(FileFormat::Yaml.parse::<ScenarioFile>("version: 1"), Err(YAML_FEATURE_ERROR.to_string()));
+    }
+
+    #[test]
+    fn save_and_load_in_each_format() {
+        let scenario = ScenarioFile::preset("free-throw").unwrap();
+        let extensions: & [& str] = if cfg!(feature = "yaml") { &["json", "toml", "yaml"] } else { &["json", "toml"] };
+        for extension in extensions {
+            let filename = temp_filename(& format!("scenario.{}", extension));
+            scenario.save(& filename).unwrap();
+            let loaded = ScenarioFile::load(& filename);
+            let _ = std::fs::remove_file(& filename);
+            assert_eq!(loaded.unwrap(), scenario);
+        }
+    }
+
+    #[test]
+    fn batch_in_toml() {
+        let filename = temp_filename("batch.toml");
+        std::fs::write(& filename, "version = 1\n\n[[scenarios]]\nname = \"close\"\n\n[scenarios.throw]\nv_0 = 8.0\n").unwrap();
+        let batch = BatchFile::load(& filename);
+        let _ = std::fs::remove_file(& filename);
+        let batch = batch.unwrap();
+        assert_eq!(batch.scenarios.len(), 1);
+        assert_eq!(batch.scenarios[0].name, "close");
+        assert_eq!(batch.scenarios[0].throw.v_0, 8.0);
+        assert_eq!(batch.scenarios[0].throw.teta_0_deg, Throw::default().teta_0_deg);
+    }
+
+    #[test]
+    fn validate_rejects_invalid_values() {
+        for scenario in presets() {
+            assert_eq!(scenario.validate(), Ok(()));
+        }
+        let changed = |change: fn(& mut ScenarioFile)| {
+            let mut scenario = ScenarioFile::default();
+            change(& mut scenario);
+            scenario
+        };
+        let invalid = [
+            (changed(|scenario| scenario.throw.v_0 = 0.0), "throw.v_0"),
+            (changed(|scenario| scenario.simulation.simulation_sec = f64::NAN), "simulation.simulation_sec"),
+            (changed(|scenario| scenario.simulation.num_steps = 2), "simulation.num_steps"),
+            (changed(|scenario| scenario.simulation.post_score_sec = Some(-1.0)), "simulation.post_score_sec"),
+            (changed(|scenario| scenario.air.humidity_percent = 120.0), "air.humidity_percent"),
+        ];
+        for (scenario, field) in invalid {
+            let error = scenario.validate().unwrap_err();
+            assert!(error.starts_with(field), "{}", error);
+        }
+    }
+
+    #[test]
+    fn invalid_text_is_an_error() {
+        assert!(ScenarioFile::from_json("{\"throw\": {\"v_0\": \"fast\"}}").unwrap_err().starts_with("Invalid scenario"));
+        assert!(ScenarioFile::from_toml("throw = 3").unwrap_err().starts_with("Invalid scenario"));
+    }
+}
@@ -0,0 +1,110 @@
+//! Scripts of scenarios in rhai, for studies that loop over the inputs without writing Rust.
+//!
+//! ```text
+//! let shot = scenario();
+//! for angle in 40..66 {
+//!     shot.teta_0_deg = angle;
+//!     let result = simulate(shot);
+//!     print(`${angle}° -> scored: ${result.scored}, closest: ${result.closest_dist}`);
+//! }
+//! ```
+//!
+//! Functions:
+//!
+//! * scenario() - The default scenario, with the fields pos_0_x, pos_0_y, v_0, teta_0_deg,
+//!   basket_pos_x, basket_pos_y, simulation_sec and num_steps.
+//! * load_scenario(filename), save_scenario(scenario, filename) - The scenario files.
+//! * simulate(scenario) - The summary of the shot, a map with scored, apex, range, flight,
+//!   closest_dist and closest_t.
+//! * trajectory(scenario) - The samples, an array of maps with t, x, y, vx, vy, speed, ax, ay
+//!   and entered.
+//! * save_svg(scenario, filename) - The figure of the shot.
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map};
+
+use crate::plot_svg::{plot_trajectory_svg, SvgPlotOptions};
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{sample_acceleration, sample_velocity, ShotSummary, Trajectory};
+
+/// The engine with the type Scenario and the functions of the simulation.
+pub fn script_engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.register_type_with_name::<ScenarioFile>("Scenario")
+        .register_fn("scenario", ScenarioFile::default)
+        .register_get_set("pos_0_x", |s: & mut ScenarioFile| s.throw.pos_0_x, |s: & mut ScenarioFile, value: f64| s.throw.pos_0_x = value)
+        .register_get_set("pos_0_y", |s: & mut ScenarioFile| s.throw.pos_0_y, |s: & mut ScenarioFile, value: f64| s.throw.pos_0_y = value)
+        .register_get_set("v_0", |s: & mut ScenarioFile| s.throw.v_0, |s: & mut ScenarioFile, value: f64| s.throw.v_0 = value)
+        .register_get_set("teta_0_deg", |s: & mut ScenarioFile| s.throw.teta_0_deg, |s: & mut ScenarioFile, value: f64| s.throw.teta_0_deg = value)
+        .register_get_set("basket_pos_x", |s: & mut ScenarioFile| s.scene.basket_pos_x, |s: & mut ScenarioFile, value: f64| s.scene.basket_pos_x = value)
+        .register_get_set("basket_pos_y", |s: & mut ScenarioFile| s.scene.basket_pos_y, |s: & mut ScenarioFile, value: f64| s.scene.basket_pos_y = value)
+        .register_get_set("simulation_sec", |s: & mut ScenarioFile| s.simulation.simulation_sec, |s: & mut ScenarioFile, value: f64| s.simulation.simulation_sec = value)
+        .register_get_set("num_steps", |s: & mut ScenarioFile| s.simulation.num_steps as i64,
+                          |s: & mut ScenarioFile, value: i64| s.simulation.num_steps = u32::try_from(value).unwrap_or(0))
+        // The integer angles of a for loop, like 40..66.
+        .register_set("teta_0_deg", |s: & mut ScenarioFile, value: i64| s.throw.teta_0_deg = value as f64)
+        .register_set("v_0", |s: & mut ScenarioFile, value: i64| s.throw.v_0 = value as f64)
+        .register_fn("load_scenario", |filename: & str| -> Result<ScenarioFile, Box<EvalAltResult>> {
+            Ok(ScenarioFile::load(filename)?)
+        })
+        .register_fn("save_scenario", |scenario: ScenarioFile, filename: & str| -> Result<(), Box<EvalAltResult>> {
+            Ok(scenario.save(filename)?)
+        })
+        .register_fn("simulate", |scenario: ScenarioFile| -> Result<Dynamic, Box<EvalAltResult>> {
+            let trajectory_2d = simulate_checked(& scenario)?;
+            Ok(match ShotSummary::new(& trajectory_2d, scenario.scene.basket_pos_x, scenario.scene.basket_pos_y) {
+                Some(summary) => {
+                    let mut map = Map::new();
+                    map.insert("scored".into(), summary.flag_scored.into());
+                    map.insert("apex".into(), summary.apex_y.into());
+                    map.insert("range".into(), summary.range_x.into());
+                    map.insert("flight".into(), summary.flight_sec.into());
+                    map.insert("closest_dist".into(), summary.closest_dist.into());
+                    map.insert("closest_t".into(), summary.closest_t.into());
+                    map.into()
+                },
+                None => Dynamic::UNIT,
+            })
+        })
+        .register_fn("trajectory", |scenario: ScenarioFile| -> Result<Array, Box<EvalAltResult>> {
+            let trajectory_2d = simulate_checked(& scenario)?;
+            let samples = & trajectory_2d.1;
+            Ok(samples.iter().enumerate().map(|(i, (t, (x, y), flag_enter_instant))| {
+                let (v_x, v_y) = sample_velocity(samples, i);
+                let (a_x, a_y) = sample_acceleration(samples, i);
+                let mut map = Map::new();
+                map.insert("t".into(), (*t).into());
+                map.insert("x".into(), (*x).into());
+                map.insert("y".into(), (*y).into());
+                map.insert("vx".into(), v_x.into());
+                map.insert("vy".into(), v_y.into());
+                map.insert("speed".into(), f64::hypot(v_x, v_y).into());
+                map.insert("ax".into(), a_x.into());
+                map.insert("ay".into(), a_y.into());
+                map.insert("entered".into(), (*flag_enter_instant).into());
+                Dynamic::from_map(map)
+            }).collect())
+        })
+        .register_fn("save_svg", |scenario: ScenarioFile, filename: & str| -> Result<(), Box<EvalAltResult>> {
+            let trajectory_2d = simulate_checked(& scenario)?;
+            if trajectory_2d.1.is_empty() {
+                return Err("The ball starts below the floor.".into());
+            }
+            let label = format!("v_0 = {:0.2} m/s & teta_0 = {:0.2}° -> {}", scenario.throw.v_0, scenario.throw.teta_0_deg,
+                                if trajectory_2d.0 {"entered the basket"} else {"missed the basket"});
+            let svg = plot_trajectory_svg(& trajectory_2d, Some((scenario.scene.basket_pos_x, scenario.scene.basket_pos_y)),
+                                          & SvgPlotOptions::default(), &label);
+            Ok(svg.to_file(filename, "")?)
+        });
+    engine
+}
+
+/// Runs the script of the file, the errors with the line of the script.
+pub fn run_script_file(filename: & str) -> Result<(), String> {
+    script_engine().run_file(filename.into()).map_err(|error| format!("{}: {}", filename, error))
+}
+
+// The simulation asserts the inputs, in a script they are an error.
+fn simulate_checked(scenario: & ScenarioFile) -> Result<Trajectory, Box<EvalAltResult>> {
+    scenario.validate()?;
+    Ok(scenario.simulate_2d())
+}
@@ -0,0 +1,211 @@
+//! The outcome of the shot in the words of the game, from the collision model of the rebound
+//! module, the ball with its radius against the rim and the backboard, and from the scoring
+//! model of the trajectory, the ball through the center of the basket, and from the obstacles of
+//! the scenario.
+
+use crate::advice::{MissCorrection, MissKind};
+use crate::i18n::Messages;
+use crate::monte_carlo::Defender;
+use crate::obstacles::ObstacleHit;
+use crate::rebound::{self, ContactKind};
+use crate::scenario::ScenarioFile;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ShotResult {
+    /// In without touching the rim or the backboard.
+    Swish,
+    RimIn,
+    /// Off the backboard first and in.
+    BackboardIn,
+    /// Touched the rim and didn't go in.
+    RimOut,
+    /// The top of the arc is below the rim.
+    Airball,
+    /// The ball passed below the hand of the defender.
+    Blocked,
+    /// Came down before the basket without touching it.
+    Short,
+    /// Went beyond the basket, without touching the rim.
+    Long,
+    /// Hit an obstacle of the scenario before the basket.
+    Obstacle,
+}
+
+impl ShotResult {
+    /// The result of the shot of the scenario, flag_scored and obstacle_hit are the ones of its
+    /// trajectory. The defender blocks the ball that doesn't pass over the hand.
+    pub fn classify(scenario: & ScenarioFile, flag_scored: bool, defender: Option<Defender>,
+                    obstacle_hit: Option<ObstacleHit>) -> ShotResult {
+        if obstacle_hit.is_some() {
+            return ShotResult::Obstacle;
+        }
+        let throw = & scenario.throw;
+        let clearance = defender.and_then(|defender| defender.clearance(scenario, throw.v_0, throw.teta_0_deg));
+        if clearance.is_some_and(|clearance| clearance < 0.0) {
+            return ShotResult::Blocked;
+        }
+        // The trajectory decides if the ball went in, the bounces how. A ball that bounced
+        // and rolled in is also a make.
+        let rebound = rebound::simulate_rebound(scenario);
+        let contacts = rebound.as_ref().map_or(& [][..], |rebound| & rebound.contacts[..]);
+        let flag_backboard_first = contacts.first().is_some_and(|contact| contact.kind == ContactKind::Backboard);
+        let flag_rim = contacts.iter().any(|contact| matches!(contact.kind, ContactKind::FrontRim | ContactKind::BackRim));
+        if flag_scored || rebound.as_ref().is_some_and(|rebound| rebound.flag_rolled_in) {
+            return match (flag_backboard_first, flag_rim) {
+                (true, _) => ShotResult::BackboardIn,
+                (false, true) => ShotResult::RimIn,
+                (false, false) => ShotResult::Swish,
+            };
+        }
+        if flag_rim {
+            return ShotResult::RimOut;
+        }
+        match MissCorrection::new(scenario).map(|correction| correction.kind) {
+            Some(MissKind::Low) => ShotResult::Airball,
+            Some(MissKind::Short) => ShotResult::Short,
+            // Off the backboard, or through the center between two samples.
+            Some(MissKind::Long) | None => ShotResult::Long,
+        }
+    }
+
+    pub fn is_make(self) -> bool {
+        matches!(self, ShotResult::Swish | ShotResult::RimIn | ShotResult::BackboardIn)
+    }
+
+    /// The name in the JSON records, like "rim_out".
+    pub fn name(self) -> &'static str {
+        match self {
+            ShotResult::Swish => "swish",
+            ShotResult::RimIn => "rim_in",
+            ShotResult::BackboardIn => "backboard_in",
+            ShotResult::RimOut => "rim_out",
+            ShotResult::Airball => "airball",
+            ShotResult::Blocked => "blocked",
+            ShotResult::Short => "short",
+            ShotResult::Long => "long",
+            ShotResult::Obstacle => "obstacle",
+        }
+    }
+
+    pub fn label(self, messages: & Messages) -> &'static str {
+        match self {
+            ShotResult::Swish => messages.result_swish,
+            ShotResult::RimIn => messages.result_rim_in,
+            ShotResult::BackboardIn => messages.result_backboard_in,
+            ShotResult::RimOut => messages.result_rim_out,
+            ShotResult::Airball => messages.result_airball,
+            ShotResult::Blocked => messages.result_blocked,
+            ShotResult::Short => messages.result_short,
+            ShotResult::Long => messages.result_long,
+            ShotResult::Obstacle => messages.result_obstacle,
+        }
+    }
+
+    /// The exit code of the program with --exit-code, 0 for a make and one for each kind of miss,
+    /// 1 is left for the errors.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ShotResult::Swish | ShotResult::RimIn | ShotResult::BackboardIn => 0,
+            ShotResult::RimOut => 2,
+            ShotResult::Airball => 3,
+            ShotResult::Blocked => 4,
+            ShotResult::Short => 5,
+            ShotResult::Long => 6,
+            ShotResult::Obstacle => 7,
+        }
+    }
+
+    pub fn json(self) -> String {
+        format!("{{\"type\":\"result\",\"result\":\"{}\",\"made\":{}}}", self.name(), self.is_make())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scenario::FREE_THROW_DISTANCE;
+    use crate::trajectory::{basket_backboard_x, required_speed};
+    use crate::units::degrees_to_radians;
+
+    // A free throw at teta_0_deg aimed at (aim_x, aim_y).
+    fn shot_at(aim_x: f64, aim_y: f64, teta_0_deg: f64) -> ScenarioFile {
+        let mut scenario = ScenarioFile::preset("free-throw").unwrap();
+        let throw = & mut scenario.throw;
+        throw.teta_0_deg = teta_0_deg;
+        throw.v_0 = required_speed(throw.pos_0_x, throw.pos_0_y, degrees_to_radians(teta_0_deg), aim_x, aim_y).unwrap();
+        scenario
+    }
+
+    fn classify(scenario: & ScenarioFile) -> ShotResult {
+        ShotResult::classify(scenario, scenario.simulate_2d().0, None, None)
+    }
+
+    const ALL: [ShotResult; 9] = [ShotResult::Swish, ShotResult::RimIn, ShotResult::BackboardIn, ShotResult::RimOut,
+                                  ShotResult::Airball, ShotResult::Blocked, ShotResult::Short, ShotResult::Long,
+                                  ShotResult::Obstacle];
+
+    #[test]
+    fn makes() {
+        assert_eq!(classify(& shot_at(FREE_THROW_DISTANCE, 3.05, 52.0)), ShotResult::Swish);
+        // 15 cm short, off the front of the rim and in.
+        assert_eq!(classify(& shot_at(FREE_THROW_DISTANCE - 0.15, 3.05, 52.0)), ShotResult::RimIn);
+        // At the backboard, 30 cm above the rim.
+        assert_eq!(classify(& shot_at(basket_backboard_x(FREE_THROW_DISTANCE), 3.35, 52.0)), ShotResult::BackboardIn);
+    }
+
+    #[test]
+    fn rim_out() {
+        assert_eq!(classify(& shot_at(FREE_THROW_DISTANCE - 0.3, 3.05, 45.0)), ShotResult::RimOut);
+    }
+
+    #[test]
+    fn airball() {
+        // The top of the arc is 50 cm above the release, below the rim.
+        let mut scenario = ScenarioFile::preset("free-throw").unwrap();
+        scenario.throw.v_0 = 4.0;
+        assert_eq!(classify(& scenario), ShotResult::Airball);
+    }
+
+    #[test]
+    fn blocked() {
+        // The defender 1 m in front of the shooter reaches 3.5 m.
+        let scenario = shot_at(FREE_THROW_DISTANCE, 3.05, 52.0);
+        let defender = Defender { distance: 1.0, reach: 3.5 };
+        assert_eq!(ShotResult::classify(& scenario, true, Some(defender), None), ShotResult::Blocked);
+        let defender = Defender { distance: 1.0, reach: 2.0 };
+        assert_eq!(ShotResult::classify(& scenario, true, Some(defender), None), ShotResult::Swish);
+    }
+
+    #[test]
+    fn short_and_long() {
+        assert_eq!(classify(& shot_at(FREE_THROW_DISTANCE - 1.0, 3.05, 52.0)), ShotResult::Short);
+        // Off the backboard and onto the floor.
+        assert_eq!(classify(& shot_at(FREE_THROW_DISTANCE + 1.5, 3.05, 45.0)), ShotResult::Long);
+    }
+
+    #[test]
+    fn obstacle() {
+        let scenario = shot_at(FREE_THROW_DISTANCE, 3.05, 52.0);
+        let obstacle_hit = ObstacleHit { index: 0, t: 0.2, x: 1.0, y: 3.0 };
+        assert_eq!(ShotResult::classify(& scenario, false, None, Some(obstacle_hit)), ShotResult::Obstacle);
+    }
+
+    #[test]
+    fn exit_codes() {
+        let exit_codes: Vec<i32> = ALL.iter().map(|result| result.exit_code()).collect();
+        assert_eq!(exit_codes, [0, 0, 0, 2, 3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn json_records() {
+        assert_eq!(ShotResult::Swish.json(), "{\"type\":\"result\",\"result\":\"swish\",\"made\":true}");
+        assert_eq!(ShotResult::RimOut.json(), "{\"type\":\"result\",\"result\":\"rim_out\",\"made\":false}");
+        let names: Vec<& str> = ALL.iter().map(|result| result.name()).collect();
+        assert_eq!(names, ["swish", "rim_in", "backboard_in", "rim_out", "airball", "blocked", "short", "long", "obstacle"]);
+        for result in ALL {
+            let value: serde_json::Value = serde_json::from_str(& result.json()).unwrap();
+            assert_eq!(value["result"], result.name());
+            assert_eq!(value["made"], result.is_make());
+        }
+    }
+}
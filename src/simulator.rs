@@ -0,0 +1,134 @@
+//! The shot in 2D advanced on demand, for the interactive frontends that draw the ball while it
+//! flies, a step at a time, instead of the whole trajectory of basketball_2d() at once. The
+//! parameters can change in the middle of the flight, like a gust of wind, and the shot starts
+//! again from the release with reset().
+//!
+//! Without the drag the steps are exact, with it they are the RK4 steps of basketball_2d_drag(),
+//! of at most DRAG_MAX_DT each.
+
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{drag_factor, drag_rk4_step, euclidean_distance, DRAG_MAX_DT, GRAVITY,
+                        MIN_BALL_DELTA_TO_BASKET_CENTER};
+use crate::wind::{wind_at, WindSample};
+
+/// The ball at the instant t, the position and the velocity in m and m/s.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SimState {
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+    pub v_x: f64,
+    pub v_y: f64,
+}
+
+pub struct Simulator {
+    initial: SimState,
+    state: SimState,
+    basket_pos_x: f64,
+    basket_pos_y: f64,
+    /// The factor of the drag, None without the air.
+    drag_k: Option<f64>,
+    wind: Vec<WindSample>,
+    /// The wind from the gust on, instead of the samples.
+    gust: Option<(f64, f64)>,
+    flag_entered: bool,
+}
+
+impl Simulator {
+    /// The shot of the scenario at its release, with the air drag and the wind of the scenario
+    /// when flag_drag.
+    pub fn new(scenario: & ScenarioFile, flag_drag: bool) -> Simulator {
+        let throw = & scenario.throw;
+        let teta_0 = throw.teta_0_deg.to_radians();
+        let initial = SimState {
+            t: 0.0,
+            x: throw.pos_0_x,
+            y: throw.pos_0_y,
+            v_x: throw.v_0 * f64::cos(teta_0),
+            v_y: throw.v_0 * f64::sin(teta_0),
+        };
+        Simulator {
+            initial,
+            state: initial,
+            basket_pos_x: scenario.scene.basket_pos_x,
+            basket_pos_y: scenario.scene.basket_pos_y,
+            drag_k: flag_drag.then(|| drag_factor(scenario.ball.radius, scenario.ball.mass, scenario.air.density())),
+            wind: scenario.wind.clone(),
+            gust: None,
+            flag_entered: false,
+        }
+    }
+
+    pub fn state(& self) -> SimState {
+        self.state
+    }
+
+    /// If the ball entered the basket at one of the steps until now.
+    pub fn has_entered(& self) -> bool {
+        self.flag_entered
+    }
+
+    /// The center of the ball is below the floor.
+    pub fn has_landed(& self) -> bool {
+        self.state.y < 0.0
+    }
+
+    /// Advances the shot dt seconds and returns the new state.
+    pub fn step(& mut self, dt: f64) -> SimState {
+        assert!(dt > 0.0);
+        let SimState { t, x, y, v_x, v_y } = self.state;
+        self.state = match self.drag_k {
+            None => SimState {
+                t: t + dt,
+                x: x + v_x * dt,
+                y: y + v_y * dt - (1.0/2.0) * GRAVITY * dt * dt,
+                v_x,
+                v_y: v_y - GRAVITY * dt,
+            },
+            Some(k) => {
+                let num_substeps = f64::ceil(dt / DRAG_MAX_DT) as usize;
+                let sub_dt = dt / num_substeps as f64;
+                let (wind, gust) = (& self.wind, self.gust);
+                let wind_now = |t: f64| gust.unwrap_or_else(|| wind_at(wind, t));
+                let mut ball = (x, y, v_x, v_y);
+                for i in 0..num_substeps {
+                    ball = drag_rk4_step(ball, t + i as f64 * sub_dt, sub_dt, k, wind_now);
+                }
+                let (x, y, v_x, v_y) = ball;
+                SimState { t: t + dt, x, y, v_x, v_y }
+            },
+        };
+        let dist = euclidean_distance(self.state.x, self.state.y, 0.0, self.basket_pos_x, self.basket_pos_y, 0.0);
+        self.flag_entered |= dist <= MIN_BALL_DELTA_TO_BASKET_CENTER;
+        self.state
+    }
+
+    /// Advances the shot in steps of at most max_dt until the instant t, nothing when it's
+    /// already there.
+    pub fn run_until(& mut self, t: f64, max_dt: f64) -> SimState {
+        assert!(max_dt > 0.0);
+        while self.state.t < t {
+            self.step(f64::min(max_dt, t - self.state.t));
+        }
+        self.state
+    }
+
+    /// Back to the release, without the changes of the flight.
+    pub fn reset(& mut self) {
+        self.state = self.initial;
+        self.gust = None;
+        self.flag_entered = false;
+    }
+
+    /// A constant wind (v_x, v_y) in m/s from now on, instead of the wind of the scenario. Only
+    /// the shot with the drag feels it.
+    pub fn set_wind(& mut self, v_x: f64, v_y: f64) {
+        self.gust = Some((v_x, v_y));
+    }
+
+    /// Changes the velocity of the ball now, like a touch of a hand.
+    pub fn set_velocity(& mut self, v_x: f64, v_y: f64) {
+        self.state.v_x = v_x;
+        self.state.v_y = v_y;
+    }
+}
@@ -0,0 +1,169 @@
+//! The goals of the other sports, the kinematics of a ball are the same in all of them. The goal
+//! is in the scenario file, in TOML the [goal] table with its kind:
+//!
+//! ```text
+//! [goal]
+//! kind = "soccer-goal"
+//! line_x = 11.0
+//! crossbar_y = 2.44
+//! ```
+//!
+//! Each one has its success: a soccer ball crosses the goal line under the crossbar, a
+//! volleyball clears the net and lands in the court on the other side, a dart hits the board,
+//! and the closer to the center the more points. They are checked at the crossing of the plane
+//! of the goal between the samples, the ball with its radius.
+
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{self, Messages, Num};
+
+// The goals of the presets.
+pub const SOCCER_PENALTY_DISTANCE: f64 = 11.0; // m - From the penalty spot to the goal line.
+pub const SOCCER_CROSSBAR_HEIGHT: f64 = 2.44;  // m - The bottom of the crossbar.
+pub const VOLLEYBALL_NET_HEIGHT: f64 = 2.43;   // m - Men.
+pub const VOLLEYBALL_HALF_COURT: f64 = 9.0;    // m - From the net to the end line.
+pub const DARTBOARD_DISTANCE: f64 = 2.37;      // m - From the oche to the face of the board.
+pub const DARTBOARD_HEIGHT: f64 = 1.73;        // m - The center, the bullseye.
+pub const DARTBOARD_RADIUS: f64 = 0.17;        // m - To the outside of the double ring.
+pub const DARTBOARD_NUM_RINGS: u32 = 10;       // The score of the center, the rings are 1 point apart.
+
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Goal {
+    /// The goal line at line_x, the ball scores under the crossbar at crossbar_y.
+    SoccerGoal { line_x: f64, crossbar_y: f64 },
+    /// The net at net_x with its top at net_y, the court on the other side is court_length long.
+    VolleyballNet { net_x: f64, net_y: f64, court_length: f64 },
+    /// The face of the board at x, the center at y.
+    Dartboard { x: f64, y: f64, radius: f64 },
+}
+
+/// What the ball did at the goal, the distances in meters.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GoalOutcome {
+    /// The ball lands before the plane of the goal.
+    Short,
+    /// Under the crossbar, the clearance between the top of the ball and the crossbar.
+    SoccerScored { clearance: f64 },
+    SoccerOverTheBar { clearance: f64 },
+    /// Over the net, the clearance between the bottom of the ball and the top of the net, and
+    /// where the ball lands, from the net, None if it doesn't land in the simulated time.
+    VolleyballIn { clearance: f64, landing: f64 },
+    VolleyballNet { clearance: f64 },
+    VolleyballOut { clearance: f64, landing: Option<f64> },
+    /// The distance from the center of the board, and the score of its ring.
+    DartboardHit { distance: f64, score: u32 },
+    DartboardMiss { distance: f64 },
+}
+
+impl Goal {
+    pub fn name(& self, messages: & Messages) -> &'static str {
+        match self {
+            Goal::SoccerGoal { .. } => messages.goal_soccer,
+            Goal::VolleyballNet { .. } => messages.goal_volleyball,
+            Goal::Dartboard { .. } => messages.goal_dartboard,
+        }
+    }
+
+    /// The position x of the plane of the goal.
+    pub fn plane_x(& self) -> f64 {
+        match * self {
+            Goal::SoccerGoal { line_x, .. } => line_x,
+            Goal::VolleyballNet { net_x, .. } => net_x,
+            Goal::Dartboard { x, .. } => x,
+        }
+    }
+
+    /// The position of the plane must be finite, the heights and the lengths positive.
+    pub fn validate(& self) -> Result<(), String> {
+        let ((plane_name, plane_x), lengths) = match * self {
+            Goal::SoccerGoal { line_x, crossbar_y } => (("line_x", line_x), vec![("crossbar_y", crossbar_y)]),
+            Goal::VolleyballNet { net_x, net_y, court_length } => (("net_x", net_x), vec![("net_y", net_y), ("court_length", court_length)]),
+            Goal::Dartboard { x, y, radius } => (("x", x), vec![("y", y), ("radius", radius)]),
+        };
+        if !plane_x.is_finite() {
+            return Err(format!("goal.{} is {}, expected a finite number.", plane_name, plane_x));
+        }
+        for (name, value) in lengths {
+            if !(value > 0.0 && value.is_finite()) {
+                return Err(format!("goal.{} is {}, expected a positive number.", name, value));
+            }
+        }
+        Ok(())
+    }
+
+    /// The outcome of the path of the samples (t, (x, y), flag_enter_instant) of a ball of radius.
+    pub fn outcome(& self, samples: & [(f64, (f64, f64), bool)], radius: f64) -> GoalOutcome {
+        let Some(y) = height_at_x(samples, self.plane_x()) else {
+            return GoalOutcome::Short;
+        };
+        match * self {
+            Goal::SoccerGoal { crossbar_y, .. } => {
+                let clearance = crossbar_y - (y + radius);
+                if clearance >= 0.0 { GoalOutcome::SoccerScored { clearance } } else { GoalOutcome::SoccerOverTheBar { clearance } }
+            },
+            Goal::VolleyballNet { net_x, net_y, court_length } => {
+                let clearance = y - radius - net_y;
+                if clearance < 0.0 {
+                    return GoalOutcome::VolleyballNet { clearance };
+                }
+                let landing = landing_x(samples, net_x, radius).map(|x| f64::abs(x - net_x));
+                match landing {
+                    Some(landing) if landing <= court_length => GoalOutcome::VolleyballIn { clearance, landing },
+                    _ => GoalOutcome::VolleyballOut { clearance, landing },
+                }
+            },
+            Goal::Dartboard { y: center_y, radius: board_radius, .. } => {
+                let distance = f64::abs(y - center_y);
+                if distance > board_radius {
+                    return GoalOutcome::DartboardMiss { distance };
+                }
+                let ring = f64::floor(distance / board_radius * DARTBOARD_NUM_RINGS as f64) as u32;
+                GoalOutcome::DartboardHit { distance, score: DARTBOARD_NUM_RINGS - u32::min(ring, DARTBOARD_NUM_RINGS - 1) }
+            },
+        }
+    }
+}
+
+impl GoalOutcome {
+    pub fn is_success(& self) -> bool {
+        matches!(self, GoalOutcome::SoccerScored { .. } | GoalOutcome::VolleyballIn { .. } | GoalOutcome::DartboardHit { .. })
+    }
+
+    /// The outcome in words, with the numbers at precision decimal places.
+    pub fn describe(& self, precision: usize, messages: & Messages) -> String {
+        let number = |value: f64| format!("{:0.*}", precision, Num(value));
+        match * self {
+            GoalOutcome::Short => messages.goal_short.to_string(),
+            GoalOutcome::SoccerScored { clearance } => i18n::fill(messages.goal_soccer_scored, &[& number(clearance)]),
+            GoalOutcome::SoccerOverTheBar { clearance } => i18n::fill(messages.goal_soccer_over, &[& number(-clearance)]),
+            GoalOutcome::VolleyballIn { clearance, landing } => i18n::fill(messages.goal_volleyball_in, &[& number(clearance), & number(landing)]),
+            GoalOutcome::VolleyballNet { clearance } => i18n::fill(messages.goal_volleyball_net, &[& number(-clearance)]),
+            GoalOutcome::VolleyballOut { clearance, landing } => match landing {
+                Some(landing) => i18n::fill(messages.goal_volleyball_out, &[& number(clearance), & number(landing)]),
+                None => i18n::fill(messages.goal_volleyball_in_air, &[& number(clearance)]),
+            },
+            GoalOutcome::DartboardHit { distance, score } => i18n::fill(messages.goal_dartboard_hit, &[& number(distance), & score]),
+            GoalOutcome::DartboardMiss { distance } => i18n::fill(messages.goal_dartboard_miss, &[& number(distance)]),
+        }
+    }
+}
+
+// The height of the center of the ball at its first crossing of x, interpolated between the
+// samples, None if it doesn't get there.
+fn height_at_x(samples: & [(f64, (f64, f64), bool)], x: f64) -> Option<f64> {
+    samples.windows(2).find_map(|pair| {
+        let ((x_0, y_0), (x_1, y_1)) = (pair[0].1, pair[1].1);
+        let flag_crosses = (x_0 - x) * (x_1 - x) <= 0.0 && x_0 != x_1;
+        flag_crosses.then(|| y_0 + (y_1 - y_0) * (x - x_0) / (x_1 - x_0))
+    })
+}
+
+// Where the ball first touches the floor after it crosses x, None if it doesn't in the samples.
+fn landing_x(samples: & [(f64, (f64, f64), bool)], x: f64, radius: f64) -> Option<f64> {
+    let i_cross = samples.windows(2).position(|pair| (pair[0].1.0 - x) * (pair[1].1.0 - x) <= 0.0)?;
+    samples[i_cross..].windows(2).find_map(|pair| {
+        let ((x_0, y_0), (x_1, y_1)) = (pair[0].1, pair[1].1);
+        (y_0 >= radius && y_1 < radius).then(|| x_0 + (x_1 - x_0) * (y_0 - radius) / (y_0 - y_1))
+    })
+}
@@ -0,0 +1,199 @@
+//! Streaming of the long simulations, like a million steps. The samples of Flight2D are written
+//! to the CSV or to the SVG as they are computed, through a buffered writer, so the memory
+//! doesn't grow with the number of steps.
+//!
+//! The SVG is a static figure, the arc line, the basket and the instant that the ball entered
+//! the basket. Its bounds come from the equations, before the first sample, and the points of
+//! the line closer than half a pixel to the previous one are skipped.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+
+use crate::plot_svg::SvgPlotOptions;
+use crate::scenario::ScenarioFile;
+use crate::svg_gen::{escape_text, Style};
+use crate::trajectory::{basket_backboard_x, sample_acceleration, sample_velocity, GRAVITY, BACKBOARD_BELOW_RIM, BACKBOARD_HEIGHT};
+
+/// What was streamed, the samples above the floor and if the ball entered the basket.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StreamSummary {
+    pub num_samples: usize,
+    pub flag_scored: bool,
+}
+
+/// Decimal places of the numbers of the CSV, by default.
+pub const DEFAULT_CSV_PRECISION: usize = 6;
+
+/// Writes a line t,x,y,vx,vy,speed,ax,ay,entered for each sample above the floor, in seconds,
+/// meters, m/s and m/s², with precision decimal places. The velocity and the acceleration are
+/// from the parabola through the sample and its 2 neighbours, so each line is written when the
+/// next sample arrives.
+pub fn write_samples_csv<W: Write>(writer: W, samples: impl Iterator<Item = (f64, (f64, f64), bool)>,
+                                   precision: usize) -> io::Result<StreamSummary> {
+    let mut writer = BufWriter::new(writer);
+    let mut summary = StreamSummary::default();
+    writeln!(writer, "t,x,y,vx,vy,speed,ax,ay,entered")?;
+    let mut window: Vec<(f64, (f64, f64), bool)> = Vec::with_capacity(3);
+    let mut num_samples_in = 0_usize;
+    for sample in samples {
+        summary.flag_scored |= sample.2;
+        if window.len() == 3 {
+            window.remove(0);
+        }
+        window.push(sample);
+        num_samples_in += 1;
+        // The first sample is written with the second one, they share the parabola.
+        if num_samples_in == 3 {
+            write_sample_csv(& mut writer, & window, 0, precision, & mut summary)?;
+        }
+        if window.len() == 3 {
+            write_sample_csv(& mut writer, & window, 1, precision, & mut summary)?;
+        }
+    }
+    // The samples not written yet, the last one, or all when there are fewer than 3.
+    let first_pending = if window.len() == 3 {2} else {0};
+    for i in first_pending..window.len() {
+        write_sample_csv(& mut writer, & window, i, precision, & mut summary)?;
+    }
+    writer.flush()?;
+    Ok(summary)
+}
+
+// A line of the CSV, when the i-th sample of the window is above the floor.
+fn write_sample_csv<W: Write>(writer: & mut W, window: & [(f64, (f64, f64), bool)], i: usize, precision: usize,
+                              summary: & mut StreamSummary) -> io::Result<()> {
+    let (t, (x, y), flag_enter_instant) = window[i];
+    if y < 0.0 {
+        return Ok(());
+    }
+    let (v_x, v_y) = sample_velocity(window, i);
+    let (a_x, a_y) = sample_acceleration(window, i);
+    for value in [t, x, y, v_x, v_y, f64::hypot(v_x, v_y), a_x, a_y] {
+        write!(writer, "{:.*},", precision, value)?;
+    }
+    writeln!(writer, "{}", u8::from(flag_enter_instant))?;
+    summary.num_samples += 1;
+    Ok(())
+}
+
+/// Writes the figure of the shot of the scenario, call validate() first.
+/// Only the size, the margin, the theme and the precision of the options are used.
+pub fn write_scenario_svg<W: Write>(writer: W, scenario: & ScenarioFile, options: & SvgPlotOptions) -> io::Result<StreamSummary> {
+    let mut writer = BufWriter::new(writer);
+    let theme = & options.theme;
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+
+    // The same scale as plot_trajectory_svg(), with the bounds of the scene from the equations.
+    let (x_min, x_max, y_max) = scene_bounds(scenario);
+    let margin = f64::min(options.margin as f64, f64::min(options.width as f64, options.height as f64) / 4.0);
+    let plot_width  = options.width as f64 - 2.0 * margin;
+    let plot_height = options.height as f64 - 2.0 * margin;
+    let scale_factor = f64::min(plot_width / f64::max(x_max - x_min, 1e-9),
+                                plot_height / f64::max(y_max, 1e-9));
+    let floor_svg_y = options.height as f64 - margin;
+    let to_svg_x = |x: f64| margin + (x - x_min) * scale_factor;
+    let to_svg_y = |y: f64| floor_svg_y - y * scale_factor;
+    let precision = options.precision;
+
+    let mut elem_str = String::with_capacity(200);
+    writeln!(writer, "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0:.2}\" height=\"{1:.2}\" viewBox=\"0 0 {0:.2} {1:.2}\">",
+             options.width, options.height)?;
+    let _ = write!(elem_str, "<rect width=\"100%\" height=\"100%\"");
+    Style::new().fill(theme.background.clone()).write_attrs(& mut elem_str);
+    elem_str.push_str(" />\n<path");
+    Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5).write_attrs(& mut elem_str);
+    elem_str.push_str(" d=\"");
+    writer.write_all(elem_str.as_bytes())?;
+
+    let mut summary = StreamSummary::default();
+    let mut enter_point: Option<(f64, f64, f64)> = None;
+    let mut last_written: Option<(f64, f64)> = None;
+    let mut pending: Option<(f64, f64)> = None;
+    for (t, (x, y), flag_enter_instant) in scenario.flight_2d() {
+        summary.flag_scored |= flag_enter_instant;
+        if y < 0.0 {
+            continue;
+        }
+        summary.num_samples += 1;
+        if flag_enter_instant && enter_point.is_none() {
+            enter_point = Some((t, x, y));
+        }
+        let point = (to_svg_x(x), to_svg_y(y));
+        pending = Some(point);
+        let flag_far = last_written.is_none_or(|(x_0, y_0)| f64::hypot(point.0 - x_0, point.1 - y_0) >= 0.5);
+        if flag_far {
+            write!(writer, "{}{:.3$},{:.3$}", if last_written.is_none() {"M"} else {" L"}, point.0, point.1, precision)?;
+            last_written = Some(point);
+            pending = None;
+        }
+    }
+    // The last sample ends the line, even inside the same pixel.
+    if let Some((x, y)) = pending {
+        write!(writer, " L{:.2$},{:.2$}", x, y, precision)?;
+    }
+    writeln!(writer, "\" />")?;
+
+    elem_str.clear();
+    // The floor, the rim and the backboard.
+    let court_style = Style::new().stroke(theme.basket.clone()).stroke_width(1.0);
+    let _ = write!(elem_str, "<line x1=\"{:.4$}\" y1=\"{:.4$}\" x2=\"{:.4$}\" y2=\"{:.4$}\"",
+                   margin, floor_svg_y, options.width as f64 - margin, floor_svg_y, precision);
+    court_style.write_attrs(& mut elem_str);
+    elem_str.push_str(" />\n");
+    let backboard_x = to_svg_x(basket_backboard_x(basket_pos_x));
+    let backboard_bottom = basket_pos_y - BACKBOARD_BELOW_RIM;
+    let _ = write!(elem_str, "<line x1=\"{0:.3$}\" y1=\"{1:.3$}\" x2=\"{0:.3$}\" y2=\"{2:.3$}\"",
+                   backboard_x, to_svg_y(backboard_bottom), to_svg_y(backboard_bottom + BACKBOARD_HEIGHT), precision);
+    court_style.write_attrs(& mut elem_str);
+    elem_str.push_str(" />\n");
+    let _ = write!(elem_str, "<rect x=\"{:.2$}\" y=\"{:.2$}\" width=\"20.00\" height=\"4.00\"",
+                   to_svg_x(basket_pos_x) - 10.0, to_svg_y(basket_pos_y) - 2.0, precision);
+    Style::new().fill(theme.basket.clone()).write_attrs(& mut elem_str);
+    elem_str.push_str(" />\n");
+    if let Some((t, x, y)) = enter_point {
+        let _ = write!(elem_str, "<circle cx=\"{:.2$}\" cy=\"{:.2$}\" r=\"3.00\"", to_svg_x(x), to_svg_y(y), precision);
+        Style::new().fill(theme.score_marker.clone()).write_attrs(& mut elem_str);
+        let _ = writeln!(elem_str, "><title>t: {:0.4} s, ball entered the basket</title></circle>", t);
+    }
+    let label = format!("v_0 = {:0.2} m/s & teta_0 = {:0.2}° -> {}, {} samples", scenario.throw.v_0, scenario.throw.teta_0_deg,
+                        if summary.flag_scored {"entered the basket"} else {"missed the basket"}, summary.num_samples);
+    let _ = write!(elem_str, "<text x=\"{:.2$}\" y=\"{:.2$}\" font-size=\"12\"", margin, margin, precision);
+    Style::new().fill(theme.text.clone()).write_attrs(& mut elem_str);
+    let _ = writeln!(elem_str, ">{}</text>\n</svg>", escape_text(&label));
+    writer.write_all(elem_str.as_bytes())?;
+    writer.flush()?;
+    Ok(summary)
+}
+
+/// Streams the samples of the scenario to a CSV file, with precision decimal places.
+pub fn stream_scenario_csv(scenario: & ScenarioFile, filename: & str, precision: usize) -> Result<StreamSummary, String> {
+    let file = File::create(filename).map_err(|error| format!("Can't create the file {}: {}", filename, error))?;
+    write_samples_csv(file, scenario.flight_2d(), precision).map_err(|error| format!("Can't write the file {}: {}", filename, error))
+}
+
+/// Streams the figure of the scenario to an SVG file.
+pub fn stream_scenario_svg(scenario: & ScenarioFile, filename: & str, options: & SvgPlotOptions) -> Result<StreamSummary, String> {
+    let file = File::create(filename).map_err(|error| format!("Can't create the file {}: {}", filename, error))?;
+    write_scenario_svg(file, scenario, options).map_err(|error| format!("Can't write the file {}: {}", filename, error))
+}
+
+// The XX range and the maximum height of the flight above the floor, the backboard and the basket.
+fn scene_bounds(scenario: & ScenarioFile) -> (f64, f64, f64) {
+    let (x_0, y_0) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let teta_0 = scenario.throw.teta_0_deg.to_radians();
+    let v_0_x = scenario.throw.v_0 * f64::cos(teta_0);
+    let v_0_y = scenario.throw.v_0 * f64::sin(teta_0);
+    let y_at = |t: f64| y_0 + v_0_y * t - (1.0/2.0) * GRAVITY * t * t;
+    // The last instant above the floor, the ball lands or the simulation ends.
+    let t_floor = (v_0_y + f64::sqrt(f64::max(v_0_y * v_0_y + 2.0 * GRAVITY * y_0, 0.0))) / GRAVITY;
+    let t_last = f64::clamp(t_floor, 0.0, scenario.simulated_sec());
+    let t_apex = f64::clamp(v_0_y / GRAVITY, 0.0, t_last);
+    let x_last = x_0 + v_0_x * t_last;
+    let backboard_x = basket_backboard_x(scenario.scene.basket_pos_x);
+    let backboard_top = scenario.scene.basket_pos_y - BACKBOARD_BELOW_RIM + BACKBOARD_HEIGHT;
+    let x_min = f64::min(f64::min(x_0, x_last), scenario.scene.basket_pos_x);
+    let x_max = f64::max(f64::max(x_0, x_last), backboard_x);
+    let y_max = f64::max(f64::max(y_at(t_apex), y_0), backboard_top);
+    (x_min, x_max, y_max)
+}
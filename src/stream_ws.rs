@@ -0,0 +1,44 @@
+//! Streaming of the samples over a WebSocket, a dashboard or a browser page animates the flight live.
+//!
+//! Each sample is one text message, a JSON object sent at the simulated instant:
+//!
+//! ```text
+//! {"type":"sample","t":1.6271,"x":11.5055,"y":0.0234,"vx":7.0711,"vy":-8.8861,"entered":false}
+//! ```
+//!
+//! After the last sample there is one message with the summary of the shot, null without samples:
+//!
+//! ```text
+//! {"type":"end","summary":{"scored":false,"apex_m":4.0488,...}}
+//! ```
+
+use std::net::TcpListener;
+
+use tungstenite::Message;
+
+use crate::telemetry::{pace_samples, sample_json};
+use crate::trajectory::{ShotSummary, Trajectory};
+
+/// Waits at the address, like "127.0.0.1:9001", for one client and sends it the samples at
+/// the simulated rate, then the summary, and closes the connection. The samples have precision
+/// decimal places.
+pub fn stream_trajectory_ws(addr: & str, trajectory_2d: & Trajectory, basket_pos_x: f64, basket_pos_y: f64,
+                            precision: usize) -> Result<(), String> {
+    let listener = TcpListener::bind(addr).map_err(|error| format!("Can't listen at {}: {}", addr, error))?;
+    println!("Waiting for a WebSocket client at ws://{} ...", addr);
+    let (stream, client_addr) = listener.accept().map_err(|error| format!("Can't accept the client: {}", error))?;
+    let mut websocket = tungstenite::accept(stream)
+        .map_err(|error| format!("WebSocket handshake with {} failed: {}", client_addr, error))?;
+    println!("Streaming the samples to {}.", client_addr);
+
+    let send_error = |error: tungstenite::Error| format!("Can't send to {}: {}", client_addr, error);
+    let samples = & trajectory_2d.1;
+    pace_samples(samples, |i| websocket.send(Message::text(sample_json(samples, i, precision))).map_err(send_error))?;
+    let summary_json = ShotSummary::new(trajectory_2d, basket_pos_x, basket_pos_y)
+        .map_or("null".to_string(), |summary| summary.to_json());
+    websocket.send(Message::text(format!("{{\"type\":\"end\",\"summary\":{}}}", summary_json))).map_err(send_error)?;
+    // The client may already be gone, the samples were sent.
+    let _ = websocket.close(None);
+    let _ = websocket.flush();
+    Ok(())
+}
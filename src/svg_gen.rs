@@ -1,8 +1,11 @@
-/// File that creates and generates the SVG to a string or to a file.
+//! File that creates and generates the SVG to a string or to a file.
 
 // use std::io;
+use std::borrow::Cow;
+use std::fmt;
 use std::fmt::Write;
 
+#[derive(Clone, Debug, PartialEq)]
 pub enum Color {
     Black,
     White,
@@ -11,27 +14,352 @@ pub enum Color {
     Red,
     Yellow,
     Rgb(u8, u8, u8),
+    /// Red, green, blue and the alpha from 0.0 (transparent) to 1.0 (opaque).
+    Rgba(u8, u8, u8, f32),
+    /// Hue in degrees 0.0 to 360.0, saturation and lightness in percent 0.0 to 100.0.
+    Hsl(f32, f32, f32),
+    /// Hexadecimal notation "#rgb", "#rrggbb" or "#rrggbbaa", use Color::parse() to validate it.
+    Hex(String),
 }
 
 impl Color {
-    pub fn to_string(&self) -> String {
+    /// Parses a color name ("green"), or a hexadecimal color ("#ff8800", "#f80" or "#ff880080").
+    pub fn parse(color_str: & str) -> Result<Color, String> {
+        let color_str = color_str.trim();
+        match color_str.to_lowercase().as_str() {
+            "black"  => return Ok(Color::Black),
+            "white"  => return Ok(Color::White),
+            "blue"   => return Ok(Color::Blue),
+            "green"  => return Ok(Color::Green),
+            "red"    => return Ok(Color::Red),
+            "yellow" => return Ok(Color::Yellow),
+            _ => (),
+        }
+        let digits = color_str.strip_prefix('#')
+            .ok_or(format!("Invalid color \"{}\", expected a name or #rrggbb.", color_str))?;
+        let valid_len = matches!(digits.len(), 3 | 6 | 8);
+        if !valid_len || !digits.chars().all(|ch| ch.is_ascii_hexdigit()) {
+            return Err(format!("Invalid hexadecimal color \"{}\", expected #rgb, #rrggbb or #rrggbbaa.", color_str));
+        }
+        Ok(Color::Hex(format!("#{}", digits.to_lowercase())))
+    }
+
+    /// Same color with an alpha, from 0.0 (transparent) to 1.0 (opaque).
+    /// Only the named, Rgb and Hex colors can be converted to Rgba, the others are returned as is.
+    pub fn with_alpha(&self, alpha: f32) -> Color {
+        match self.to_rgb() {
+            Some((r, g, b)) => Color::Rgba(r, g, b, alpha.clamp(0.0, 1.0)),
+            None => self.clone(),
+        }
+    }
+
+    fn to_rgb(&self) -> Option<(u8, u8, u8)> {
         match self {
-            Color::Black  => "black".to_string(),
-            Color::White  => "white".to_string(),
-            Color::Blue   => "blue".to_string(),
-            Color::Green  => "green".to_string(),
-            Color::Red    => "red".to_string(),
-            Color::Yellow => "yellow".to_string(),
-            Color::Rgb(r, g, b) => format!("rgb({},{},{})", r, g, b),
+            Color::Black  => Some((0, 0, 0)),
+            Color::White  => Some((255, 255, 255)),
+            Color::Blue   => Some((0, 0, 255)),
+            Color::Green  => Some((0, 128, 0)),
+            Color::Red    => Some((255, 0, 0)),
+            Color::Yellow => Some((255, 255, 0)),
+            Color::Rgb(r, g, b) | Color::Rgba(r, g, b, _) => Some((*r, *g, *b)),
+            Color::Hsl(_, _, _) => None,
+            Color::Hex(hex) => {
+                let digits = hex.strip_prefix('#')?;
+                let channel = |i: usize, len: usize| {
+                    let value = u8::from_str_radix(digits.get(i * len..(i + 1) * len)?, 16).ok()?;
+                    // In the short notation "#f80" each digit is repeated, "f" is "ff".
+                    Some(if len == 1 { value * 17 } else { value })
+                };
+                let len = if digits.len() == 3 { 1 } else { 2 };
+                Some((channel(0, len)?, channel(1, len)?, channel(2, len)?))
+            },
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: & mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Black  => write!(f, "black"),
+            Color::White  => write!(f, "white"),
+            Color::Blue   => write!(f, "blue"),
+            Color::Green  => write!(f, "green"),
+            Color::Red    => write!(f, "red"),
+            Color::Yellow => write!(f, "yellow"),
+            Color::Rgb(r, g, b) => write!(f, "rgb({},{},{})", r, g, b),
+            Color::Rgba(r, g, b, a) => write!(f, "rgba({},{},{},{:.3})", r, g, b, a),
+            Color::Hsl(h, s, l) => write!(f, "hsl({:.1},{:.1}%,{:.1}%)", h, s, l),
+            Color::Hex(hex) => write!(f, "{}", hex),
+        }
+    }
+}
+
+/// Palettes for drawing many trajectories in the same figure and for heatmaps.
+pub mod palette {
+    use super::Color;
+
+    // Okabe-Ito qualitative palette, distinguishable by color blind people.
+    const QUALITATIVE: [(u8, u8, u8); 8] = [
+        (230, 159,   0),  // Orange
+        ( 86, 180, 233),  // Sky blue
+        (  0, 158, 115),  // Bluish green
+        (240, 228,  66),  // Yellow
+        (  0, 114, 178),  // Blue
+        (213,  94,   0),  // Vermillion
+        (204, 121, 167),  // Reddish purple
+        (255, 255, 255),  // White, instead of the original black, to be visible on dark backgrounds.
+    ];
+
+    // Anchor points of a viridis like sequential palette, from dark purple to yellow.
+    const SEQUENTIAL: [(u8, u8, u8); 5] = [
+        ( 68,   1,  84),
+        ( 59,  82, 139),
+        ( 33, 145, 140),
+        ( 94, 201,  98),
+        (253, 231,  37),
+    ];
+
+    /// The i-th color of the qualitative palette, it cycles after 8 colors.
+    pub fn qualitative(i: usize) -> Color {
+        let (r, g, b) = QUALITATIVE[i % QUALITATIVE.len()];
+        Color::Rgb(r, g, b)
+    }
+
+    /// The first n colors of the qualitative palette.
+    pub fn qualitative_n(n: usize) -> Vec<Color> {
+        (0..n).map(qualitative).collect()
+    }
+
+    /// Color for a value between 0.0 (low) and 1.0 (high), values outside are clamped.
+    pub fn sequential(value: f64) -> Color {
+        let value = if value.is_nan() { 0.0 } else { value.clamp(0.0, 1.0) };
+        let pos = value * (SEQUENTIAL.len() - 1) as f64;
+        let i = usize::min(pos.floor() as usize, SEQUENTIAL.len() - 2);
+        let frac = pos - i as f64;
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * frac).round() as u8;
+        let (r_0, g_0, b_0) = SEQUENTIAL[i];
+        let (r_1, g_1, b_1) = SEQUENTIAL[i + 1];
+        Color::Rgb(lerp(r_0, r_1), lerp(g_0, g_1), lerp(b_0, b_1))
+    }
+}
+
+/// Escapes text content that goes between tags, ex: "v<10 m/s & θ=45°".
+/// Only allocates a new string if there is something to escape.
+pub fn escape_text(text: & str) -> Cow<'_, str> {
+    escape(text, false)
+}
+
+/// Escapes a value that goes inside a double or single quoted attribute.
+/// Only allocates a new string if there is something to escape.
+pub fn escape_attr(value: & str) -> Cow<'_, str> {
+    escape(value, true)
+}
+
+fn escape(s: & str, flag_attr: bool) -> Cow<'_, str> {
+    let needs_escape = |ch: char| match ch {
+        '&' | '<' | '>' => true,
+        '"' | '\'' | '\n' | '\r' | '\t' => flag_attr,
+        // Control characters are not allowed in XML 1.0.
+        _ => ch.is_control() && !matches!(ch, '\n' | '\r' | '\t'),
+    };
+    if !s.chars().any(needs_escape) {
+        return Cow::Borrowed(s);
+    }
+    let mut res = String::with_capacity(s.len() + 16);
+    for ch in s.chars() {
+        match ch {
+            '&'  => res.push_str("&amp;"),
+            '<'  => res.push_str("&lt;"),
+            '>'  => res.push_str("&gt;"),
+            '"'  if flag_attr => res.push_str("&quot;"),
+            '\'' if flag_attr => res.push_str("&apos;"),
+            // Inside attributes the parser normalizes whitespace, so keep it as a char reference.
+            '\n' | '\r' | '\t' if flag_attr => { let _ = write!(res, "&#{};", ch as u32); },
+            '\n' | '\r' | '\t' => res.push(ch),
+            // Drop the control characters that can't be represented in XML 1.0.
+            _ if ch.is_control() => (),
+            _ => res.push(ch),
         }
     }
+    Cow::Owned(res)
+}
+
+/// How the generated SVG text is laid out.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputMode {
+    /// One tag per line, indented by nesting depth. Good for learning and for diffs.
+    Pretty,
+    /// No whitespace between tags. Smaller files for embedding.
+    Minified,
+}
+
+impl OutputMode {
+    pub fn from_name(name: & str) -> Option<OutputMode> {
+        match name {
+            "pretty"   => Some(OutputMode::Pretty),
+            "minified" | "minify" => Some(OutputMode::Minified),
+            _ => None,
+        }
+    }
+}
+
+/// Presentation of an element: fill, stroke and opacity.
+///
+///    let style = Style::new().fill(Color::Green).stroke(Color::Green).stroke_width(1.0);
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Style {
+    /// None is written as fill="none".
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub stroke_width: Option<f32>,
+    /// Lengths of the dashes and gaps, ex: [4.0, 2.0].
+    pub dash_array: Vec<f32>,
+    /// From 0.0 (transparent) to 1.0 (opaque).
+    pub opacity: Option<f32>,
+    /// Id of a clipPath, returned by SVG::add_clip_rect().
+    pub clip_path: Option<String>,
 }
 
+impl Style {
+    pub fn new() -> Self {
+        Style::default()
+    }
+
+    pub fn fill(mut self, color: Color) -> Self {
+        self.fill = Some(color);
+        self
+    }
+
+    pub fn stroke(mut self, color: Color) -> Self {
+        self.stroke = Some(color);
+        self
+    }
+
+    pub fn stroke_width(mut self, width: f32) -> Self {
+        self.stroke_width = Some(width);
+        self
+    }
+
+    pub fn dash_array(mut self, dash_array: & [f32]) -> Self {
+        self.dash_array = dash_array.to_vec();
+        self
+    }
+
+    pub fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = Some(opacity.clamp(0.0, 1.0));
+        self
+    }
+
+    pub fn clip_path(mut self, clip_id: & str) -> Self {
+        self.clip_path = Some(clip_id.to_string());
+        self
+    }
+
+    /// Appends the presentation attributes, each one with a leading space.
+    pub fn write_attrs(& self, str_buf: & mut String) {
+        match & self.fill {
+            Some(color) => write_color_attr(str_buf, "fill", color),
+            None => str_buf.push_str(" fill=\"none\""),
+        }
+        if let Some(color) = & self.stroke {
+            write_color_attr(str_buf, "stroke", color);
+        }
+        if let Some(width) = self.stroke_width {
+            let _ = write!(str_buf, " stroke-width=\"{:.2}\"", width);
+        }
+        if !self.dash_array.is_empty() {
+            str_buf.push_str(" stroke-dasharray=\"");
+            for (i, len) in self.dash_array.iter().enumerate() {
+                let _ = write!(str_buf, "{}{:.2}", if i > 0 {","} else {""}, len);
+            }
+            str_buf.push('"');
+        }
+        if let Some(opacity) = self.opacity {
+            let _ = write!(str_buf, " opacity=\"{:.3}\"", opacity);
+        }
+        if let Some(clip_id) = & self.clip_path {
+            let _ = write!(str_buf, " clip-path=\"url(#{})\"", escape_attr(clip_id));
+        }
+    }
+}
+
+// Only the hex colors are user text, the other ones are written without a temporary string.
+fn write_color_attr(str_buf: & mut String, name: & str, color: & Color) {
+    match color {
+        Color::Hex(hex) => { let _ = write!(str_buf, " {}=\"{}\"", name, escape_attr(hex)); },
+        _ => { let _ = write!(str_buf, " {}=\"{}\"", name, color); },
+    }
+}
+
+/// Decimal places of the coordinates, by default.
+pub const DEFAULT_SVG_PRECISION: usize = 2;
+
+// A buffer of elements laid out as they are written, in Pretty mode one tag per line indented by
+// its nesting depth, in Minified mode without any whitespace between the tags.
+struct Layout {
+    str_buf: String,
+    depth: usize,
+    output_mode: OutputMode,
+}
+
+impl Layout {
+    const INDENT: &'static str = "  ";
+
+    fn new(depth: usize) -> Layout {
+        Layout { str_buf: String::new(), depth, output_mode: OutputMode::Pretty }
+    }
+
+    // The indentation before a tag, in Pretty mode.
+    fn line_start(& mut self) {
+        if self.output_mode == OutputMode::Pretty {
+            for _ in 0..self.depth {
+                self.str_buf.push_str(Self::INDENT);
+            }
+        }
+    }
+
+    // The end of the line of a tag, in Pretty mode.
+    fn line_end(& mut self) {
+        if self.output_mode == OutputMode::Pretty {
+            self.str_buf.push('\n');
+        }
+    }
+
+    // A tag, or an element in a single line, like <title>...</title>.
+    fn line(& mut self, tag: impl fmt::Display) {
+        self.line_start();
+        let _ = write!(self.str_buf, "{}", tag);
+        self.line_end();
+    }
+
+    // The start tag of an element with children, they are one level deeper.
+    fn open(& mut self, tag: impl fmt::Display) {
+        self.line(tag);
+        self.depth += 1;
+    }
+
+    fn close(& mut self, name: & str) {
+        self.depth = self.depth.saturating_sub(1);
+        self.line(format_args!("</{}>", name));
+    }
+}
+
+#[allow(clippy::upper_case_acronyms)]
 pub struct SVG {
     width: f32,
     height: f32,
     background_color: Option<Color>,
-    elem_str_vec: Vec<String>,
+    title: Option<String>,
+    output_mode: OutputMode,
+    flag_responsive: bool,
+    // Definitions, like the clip paths, written inside <defs> before the elements.
+    defs: Layout,
+    num_defs: usize,
+    // All the elements are written to this buffer, laid out in the output mode.
+    elems: Layout,
+    // Decimal places of the coordinates.
+    precision: usize,
 }
 
 impl SVG {
@@ -40,74 +368,213 @@ impl SVG {
             width,
             height,
             background_color,
-            elem_str_vec: Vec::new(),
+            title: None,
+            output_mode: OutputMode::Pretty,
+            flag_responsive: false,
+            // Inside <svg><defs> and inside <svg>.
+            defs: Layout::new(2),
+            num_defs: 0,
+            elems: Layout::new(1),
+            precision: DEFAULT_SVG_PRECISION,
         }
     }
 
-    pub fn add_elem(& mut self, elem_str: String) {
-        self.elem_str_vec.push(elem_str);
+    /// Title of the document, shown by the browsers as a tooltip. It's escaped on output.
+    pub fn set_title(& mut self, title: & str) {
+        self.title = Some(title.to_string());
     }
 
-    // It doesn't make any intermediate allocation, only allocates one string buffer.
-    pub fn to_string_append(&self, str_buf: & mut String) {
-        if let Some(color) = & self.background_color {
-            let _ = write!(str_buf, "<rect width=\"100%\" height=\"100%\" fill=\"{}\" />\n", color.to_string());
-        } 
-
-        // Problem: We need to test the boolean value so that the String.end_with("\n"), doesn't
-        //          to be used. If it was used, because String's are UTF-8, the only possible way
-        //          to interpret the size of each character at the end was if it scanned from the 
-        //          beginning of the string to the end.
-        // 
-        //    TODO: Go deeper and find with details if this assumption are correct, because 
-        //          in theory, the last 3 bytes of the four bytes string have a starting
-        //          bit pattern that is different if they are from a single byte character,
-        //          fom a second byte character, from a 3 bytes character, or from a 4 bytes
-        //          character. With that in mind the function String.end_with("\n") would not
-        //          need a full string scan from the beginning of the string, and it should be
-        //          enough to do a scan of at most, the last 4 bytes of the string that
-        //          internally would be accessed in a constant time by the underling Vec.
-        //          That would mean that the cost of using it would be really small.
-        //
-        //    Conclusion: String.ends_width is not linear with the size of the string, it is constant
-        //                time, so we can use it without fear of a full String scan from the beginning
-        //                of the String.
-        //                See this discussion about a similar method but on paths and it mentions
-        //                UTF-8, char boundary detection, because each byte in a multi byte character
-        //                has a preamble.
-        //
-        //              'Path::ends_with' is super super duper slow
-        //              https://users.rust-lang.org/t/path-ends-with-is-super-super-duper-slow/18660
-        //
-        for elem_str in & self.elem_str_vec {
-            str_buf.push_str(elem_str);
-            if !elem_str.ends_with('\n') {
-                str_buf.push('\n');
-            }
+    /// The layout of the elements is written as they are added, set it before adding them.
+    pub fn set_output_mode(& mut self, output_mode: OutputMode) {
+        debug_assert!(self.elems.str_buf.is_empty() && self.defs.str_buf.is_empty(), "The output mode is set after the elements.");
+        self.output_mode = output_mode;
+        self.defs.output_mode = output_mode;
+        self.elems.output_mode = output_mode;
+    }
+
+    /// Decimal places of the coordinates of the elements added after it, the pixels have 2.
+    pub fn set_precision(& mut self, precision: usize) {
+        self.precision = precision;
+    }
+
+    pub fn precision(& self) -> usize {
+        self.precision
+    }
+
+    /// With responsive the figure scales to the width of its container, when embedded
+    /// in an HTML page, instead of having a fixed width and height in pixels.
+    /// The coordinates of the elements are the same, they are mapped by the viewBox.
+    pub fn set_responsive(& mut self, flag_responsive: bool) {
+        self.flag_responsive = flag_responsive;
+    }
+
+    // The size attributes of the svg tag.
+    fn size_attrs(& self) -> String {
+        if self.flag_responsive {
+            format!("width=\"100%\" viewBox=\"0 0 {0:.2} {1:.2}\" preserveAspectRatio=\"xMidYMid meet\"",
+                    self.width,
+                    self.height)
+        } else {
+            format!("width=\"{0:.2}\" height=\"{1:.2}\"", self.width, self.height)
         }
     }
 
-    pub fn to_string(&self) -> String {
-        let mut res_str = String::new();
-        self.to_string_append(& mut res_str);
+    /// Adds a rectangular clip path and returns its id, to be used in Style::clip_path().
+    /// The parts of the elements outside the rectangle are not drawn.
+    pub fn add_clip_rect(& mut self, x: f32, y: f32, width: f32, height: f32) -> String {
+        let clip_id = format!("clip_{}", self.num_defs);
+        self.defs.open(format_args!("<clipPath id=\"{}\">", clip_id));
+        self.defs.line(format_args!("<rect x=\"{0:.4$}\" y=\"{1:.4$}\" width=\"{2:.4$}\" height=\"{3:.4$}\" />",
+                                    x, y, width, height, self.precision));
+        self.defs.close("clipPath");
+        self.num_defs += 1;
+        clip_id
+    }
 
-        res_str
+    /// Starts a group of elements clipped by the clip path, close it with end_group().
+    pub fn begin_clip_group(& mut self, clip_id: & str) {
+        self.elems.open(format_args!("<g clip-path=\"url(#{})\">", escape_attr(clip_id)));
+    }
+
+    pub fn end_group(& mut self) {
+        self.elems.close("g");
+    }
+
+    /// Adds a CSS style sheet, inside a CDATA section so the CSS doesn't need to be escaped.
+    pub fn add_style(& mut self, css: & str) {
+        self.elems.open("<style>");
+        let css = css.replace("]]>", "]]]]><![CDATA[>");
+        // The CSS is written as it is, without the indentation.
+        let _ = write!(self.elems.str_buf, "<![CDATA[\n{}{}]]>", css, if css.ends_with('\n') {""} else {"\n"});
+        self.elems.line_end();
+        self.elems.close("style");
+    }
+
+    /// Adds an already formatted element without children, a &str, a String or format_args!(),
+    /// without the newline, the layout is added to it. Any user text inside it must be passed
+    /// through escape_text() or escape_attr() by the caller.
+    pub fn add_elem(& mut self, elem: impl fmt::Display) {
+        self.elems.line(elem);
+    }
+
+    /// Starts an element with children, its already formatted start tag like in add_elem(), the
+    /// elements added until end_elem() are inside it.
+    pub fn begin_elem(& mut self, start_tag: impl fmt::Display) {
+        self.elems.open(start_tag);
+    }
+
+    /// Ends the element of begin_elem() with the name of its tag.
+    pub fn end_elem(& mut self, name: & str) {
+        self.elems.close(name);
+    }
+
+    /// Reserves space for additional bytes of elements, for the callers that know the size.
+    pub fn reserve(& mut self, additional: usize) {
+        self.elems.str_buf.reserve(additional);
+    }
+
+    /// Adds a text label, the text is escaped so any user string is safe.
+    /// The text color is the style fill.
+    pub fn add_text(& mut self, x: f32, y: f32, font_size: f32, style: & Style, text: & str) {
+        self.elems.line_start();
+        let _ = write!(self.elems.str_buf, "<text x=\"{0:.3$}\" y=\"{1:.3$}\" font-size=\"{2:.2}\"", x, y, font_size, self.precision);
+        style.write_attrs(& mut self.elems.str_buf);
+        let _ = write!(self.elems.str_buf, ">{}</text>", escape_text(text));
+        self.elems.line_end();
+    }
+
+    pub fn add_circle(& mut self, cx: f32, cy: f32, r: f32, style: & Style) {
+        self.write_circle(cx, cy, r, style);
+        self.elems.str_buf.push_str(" />");
+        self.elems.line_end();
+    }
+
+    /// Circle with a tooltip, the title is shown when the mouse hovers it in a browser. It doesn't
+    /// allocate, for the many circles of the instants of a trajectory.
+    pub fn add_circle_with_title(& mut self, cx: f32, cy: f32, r: f32, style: & Style, title: & str) {
+        self.write_circle(cx, cy, r, style);
+        self.elems.str_buf.push('>');
+        self.elems.line_end();
+        self.elems.depth += 1;
+        self.elems.line(format_args!("<title>{}</title>", escape_text(title)));
+        self.elems.close("circle");
+    }
+
+    // The start of the circle tag, without its end.
+    fn write_circle(& mut self, cx: f32, cy: f32, r: f32, style: & Style) {
+        self.elems.line_start();
+        let _ = write!(self.elems.str_buf, "<circle cx=\"{0:.3$}\" cy=\"{1:.3$}\" r=\"{2:.3$}\"", cx, cy, r, self.precision);
+        style.write_attrs(& mut self.elems.str_buf);
+    }
+
+    pub fn add_rect(& mut self, x: f32, y: f32, width: f32, height: f32, style: & Style) {
+        self.elems.line_start();
+        let _ = write!(self.elems.str_buf, "<rect x=\"{0:.4$}\" y=\"{1:.4$}\" width=\"{2:.4$}\" height=\"{3:.4$}\"",
+                       x, y, width, height, self.precision);
+        style.write_attrs(& mut self.elems.str_buf);
+        self.elems.str_buf.push_str(" />");
+        self.elems.line_end();
+    }
+
+    pub fn add_line(& mut self, x_1: f32, y_1: f32, x_2: f32, y_2: f32, style: & Style) {
+        self.elems.line_start();
+        let _ = write!(self.elems.str_buf, "<line x1=\"{0:.4$}\" y1=\"{1:.4$}\" x2=\"{2:.4$}\" y2=\"{3:.4$}\"",
+                       x_1, y_1, x_2, y_2, self.precision);
+        style.write_attrs(& mut self.elems.str_buf);
+        self.elems.str_buf.push_str(" />");
+        self.elems.line_end();
+    }
+
+    /// Open line through all the points, a single element for a full trajectory.
+    pub fn add_polyline(& mut self, points: & [(f32, f32)], style: & Style) {
+        self.add_poly("polyline", points, style);
+    }
+
+    /// Closed shape through all the points.
+    pub fn add_polygon(& mut self, points: & [(f32, f32)], style: & Style) {
+        self.add_poly("polygon", points, style);
+    }
+
+    fn add_poly(& mut self, tag: & str, points: & [(f32, f32)], style: & Style) {
+        // Each point takes at most around 16 bytes "1234.56,1234.56 ", and the decimal places.
+        self.elems.str_buf.reserve(64 + points.len() * (12 + 2 * self.precision));
+        self.elems.line_start();
+        let _ = write!(self.elems.str_buf, "<{} points=\"", tag);
+        for (i, (x, y)) in points.iter().enumerate() {
+            let _ = write!(self.elems.str_buf, "{}{:.3$},{:.3$}", if i > 0 {" "} else {""}, x, y, self.precision);
+        }
+        self.elems.str_buf.push('"');
+        style.write_attrs(& mut self.elems.str_buf);
+        self.elems.str_buf.push_str(" />");
+        self.elems.line_end();
+    }
+
+    // It doesn't make any intermediate allocation, only allocates one string buffer.
+    pub fn to_string_append(&self, str_buf: & mut String) {
+        let mut head = Layout { str_buf: std::mem::take(str_buf), depth: 1, output_mode: self.output_mode };
+        if let Some(title) = & self.title {
+            head.line(format_args!("<title>{}</title>", escape_text(title)));
+        }
+        if let Some(color) = & self.background_color {
+            head.line(format_args!("<rect width=\"100%\" height=\"100%\" fill=\"{}\" />", color));
+        }
+        if !self.defs.str_buf.is_empty() {
+            head.open("<defs>");
+            head.str_buf.push_str(& self.defs.str_buf);
+            head.close("defs");
+        }
+        head.str_buf.push_str(& self.elems.str_buf);
+        *str_buf = head.str_buf;
     }
 
     // Calculate total capacity required for the string buffer, so it doesn't need to resize a make copies.
     fn calc_estimate_total_string_size(& self, preambule_len: Option<usize>) -> usize {
-        let mut total_str_len = match preambule_len {
-                                              Some(len) => len,
-                                              // The maximum with is the to file preambule with background.
-                                              None => 400,
-                                      };
-        for string_tmp in & self.elem_str_vec {
-            total_str_len += string_tmp.len();
+        // The maximum with is the to file preambule with background.
+        let mut total_str_len = preambule_len.unwrap_or(400);
+        if let Some(title) = & self.title {
+            total_str_len += title.len() + 16;
         }
-        let newlines_coutner = self.elem_str_vec.len();
-        total_str_len += newlines_coutner;
-
-        total_str_len
+        total_str_len + self.defs.str_buf.len() + self.elems.str_buf.len()
     }
 
     /// It's faster, because it doesn't copy to intermediate memory the different substrings.
@@ -121,46 +588,72 @@ impl SVG {
 
         // Write header.
         let _ = write!(res_str,
-"<svg version=\"1.1\"
-baseProfile=\"full\"
-width=\"{0:.2}\" height=\"{1:.2}\"
-xmlns=\"http://www.w3.org/2000/svg\"
-xmlns:xlink=\"http://www.w3.org/1999/xlink\">\n",
-                self.width,
-                self.height);
+                       "<svg version=\"1.1\" baseProfile=\"full\" {0} xmlns=\"http://www.w3.org/2000/svg\" xmlns:xlink=\"http://www.w3.org/1999/xlink\">",
+                       self.size_attrs());
+        if self.output_mode == OutputMode::Pretty {
+            res_str.push('\n');
+        }
 
         // Write all body elements.
         self.to_string_append(& mut res_str);
 
         // Write footer.
         res_str.push_str("</svg>\n");
-      
         res_str
     }
 
     /// Save to file.
     pub fn to_file(&self, filename: & str, file_path: & str) -> Result<(), String> {
-        let res_str = self.to_file_string();
-
-        use std::fs::File;
-        use std::io::Write;
+        write_file(filename, file_path, &self.to_file_string())
+    }
 
-        let mut f;
-        match File::create(String::new() + file_path + filename) {
-            Ok(file) => f = file,
-            Err(error) => {
-                                    eprint!("{}", error);
-                                    return Err(error.to_string());
-                                }
+    /// A full HTML page with the SVG inline. With animation controls it has a play/pause
+    /// button and a slider to scrub the animation to the instant t, that goes from 0 to
+    /// animation_dur_sec seconds. With pan and zoom the mouse wheel zooms, dragging pans
+    /// and a double click resets the view.
+    pub fn to_html_file_string(&self, page_title: & str, flag_animation_controls: bool, flag_pan_zoom: bool,
+                               animation_dur_sec: f32) -> String {
+        let svg_str = self.to_string_insert_in_html();
+        let mut res_str = String::with_capacity(svg_str.len() + 3_000);
+        let _ = write!(res_str,
+"<!DOCTYPE html>
+<html>
+<head>
+<meta charset=\"utf-8\">
+<title>{0}</title>
+</head>
+<body>
+<div id=\"figure\">
+{1}</div>
+",
+                escape_text(page_title),
+                svg_str);
+        if flag_animation_controls {
+            res_str.push_str(&HTML_ANIMATION_CONTROLS.replace("__ANIMATION_DUR_SEC__", &format!("{:.3}", animation_dur_sec)));
         }
-        match f.write_all(res_str.as_bytes()) {
-            Ok(()) => (),
-            Err(error) => {
-                                    eprint!("{}", error);
-                                    return Err(error.to_string());
-                                }
+        if flag_pan_zoom {
+            res_str.push_str(HTML_PAN_ZOOM);
         }
-        Ok( () )
+        res_str.push_str("</body>\n</html>\n");
+        res_str
+    }
+
+    /// Save the HTML page to file.
+    pub fn to_html_file(&self, filename: & str, file_path: & str, page_title: & str,
+                        flag_animation_controls: bool, flag_pan_zoom: bool,
+                        animation_dur_sec: f32) -> Result<(), String> {
+        write_file(filename, file_path,
+                   &self.to_html_file_string(page_title, flag_animation_controls, flag_pan_zoom, animation_dur_sec))
+    }
+
+    /// Prints the figure for the Rust Jupyter kernel, evcxr, as HTML. The SVG is inside an iframe,
+    /// so its ids, like the ones of the animation, don't collide with the figures in other cells.
+    pub fn evcxr_display(&self) {
+        let html_str = self.to_html_file_string(self.title.as_deref().unwrap_or(""), false, false, 0.0);
+        println!("EVCXR_BEGIN_CONTENT text/html\n\
+                  <iframe srcdoc=\"{0}\" width=\"{1}\" height=\"{2}\" style=\"border: none;\"></iframe>\n\
+                  EVCXR_END_CONTENT",
+                 escape_attr(&html_str), self.width + 40.0, self.height + 40.0);
     }
 
     /// It's faster, because it doesn't copy to intermediate memory the different substrings.
@@ -169,19 +662,298 @@ xmlns:xlink=\"http://www.w3.org/1999/xlink\">\n",
         let mut res_str = String::with_capacity(self.calc_estimate_total_string_size(None)); 
 
         // Write header.
-        let _= write!(res_str, 
-                      "<svg width=\"{0:.2}\" height=\"{1:.2}\">\n",
-                      self.width,
-                      self.height);
-        
+        let _ = write!(res_str, "<svg {0}>", self.size_attrs());
+        if self.output_mode == OutputMode::Pretty {
+            res_str.push('\n');
+        }
+
         // Write all body elements.
         self.to_string_append(& mut res_str);
 
         // Write footer.
         res_str.push_str("</svg>\n");
-      
         res_str
     }
-    
 }
 
+// The controls use the SMIL API of the svg element, pauseAnimations(), setCurrentTime(t), ...
+const HTML_ANIMATION_CONTROLS: &str = "<div id=\"controls\">
+<button id=\"play_pause\" type=\"button\">Pause</button>
+<input id=\"scrub\" type=\"range\" min=\"0\" max=\"1000\" value=\"0\" style=\"width: 300px\">
+<span id=\"time\">t = 0.00 s</span>
+</div>
+<script>
+(function () {
+    var svg = document.querySelector('#figure svg');
+    var button = document.getElementById('play_pause');
+    var slider = document.getElementById('scrub');
+    var label = document.getElementById('time');
+    var dur = __ANIMATION_DUR_SEC__;
+    // The ball is animated with SMIL or with CSS, the CSS animations are controlled by the Web Animations API.
+    var css_anims = svg.getAnimations ? svg.getAnimations({ subtree: true }) : [];
+    function is_paused() {
+        return css_anims.length > 0 ? css_anims[0].playState === 'paused' : svg.animationsPaused();
+    }
+    function pause() {
+        css_anims.forEach(function (anim) { anim.pause(); });
+        svg.pauseAnimations();
+    }
+    function play() {
+        css_anims.forEach(function (anim) { anim.play(); });
+        svg.unpauseAnimations();
+    }
+    function seek(t) {
+        css_anims.forEach(function (anim) { anim.currentTime = t * 1000; });
+        svg.setCurrentTime(t);
+    }
+    function current_time() {
+        return css_anims.length > 0 ? css_anims[0].currentTime / 1000 : svg.getCurrentTime();
+    }
+    function show(t) {
+        slider.value = Math.round(t / dur * 1000);
+        label.textContent = 't = ' + t.toFixed(2) + ' s';
+    }
+    button.addEventListener('click', function () {
+        if (is_paused()) {
+            play();
+            button.textContent = 'Pause';
+        } else {
+            pause();
+            button.textContent = 'Play';
+        }
+    });
+    slider.addEventListener('input', function () {
+        pause();
+        button.textContent = 'Play';
+        var t = slider.value * dur / 1000;
+        seek(t);
+        show(t);
+    });
+    setInterval(function () {
+        if (!is_paused()) {
+            show(current_time() % dur);
+        }
+    }, 50);
+})();
+</script>
+";
+
+// Pan and zoom by changing the viewBox of the svg element, in the coordinates of the figure.
+const HTML_PAN_ZOOM: &str = "<script>
+(function () {
+    var svg = document.querySelector('#figure svg');
+    if (!svg.getAttribute('viewBox')) {
+        svg.setAttribute('viewBox', '0 0 ' + svg.getAttribute('width') + ' ' + svg.getAttribute('height'));
+    }
+    var initial = svg.getAttribute('viewBox').split(/[ ,]+/).map(Number);
+    var view = initial.slice();
+    var drag = null;
+    svg.style.cursor = 'grab';
+    function apply() {
+        svg.setAttribute('viewBox', view.join(' '));
+    }
+    // From the mouse position in pixels to the coordinates of the figure.
+    function to_figure(event) {
+        var rect = svg.getBoundingClientRect();
+        return [view[0] + (event.clientX - rect.left) / rect.width * view[2],
+                view[1] + (event.clientY - rect.top) / rect.height * view[3]];
+    }
+    svg.addEventListener('wheel', function (event) {
+        event.preventDefault();
+        var p = to_figure(event);
+        var factor = event.deltaY < 0 ? 0.8 : 1.25;
+        // Zoom around the point under the mouse.
+        view = [p[0] - (p[0] - view[0]) * factor, p[1] - (p[1] - view[1]) * factor,
+                view[2] * factor, view[3] * factor];
+        apply();
+    }, { passive: false });
+    svg.addEventListener('mousedown', function (event) {
+        drag = { x: event.clientX, y: event.clientY, view: view.slice() };
+        svg.style.cursor = 'grabbing';
+    });
+    window.addEventListener('mousemove', function (event) {
+        if (drag) {
+            var rect = svg.getBoundingClientRect();
+            view[0] = drag.view[0] - (event.clientX - drag.x) / rect.width * view[2];
+            view[1] = drag.view[1] - (event.clientY - drag.y) / rect.height * view[3];
+            apply();
+        }
+    });
+    window.addEventListener('mouseup', function () {
+        drag = null;
+        svg.style.cursor = 'grab';
+    });
+    svg.addEventListener('dblclick', function () {
+        view = initial.slice();
+        apply();
+    });
+})();
+</script>
+";
+
+/// Writes the text content to the file in file_path, the file_path ends with a "/".
+pub fn write_file(filename: & str, file_path: & str, content: & str) -> Result<(), String> {
+    use std::fs::File;
+    use std::io::Write;
+
+    let mut f;
+    match File::create(String::new() + file_path + filename) {
+        Ok(file) => f = file,
+        Err(error) => {
+                                eprint!("{}", error);
+                                return Err(error.to_string());
+                            }
+    }
+    match f.write_all(content.as_bytes()) {
+        Ok(()) => (),
+        Err(error) => {
+                                eprint!("{}", error);
+                                return Err(error.to_string());
+                            }
+    }
+    Ok( () )
+}
+
+/// Only the body elements, without the svg header and footer.
+impl fmt::Display for SVG {
+    fn fmt(&self, f: & mut fmt::Formatter) -> fmt::Result {
+        let mut res_str = String::new();
+        self.to_string_append(& mut res_str);
+        f.write_str(&res_str)
+    }
+}
+
+/// Ramer-Douglas-Peucker simplification of a line. Removes the points that are at
+/// less than tolerance from the simplified line, the first and last points are kept.
+pub fn simplify_polyline(points: & [(f32, f32)], tolerance: f32) -> Vec<(f32, f32)> {
+    if points.len() < 3 || tolerance <= 0.0 {
+        return points.to_vec();
+    }
+    // Distance from p to the segment a-b.
+    let dist_to_segment = |p: (f32, f32), a: (f32, f32), b: (f32, f32)| -> f32 {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let len_sq = dx * dx + dy * dy;
+        let u = if len_sq > 0.0 { (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / len_sq).clamp(0.0, 1.0) } else { 0.0 };
+        let (qx, qy) = (a.0 + u * dx, a.1 + u * dy);
+        f32::sqrt((p.0 - qx).powi(2) + (p.1 - qy).powi(2))
+    };
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[points.len() - 1] = true;
+    // With an explicit stack instead of recursion, so that long lines don't overflow the stack.
+    let mut stack: Vec<(usize, usize)> = vec![(0, points.len() - 1)];
+    while let Some((first, last)) = stack.pop() {
+        let mut max_dist = 0.0;
+        let mut max_index = first;
+        for i in (first + 1)..last {
+            let dist = dist_to_segment(points[i], points[first], points[last]);
+            if dist > max_dist {
+                max_dist = dist;
+                max_index = i;
+            }
+        }
+        if max_dist > tolerance {
+            keep[max_index] = true;
+            stack.push((first, max_index));
+            stack.push((max_index, last));
+        }
+    }
+    points.iter().zip(keep.iter()).filter(|(_, keep)| **keep).map(|(p, _)| *p).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_hex_colors() {
+        assert_eq!(Color::parse("#FF8800"), Ok(Color::Hex("#ff8800".to_string())));
+        assert_eq!(Color::parse(" #f80 "), Ok(Color::Hex("#f80".to_string())));
+        assert_eq!(Color::parse("#f80").unwrap().to_rgb(), Some((255, 136, 0)));
+        assert_eq!(Color::parse("#ff880080").unwrap().to_rgb(), Some((255, 136, 0)));
+        assert_eq!(Color::parse("#102030").unwrap().to_string(), "#102030");
+    }
+
+    #[test]
+    fn parse_named_colors() {
+        assert_eq!(Color::parse("green"), Ok(Color::Green));
+        assert_eq!(Color::parse("White"), Ok(Color::White));
+        assert_eq!(Color::parse("YELLOW").unwrap().to_rgb(), Some((255, 255, 0)));
+    }
+
+    #[test]
+    fn parse_invalid_colors() {
+        for color_str in ["", "orange", "ff8800", "#ff88", "#ff880", "#gg8800", "#ff8800801"] {
+            assert!(Color::parse(color_str).is_err(), "{:?}", color_str);
+        }
+    }
+
+    #[test]
+    fn display_rgba_and_hsl() {
+        assert_eq!(Color::Rgba(255, 0, 10, 0.5).to_string(), "rgba(255,0,10,0.500)");
+        assert_eq!(Color::Hsl(120.0, 50.0, 25.5).to_string(), "hsl(120.0,50.0%,25.5%)");
+        assert_eq!(Color::Green.with_alpha(2.0).to_string(), "rgba(0,128,0,1.000)");
+        assert_eq!(Color::Hsl(0.0, 0.0, 0.0).with_alpha(0.5), Color::Hsl(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn hex_color_attribute_is_escaped() {
+        let mut str_buf = String::new();
+        Style::new().fill(Color::Hex("#f80\"/>".to_string())).write_attrs(& mut str_buf);
+        assert_eq!(str_buf, " fill=\"#f80&quot;/&gt;\"");
+    }
+
+    #[test]
+    fn opacity_attribute() {
+        let mut str_buf = String::new();
+        Style::new().fill(Color::Blue).opacity(0.25).write_attrs(& mut str_buf);
+        assert_eq!(str_buf, " fill=\"blue\" opacity=\"0.250\"");
+        // It's clamped to the range 0.0 to 1.0.
+        assert_eq!(Style::new().opacity(1.5).opacity, Some(1.0));
+    }
+
+    #[test]
+    fn clip_path_attribute() {
+        let mut svg = SVG::new(10.0, 10.0, None);
+        let clip_id = svg.add_clip_rect(1.0, 1.0, 8.0, 8.0);
+        let mut str_buf = String::new();
+        Style::new().clip_path(& clip_id).write_attrs(& mut str_buf);
+        assert_eq!(str_buf, format!(" fill=\"none\" clip-path=\"url(#{})\"", clip_id));
+        assert!(svg.to_file_string().contains(& format!("<clipPath id=\"{}\">", clip_id)));
+    }
+
+    #[test]
+    fn escape_text_markup() {
+        assert_eq!(escape_text("a < b && c > d"), "a &lt; b &amp;&amp; c &gt; d");
+        // The quotes and the whitespace are kept in the text.
+        assert_eq!(escape_text("\"shot\" 'A'\n\tB"), "\"shot\" 'A'\n\tB");
+        assert_eq!(escape_text("bell\u{7} end"), "bell end");
+        assert!(matches!(escape_text("v_0 = 10.00 m/s"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn escape_attr_quotes_and_whitespace() {
+        assert_eq!(escape_attr("a \"b\" 'c' <&>"), "a &quot;b&quot; &apos;c&apos; &lt;&amp;&gt;");
+        assert_eq!(escape_attr("1\n2\t3\r"), "1&#10;2&#9;3&#13;");
+        assert_eq!(escape_attr("x\u{0}y"), "xy");
+        assert!(matches!(escape_attr("clip_0"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn layout_of_the_output_modes() {
+        let figure = |output_mode: OutputMode| {
+            let mut svg = SVG::new(10.0, 10.0, None);
+            svg.set_output_mode(output_mode);
+            svg.begin_elem("<g>");
+            svg.add_text(1.0, 2.0, 12.0, & Style::new().fill(Color::White), "two  spaces");
+            svg.end_elem("g");
+            svg.to_file_string()
+        };
+        let pretty = figure(OutputMode::Pretty);
+        assert!(pretty.contains("\n  <g>\n    <text "), "{}", pretty);
+        assert!(pretty.contains(">two  spaces</text>\n  </g>\n</svg>\n"), "{}", pretty);
+        let minified = figure(OutputMode::Minified);
+        assert!(minified.contains("\"><g><text "), "{}", minified);
+        assert!(minified.ends_with(">two  spaces</text></g></svg>\n"), "{}", minified);
+    }
+}
@@ -0,0 +1,103 @@
+//! Kernel of the sweeps of the angle and the speed, it only finds if each candidate scored,
+//! without building the trajectories. With the feature "simd" it tests 4 candidates per
+//! instruction, the scalar kernel is the fallback.
+//!
+//! Both kernels do the same operations as basketball_2d(), in the same order, so they
+//! classify each candidate the same as the full simulation.
+
+#[cfg(feature = "simd")]
+use wide::f64x4;
+
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{euclidean_distance, get_time_steps, GRAVITY, MIN_BALL_DELTA_TO_BASKET_CENTER};
+
+/// If each candidate (teta_0 in degrees, v_0 in m/s) scored, with the other inputs of the scenario.
+pub fn classify_sweep(scenario: & ScenarioFile, candidates: & [(f64, f64)]) -> Vec<bool> {
+    #[cfg(feature = "simd")]
+    let scored = classify_sweep_simd(scenario, candidates);
+    #[cfg(not(feature = "simd"))]
+    let scored = classify_sweep_scalar(scenario, candidates);
+    scored
+}
+
+pub fn classify_sweep_scalar(scenario: & ScenarioFile, candidates: & [(f64, f64)]) -> Vec<bool> {
+    let (x_0, y_0) = (scenario.throw.pos_0_x, scenario.throw.pos_0_y);
+    let (basket_pos_x, basket_pos_y) = (scenario.scene.basket_pos_x, scenario.scene.basket_pos_y);
+    let time_steps = get_time_steps(scenario.simulation.simulation_sec, scenario.simulation.num_steps);
+    candidates.iter().map(|(teta_0_deg, v_0)| {
+        let teta_0 = teta_0_deg.to_radians();
+        let v_0_x = v_0 * f64::cos(teta_0);
+        let v_0_y = v_0 * f64::sin(teta_0);
+        time_steps.iter().any(|t| {
+            let ball_x = x_0 + v_0_x * t;
+            let ball_y = y_0 + v_0_y * t - (1.0/2.0) * GRAVITY * t * t;
+            euclidean_distance(ball_x, ball_y, 0.0, basket_pos_x, basket_pos_y, 0.0) <= MIN_BALL_DELTA_TO_BASKET_CENTER
+        })
+    }).collect()
+}
+
+#[cfg(feature = "simd")]
+pub fn classify_sweep_simd(scenario: & ScenarioFile, candidates: & [(f64, f64)]) -> Vec<bool> {
+    let x_0 = f64x4::splat(scenario.throw.pos_0_x);
+    let y_0 = f64x4::splat(scenario.throw.pos_0_y);
+    let basket_pos_x = f64x4::splat(scenario.scene.basket_pos_x);
+    let basket_pos_y = f64x4::splat(scenario.scene.basket_pos_y);
+    let half_gravity = f64x4::splat((1.0/2.0) * GRAVITY);
+    let min_delta = f64x4::splat(MIN_BALL_DELTA_TO_BASKET_CENTER);
+    let time_steps = get_time_steps(scenario.simulation.simulation_sec, scenario.simulation.num_steps);
+
+    let mut scored = Vec::with_capacity(candidates.len());
+    for chunk in candidates.chunks(4) {
+        // The sin and the cos are scalar, the ones of wide are approximations. The missing
+        // lanes of the last chunk repeat its first candidate.
+        let mut v_0_x = [0.0; 4];
+        let mut v_0_y = [0.0; 4];
+        for lane in 0..4 {
+            let (teta_0_deg, v_0) = chunk[if lane < chunk.len() { lane } else { 0 }];
+            let teta_0 = teta_0_deg.to_radians();
+            v_0_x[lane] = v_0 * f64::cos(teta_0);
+            v_0_y[lane] = v_0 * f64::sin(teta_0);
+        }
+        let (v_0_x, v_0_y) = (f64x4::new(v_0_x), f64x4::new(v_0_y));
+
+        let mut scored_mask = 0_u32;
+        for t in & time_steps {
+            let t = f64x4::splat(*t);
+            let ball_x = x_0 + v_0_x * t;
+            let ball_y = y_0 + v_0_y * t - half_gravity * t * t;
+            let delta_x = ball_x - basket_pos_x;
+            let delta_y = ball_y - basket_pos_y;
+            let dist = (delta_x * delta_x + delta_y * delta_y).sqrt();
+            scored_mask |= dist.simd_le(min_delta).to_bitmask();
+            if scored_mask == 0b1111 {
+                break;
+            }
+        }
+        scored.extend((0..chunk.len()).map(|lane| scored_mask & (1 << lane) != 0));
+    }
+    scored
+}
+
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simd_classifies_the_same_as_scalar() {
+        let scenario = ScenarioFile::default();
+        // 81 x 81 candidates, 6561 is not a multiple of 4, the last lane is a remainder.
+        let candidates: Vec<(f64, f64)> = (0..81).flat_map(|i| (0..81).map(move |j| (30.0 + 0.5 * i as f64, 8.0 + 0.05 * j as f64)))
+            .collect();
+        assert_ne!(candidates.len() % 4, 0);
+        let scored = classify_sweep_scalar(& scenario, & candidates);
+        assert!(scored.iter().any(|flag_scored| *flag_scored) && scored.iter().any(|flag_scored| !flag_scored));
+        assert_eq!(classify_sweep_simd(& scenario, & candidates), scored);
+        // Every length of the remainder, with scored candidates in the last lanes.
+        let near_scored: Vec<(f64, f64)> = candidates.iter().zip(& scored).filter(|(_, flag_scored)| **flag_scored)
+            .map(|(candidate, _)| *candidate).take(7).collect();
+        assert_eq!(near_scored.len(), 7);
+        for len in 1..=near_scored.len() {
+            assert_eq!(classify_sweep_simd(& scenario, & near_scored[..len]), classify_sweep_scalar(& scenario, & near_scored[..len]));
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! More targets than the basket in the plane of the shot, like the basket at the other end of
+//! the court or the hoops of a ring toss. They are in the scenario file, in TOML each one in a
+//! [[targets]] table, the center of the target and its tolerance:
+//!
+//! ```text
+//! [[targets]]
+//! name = "low hoop"
+//! x = 5.0
+//! y = 2.0
+//! tolerance = 0.15
+//! ```
+//!
+//! The ball hits a target when its center passes within the tolerance of the center of the
+//! target, like the basket with MIN_BALL_DELTA_TO_BASKET_CENTER. Each target is checked on its
+//! own, between the samples, until the ball enters the basket.
+
+use serde::{Deserialize, Serialize};
+
+use crate::trajectory::MIN_BALL_DELTA_TO_BASKET_CENTER;
+use crate::units::serde_quantity;
+
+/// A target, the positions in meters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Target {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub x: f64,
+    #[serde(deserialize_with = "serde_quantity::length")]
+    pub y: f64,
+    #[serde(default = "default_tolerance", deserialize_with = "serde_quantity::length")]
+    pub tolerance: f64,
+}
+
+fn default_tolerance() -> f64 {
+    MIN_BALL_DELTA_TO_BASKET_CENTER
+}
+
+/// The first instant that the center of the ball is within the tolerance of a target, and its
+/// position.
+#[derive(Clone, Copy, Debug)]
+pub struct TargetHit {
+    pub t: f64,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// What the shot did to a target, its hit, None when it's missed, and the closest distance from
+/// the center of the ball to the center of the target.
+#[derive(Clone, Copy, Debug)]
+pub struct TargetResult {
+    pub hit: Option<TargetHit>,
+    pub closest: f64,
+}
+
+type Point = (f64, f64);
+
+impl Target {
+    /// A target at (x, y) with the tolerance of the basket.
+    pub fn new(x: f64, y: f64) -> Target {
+        Target { name: String::new(), x, y, tolerance: default_tolerance() }
+    }
+
+    /// The name, or "target" and its number from 1.
+    pub fn label(& self, index: usize) -> String {
+        if self.name.is_empty() { format!("target {}", index + 1) } else { self.name.clone() }
+    }
+}
+
+/// The positions must be finite and the tolerances positive.
+pub fn validate_targets(targets: & [Target]) -> Result<(), String> {
+    for (i, target) in targets.iter().enumerate() {
+        if !(target.x.is_finite() && target.y.is_finite()) {
+            return Err(format!("targets[{}] has a position that isn't a finite number.", i));
+        }
+        if !(target.tolerance > 0.0 && target.tolerance.is_finite()) {
+            return Err(format!("targets[{}].tolerance is {}, expected a positive number.", i, target.tolerance));
+        }
+    }
+    Ok(())
+}
+
+/// The result of each target, in their order, for the path between the samples until the ball
+/// enters the basket.
+pub fn target_results(samples: & [(f64, (f64, f64), bool)], targets: & [Target]) -> Vec<TargetResult> {
+    // The samples after the one that entered the basket aren't flown.
+    let num_flown = samples.iter().position(|(_t, _position, flag_enter_instant)| *flag_enter_instant)
+        .map_or(samples.len(), |i| i + 1);
+    let samples = & samples[..num_flown];
+    targets.iter().map(|target| {
+        let center = (target.x, target.y);
+        let mut result = TargetResult { hit: None, closest: f64::INFINITY };
+        if let [(t, position, _)] = samples {
+            result.closest = distance(*position, center);
+            if result.closest <= target.tolerance {
+                result.hit = Some(TargetHit { t: *t, x: position.0, y: position.1 });
+            }
+        }
+        for pair in samples.windows(2) {
+            let ((t_0, p_0, _), (t_1, p_1, _)) = (pair[0], pair[1]);
+            result.closest = f64::min(result.closest, segment_distance((p_0, p_1), center));
+            if result.hit.is_none() {
+                result.hit = first_touch((p_0, p_1), center, target.tolerance).map(|frac| TargetHit {
+                    t: t_0 + (t_1 - t_0) * frac,
+                    x: p_0.0 + (p_1.0 - p_0.0) * frac,
+                    y: p_0.1 + (p_1.1 - p_0.1) * frac,
+                });
+            }
+        }
+        result
+    }).collect()
+}
+
+fn distance(p: Point, q: Point) -> f64 {
+    f64::hypot(p.0 - q.0, p.1 - q.1)
+}
+
+// The distance from the point to the path.
+fn segment_distance((a, b): (Point, Point), p: Point) -> f64 {
+    let (d_x, d_y) = (b.0 - a.0, b.1 - a.1);
+    let length_square = d_x * d_x + d_y * d_y;
+    let frac = if length_square > 0.0 { (((p.0 - a.0) * d_x + (p.1 - a.1) * d_y) / length_square).clamp(0.0, 1.0) } else { 0.0 };
+    distance((a.0 + d_x * frac, a.1 + d_y * frac), p)
+}
+
+// The fraction of the path where it first comes within the radius of the center, None if it
+// doesn't, the first root of |a + (b - a) * frac - center| = radius.
+fn first_touch((a, b): (Point, Point), center: Point, radius: f64) -> Option<f64> {
+    let (d_x, d_y) = (b.0 - a.0, b.1 - a.1);
+    let (f_x, f_y) = (a.0 - center.0, a.1 - center.1);
+    let c = f_x * f_x + f_y * f_y - radius * radius;
+    if c <= 0.0 {
+        return Some(0.0);
+    }
+    let a_2 = d_x * d_x + d_y * d_y;
+    let b_2 = 2.0 * (f_x * d_x + f_y * d_y);
+    let discriminant = b_2 * b_2 - 4.0 * a_2 * c;
+    if a_2 == 0.0 || discriminant < 0.0 {
+        return None;
+    }
+    let frac = (-b_2 - f64::sqrt(discriminant)) / (2.0 * a_2);
+    (0.0..=1.0).contains(&frac).then_some(frac)
+}
@@ -0,0 +1,75 @@
+//! Telemetry of the ball, each sample sent in a UDP packet at the simulated rate, for the tools
+//! that react to the flight in real time, like a Processing sketch or the lights of a stage.
+//!
+//! The packet is the JSON of sample_json(), or an OSC message with the address /ball and the
+//! arguments t, x, y, vx, vy as floats and entered as an int 0 or 1.
+
+use std::net::UdpSocket;
+
+use crate::trajectory::sample_velocity;
+
+/// The format of the UDP packets.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TelemetryFormat {
+    Json,
+    Osc,
+}
+
+/// Decimal places of the numbers of the JSON messages, by default.
+pub const DEFAULT_JSON_PRECISION: usize = 4;
+
+/// The JSON message of the i-th sample, with the position in meters and the velocity in m/s,
+/// with precision decimal places.
+pub fn sample_json(samples: & [(f64, (f64, f64), bool)], i: usize, precision: usize) -> String {
+    let (t, (x, y), flag_enter_instant) = samples[i];
+    let (v_x, v_y) = sample_velocity(samples, i);
+    format!("{{\"type\":\"sample\",\"t\":{:.6$},\"x\":{:.6$},\"y\":{:.6$},\"vx\":{:.6$},\"vy\":{:.6$},\"entered\":{}}}",
+            t, x, y, v_x, v_y, flag_enter_instant, precision)
+}
+
+/// The OSC message of the i-th sample, /ball with the types ",fffffi".
+pub fn sample_osc(samples: & [(f64, (f64, f64), bool)], i: usize) -> Vec<u8> {
+    let (t, (x, y), flag_enter_instant) = samples[i];
+    let (v_x, v_y) = sample_velocity(samples, i);
+    let mut packet = Vec::with_capacity(40);
+    push_osc_string(& mut packet, "/ball");
+    push_osc_string(& mut packet, ",fffffi");
+    for value in [t, x, y, v_x, v_y] {
+        packet.extend_from_slice(& (value as f32).to_be_bytes());
+    }
+    packet.extend_from_slice(& i32::from(flag_enter_instant).to_be_bytes());
+    packet
+}
+
+// OSC strings end with a null and are padded with nulls to a multiple of 4 bytes.
+fn push_osc_string(packet: & mut Vec<u8>, text: & str) {
+    packet.extend_from_slice(text.as_bytes());
+    let num_nulls = 4 - text.len() % 4;
+    packet.extend(std::iter::repeat_n(0, num_nulls));
+}
+
+/// Calls send with the index of each sample at its simulated instant, stops at the first error.
+pub fn pace_samples<E>(samples: & [(f64, (f64, f64), bool)], mut send: impl FnMut(usize) -> Result<(), E>) -> Result<(), E> {
+    let mut t_previous = samples.first().map_or(0.0, |sample| sample.0);
+    for (i, (t, _pos, _flag_enter_instant)) in samples.iter().enumerate() {
+        std::thread::sleep(std::time::Duration::from_secs_f64(f64::max(t - t_previous, 0.0)));
+        t_previous = *t;
+        send(i)?;
+    }
+    Ok(())
+}
+
+/// Sends the samples to the address, like "127.0.0.1:9000", at the simulated rate. The JSON
+/// has precision decimal places.
+pub fn send_trajectory_udp(addr: & str, samples: & [(f64, (f64, f64), bool)], format: TelemetryFormat,
+                           precision: usize) -> Result<(), String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").map_err(|error| format!("Can't open the UDP socket: {}", error))?;
+    socket.connect(addr).map_err(|error| format!("Invalid UDP address {}: {}", addr, error))?;
+    pace_samples(samples, |i| {
+        let packet = match format {
+            TelemetryFormat::Json => sample_json(samples, i, precision).into_bytes(),
+            TelemetryFormat::Osc => sample_osc(samples, i),
+        };
+        socket.send(&packet).map(|_| ()).map_err(|error| format!("Can't send to {}: {}", addr, error))
+    })
+}
@@ -0,0 +1,82 @@
+//! The automatic end of the simulation, instead of the simulation_sec of the scenario, so it
+//! doesn't have to be guessed. With simulation.auto_stop, or --auto-stop, the shot is simulated
+//! until the ball enters the basket, reaches the floor or leaves the court, and the rebound of a
+//! miss goes on bouncing until the ball is at rest or leaves the court, instead of stopping at
+//! its second bounce on the floor.
+//!
+//! The court is the one of court, the basket at BASKET_FROM_BASELINE of its baseline and the
+//! other baseline two half courts away.
+
+use crate::court::{BASKET_FROM_BASELINE, HALF_COURT_LENGTH};
+use crate::i18n::Messages;
+use crate::scenario::ScenarioFile;
+use crate::trajectory::{Flight2D, GRAVITY};
+
+pub const AUTO_STOP_MAX_SEC: f64 = 30.0; // s - The end when nothing else stops the ball.
+const AUTO_STOP_DT: f64 = 0.001;         // s - Between the instants that are checked.
+// The ball is at rest when it rolls slower than this, and it rolls when its bounces on the floor
+// are lower than REST_HEIGHT.
+pub const REST_SPEED: f64 = 0.3;         // m/s
+pub const REST_HEIGHT: f64 = 0.01;       // m
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StopReason {
+    Scored,
+    Floor,
+    AtRest,
+    OutOfCourt,
+    TimeLimit,
+}
+
+impl StopReason {
+    pub fn name(self, messages: & Messages) -> &'static str {
+        match self {
+            StopReason::Scored => messages.stop_scored,
+            StopReason::Floor => messages.stop_floor,
+            StopReason::AtRest => messages.stop_at_rest,
+            StopReason::OutOfCourt => messages.stop_out_of_court,
+            StopReason::TimeLimit => messages.stop_time_limit,
+        }
+    }
+}
+
+/// The positions x of the two baselines, the first one the nearest to the release.
+pub fn court_bounds(scenario: & ScenarioFile) -> (f64, f64) {
+    // The shot can go to the left, the baseline of the basket is behind it.
+    let direction = if scenario.scene.basket_pos_x < scenario.throw.pos_0_x {-1.0} else {1.0};
+    let baseline_x = scenario.scene.basket_pos_x + direction * BASKET_FROM_BASELINE;
+    (baseline_x - direction * 2.0 * HALF_COURT_LENGTH, baseline_x)
+}
+
+/// If x is past one of the baselines.
+pub fn is_out_of_court(x: f64, (baseline_x_0, baseline_x_1): (f64, f64)) -> bool {
+    x < f64::min(baseline_x_0, baseline_x_1) || x > f64::max(baseline_x_0, baseline_x_1)
+}
+
+/// If the bounce of the ball, with its center at y and its speed v_y up, is lower than REST_HEIGHT.
+pub fn is_bounce_over(y: f64, v_y: f64, radius: f64) -> bool {
+    y - radius + f64::max(v_y, 0.0).powi(2) / (2.0 * GRAVITY) < REST_HEIGHT
+}
+
+/// The instant that the shot of the scenario ends and why, without the air. The ball that
+/// enters the basket ends there, the rest end at the floor or at a baseline.
+pub fn auto_stop(scenario: & ScenarioFile) -> (f64, StopReason) {
+    let bounds = court_bounds(scenario);
+    let num_steps = f64::ceil(AUTO_STOP_MAX_SEC / AUTO_STOP_DT) as u32 + 1;
+    let flight_2d = Flight2D::new(scenario.throw.pos_0_x, scenario.throw.pos_0_y,
+                                  scenario.throw.v_0, scenario.throw.teta_0_deg.to_radians(),
+                                  scenario.scene.basket_pos_x, scenario.scene.basket_pos_y,
+                                  AUTO_STOP_MAX_SEC, num_steps);
+    for (t, (x, y), flag_enter_instant) in flight_2d.skip(1) {
+        if flag_enter_instant {
+            return (t, StopReason::Scored);
+        }
+        if y < 0.0 {
+            return (t, StopReason::Floor);
+        }
+        if is_out_of_court(x, bounds) {
+            return (t, StopReason::OutOfCourt);
+        }
+    }
+    (AUTO_STOP_MAX_SEC, StopReason::TimeLimit)
+}
@@ -0,0 +1,36 @@
+//! The time of the shot against a time budget, like the seconds left in the shot clock or the
+//! time until a defender closes out. The shot takes the release time, from the catch to the ball
+//! leaving the hands, and the flight time to the basket. A high lob can score and still be too slow.
+
+use crate::trajectory::ShotSummary;
+
+pub const DEFAULT_RELEASE_TIME: f64 = 0.5; // s - The gather and the release of a jump shot.
+
+/// The times of the shot, in seconds.
+#[derive(Clone, Copy, Debug)]
+pub struct ShotTiming {
+    pub release_time: f64,
+    /// From the release to the ball in the basket, or to the closest instant when it misses.
+    pub flight_time: f64,
+    pub budget: f64,
+}
+
+impl ShotTiming {
+    pub fn new(summary: & ShotSummary, release_time: f64, budget: f64) -> ShotTiming {
+        let flight_time = summary.entry.map_or(summary.closest_t, |entry| entry.t);
+        ShotTiming { release_time, flight_time, budget }
+    }
+
+    pub fn total(& self) -> f64 {
+        self.release_time + self.flight_time
+    }
+
+    /// The time left in the budget, negative when the shot is too slow.
+    pub fn margin(& self) -> f64 {
+        self.budget - self.total()
+    }
+
+    pub fn fits(& self) -> bool {
+        self.margin() >= 0.0
+    }
+}
@@ -0,0 +1,170 @@
+//! Measured positions of a real shot, like digitized from a phone video, to compare with the simulation.
+//!
+//! The CSV file has one point per line, t in seconds and x, y in meters:
+//!
+//! ```text
+//! t,x,y
+//! 0.00,0.00,1.50
+//! 0.10,0.53,2.30
+//! ```
+//!
+//! The header, the empty lines and the lines starting with # are skipped, extra columns are ignored.
+//!
+//! The points of a video have the noise of the clicks, they can be smoothed before the comparison,
+//! with a moving average or with a Kalman filter of the flight of a projectile.
+
+use crate::trajectory::GRAVITY;
+
+// The errors of the Kalman filter, of the points measured in a video and of the acceleration of
+// the model, the drag and the spin.
+const KALMAN_MEASUREMENT_STD_DEV: f64 = 0.05; // m
+const KALMAN_ACCELERATION_STD_DEV: f64 = 1.0;  // m / s^2
+// The speed isn't known at the first point.
+const KALMAN_INITIAL_SPEED_STD_DEV: f64 = 10.0; // m / s
+/// The points of the moving average, by default.
+pub const DEFAULT_SMOOTHING_WINDOW: usize = 5;
+
+/// The measured points, the instant t and the position (x, y).
+pub type TrackingPoints = Vec<(f64, (f64, f64))>;
+
+pub fn parse_tracking_csv(text: & str) -> Result<TrackingPoints, String> {
+    let mut points = TrackingPoints::new();
+    let mut flag_first_line = true;
+    for (i, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let values: Vec<Option<f64>> = line.split(',')
+            .map(|value| value.trim().parse::<f64>().ok().filter(|number| number.is_finite()))
+            .collect();
+        match values.as_slice() {
+            [Some(t), Some(x), Some(y), ..] => points.push((*t, (*x, *y))),
+            // The header.
+            _ if flag_first_line && values.iter().all(Option::is_none) => (),
+            _ => return Err(format!("Invalid tracking point in line {}: \"{}\", expected t,x,y.", i + 1, line)),
+        }
+        flag_first_line = false;
+    }
+    if points.is_empty() {
+        return Err("The tracking file has no points.".to_string());
+    }
+    Ok(points)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Smoothing {
+    /// The mean of the points around each one.
+    MovingAverage,
+    /// The flight with the gravity, corrected by each point, in x and in y.
+    Kalman,
+}
+
+impl Smoothing {
+    pub fn from_name(name: & str) -> Option<Smoothing> {
+        match name {
+            "average" => Some(Smoothing::MovingAverage),
+            "kalman"  => Some(Smoothing::Kalman),
+            _ => None,
+        }
+    }
+}
+
+/// The points smoothed, in the order of the time. The moving average has window points, an even
+/// window is the odd one below it.
+pub fn smooth(points: & [(f64, (f64, f64))], smoothing: Smoothing, window: usize) -> TrackingPoints {
+    let mut points = points.to_vec();
+    points.sort_by(|point_1, point_2| point_1.0.total_cmp(& point_2.0));
+    match smoothing {
+        Smoothing::MovingAverage => {
+            (0..points.len()).map(|i| {
+                // Centered on the point, the ones near the ends have fewer points around them.
+                let half = usize::min((window - 1) / 2, usize::min(i, points.len() - 1 - i));
+                let around = & points[i - half..=i + half];
+                let n = around.len() as f64;
+                let x = around.iter().map(|(_, (x, _))| x).sum::<f64>() / n;
+                let y = around.iter().map(|(_, (_, y))| y).sum::<f64>() / n;
+                (points[i].0, (x, y))
+            }).collect()
+        },
+        Smoothing::Kalman => {
+            let times: Vec<f64> = points.iter().map(|(t, _)| *t).collect();
+            let xs = kalman_axis(& times, & points.iter().map(|(_, (x, _))| *x).collect::<Vec<f64>>(), 0.0);
+            let ys = kalman_axis(& times, & points.iter().map(|(_, (_, y))| *y).collect::<Vec<f64>>(), -GRAVITY);
+            times.into_iter().zip(xs.into_iter().zip(ys)).collect()
+        },
+    }
+}
+
+// The Kalman filter of the position and the velocity along an axis with the constant acceleration,
+// the filtered positions at the instants of the measured ones.
+fn kalman_axis(times: & [f64], measured: & [f64], acceleration: f64) -> Vec<f64> {
+    let r = KALMAN_MEASUREMENT_STD_DEV * KALMAN_MEASUREMENT_STD_DEV;
+    let q = KALMAN_ACCELERATION_STD_DEV * KALMAN_ACCELERATION_STD_DEV;
+    let (Some(&t_first), Some(&z_first)) = (times.first(), measured.first()) else {
+        return Vec::new();
+    };
+    // The state (z, v) and its covariance [[p_00, p_01], [p_01, p_11]].
+    let (mut z, mut v, mut t_state) = (z_first, 0.0, t_first);
+    let (mut p_00, mut p_01, mut p_11) = (r, 0.0, KALMAN_INITIAL_SPEED_STD_DEV * KALMAN_INITIAL_SPEED_STD_DEV);
+    let mut filtered = Vec::with_capacity(measured.len());
+    for (&t, &z_measured) in times.iter().zip(measured) {
+        // The prediction to the instant of the point, with the noise of the acceleration.
+        let dt = t - t_state;
+        t_state = t;
+        z += v * dt + (1.0/2.0) * acceleration * dt * dt;
+        v += acceleration * dt;
+        let (dt_2, dt_3, dt_4) = (dt * dt, dt * dt * dt, dt * dt * dt * dt);
+        p_00 += 2.0 * dt * p_01 + dt_2 * p_11 + q * dt_4 / 4.0;
+        p_01 += dt * p_11 + q * dt_3 / 2.0;
+        p_11 += q * dt_2;
+        // The correction by the measured position.
+        let (k_0, k_1) = (p_00 / (p_00 + r), p_01 / (p_00 + r));
+        let innovation = z_measured - z;
+        z += k_0 * innovation;
+        v += k_1 * innovation;
+        (p_00, p_01, p_11) = ((1.0 - k_0) * p_00, (1.0 - k_0) * p_01, p_11 - k_1 * p_01);
+        filtered.push(z);
+    }
+    filtered
+}
+
+pub fn read_tracking_csv(filename: & str) -> Result<TrackingPoints, String> {
+    let text = std::fs::read_to_string(filename)
+        .map_err(|error| format!("Can't read the tracking file {}: {}", filename, error))?;
+    parse_tracking_csv(&text)
+}
+
+/// Root mean square of the distance from the measured points to the simulated positions at the
+/// same instants, and the number of points compared. Only the points inside the simulated
+/// time are compared, None if there are none.
+pub fn rms_error(samples: & [(f64, (f64, f64), bool)], points: & [(f64, (f64, f64))]) -> Option<(f64, usize)> {
+    let mut sum_square = 0.0;
+    let mut num_points = 0;
+    for (t, (x, y)) in points {
+        if let Some((x_sim, y_sim)) = position_at(samples, *t) {
+            sum_square += (x - x_sim).powi(2) + (y - y_sim).powi(2);
+            num_points += 1;
+        }
+    }
+    if num_points == 0 {
+        return None;
+    }
+    Some((f64::sqrt(sum_square / num_points as f64), num_points))
+}
+
+// Simulated position at the instant t, interpolated between the samples.
+fn position_at(samples: & [(f64, (f64, f64), bool)], t: f64) -> Option<(f64, f64)> {
+    let (t_first, t_last) = (samples.first()?.0, samples.last()?.0);
+    if t < t_first || t > t_last {
+        return None;
+    }
+    let i = samples.iter().position(|sample| sample.0 >= t)?;
+    if i == 0 {
+        return Some(samples[0].1);
+    }
+    let (t_0, (x_0, y_0), _) = samples[i - 1];
+    let (t_1, (x_1, y_1), _) = samples[i];
+    let frac = if t_1 > t_0 { (t - t_0) / (t_1 - t_0) } else { 0.0 };
+    Some((x_0 + (x_1 - x_0) * frac, y_0 + (y_1 - y_0) * frac))
+}
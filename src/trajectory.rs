@@ -0,0 +1,526 @@
+//! Simulation of the trajectory of the ball, the uniformly accelerated movement in 2D and 3D.
+
+use serde::{Deserialize, Serialize};
+
+use crate::units::radians_to_degrees;
+use crate::wind::{wind_at, WindSample};
+
+pub const GRAVITY: f64 = 9.807; // m / s^2 - Meters per second square.
+pub const MIN_BALL_DELTA_TO_BASKET_CENTER: f64 = 0.1; // 10 cm
+
+// Air drag, F = 1/2 * rho * Cd * A * v^2 opposite to the velocity.
+pub const AIR_DENSITY: f64 = 1.204;                  // kg / m^3 - Dry air at 20 °C at the sea level.
+const GAS_CONSTANT_DRY_AIR: f64 = 287.058;           // J / (kg K)
+const GAS_CONSTANT_WATER_VAPOR: f64 = 461.495;       // J / (kg K)
+pub const BALL_DRAG_COEFFICIENT: f64 = 0.54;         // Measured for a basketball, a smooth sphere is 0.47.
+pub(crate) const DRAG_MAX_DT: f64 = 0.001;           // s - Maximum step of the integration.
+
+// Basket dimensions.
+pub const RIM_DIAMETER: f64 = 0.457;                 // m - 45.7 cm inner diameter.
+pub const BACKBOARD_TO_RIM: f64 = 0.151;             // m - From the backboard to the back of the rim.
+pub const BACKBOARD_BELOW_RIM: f64 = 0.15;           // m - Bottom of the backboard, 2.90 m for the 3.05 m rim.
+pub const BACKBOARD_HEIGHT: f64 = 1.05;              // m
+pub const BACKBOARD_WIDTH: f64 = 1.8;                // m
+pub const RIM_TUBE_RADIUS: f64 = 0.01;               // m - The section of the rod of the rim.
+
+/// If the ball entered the basket, and the samples above the floor with the
+/// instant t, the position (x, y) and if the ball entered the basket at that instant.
+pub type Trajectory = (bool, Vec<(f64, (f64, f64), bool)>);
+
+/// The same as Trajectory, with the position (x, y, z), the YY axis is up.
+pub type Trajectory3D = (bool, Vec<(f64, (f64, f64, f64), bool)>);
+
+#[allow(clippy::too_many_arguments)]
+pub fn basketball_2d(pos_0_x: f64, pos_0_y: f64, 
+                 v_0: f64, teta_0: f64,
+                 basket_pos_x: f64, basket_pos_y: f64,
+                 simulation_sec: f64, num_steps: u32)
+                 -> Trajectory {
+    
+    let flight_2d = Flight2D::new(pos_0_x, pos_0_y, v_0, teta_0,
+                                  basket_pos_x, basket_pos_y, simulation_sec, num_steps);
+
+    let mut trajectory_2d: Vec<(f64, (f64, f64), bool)> = Vec::new();
+
+    let mut flag_into_the_basket = false;
+
+    for (t, (ball_x, ball_y), flag_enter_instant) in flight_2d {
+        flag_into_the_basket |= flag_enter_instant;
+        if ball_y >= 0.0 {
+            trajectory_2d.push( (t, (ball_x, ball_y), flag_enter_instant) );
+        }
+    }
+    (flag_into_the_basket, trajectory_2d)
+}
+
+/// The instants of the shot in 2D computed one at a time, for the simulations with too many
+/// steps to keep in memory. It yields all the instants of get_time_steps(), also the ones
+/// below the floor, with the position (x, y) and if the ball entered the basket at that instant.
+pub struct Flight2D {
+    x_0: f64,
+    y_0: f64,
+    v_0_x: f64,
+    v_0_y: f64,
+    basket_pos_x: f64,
+    basket_pos_y: f64,
+    simulation_sec: f64,
+    num_steps: u32,
+    delta_t: f64,
+    step: u32,
+}
+
+impl Flight2D {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(pos_0_x: f64, pos_0_y: f64,
+               v_0: f64, teta_0: f64,
+               basket_pos_x: f64, basket_pos_y: f64,
+               simulation_sec: f64, num_steps: u32)
+               -> Self {
+        // The velocity is positive and not zero.
+        assert!(v_0 > 0.0);
+        // We will simulate a non negative and a non zero time.
+        assert!(simulation_sec > 0.0);
+        // We will simulate at least 2 steps.
+        assert!(num_steps > 2);
+
+        Flight2D {
+            x_0: pos_0_x,
+            y_0: pos_0_y,
+            v_0_x: v_0 * f64::cos(teta_0),
+            v_0_y: v_0 * f64::sin(teta_0),
+            basket_pos_x,
+            basket_pos_y,
+            simulation_sec,
+            num_steps,
+            // The same instants as get_time_steps().
+            delta_t: simulation_sec / (num_steps - 1) as f64,
+            step: 0,
+        }
+    }
+
+}
+
+impl Iterator for Flight2D {
+    type Item = (f64, (f64, f64), bool);
+
+    fn next(& mut self) -> Option<Self::Item> {
+        if self.step > self.num_steps {
+            return None;
+        }
+        let t = match self.step {
+            0 => 0.0,
+            step if step == self.num_steps => self.simulation_sec,
+            step => self.delta_t * step as f64,
+        };
+        self.step += 1;
+        let ball_x = self.x_0 + self.v_0_x * t;
+        let ball_y = self.y_0 + self.v_0_y * t - (1.0/2.0) * GRAVITY * t * t;
+        let dist = euclidean_distance(
+            ball_x, ball_y, 0.0,
+            self.basket_pos_x, self.basket_pos_y, 0.0);
+        Some( (t, (ball_x, ball_y), dist <= MIN_BALL_DELTA_TO_BASKET_CENTER) )
+    }
+
+    fn size_hint(& self) -> (usize, Option<usize>) {
+        let num_left = (self.num_steps + 1).saturating_sub(self.step) as usize;
+        (num_left, Some(num_left))
+    }
+}
+
+/// The density of the air in kg/m^3 at the temperature in °C, the relative humidity in % and the
+/// pressure in hPa. The water vapor is lighter than the dry air, the humid air is less dense.
+pub fn air_density(temperature_c: f64, humidity_percent: f64, pressure_hpa: f64) -> f64 {
+    let temperature_k = temperature_c + 273.15;
+    // Pressure of the saturated water vapor in Pa, Tetens equation.
+    let saturation_pa = 610.78 * f64::powf(10.0, 7.5 * temperature_c / (temperature_c + 237.3));
+    let vapor_pa = humidity_percent / 100.0 * saturation_pa;
+    let dry_pa = pressure_hpa * 100.0 - vapor_pa;
+    dry_pa / (GAS_CONSTANT_DRY_AIR * temperature_k) + vapor_pa / (GAS_CONSTANT_WATER_VAPOR * temperature_k)
+}
+
+/// The factor k of the acceleration of the drag, -k * |v - w| * (v - w) with the velocity w of
+/// the wind.
+pub(crate) fn drag_factor(ball_radius: f64, ball_mass: f64, air_density: f64) -> f64 {
+    0.5 * air_density * BALL_DRAG_COEFFICIENT * std::f64::consts::PI * ball_radius * ball_radius / ball_mass
+}
+
+/// A step dt of RK4 of the state (x, y, v_x, v_y) at the instant t, with the drag of the factor
+/// k and the wind (w_x, w_y) of each instant.
+pub(crate) fn drag_rk4_step((x, y, v_x, v_y): (f64, f64, f64, f64), t: f64, dt: f64, k: f64,
+                            wind: impl Fn(f64) -> (f64, f64)) -> (f64, f64, f64, f64) {
+    let acceleration = |t: f64, v_x: f64, v_y: f64| {
+        let (w_x, w_y) = wind(t);
+        let (v_air_x, v_air_y) = (v_x - w_x, v_y - w_y);
+        let speed = f64::sqrt(v_air_x * v_air_x + v_air_y * v_air_y);
+        (-k * speed * v_air_x, -GRAVITY - k * speed * v_air_y)
+    };
+    let (a_1x, a_1y) = acceleration(t, v_x, v_y);
+    let (v_2x, v_2y) = (v_x + a_1x * dt / 2.0, v_y + a_1y * dt / 2.0);
+    let (a_2x, a_2y) = acceleration(t + dt / 2.0, v_2x, v_2y);
+    let (v_3x, v_3y) = (v_x + a_2x * dt / 2.0, v_y + a_2y * dt / 2.0);
+    let (a_3x, a_3y) = acceleration(t + dt / 2.0, v_3x, v_3y);
+    let (v_4x, v_4y) = (v_x + a_3x * dt, v_y + a_3y * dt);
+    let (a_4x, a_4y) = acceleration(t + dt, v_4x, v_4y);
+    (x + dt / 6.0 * (v_x + 2.0 * v_2x + 2.0 * v_3x + v_4x),
+     y + dt / 6.0 * (v_y + 2.0 * v_2y + 2.0 * v_3y + v_4y),
+     v_x + dt / 6.0 * (a_1x + 2.0 * a_2x + 2.0 * a_3x + a_4x),
+     v_y + dt / 6.0 * (a_1y + 2.0 * a_2y + 2.0 * a_3y + a_4y))
+}
+
+/// The same as basketball_2d() with the air drag of a ball of radius in m and mass in kg, in the
+/// air of density in kg/m^3 and the wind, at the same instants. It's integrated with Runge-Kutta 4 in steps of at most 1 ms.
+#[allow(clippy::too_many_arguments)]
+pub fn basketball_2d_drag(pos_0_x: f64, pos_0_y: f64,
+                          v_0: f64, teta_0: f64,
+                          basket_pos_x: f64, basket_pos_y: f64,
+                          simulation_sec: f64, num_steps: u32,
+                          ball_radius: f64, ball_mass: f64,
+                          air_density: f64, wind: & [WindSample])
+                          -> Trajectory {
+
+    assert!(v_0 > 0.0);
+    assert!(simulation_sec > 0.0);
+    assert!(num_steps > 2);
+    assert!(ball_radius > 0.0 && ball_mass > 0.0);
+    assert!(air_density > 0.0);
+
+    let k = drag_factor(ball_radius, ball_mass, air_density);
+    let mut state = (pos_0_x, pos_0_y, v_0 * f64::cos(teta_0), v_0 * f64::sin(teta_0));
+    let mut t_state = 0.0;
+    let mut trajectory_2d: Vec<(f64, (f64, f64), bool)> = Vec::new();
+    let mut flag_into_the_basket = false;
+
+    for t in get_time_steps(simulation_sec, num_steps) {
+        // From the previous instant to this one in equal steps.
+        let num_substeps = f64::ceil((t - t_state) / DRAG_MAX_DT) as usize;
+        let dt = (t - t_state) / num_substeps as f64;
+        for i in 0..num_substeps {
+            state = drag_rk4_step(state, t_state + i as f64 * dt, dt, k, |t| wind_at(wind, t));
+        }
+        t_state = t;
+        let (ball_x, ball_y, _v_x, _v_y) = state;
+        let dist = euclidean_distance(
+            ball_x, ball_y, 0.0,
+            basket_pos_x, basket_pos_y, 0.0);
+        let flag_enter_instant = dist <= MIN_BALL_DELTA_TO_BASKET_CENTER;
+        flag_into_the_basket |= flag_enter_instant;
+        if ball_y >= 0.0 {
+            trajectory_2d.push( (t, (ball_x, ball_y), flag_enter_instant) );
+        }
+    }
+    (flag_into_the_basket, trajectory_2d)
+}
+
+/// The shot in 3D, teta_0 is the angle from the XX axis to the YY axis (up) and phi_0 is the
+/// angle from the XX axis to the ZZ axis, around the vertical, both in radians.
+#[allow(clippy::too_many_arguments)]
+pub fn basketball_3d(pos_0_x: f64, pos_0_y: f64, pos_0_z: f64,
+                     v_0: f64, teta_0: f64, phi_0: f64,
+                     basket_pos_x: f64, basket_pos_y: f64, basket_pos_z: f64,
+                     simulation_sec: f64, num_steps: u32)
+                     -> Trajectory3D {
+
+    assert!(v_0 > 0.0);
+    assert!(simulation_sec > 0.0);
+    assert!(num_steps > 2);
+
+    let v_0_horizontal = v_0 * f64::cos(teta_0);
+    let v_0_x = v_0_horizontal * f64::cos(phi_0);
+    let v_0_y = v_0 * f64::sin(teta_0);
+    let v_0_z = v_0_horizontal * f64::sin(phi_0);
+
+    let mut trajectory_3d: Vec<(f64, (f64, f64, f64), bool)> = Vec::new();
+    let mut flag_into_the_basket = false;
+
+    for t in get_time_steps(simulation_sec, num_steps) {
+        let ball_x = pos_0_x + v_0_x * t;
+        let ball_y = pos_0_y + v_0_y * t - (1.0/2.0) * GRAVITY * t * t;
+        let ball_z = pos_0_z + v_0_z * t;
+        let dist = euclidean_distance(
+            ball_x, ball_y, ball_z,
+            basket_pos_x, basket_pos_y, basket_pos_z);
+        let flag_enter_instant = dist <= MIN_BALL_DELTA_TO_BASKET_CENTER;
+        flag_into_the_basket |= flag_enter_instant;
+        if ball_y >= 0.0 {
+            trajectory_3d.push( (t, (ball_x, ball_y, ball_z), flag_enter_instant) );
+        }
+    }
+    (flag_into_the_basket, trajectory_3d)
+}
+
+pub fn get_time_steps(simulation_sec: f64, num_steps: u32) -> Vec<f64> {
+    let inner_steps = num_steps - 1;
+    let delta_t = simulation_sec / inner_steps as f64;
+    let mut time_steps_vec: Vec<f64> = Vec::new();
+    // Add the first instant 0.0 s.
+    time_steps_vec.push(0.0);
+    for step in 1..(inner_steps + 1) {
+        time_steps_vec.push(delta_t * step as f64);    
+    }
+    // Add the last instant simulation_sec s.
+    time_steps_vec.push(simulation_sec);
+    time_steps_vec
+}
+
+pub fn euclidean_distance(p_x: f64, p_y: f64, p_z: f64,
+                      q_x: f64, q_y: f64, q_z: f64)
+                      -> f64 {
+    f64::sqrt((p_x - q_x).powi(2) + (p_y - q_y).powi(2) + (p_z - q_z).powi(2))
+}
+
+/// The speed that takes the ball through the center of the basket with the angle teta_0 in
+/// radians, None if no speed does, like when the basket is behind or above the line of the throw.
+pub fn required_speed(pos_0_x: f64, pos_0_y: f64, teta_0: f64, basket_pos_x: f64, basket_pos_y: f64) -> Option<f64> {
+    // From y = y_0 + tan(teta_0) * dx - g * dx^2 / (2 * v_0^2 * cos(teta_0)^2).
+    let delta_x = basket_pos_x - pos_0_x;
+    let delta_y = basket_pos_y - pos_0_y;
+    let denominator = 2.0 * f64::cos(teta_0).powi(2) * (delta_x * f64::tan(teta_0) - delta_y);
+    if delta_x <= 0.0 || denominator <= 0.0 {
+        return None;
+    }
+    Some(f64::sqrt(GRAVITY * delta_x * delta_x / denominator))
+}
+
+/// Speed at the i-th sample, from the positions of the neighbour samples.
+pub fn sample_speed(samples: & [(f64, (f64, f64), bool)], i: usize) -> f64 {
+    let (v_x, v_y) = sample_velocity(samples, i);
+    f64::sqrt(v_x * v_x + v_y * v_y)
+}
+
+/// Velocity (v_x, v_y) at the i-th sample, from the positions of the neighbour samples.
+/// It's the derivative of the parabola through 3 samples, exact for the parabolic movement.
+pub fn sample_velocity(samples: & [(f64, (f64, f64), bool)], i: usize) -> (f64, f64) {
+    let Some((a, b, c)) = parabola_samples(samples, i) else {
+        return (0.0, 0.0);
+    };
+    let t = samples[i].0;
+    let (t_a, t_b, t_c) = (samples[a].0, samples[b].0, samples[c].0);
+    let w_a = (2.0 * t - t_b - t_c) / ((t_a - t_b) * (t_a - t_c));
+    let w_b = (2.0 * t - t_a - t_c) / ((t_b - t_a) * (t_b - t_c));
+    let w_c = (2.0 * t - t_a - t_b) / ((t_c - t_a) * (t_c - t_b));
+    let v_x = w_a * samples[a].1.0 + w_b * samples[b].1.0 + w_c * samples[c].1.0;
+    let v_y = w_a * samples[a].1.1 + w_b * samples[b].1.1 + w_c * samples[c].1.1;
+    (v_x, v_y)
+}
+
+/// Acceleration (a_x, a_y) at the i-th sample, the second derivative of the same parabola,
+/// (0, -GRAVITY) for the movement without the air drag.
+pub fn sample_acceleration(samples: & [(f64, (f64, f64), bool)], i: usize) -> (f64, f64) {
+    let Some((a, b, c)) = parabola_samples(samples, i) else {
+        return (0.0, 0.0);
+    };
+    let (t_a, t_b, t_c) = (samples[a].0, samples[b].0, samples[c].0);
+    let w_a = 2.0 / ((t_a - t_b) * (t_a - t_c));
+    let w_b = 2.0 / ((t_b - t_a) * (t_b - t_c));
+    let w_c = 2.0 / ((t_c - t_a) * (t_c - t_b));
+    let a_x = w_a * samples[a].1.0 + w_b * samples[b].1.0 + w_c * samples[c].1.0;
+    let a_y = w_a * samples[a].1.1 + w_b * samples[b].1.1 + w_c * samples[c].1.1;
+    (a_x, a_y)
+}
+
+// The 3 samples of the parabola of the i-th sample, its neighbours when it has them.
+fn parabola_samples(samples: & [(f64, (f64, f64), bool)], i: usize) -> Option<(usize, usize, usize)> {
+    // Neighbour samples with a different instant, the last instant can be repeated.
+    let prev = |j: usize| (0..j).rev().find(|&k| samples[k].0 < samples[j].0);
+    let next = |j: usize| (j + 1..samples.len()).find(|&k| samples[k].0 > samples[j].0);
+    match (prev(i), next(i)) {
+        (Some(p), Some(n)) => Some((p, i, n)),
+        (None, Some(n)) => next(n).map(|n_2| (i, n, n_2)),
+        (Some(p), None) => prev(p).map(|p_2| (p_2, p, i)),
+        (None, None) => None,
+    }
+}
+
+/// The distance traveled by the ball from the first sample to each one, along the straight
+/// segments between the samples.
+pub fn cumulative_distance(samples: & [(f64, (f64, f64), bool)]) -> Vec<f64> {
+    let mut distance = 0.0;
+    samples.iter().enumerate().map(|(i, (_, (x, y), _))| {
+        if i > 0 {
+            let (x_prev, y_prev) = samples[i - 1].1;
+            distance += f64::hypot(x - x_prev, y - y_prev);
+        }
+        distance
+    }).collect()
+}
+
+/// The length of the arc of the samples, the distance traveled by the ball through the air.
+pub fn arc_length(samples: & [(f64, (f64, f64), bool)]) -> f64 {
+    cumulative_distance(samples).last().copied().unwrap_or(0.0)
+}
+
+/// The instants that the center of the ball is at x, like at the plane of the backboard, in
+/// order. None, one, or two when the ball comes back, like after a rebound.
+pub fn time_at_x(samples: & [(f64, (f64, f64), bool)], x: f64) -> Vec<f64> {
+    crossing_times(samples, x, |(x, _)| x)
+}
+
+/// The instants that the center of the ball is at the height y, like at the plane of the rim,
+/// in order. None, one, or two, going up and coming down.
+pub fn time_at_height(samples: & [(f64, (f64, f64), bool)], y: f64) -> Vec<f64> {
+    crossing_times(samples, y, |(_, y)| y)
+}
+
+// The instants that the coordinate of the position is the value, interpolated between the
+// samples on each side of it. A sample at the value is a single crossing.
+fn crossing_times(samples: & [(f64, (f64, f64), bool)], value: f64, coordinate: impl Fn((f64, f64)) -> f64) -> Vec<f64> {
+    let mut times = Vec::new();
+    if let Some((t, position, _)) = samples.first() {
+        if coordinate(*position) == value {
+            times.push(*t);
+        }
+    }
+    for pair in samples.windows(2) {
+        let ((t_0, position_0, _), (t_1, position_1, _)) = (pair[0], pair[1]);
+        let (delta_0, delta_1) = (coordinate(position_0) - value, coordinate(position_1) - value);
+        if delta_1 == 0.0 {
+            times.push(t_1);
+        } else if delta_0 != 0.0 && (delta_0 < 0.0) != (delta_1 < 0.0) {
+            times.push(t_0 + (t_1 - t_0) * delta_0 / (delta_0 - delta_1));
+        }
+    }
+    times
+}
+
+/// XX position of the backboard, it's behind the rim, the player throws in the direction of the XX axis.
+pub fn basket_backboard_x(basket_pos_x: f64) -> f64 {
+    basket_pos_x + RIM_DIAMETER / 2.0 + BACKBOARD_TO_RIM
+}
+
+/// The ball when it entered the basket, the instant, the speed in m/s and the angle in degrees
+/// below the horizontal.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ShotEntry {
+    pub t: f64,
+    pub speed: f64,
+    pub angle_deg: f64,
+}
+
+/// The key numbers of a shot.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct ShotSummary {
+    pub flag_scored: bool,
+    /// The first sample in the basket, None if it missed.
+    pub entry: Option<ShotEntry>,
+    pub apex_y: f64,
+    pub apex_t: f64,
+    /// XX position of the last sample above the floor.
+    pub range_x: f64,
+    /// Instant of the last sample above the floor.
+    pub flight_sec: f64,
+    /// Minimum distance from the ball to the center of the basket, and its instant.
+    pub closest_dist: f64,
+    pub closest_t: f64,
+}
+
+impl ShotSummary {
+    /// Returns None if there are no samples above the floor.
+    pub fn new(trajectory_2d: & Trajectory, basket_pos_x: f64, basket_pos_y: f64) -> Option<Self> {
+        let samples = & trajectory_2d.1;
+        let (last_t, (last_x, _), _) = *samples.last()?;
+        let (apex_t, apex_y) = samples.iter().map(|(t, (_, y), _)| (*t, *y))
+            .fold((0.0, f64::MIN), |apex, current| if current.1 > apex.1 { current } else { apex });
+        let entry = samples.iter().position(|(_, _, flag_enter_instant)| *flag_enter_instant).map(|i| {
+            let (v_x, v_y) = sample_velocity(samples, i);
+            ShotEntry {
+                t: samples[i].0,
+                speed: f64::sqrt(v_x * v_x + v_y * v_y),
+                angle_deg: radians_to_degrees(f64::atan2(-v_y, v_x)),
+            }
+        });
+        let (closest_t, closest_dist) = samples.iter()
+            .map(|(t, (x, y), _)| (*t, euclidean_distance(*x, *y, 0.0, basket_pos_x, basket_pos_y, 0.0)))
+            .fold((0.0, f64::MAX), |closest, current| if current.1 < closest.1 { current } else { closest });
+        Some(ShotSummary {
+            flag_scored: trajectory_2d.0,
+            entry,
+            apex_y,
+            apex_t,
+            range_x: last_x,
+            flight_sec: last_t,
+            closest_dist,
+            closest_t,
+        })
+    }
+
+    /// A JSON object with the numbers, in meters and seconds.
+    pub fn to_json(& self) -> String {
+        format!("{{\"scored\":{},\"apex_m\":{:.4},\"range_m\":{:.4},\"flight_sec\":{:.4},\"closest_dist_m\":{:.4},\"closest_t_sec\":{:.4}}}",
+                self.flag_scored, self.apex_y, self.range_x, self.flight_sec, self.closest_dist, self.closest_t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::degrees_to_radians;
+
+    // The default shot, 10 m/s at 45° from (0, 1.5) to the basket at (8, 3.05).
+    fn default_shot() -> Trajectory {
+        basketball_2d(0.0, 1.5, 10.0, degrees_to_radians(45.0), 8.0, 3.05, 2.0, 600)
+    }
+
+    fn assert_close(a: f64, b: f64, tolerance: f64) {
+        assert!(f64::abs(a - b) <= tolerance, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn time_at_height_crossings() {
+        let samples = default_shot().1;
+        let v_0_y = 10.0 * f64::sin(degrees_to_radians(45.0));
+        let root = f64::sqrt(v_0_y * v_0_y - 2.0 * GRAVITY * (3.05 - 1.5));
+        let times = time_at_height(& samples, 3.05);
+        assert_eq!(times.len(), 2);
+        assert_close(times[0], (v_0_y - root) / GRAVITY, 1e-4);
+        assert_close(times[1], (v_0_y + root) / GRAVITY, 1e-4);
+        assert!(time_at_height(& samples, 10.0).is_empty());
+        // The release, at the first sample, and the fall back to its height.
+        let times = time_at_height(& samples, 1.5);
+        assert_eq!(times.len(), 2);
+        assert_eq!(times[0], 0.0);
+    }
+
+    #[test]
+    fn time_at_x_crossings() {
+        let samples = default_shot().1;
+        let times = time_at_x(& samples, 8.0);
+        assert_eq!(times.len(), 1);
+        assert_close(times[0], 8.0 / (10.0 * f64::cos(degrees_to_radians(45.0))), 1e-9);
+        assert!(time_at_x(& samples, -1.0).is_empty());
+        // A ball that comes back, like after the backboard.
+        let rebound = vec![(0.0, (0.0, 2.0), false), (0.5, (2.0, 3.0), false), (1.0, (4.0, 3.0), false),
+                           (1.5, (2.0, 2.0), false), (2.0, (0.0, 1.0), false)];
+        assert_eq!(time_at_x(& rebound, 3.0), vec![0.75, 1.25]);
+        assert_eq!(time_at_x(& rebound, 4.0), vec![1.0]);
+    }
+
+    #[test]
+    fn required_speed_reaches_the_basket() {
+        for (pos_0_x, pos_0_y, teta_0_deg) in [(0.0, 1.5, 45.0), (3.775, 2.1, 52.0), (1.0, 2.0, 30.0), (7.0, 1.2, 80.0)] {
+            let teta_0 = degrees_to_radians(teta_0_deg);
+            let v_0 = required_speed(pos_0_x, pos_0_y, teta_0, 8.0, 3.05).unwrap();
+            // The height of the parabola at the basket.
+            let t = (8.0 - pos_0_x) / (v_0 * f64::cos(teta_0));
+            assert_close(pos_0_y + v_0 * f64::sin(teta_0) * t - 0.5 * GRAVITY * t * t, 3.05, 1e-9);
+            let trajectory_2d = basketball_2d(pos_0_x, pos_0_y, v_0, teta_0, 8.0, 3.05, 3.0, 3000);
+            assert!(trajectory_2d.0, "{} {} {}", pos_0_x, pos_0_y, teta_0_deg);
+        }
+    }
+
+    #[test]
+    fn required_speed_without_a_solution() {
+        // The basket behind the shooter, and above the line of a flat throw.
+        assert_eq!(required_speed(9.0, 1.5, degrees_to_radians(45.0), 8.0, 3.05), None);
+        assert_eq!(required_speed(0.0, 1.5, degrees_to_radians(10.0), 8.0, 3.05), None);
+    }
+
+    #[test]
+    fn time_at_height_with_drag() {
+        let samples = basketball_2d_drag(0.0, 1.5, 10.0, degrees_to_radians(45.0), 8.0, 3.05, 2.0, 600,
+                                         0.12, 0.6, AIR_DENSITY, & []).1;
+        let times = time_at_height(& samples, 3.05);
+        assert_eq!(times.len(), 2);
+        // The drag slows the ball, it comes down through the rim before the shot without the air.
+        assert!(times[1] < time_at_height(& default_shot().1, 3.05)[1]);
+    }
+}
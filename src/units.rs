@@ -0,0 +1,223 @@
+//! Conversions from the units of the simulation, meters, seconds and radians, to the units of
+//! the reports, and back.
+
+pub const METERS_PER_FOOT: f64 = 0.3048;
+pub const INCHES_PER_FOOT: f64 = 12.0;
+pub const METERS_PER_MILE: f64 = 1_609.344;
+const SECONDS_PER_HOUR: f64 = 3_600.0;
+
+pub fn meters_sec_to_km_hour(vel: f64) -> f64 {
+    vel * SECONDS_PER_HOUR / 1_000.0
+}
+
+pub fn km_hour_to_meters_sec(vel: f64) -> f64 {
+    vel * 1_000.0 / SECONDS_PER_HOUR
+}
+
+pub fn meters_sec_to_miles_hour(vel: f64) -> f64 {
+    vel * SECONDS_PER_HOUR / METERS_PER_MILE
+}
+
+pub fn miles_hour_to_meters_sec(vel: f64) -> f64 {
+    vel * METERS_PER_MILE / SECONDS_PER_HOUR
+}
+
+pub fn km_hour_to_miles_hour(vel: f64) -> f64 {
+    meters_sec_to_miles_hour(km_hour_to_meters_sec(vel))
+}
+
+pub fn miles_hour_to_km_hour(vel: f64) -> f64 {
+    meters_sec_to_km_hour(miles_hour_to_meters_sec(vel))
+}
+
+pub fn meters_to_feet(len: f64) -> f64 {
+    len / METERS_PER_FOOT
+}
+
+pub fn feet_to_meters(len: f64) -> f64 {
+    len * METERS_PER_FOOT
+}
+
+pub fn meters_to_inches(len: f64) -> f64 {
+    meters_to_feet(len) * INCHES_PER_FOOT
+}
+
+pub fn inches_to_meters(len: f64) -> f64 {
+    feet_to_meters(len / INCHES_PER_FOOT)
+}
+
+/// The length in whole feet and the inches that remain, like 10 ft 0.00 in for 3.05 m.
+pub fn meters_to_feet_inches(len: f64) -> (f64, f64) {
+    let inches = meters_to_inches(len);
+    let feet = f64::trunc(inches / INCHES_PER_FOOT);
+    (feet, inches - feet * INCHES_PER_FOOT)
+}
+
+pub fn degrees_to_radians(angle: f64) -> f64 {
+    angle.to_radians()
+}
+
+pub fn radians_to_degrees(angle: f64) -> f64 {
+    angle.to_degrees()
+}
+
+/// The kinds of the values of the command line and of the files that can have a unit, in the
+/// unit of the simulation without one: meters, meters per second, degrees and seconds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Quantity {
+    Length,
+    Speed,
+    Angle,
+    Time,
+}
+
+impl Quantity {
+    pub fn name(self) -> &'static str {
+        match self {
+            Quantity::Length => "length",
+            Quantity::Speed => "speed",
+            Quantity::Angle => "angle",
+            Quantity::Time => "time",
+        }
+    }
+
+    /// The units of the quantity and their factor to the unit of the simulation, the first one.
+    pub fn units(self) -> &'static [(&'static str, f64)] {
+        match self {
+            Quantity::Length => &[("m", 1.0), ("cm", 0.01), ("mm", 0.001), ("ft", METERS_PER_FOOT),
+                                  ("in", METERS_PER_FOOT / INCHES_PER_FOOT)],
+            Quantity::Speed => &[("m/s", 1.0), ("km/h", 1_000.0 / SECONDS_PER_HOUR), ("mph", METERS_PER_MILE / SECONDS_PER_HOUR),
+                                 ("ft/s", METERS_PER_FOOT)],
+            Quantity::Angle => &[("deg", 1.0), ("°", 1.0), ("rad", 180.0 / std::f64::consts::PI)],
+            Quantity::Time => &[("s", 1.0), ("ms", 0.001), ("min", 60.0)],
+        }
+    }
+
+    /// The names of the units, like "m, cm, mm, ft or in".
+    pub fn unit_names(self) -> String {
+        let names: Vec<&str> = self.units().iter().map(|(unit, _factor)| *unit).collect();
+        match names.split_last() {
+            Some((last, [])) => last.to_string(),
+            Some((last, rest)) => format!("{} or {}", rest.join(", "), last),
+            None => String::new(),
+        }
+    }
+}
+
+/// The finite number of the text in the unit of the simulation, the text is a number with or
+/// without a unit of the quantity after it, like "36km/h", "15 ft" or "0.8rad".
+pub fn parse_quantity(text: & str, quantity: Quantity) -> Option<f64> {
+    let text = text.trim();
+    // The unit that leaves a number, "mm" isn't "m" after "15m".
+    let (number, factor) = quantity.units().iter()
+        .filter_map(|(unit, factor)| text.strip_suffix(unit).map(|number| (number.trim_end(), *factor)))
+        .find(|(number, _factor)| number.parse::<f64>().is_ok())
+        .unwrap_or((text, 1.0));
+    number.parse::<f64>().ok().map(|value| value * factor).filter(|value| value.is_finite())
+}
+
+/// The deserializers of the fields of the files that take a number in the unit of the
+/// simulation or a text with a unit, like v_0 = "36km/h".
+pub mod serde_quantity {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer};
+
+    use super::{parse_quantity, Quantity};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NumberOrText {
+        Number(f64),
+        Text(String),
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(deserializer: D, quantity: Quantity) -> Result<f64, D::Error> {
+        match NumberOrText::deserialize(deserializer)? {
+            NumberOrText::Number(number) => Ok(number),
+            NumberOrText::Text(text) => parse_quantity(&text, quantity).ok_or_else(|| {
+                D::Error::custom(format!("invalid {} \"{}\", expected a number with a unit like {}", quantity.name(), text, quantity.unit_names()))
+            }),
+        }
+    }
+
+    pub fn length<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        deserialize(deserializer, Quantity::Length)
+    }
+
+    pub fn speed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        deserialize(deserializer, Quantity::Speed)
+    }
+
+    pub fn angle<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        deserialize(deserializer, Quantity::Angle)
+    }
+
+    pub fn time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        deserialize(deserializer, Quantity::Time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALUES: [f64; 6] = [0.0, 1.0, 3.05, 10.0, -7.5, 123.456];
+
+    fn assert_close(a: f64, b: f64) {
+        assert!(f64::abs(a - b) <= 1e-9 * f64::max(1.0, f64::abs(b)), "{} != {}", a, b);
+    }
+
+    #[test]
+    fn speeds_round_trip() {
+        for value in VALUES {
+            assert_close(km_hour_to_meters_sec(meters_sec_to_km_hour(value)), value);
+            assert_close(miles_hour_to_meters_sec(meters_sec_to_miles_hour(value)), value);
+            assert_close(miles_hour_to_km_hour(km_hour_to_miles_hour(value)), value);
+        }
+    }
+
+    #[test]
+    fn lengths_round_trip() {
+        for value in VALUES {
+            assert_close(feet_to_meters(meters_to_feet(value)), value);
+            assert_close(inches_to_meters(meters_to_inches(value)), value);
+            let (feet, inches) = meters_to_feet_inches(value);
+            assert_close(feet_to_meters(feet) + inches_to_meters(inches), value);
+        }
+    }
+
+    #[test]
+    fn angles_round_trip() {
+        for value in VALUES {
+            assert_close(radians_to_degrees(degrees_to_radians(value)), value);
+        }
+    }
+
+    #[test]
+    fn known_values() {
+        assert_close(meters_sec_to_km_hour(10.0), 36.0);
+        assert_close(miles_hour_to_km_hour(1.0), 1.609344);
+        assert_close(meters_to_feet(METERS_PER_FOOT * 10.0), 10.0);
+        assert_close(meters_to_inches(0.0254), 1.0);
+        assert_close(degrees_to_radians(180.0), std::f64::consts::PI);
+        let (feet, inches) = meters_to_feet_inches(3.05);
+        assert_close(feet, 10.0);
+        assert!(inches > 0.0 && inches < 0.1);
+    }
+
+    #[test]
+    fn quantities_with_units() {
+        assert_close(parse_quantity("3.05", Quantity::Length).unwrap(), 3.05);
+        assert_close(parse_quantity("15ft", Quantity::Length).unwrap(), 15.0 * METERS_PER_FOOT);
+        assert_close(parse_quantity("305 cm", Quantity::Length).unwrap(), 3.05);
+        assert_close(parse_quantity("3050mm", Quantity::Length).unwrap(), 3.05);
+        assert_close(parse_quantity("36km/h", Quantity::Speed).unwrap(), 10.0);
+        assert_close(parse_quantity("10 m/s", Quantity::Speed).unwrap(), 10.0);
+        assert_close(parse_quantity("0.8rad", Quantity::Angle).unwrap(), radians_to_degrees(0.8));
+        assert_close(parse_quantity("45°", Quantity::Angle).unwrap(), 45.0);
+        assert_close(parse_quantity("500ms", Quantity::Time).unwrap(), 0.5);
+        assert_eq!(parse_quantity("15km/h", Quantity::Length), None);
+        assert_eq!(parse_quantity("ft", Quantity::Length), None);
+        assert_eq!(parse_quantity("inf", Quantity::Length), None);
+    }
+}
@@ -0,0 +1,101 @@
+//! The shot as the frames of a video, at a frame rate that doesn't depend on the steps of the
+//! simulation. The positions are interpolated at the instants of the frames with
+//! compare::position_at(), a shot of 60 steps makes a smooth video at 60 fps and a shot of
+//! 100000 steps doesn't make 100000 frames.
+//!
+//! The frames are SVG figures, frame_00001.svg, frame_00002.svg, ..., with the arc until the
+//! instant of the frame and the ball, for a video made with the usual tools, like rsvg-convert
+//! to PNG and then ffmpeg.
+
+use std::fs;
+use std::path::Path;
+
+use crate::compare::position_at;
+use crate::plot_svg::SvgPlotOptions;
+use crate::svg_gen::{Style, SVG};
+use crate::trajectory::Trajectory;
+
+pub const DEFAULT_FPS: f64 = 30.0; // Frames per second of the video.
+
+/// The instants of the frames, from the first sample to the last one, at fps frames per second of
+/// the video. The playback is the speed of the video against the simulated time, 0.25 is slow
+/// motion, 4 times more frames.
+pub fn frame_times(trajectory_2d: & Trajectory, fps: f64, playback: f64) -> Vec<f64> {
+    assert!(fps > 0.0 && playback > 0.0);
+    let (Some(first), Some(last)) = (trajectory_2d.1.first(), trajectory_2d.1.last()) else {
+        return Vec::new();
+    };
+    let frame_dt = playback / fps;
+    let num_frames = f64::floor((last.0 - first.0) / frame_dt + 1e-9) as usize + 1;
+    (0..num_frames).map(|k| first.0 + k as f64 * frame_dt).collect()
+}
+
+/// The trajectory at the instants of the frames. A frame has the ball in the basket when it
+/// entered since the previous frame.
+pub fn resample_frames(trajectory_2d: & Trajectory, fps: f64, playback: f64) -> Trajectory {
+    let samples = & trajectory_2d.1;
+    let mut i_next = 0;
+    let frames = frame_times(trajectory_2d, fps, playback).into_iter().filter_map(|t| {
+        let position = position_at(trajectory_2d, t)?;
+        let mut flag_enter_instant = false;
+        while i_next < samples.len() && samples[i_next].0 <= t {
+            flag_enter_instant |= samples[i_next].2;
+            i_next += 1;
+        }
+        Some((t, position, flag_enter_instant))
+    }).collect();
+    (trajectory_2d.0, frames)
+}
+
+/// The name of the k-th frame, from 0, in the folder.
+pub fn frame_filename(k: usize) -> String {
+    format!("frame_{:05}.svg", k + 1)
+}
+
+/// Writes a figure for each frame to the folder, it's created if it doesn't exist, and returns
+/// the number of frames. The scene is the same in all the frames, the basket at basket_pos
+/// (x, y). Only the size, the margin, the theme and the precision of the options are used.
+pub fn write_svg_frames(folder: & str, trajectory_2d: & Trajectory, basket_pos: (f64, f64), options: & SvgPlotOptions,
+                        fps: f64, playback: f64) -> Result<usize, String> {
+    let folder_path = Path::new(folder);
+    fs::create_dir_all(folder_path).map_err(|error| format!("Can't create the folder {}: {}", folder, error))?;
+    let frames = resample_frames(trajectory_2d, fps, playback).1;
+
+    // The bounds of the whole shot, so the scene doesn't move between the frames.
+    let (mut x_min, mut x_max, mut y_max) = (basket_pos.0, basket_pos.0, basket_pos.1);
+    for (_t, (x, y), _flag_enter_instant) in & trajectory_2d.1 {
+        x_min = f64::min(x_min, *x);
+        x_max = f64::max(x_max, *x);
+        y_max = f64::max(y_max, *y);
+    }
+    let margin = f64::min(options.margin as f64, f64::min(options.width as f64, options.height as f64) / 4.0);
+    let scale_factor = f64::min((options.width as f64 - 2.0 * margin) / f64::max(x_max - x_min, 1e-9),
+                                (options.height as f64 - 2.0 * margin) / f64::max(y_max, 1e-9));
+    let floor_svg_y = options.height as f64 - margin;
+    let to_svg = |(x, y): (f64, f64)| ((margin + (x - x_min) * scale_factor) as f32, (floor_svg_y - y * scale_factor) as f32);
+
+    let theme = & options.theme;
+    let mut flag_entered = false;
+    for (k, (t, position, flag_enter_instant)) in frames.iter().enumerate() {
+        flag_entered |= *flag_enter_instant;
+        let mut svg = SVG::new(options.width, options.height, Some(theme.background.clone()));
+        svg.set_precision(options.precision);
+        svg.set_output_mode(options.output_mode);
+        svg.add_line(margin as f32, floor_svg_y as f32, options.width - margin as f32, floor_svg_y as f32,
+                     & Style::new().stroke(theme.text.clone()).stroke_width(1.0));
+        let (basket_svg_x, basket_svg_y) = to_svg(basket_pos);
+        svg.add_rect(basket_svg_x - 10.0, basket_svg_y - 2.0, 20.0, 4.0, & Style::new().fill(theme.basket.clone()));
+        // The arc of the frames until this one, above the floor.
+        let arc_points: Vec<(f32, f32)> = frames[..=k].iter()
+            .filter(|(_t, (_x, y), _flag_enter_instant)| *y >= 0.0)
+            .map(|(_t, position, _flag_enter_instant)| to_svg(*position))
+            .collect();
+        svg.add_polyline(&arc_points, & Style::new().stroke(theme.trajectory.clone()).stroke_width(1.5));
+        let (ball_svg_x, ball_svg_y) = to_svg(*position);
+        let ball_color = if flag_entered { theme.score_marker.clone() } else { theme.ball.clone() };
+        svg.add_circle(ball_svg_x, ball_svg_y, 5.0, & Style::new().fill(ball_color));
+        svg.add_text(margin as f32, margin as f32, 12.0, & Style::new().fill(theme.text.clone()), &format!("t: {:0.2} s", t));
+        svg.to_file(& frame_filename(k), & format!("{}/", folder))?;
+    }
+    Ok(frames.len())
+}
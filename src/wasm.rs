@@ -0,0 +1,124 @@
+//! Bindings for JavaScript, the simulation and the SVG figure in the browser.
+//!
+//! Build with:
+//!
+//!     cargo build --lib --release --target wasm32-unknown-unknown --features wasm
+//!     wasm-bindgen --target web --out-dir www/pkg \
+//!         target/wasm32-unknown-unknown/release/basketball_trajectory.wasm
+//!
+//! And serve the www directory, see www/index.html.
+
+use wasm_bindgen::prelude::*;
+
+use crate::plot_svg::{plot_trajectory_svg, SvgPlotOptions, Theme};
+use crate::scenario::ScenarioFile;
+use crate::simulator::Simulator;
+use crate::trajectory::{basketball_2d, ShotSummary, Trajectory};
+
+// The checks of basketball_2d, as an error for JavaScript instead of a panic.
+#[allow(clippy::too_many_arguments)]
+fn simulate(pos_0_x: f64, pos_0_y: f64,
+            v_0: f64, teta_0_deg: f64,
+            basket_pos_x: f64, basket_pos_y: f64,
+            simulation_sec: f64, num_steps: u32) -> Result<Trajectory, String> {
+    if !(v_0 > 0.0 && v_0.is_finite()) {
+        return Err(format!("Invalid v_0 {}, expected a positive number.", v_0));
+    }
+    if !(simulation_sec > 0.0 && simulation_sec.is_finite()) {
+        return Err(format!("Invalid simulation_sec {}, expected a positive number.", simulation_sec));
+    }
+    if num_steps <= 2 {
+        return Err(format!("Invalid num_steps {}, expected at least 3.", num_steps));
+    }
+    Ok(basketball_2d(pos_0_x, pos_0_y,
+                     v_0, teta_0_deg.to_radians(),
+                     basket_pos_x, basket_pos_y,
+                     simulation_sec, num_steps))
+}
+
+/// The SVG file of the shot, with the angle teta_0 in degrees and the theme "dark" or "light".
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn trajectory_svg(pos_0_x: f64, pos_0_y: f64,
+                      v_0: f64, teta_0_deg: f64,
+                      basket_pos_x: f64, basket_pos_y: f64,
+                      simulation_sec: f64, num_steps: u32,
+                      theme_name: & str) -> Result<String, String> {
+    let trajectory_2d = simulate(pos_0_x, pos_0_y, v_0, teta_0_deg, basket_pos_x, basket_pos_y, simulation_sec, num_steps)?;
+    if trajectory_2d.1.is_empty() {
+        return Err("The ball starts below the floor.".to_string());
+    }
+    let options = SvgPlotOptions {
+        theme: Theme::from_name(theme_name)
+            .ok_or(format!("Invalid theme \"{}\", expected dark or light.", theme_name))?,
+        ..SvgPlotOptions::default()
+    };
+    let label = format!("v_0 = {:0.2} m/s & teta_0 = {:0.2}° -> {}",
+                        v_0, teta_0_deg, if trajectory_2d.0 {"entered the basket"} else {"missed the basket"});
+    let mut svg = plot_trajectory_svg(& trajectory_2d, Some((basket_pos_x, basket_pos_y)), & options, &label);
+    // The figure fits the width of the page.
+    svg.set_responsive(true);
+    Ok(svg.to_file_string())
+}
+
+/// The key numbers of the shot as JSON, see ShotSummary::to_json(), with the angle teta_0 in degrees.
+#[wasm_bindgen]
+#[allow(clippy::too_many_arguments)]
+pub fn trajectory_summary_json(pos_0_x: f64, pos_0_y: f64,
+                               v_0: f64, teta_0_deg: f64,
+                               basket_pos_x: f64, basket_pos_y: f64,
+                               simulation_sec: f64, num_steps: u32) -> Result<String, String> {
+    let trajectory_2d = simulate(pos_0_x, pos_0_y, v_0, teta_0_deg, basket_pos_x, basket_pos_y, simulation_sec, num_steps)?;
+    ShotSummary::new(& trajectory_2d, basket_pos_x, basket_pos_y)
+        .map(|summary| summary.to_json())
+        .ok_or("The ball starts below the floor.".to_string())
+}
+
+/// The shot of a scenario in JSON advanced a frame at a time, for the animations in the page,
+/// see simulator::Simulator.
+#[wasm_bindgen]
+pub struct ShotSimulator(Simulator);
+
+#[wasm_bindgen]
+impl ShotSimulator {
+    #[wasm_bindgen(constructor)]
+    pub fn new(scenario_json: & str, flag_drag: bool) -> Result<ShotSimulator, String> {
+        let scenario = ScenarioFile::from_json(scenario_json)?;
+        scenario.validate()?;
+        Ok(ShotSimulator(Simulator::new(& scenario, flag_drag)))
+    }
+
+    pub fn step(& mut self, dt: f64) {
+        if dt > 0.0 {
+            self.0.step(dt);
+        }
+    }
+
+    pub fn reset(& mut self) {
+        self.0.reset();
+    }
+
+    pub fn set_wind(& mut self, v_x: f64, v_y: f64) {
+        self.0.set_wind(v_x, v_y);
+    }
+
+    pub fn t(& self) -> f64 {
+        self.0.state().t
+    }
+
+    pub fn x(& self) -> f64 {
+        self.0.state().x
+    }
+
+    pub fn y(& self) -> f64 {
+        self.0.state().y
+    }
+
+    pub fn has_entered(& self) -> bool {
+        self.0.has_entered()
+    }
+
+    pub fn has_landed(& self) -> bool {
+        self.0.has_landed()
+    }
+}
@@ -0,0 +1,42 @@
+//! The wind, for the shots outdoors. It's a list of samples of its velocity in the plane of the
+//! shot, at instants of the flight, linear between them and constant before the first and after
+//! the last one, so a gust on a long lob is the same in every run. Without samples there's no
+//! wind, and with one the wind is constant.
+//!
+//! Only the model with the air drag feels the wind, the drag depends on the velocity of the ball
+//! relative to the air.
+
+use serde::{Deserialize, Serialize};
+
+/// The velocity of the wind at the instant t in s, v_x along the XX axis, towards the basket,
+/// and v_y up, in m/s.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WindSample {
+    pub t: f64,
+    pub v_x: f64,
+    pub v_y: f64,
+}
+
+/// The velocity (v_x, v_y) of the wind at the instant t.
+pub fn wind_at(wind: & [WindSample], t: f64) -> (f64, f64) {
+    let i = wind.partition_point(|sample| sample.t <= t);
+    match (i.checked_sub(1).and_then(|i| wind.get(i)), wind.get(i)) {
+        (None, None) => (0.0, 0.0),
+        (Some(sample), None) | (None, Some(sample)) => (sample.v_x, sample.v_y),
+        (Some(sample_0), Some(sample_1)) => {
+            let frac = (t - sample_0.t) / (sample_1.t - sample_0.t);
+            (sample_0.v_x + (sample_1.v_x - sample_0.v_x) * frac, sample_0.v_y + (sample_1.v_y - sample_0.v_y) * frac)
+        },
+    }
+}
+
+/// The samples that can't be interpolated, they must be finite and in the order of the time.
+pub fn validate_wind(wind: & [WindSample]) -> Result<(), String> {
+    if let Some(i) = wind.iter().position(|sample| !(sample.t.is_finite() && sample.v_x.is_finite() && sample.v_y.is_finite())) {
+        return Err(format!("wind[{}] isn't a finite number.", i));
+    }
+    if let Some(i) = (1..wind.len()).find(|&i| wind[i].t <= wind[i - 1].t) {
+        return Err(format!("wind[{}].t is {}, expected after the previous sample.", i, wind[i].t));
+    }
+    Ok(())
+}